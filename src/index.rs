@@ -1,7 +1,7 @@
 use crate::okx::datastore::brc20::redb::table::{
-  get_balance, get_balances, get_token_info, get_tokens_info, get_transaction_receipts,
-  get_transferable_assets_by_account, get_transferable_assets_by_account_ticker,
-  get_transferable_assets_by_outpoint,
+  get_balance, get_balances, get_tick_transaction_ids, get_token_info, get_tokens_info,
+  get_transaction_receipts, get_transferable_assets_by_account,
+  get_transferable_assets_by_account_ticker, get_transferable_assets_by_outpoint,
 };
 use crate::okx::datastore::ord::redb::table::{
   get_collection_inscription_id, get_collections_of_inscription, get_transaction_operations,
@@ -11,7 +11,10 @@ use crate::okx::datastore::{brc20, ScriptKey};
 
 use {
   self::{
-    entry::{Entry, HeaderValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange},
+    entry::{
+      Entry, HeaderValue, RuneEntryValue, RuneIdValue, RuneTransferEntry, SatPointValue, SatRange,
+    },
+    readonly::ReadOnlyBackend,
     reorg::*,
     runes::{Rune, RuneId},
     updater::Updater,
@@ -22,7 +25,10 @@ use {
     templates::StatusHtml,
   },
   bitcoin::block::Header,
-  bitcoincore_rpc::{json::GetBlockHeaderResult, Client},
+  bitcoincore_rpc::{
+    json::{GetBlockHeaderResult, GetTxOutResult},
+    Client,
+  },
   chrono::SubsecRound,
   indicatif::{ProgressBar, ProgressStyle},
   log::log_enabled,
@@ -35,28 +41,95 @@ use {
   std::{
     collections::{BTreeSet, HashMap},
     io::{BufWriter, Write},
-    sync::{Mutex, Once},
+    sync::{atomic::AtomicU32, Mutex, Once},
   },
+  tokio::sync::broadcast,
 };
 
 pub use self::entry::RuneEntry;
 pub(super) use self::entry::{
   InscriptionEntry, InscriptionEntryValue, InscriptionIdValue, OutPointValue, TxidValue,
 };
+pub(crate) use self::event::{Event, ReorgEvent};
 pub(super) use self::updater::BlockData;
 
+pub(crate) mod cancel;
 pub(crate) mod entry;
+mod event;
 mod fetcher;
+mod migrate;
+mod readonly;
+pub(crate) mod reindex;
 mod reorg;
 pub(crate) mod rtx;
 pub(crate) mod updater;
+pub(crate) mod verify;
 
 mod extend;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 16;
+pub(crate) const SCHEMA_VERSION: u64 = 22;
+
+/// The order in which paginated listings iterate over sequence numbers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Order {
+  /// Oldest first.
+  Asc,
+  /// Newest first.
+  Desc,
+}
+
+impl Display for Order {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Asc => write!(f, "asc"),
+      Self::Desc => write!(f, "desc"),
+    }
+  }
+}
+
+impl FromStr for Order {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "asc" => Ok(Self::Asc),
+      "desc" => Ok(Self::Desc),
+      _ => Err(anyhow!("unknown order `{s}`, expected `asc` or `desc`")),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+enum Capacity {
+  Available,
+  NearlyFull,
+  Full,
+}
+
+impl Capacity {
+  fn of(index_size: u64, max_index_size: u64) -> Self {
+    if index_size >= max_index_size {
+      Self::Full
+    } else if u128::from(index_size) * 10 >= u128::from(max_index_size) * 9 {
+      Self::NearlyFull
+    } else {
+      Self::Available
+    }
+  }
+}
+
+/// Number of batches of events buffered for each subscriber. The events of a
+/// commit are sent as a single batch, however many inscriptions its blocks
+/// contain, so only subscribers that fall behind by this many commits miss
+/// events and are notified that they lagged.
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// Number of blocks the index may trail the chain tip and still be reported
+/// as synced.
+const SYNCED_MAX_LAG: u32 = 1;
 
 macro_rules! define_table {
   ($name:ident, $key:ty, $value:ty) => {
@@ -73,18 +146,24 @@ macro_rules! define_multimap_table {
 
 define_multimap_table! { SATPOINT_TO_SEQUENCE_NUMBER, &SatPointValue, u32 }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
+define_multimap_table! { SCRIPT_PUBKEY_TO_INSCRIPTION_OUTPOINT, &[u8], &OutPointValue }
+define_multimap_table! { SCRIPT_PUBKEY_TO_RUNE_OUTPOINT, &[u8], &OutPointValue }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
+define_table! { INSCRIPTION_OUTPOINT_TO_SCRIPT_PUBKEY, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_ENTRY, &OutPointValue, &[u8]}
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
+define_table! { RUNE_ID_TO_TRANSFER, (RuneIdValue, u32, u32), &[u8] }
+define_table! { RUNE_OUTPOINT_TO_SCRIPT_PUBKEY, &OutPointValue, &[u8] }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
+define_table! { SEQUENCE_NUMBER_TO_CONTENT, u32, (Option<&str>, Option<u64>) }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
@@ -101,6 +180,7 @@ define_multimap_table! { COLLECTIONS_INSCRIPTION_ID_TO_KINDS, InscriptionIdValue
 define_table! { BRC20_BALANCES, &str, &[u8] }
 define_table! { BRC20_TOKEN, &str, &[u8] }
 define_table! { BRC20_EVENTS, &TxidValue, &[u8] }
+define_table! { BRC20_TICK_HEIGHT_TO_TXIDS, (&str, u32), &[u8] }
 define_table! { BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, &SatPointValue, &[u8] }
 define_multimap_table! { BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS, &str, &SatPointValue }
 
@@ -125,6 +205,7 @@ pub(crate) enum Statistic {
   SatRanges = 10,
   UnboundInscriptions = 11,
   IndexTransactions = 12,
+  MigratedHeight = 13,
 }
 
 impl Statistic {
@@ -180,7 +261,7 @@ pub(crate) struct TransactionInfo {
 pub(crate) struct InscriptionInfo {
   pub(crate) children: Vec<InscriptionId>,
   pub(crate) entry: InscriptionEntry,
-  pub(crate) parent: Option<InscriptionId>,
+  pub(crate) parents: Vec<InscriptionId>,
   pub(crate) output: Option<TxOut>,
   pub(crate) satpoint: SatPoint,
   pub(crate) inscription: Inscription,
@@ -214,9 +295,11 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
 }
 
 pub struct Index {
+  at_capacity: AtomicBool,
   client: Client,
   database: Database,
   durability: redb::Durability,
+  event_sender: broadcast::Sender<Arc<Vec<Event>>>,
   first_inscription_height: u32,
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
@@ -224,133 +307,480 @@ pub struct Index {
   index_runes: bool,
   index_sats: bool,
   index_transactions: bool,
+  last_chain_block_count: AtomicU32,
   options: Options,
   path: PathBuf,
+  readonly: bool,
   started: DateTime<Utc>,
+  reorgs: Mutex<VecDeque<ReorgEvent>>,
   unrecoverably_reorged: AtomicBool,
 }
 
-impl Index {
-  pub fn open(options: &Options) -> Result<Self> {
-    let client = options.bitcoin_rpc_client(None)?;
-
-    let path = options
-      .index
-      .clone()
-      .unwrap_or(options.data_dir().clone().join("index.redb"));
-
-    if let Err(err) = fs::create_dir_all(path.parent().unwrap()) {
-      bail!(
-        "failed to create data dir `{}`: {err}",
-        path.parent().unwrap().display()
-      );
-    }
-
-    let db_cache_size = match options.db_cache_size {
-      Some(db_cache_size) => db_cache_size,
-      None => {
-        let mut sys = System::new();
-        sys.refresh_memory();
-        usize::try_from(sys.total_memory() / 4)?
-      }
-    };
+/// An index opened with `Index::open`, and the only handle through which an
+/// index can be written. `Index::open_readonly` returns a bare `Index`, so a
+/// read-only index has no way to update or begin a write transaction.
+#[derive(Clone)]
+pub struct WritableIndex(Arc<Index>);
 
-    log::info!("Setting DB cache size to {} bytes", db_cache_size);
+impl Deref for WritableIndex {
+  type Target = Index;
 
-    let durability = if cfg!(test) {
-      redb::Durability::None
-    } else {
-      redb::Durability::Immediate
-    };
+  fn deref(&self) -> &Index {
+    &self.0
+  }
+}
 
-    let index_runes;
-    let index_sats;
-    let index_transactions;
+impl WritableIndex {
+  /// A handle for reading the same index, for example to serve it while
+  /// this handle updates it.
+  pub(crate) fn reader(&self) -> Arc<Index> {
+    self.0.clone()
+  }
 
-    let index_path = path.clone();
-    let once = Once::new();
-    let progress_bar = Mutex::new(None);
+  #[cfg(test)]
+  fn set_durability(&mut self, durability: redb::Durability) {
+    Arc::get_mut(&mut self.0).unwrap().durability = durability;
+  }
 
-    let repair_callback = move |progress: &mut RepairSession| {
-      once.call_once(|| println!("Index file `{}` needs recovery. This can take a long time, especially for the --index-sats index.", index_path.display()));
+  pub(crate) fn info(&self) -> Result<Info> {
+    fn insert_table_info<K: RedbKey + 'static, V: RedbValue + 'static>(
+      tables: &mut BTreeMap<String, TableInfo>,
+      wtx: &WriteTransaction,
+      database_total_bytes: u64,
+      definition: TableDefinition<K, V>,
+    ) {
+      let stats = wtx.open_table(definition).unwrap().stats().unwrap();
 
-      if !(cfg!(test) || log_enabled!(log::Level::Info) || integration_test()) {
-        let mut guard = progress_bar.lock().unwrap();
+      let fragmented_bytes = stats.fragmented_bytes();
+      let metadata_bytes = stats.metadata_bytes();
+      let stored_bytes = stats.stored_bytes();
+      let total_bytes = stored_bytes + metadata_bytes + fragmented_bytes;
 
-        let progress_bar = guard.get_or_insert_with(|| {
-          let progress_bar = ProgressBar::new(100);
-          progress_bar.set_style(
-            ProgressStyle::with_template("[repairing database] {wide_bar} {pos}/{len}").unwrap(),
-          );
-          progress_bar
-        });
+      tables.insert(
+        definition.name().into(),
+        TableInfo {
+          branch_pages: stats.branch_pages(),
+          fragmented_bytes,
+          leaf_pages: stats.leaf_pages(),
+          metadata_bytes,
+          proportion: total_bytes as f64 / database_total_bytes as f64,
+          stored_bytes,
+          total_bytes,
+          tree_height: stats.tree_height(),
+        },
+      );
+    }
 
-        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-        progress_bar.set_position((progress.progress() * 100.0) as u64);
-      }
-    };
+    fn insert_multimap_table_info<K: RedbKey + 'static, V: RedbValue + RedbKey + 'static>(
+      tables: &mut BTreeMap<String, TableInfo>,
+      wtx: &WriteTransaction,
+      database_total_bytes: u64,
+      definition: MultimapTableDefinition<K, V>,
+    ) {
+      let stats = wtx
+        .open_multimap_table(definition)
+        .unwrap()
+        .stats()
+        .unwrap();
 
-    let database = match Database::builder()
-      .set_cache_size(db_cache_size)
-      .set_repair_callback(repair_callback)
-      .open(&path)
-    {
-      Ok(database) => {
-        {
-          let tx = database.begin_read()?;
-          let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+      let fragmented_bytes = stats.fragmented_bytes();
+      let metadata_bytes = stats.metadata_bytes();
+      let stored_bytes = stats.stored_bytes();
+      let total_bytes = stored_bytes + metadata_bytes + fragmented_bytes;
 
-          let schema_version = statistics
-            .get(&Statistic::Schema.key())?
-            .map(|x| x.value())
-            .unwrap_or(0);
+      tables.insert(
+        definition.name().into(),
+        TableInfo {
+          branch_pages: stats.branch_pages(),
+          fragmented_bytes,
+          leaf_pages: stats.leaf_pages(),
+          metadata_bytes,
+          proportion: total_bytes as f64 / database_total_bytes as f64,
+          stored_bytes,
+          total_bytes,
+          tree_height: stats.tree_height(),
+        },
+      );
+    }
 
-          match schema_version.cmp(&SCHEMA_VERSION) {
-            cmp::Ordering::Less =>
-              bail!(
-                "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
-                path.display()
-              ),
-            cmp::Ordering::Greater =>
-              bail!(
-                "index at `{}` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
-                path.display()
-              ),
-            cmp::Ordering::Equal => {
-            }
-          }
+    let wtx = self.begin_write()?;
 
-          index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
-          index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
-          index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
-        }
+    let stats = wtx.stats()?;
 
-        database
-      }
-      Err(DatabaseError::Storage(StorageError::Io(error)))
-        if error.kind() == io::ErrorKind::NotFound =>
-      {
-        let database = Database::builder()
-          .set_cache_size(db_cache_size)
-          .create(&path)?;
+    let fragmented_bytes = stats.fragmented_bytes();
+    let metadata_bytes = stats.metadata_bytes();
+    let stored_bytes = stats.stored_bytes();
+    let total_bytes = fragmented_bytes + metadata_bytes + stored_bytes;
 
-        let mut tx = database.begin_write()?;
+    let mut tables: BTreeMap<String, TableInfo> = BTreeMap::new();
 
-        tx.set_durability(durability);
+    insert_multimap_table_info(&mut tables, &wtx, total_bytes, SATPOINT_TO_SEQUENCE_NUMBER);
+    insert_multimap_table_info(&mut tables, &wtx, total_bytes, SAT_TO_SEQUENCE_NUMBER);
+    insert_multimap_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      SCRIPT_PUBKEY_TO_INSCRIPTION_OUTPOINT,
+    );
+    insert_multimap_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      SCRIPT_PUBKEY_TO_RUNE_OUTPOINT,
+    );
+    insert_multimap_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_CHILDREN);
+    insert_table_info(&mut tables, &wtx, total_bytes, HEIGHT_TO_BLOCK_HEADER);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      HEIGHT_TO_LAST_SEQUENCE_NUMBER,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, HOME_INSCRIPTIONS);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      INSCRIPTION_ID_TO_SEQUENCE_NUMBER,
+    );
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER,
+    );
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      INSCRIPTION_OUTPOINT_TO_SCRIPT_PUBKEY,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_RUNE_BALANCES);
+    insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_SAT_RANGES);
+    insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_ENTRY);
+    insert_table_info(&mut tables, &wtx, total_bytes, RUNE_ID_TO_RUNE_ENTRY);
+    insert_table_info(&mut tables, &wtx, total_bytes, RUNE_ID_TO_TRANSFER);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      RUNE_OUTPOINT_TO_SCRIPT_PUBKEY,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, RUNE_TO_RUNE_ID);
+    insert_table_info(&mut tables, &wtx, total_bytes, SAT_TO_SATPOINT);
+    insert_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_CONTENT);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_RUNE_ID);
+    insert_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_SATPOINT);
+    insert_table_info(&mut tables, &wtx, total_bytes, STATISTIC_TO_COUNT);
+    insert_table_info(&mut tables, &wtx, total_bytes, TRANSACTION_ID_TO_RUNE);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      TRANSACTION_ID_TO_TRANSACTION,
+    );
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP,
+    );
 
-        tx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
-        tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
-        tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
-        tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
-        tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
-        tx.open_table(HOME_INSCRIPTIONS)?;
-        tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
-        tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
-        tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
-        tx.open_table(OUTPOINT_TO_ENTRY)?;
+    insert_table_info(&mut tables, &wtx, total_bytes, ORD_TX_TO_OPERATIONS);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      COLLECTIONS_KEY_TO_INSCRIPTION_ID,
+    );
+    insert_multimap_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      COLLECTIONS_INSCRIPTION_ID_TO_KINDS,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_BALANCES);
+    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_TOKEN);
+    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_EVENTS);
+    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_TICK_HEIGHT_TO_TXIDS);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+    );
+    insert_multimap_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+    );
+
+    for table in wtx.list_tables()? {
+      assert!(tables.contains_key(table.name()));
+    }
+
+    for table in wtx.list_multimap_tables()? {
+      assert!(tables.contains_key(table.name()));
+    }
+
+    let info = {
+      let statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+      let sat_ranges = statistic_to_count
+        .get(&Statistic::SatRanges.key())?
+        .map(|x| x.value())
+        .unwrap_or(0);
+      let outputs_traversed = statistic_to_count
+        .get(&Statistic::OutputsTraversed.key())?
+        .map(|x| x.value())
+        .unwrap_or(0);
+      Info {
+        blocks_indexed: wtx
+          .open_table(HEIGHT_TO_BLOCK_HEADER)?
+          .range(0..)?
+          .next_back()
+          .and_then(|result| result.ok())
+          .map(|(height, _header)| height.value() + 1)
+          .unwrap_or(0),
+        branch_pages: stats.branch_pages(),
+        fragmented_bytes,
+        index_file_size: fs::metadata(&self.path)?.len(),
+        index_path: self.path.clone(),
+        leaf_pages: stats.leaf_pages(),
+        metadata_bytes,
+        outputs_traversed,
+        page_size: stats.page_size(),
+        sat_ranges,
+        stored_bytes,
+        tables,
+        total_bytes,
+        transactions: wtx
+          .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
+          .range(0..)?
+          .flat_map(|result| {
+            result.map(
+              |(starting_block_count, starting_timestamp)| TransactionInfo {
+                starting_block_count: starting_block_count.value(),
+                starting_timestamp: starting_timestamp.value(),
+              },
+            )
+          })
+          .collect(),
+        tree_height: stats.tree_height(),
+        utxos_indexed: wtx.open_table(OUTPOINT_TO_SAT_RANGES)?.len()?,
+      }
+    };
+
+    Ok(info)
+  }
+
+  pub(crate) fn update(&self) -> Result {
+    let mut updater = Updater::new(self)?;
+
+    loop {
+      match updater.update_index() {
+        Ok(ok) => return Ok(ok),
+        Err(err) => {
+          log::error!("{}", err.to_string());
+
+          match err.downcast_ref() {
+            Some(&ReorgError::Recoverable { height, depth }) => {
+              let reorg = Reorg::orphaned(self, height, depth)?;
+
+              Reorg::handle_reorg(self, height, depth)?;
+
+              self.record_reorg(reorg);
+
+              updater = Updater::new(self)?;
+            }
+            Some(&ReorgError::Unrecoverable) => {
+              self
+                .unrecoverably_reorged
+                .store(true, atomic::Ordering::Relaxed);
+              return Err(anyhow!(ReorgError::Unrecoverable));
+            }
+            _ => return Err(err),
+          };
+        }
+      }
+    }
+  }
+
+  fn begin_write(&self) -> Result<WriteTransaction> {
+    let mut tx = self.database.begin_write()?;
+    tx.set_durability(self.durability);
+    Ok(tx)
+  }
+}
+
+impl Index {
+  pub fn open(options: &Options) -> Result<WritableIndex> {
+    Ok(WritableIndex(Arc::new(Self::open_with_mode(
+      options, false,
+    )?)))
+  }
+
+  /// Open an existing index without ever writing to the index file. Writes
+  /// that redb makes while reading are kept in memory.
+  pub fn open_readonly(options: &Options) -> Result<Self> {
+    Self::open_with_mode(options, true)
+  }
+
+  fn path(options: &Options) -> PathBuf {
+    options
+      .index
+      .clone()
+      .unwrap_or(options.data_dir().clone().join("index.redb"))
+  }
+
+  fn open_with_mode(options: &Options, readonly: bool) -> Result<Self> {
+    let client = options.bitcoin_rpc_client(None)?;
+
+    let path = Self::path(options);
+
+    if readonly {
+      if !path.is_file() {
+        bail!("read-only index `{}` does not exist", path.display());
+      }
+    } else if let Err(err) = fs::create_dir_all(path.parent().unwrap()) {
+      bail!(
+        "failed to create data dir `{}`: {err}",
+        path.parent().unwrap().display()
+      );
+    }
+
+    let db_cache_size = match options.db_cache_size {
+      Some(db_cache_size) => db_cache_size,
+      None => {
+        let mut sys = System::new();
+        sys.refresh_memory();
+        usize::try_from(sys.total_memory() / 4)?
+      }
+    };
+
+    log::info!("Setting DB cache size to {} bytes", db_cache_size);
+
+    let durability = if cfg!(test) {
+      redb::Durability::None
+    } else {
+      redb::Durability::Immediate
+    };
+
+    let index_runes;
+    let index_sats;
+    let index_transactions;
+
+    let index_path = path.clone();
+    let once = Once::new();
+    let progress_bar = Mutex::new(None);
+
+    let repair_callback = move |progress: &mut RepairSession| {
+      once.call_once(|| println!("Index file `{}` needs recovery. This can take a long time, especially for the --index-sats index.", index_path.display()));
+
+      if !(cfg!(test) || log_enabled!(log::Level::Info) || integration_test()) {
+        let mut guard = progress_bar.lock().unwrap();
+
+        let progress_bar = guard.get_or_insert_with(|| {
+          let progress_bar = ProgressBar::new(100);
+          progress_bar.set_style(
+            ProgressStyle::with_template("[repairing database] {wide_bar} {pos}/{len}").unwrap(),
+          );
+          progress_bar
+        });
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        progress_bar.set_position((progress.progress() * 100.0) as u64);
+      }
+    };
+
+    let mut builder = Database::builder();
+
+    builder
+      .set_cache_size(db_cache_size)
+      .set_repair_callback(repair_callback);
+
+    let database = if readonly {
+      ReadOnlyBackend::open(&path)
+        .map_err(|err| StorageError::Io(err).into())
+        .and_then(|backend| builder.create_with_backend(backend))
+    } else {
+      builder.open(&path)
+    };
+
+    let database = match database {
+      Ok(database) => {
+        {
+          let tx = database.begin_read()?;
+          let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+
+          let schema_version = statistics
+            .get(&Statistic::Schema.key())?
+            .map(|x| x.value())
+            .unwrap_or(0);
+
+          match schema_version.cmp(&SCHEMA_VERSION) {
+            cmp::Ordering::Less if migrate::can_migrate(schema_version) =>
+              bail!(
+                "index at `{}` was built with an older version of ord, run `ord migrate-index` to migrate it: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+                path.display()
+              ),
+            cmp::Ordering::Less =>
+              bail!(
+                "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+                path.display()
+              ),
+            cmp::Ordering::Greater =>
+              bail!(
+                "index at `{}` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+                path.display()
+              ),
+            cmp::Ordering::Equal => {
+            }
+          }
+
+          index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
+          index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
+          index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
+        }
+
+        database
+      }
+      Err(DatabaseError::Storage(StorageError::Io(error)))
+        if !readonly && error.kind() == io::ErrorKind::NotFound =>
+      {
+        let database = Database::builder()
+          .set_cache_size(db_cache_size)
+          .create(&path)?;
+
+        let mut tx = database.begin_write()?;
+
+        tx.set_durability(durability);
+
+        tx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_INSCRIPTION_OUTPOINT)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT)?;
+        tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+        tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+        tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+        tx.open_table(HOME_INSCRIPTIONS)?;
+        tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(INSCRIPTION_OUTPOINT_TO_SCRIPT_PUBKEY)?;
+        tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(OUTPOINT_TO_ENTRY)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+        tx.open_table(RUNE_ID_TO_TRANSFER)?;
+        tx.open_table(RUNE_OUTPOINT_TO_SCRIPT_PUBKEY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
         tx.open_table(SAT_TO_SATPOINT)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_CONTENT)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
@@ -367,6 +797,7 @@ impl Index {
         tx.open_table(BRC20_BALANCES)?;
         tx.open_table(BRC20_TOKEN)?;
         tx.open_table(BRC20_EVENTS)?;
+        tx.open_table(BRC20_TICK_HEIGHT_TO_TXIDS)?;
         tx.open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?;
 
         {
@@ -408,342 +839,192 @@ impl Index {
       options.chain().genesis_block().coinbase().unwrap().clone();
 
     Ok(Self {
+      at_capacity: AtomicBool::new(false),
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.txid(),
       client,
       database,
       durability,
+      event_sender: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
       first_inscription_height: options.first_inscription_height(),
       genesis_block_coinbase_transaction,
       height_limit: options.height_limit,
-      index_runes,
-      index_sats,
-      index_transactions,
-      options: options.clone(),
-      path,
-      started: Utc::now(),
-      unrecoverably_reorged: AtomicBool::new(false),
-    })
-  }
-
-  pub(crate) fn get_chain(&self) -> Chain {
-    self.options.chain()
-  }
-
-  #[cfg(test)]
-  fn set_durability(&mut self, durability: redb::Durability) {
-    self.durability = durability;
-  }
-
-  pub(crate) fn check_sync(&self, utxos: &BTreeMap<OutPoint, Amount>) -> Result<bool> {
-    let rtx = self.database.begin_read()?;
-    let outpoint_to_entry = rtx.open_table(OUTPOINT_TO_ENTRY)?;
-    for outpoint in utxos.keys() {
-      if outpoint_to_entry.get(&outpoint.store())?.is_none() {
-        return Err(anyhow!(
-          "output in Bitcoin Core wallet but not in ord index: {outpoint}"
-        ));
-      }
-    }
-
-    Ok(true)
-  }
-
-  pub(crate) fn has_rune_index(&self) -> bool {
-    self.index_runes
-  }
-
-  pub(crate) fn has_transactions_index(&self) -> bool {
-    self.index_transactions
-  }
-
-  pub(crate) fn has_sat_index(&self) -> bool {
-    self.index_sats
-  }
-
-  pub(crate) fn status(&self) -> Result<StatusHtml> {
-    let rtx = self.database.begin_read()?;
-
-    let statistic_to_count = rtx.open_table(STATISTIC_TO_COUNT)?;
-
-    let statistic = |statistic: Statistic| -> Result<u64> {
-      Ok(
-        statistic_to_count
-          .get(statistic.key())?
-          .map(|guard| guard.value())
-          .unwrap_or_default(),
-      )
-    };
-
-    let height = rtx
-      .open_table(HEIGHT_TO_BLOCK_HEADER)?
-      .range(0..)?
-      .next_back()
-      .transpose()?
-      .map(|(height, _header)| height.value());
-
-    let next_height = height.map(|height| height + 1).unwrap_or(0);
-
-    let blessed_inscriptions = statistic(Statistic::BlessedInscriptions)?;
-    let cursed_inscriptions = statistic(Statistic::CursedInscriptions)?;
-
-    Ok(StatusHtml {
-      blessed_inscriptions,
-      chain: self.options.chain(),
-      cursed_inscriptions,
-      height,
-      inscriptions: blessed_inscriptions + cursed_inscriptions,
-      lost_sats: statistic(Statistic::LostSats)?,
-      minimum_rune_for_next_block: Rune::minimum_at_height(
-        self.options.chain(),
-        Height(next_height),
-      ),
-      rune_index: statistic(Statistic::IndexRunes)? != 0,
-      runes: statistic(Statistic::Runes)?,
-      sat_index: statistic(Statistic::IndexSats)? != 0,
-      started: self.started,
-      transaction_index: statistic(Statistic::IndexTransactions)? != 0,
-      unrecoverably_reorged: self.unrecoverably_reorged.load(atomic::Ordering::Relaxed),
-      uptime: (Utc::now() - self.started).to_std()?,
-    })
-  }
-
-  pub(crate) fn info(&self) -> Result<Info> {
-    fn insert_table_info<K: RedbKey + 'static, V: RedbValue + 'static>(
-      tables: &mut BTreeMap<String, TableInfo>,
-      wtx: &WriteTransaction,
-      database_total_bytes: u64,
-      definition: TableDefinition<K, V>,
-    ) {
-      let stats = wtx.open_table(definition).unwrap().stats().unwrap();
-
-      let fragmented_bytes = stats.fragmented_bytes();
-      let metadata_bytes = stats.metadata_bytes();
-      let stored_bytes = stats.stored_bytes();
-      let total_bytes = stored_bytes + metadata_bytes + fragmented_bytes;
-
-      tables.insert(
-        definition.name().into(),
-        TableInfo {
-          branch_pages: stats.branch_pages(),
-          fragmented_bytes,
-          leaf_pages: stats.leaf_pages(),
-          metadata_bytes,
-          proportion: total_bytes as f64 / database_total_bytes as f64,
-          stored_bytes,
-          total_bytes,
-          tree_height: stats.tree_height(),
-        },
-      );
-    }
-
-    fn insert_multimap_table_info<K: RedbKey + 'static, V: RedbValue + RedbKey + 'static>(
-      tables: &mut BTreeMap<String, TableInfo>,
-      wtx: &WriteTransaction,
-      database_total_bytes: u64,
-      definition: MultimapTableDefinition<K, V>,
-    ) {
-      let stats = wtx
-        .open_multimap_table(definition)
-        .unwrap()
-        .stats()
-        .unwrap();
-
-      let fragmented_bytes = stats.fragmented_bytes();
-      let metadata_bytes = stats.metadata_bytes();
-      let stored_bytes = stats.stored_bytes();
-      let total_bytes = stored_bytes + metadata_bytes + fragmented_bytes;
-
-      tables.insert(
-        definition.name().into(),
-        TableInfo {
-          branch_pages: stats.branch_pages(),
-          fragmented_bytes,
-          leaf_pages: stats.leaf_pages(),
-          metadata_bytes,
-          proportion: total_bytes as f64 / database_total_bytes as f64,
-          stored_bytes,
-          total_bytes,
-          tree_height: stats.tree_height(),
-        },
-      );
-    }
-
-    let wtx = self.begin_write()?;
-
-    let stats = wtx.stats()?;
-
-    let fragmented_bytes = stats.fragmented_bytes();
-    let metadata_bytes = stats.metadata_bytes();
-    let stored_bytes = stats.stored_bytes();
-    let total_bytes = fragmented_bytes + metadata_bytes + stored_bytes;
-
-    let mut tables: BTreeMap<String, TableInfo> = BTreeMap::new();
-
-    insert_multimap_table_info(&mut tables, &wtx, total_bytes, SATPOINT_TO_SEQUENCE_NUMBER);
-    insert_multimap_table_info(&mut tables, &wtx, total_bytes, SAT_TO_SEQUENCE_NUMBER);
-    insert_multimap_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_CHILDREN);
-    insert_table_info(&mut tables, &wtx, total_bytes, HEIGHT_TO_BLOCK_HEADER);
-    insert_table_info(
-      &mut tables,
-      &wtx,
-      total_bytes,
-      HEIGHT_TO_LAST_SEQUENCE_NUMBER,
-    );
-    insert_table_info(&mut tables, &wtx, total_bytes, HOME_INSCRIPTIONS);
-    insert_table_info(
-      &mut tables,
-      &wtx,
-      total_bytes,
-      INSCRIPTION_ID_TO_SEQUENCE_NUMBER,
-    );
-    insert_table_info(
-      &mut tables,
-      &wtx,
-      total_bytes,
-      INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER,
-    );
-    insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_RUNE_BALANCES);
-    insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_SAT_RANGES);
-    insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_ENTRY);
-    insert_table_info(&mut tables, &wtx, total_bytes, RUNE_ID_TO_RUNE_ENTRY);
-    insert_table_info(&mut tables, &wtx, total_bytes, RUNE_TO_RUNE_ID);
-    insert_table_info(&mut tables, &wtx, total_bytes, SAT_TO_SATPOINT);
-    insert_table_info(
-      &mut tables,
-      &wtx,
-      total_bytes,
-      SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY,
-    );
-    insert_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_RUNE_ID);
-    insert_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_SATPOINT);
-    insert_table_info(&mut tables, &wtx, total_bytes, STATISTIC_TO_COUNT);
-    insert_table_info(&mut tables, &wtx, total_bytes, TRANSACTION_ID_TO_RUNE);
-    insert_table_info(
-      &mut tables,
-      &wtx,
-      total_bytes,
-      TRANSACTION_ID_TO_TRANSACTION,
-    );
-    insert_table_info(
-      &mut tables,
-      &wtx,
-      total_bytes,
-      WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP,
-    );
+      index_runes,
+      index_sats,
+      index_transactions,
+      last_chain_block_count: AtomicU32::new(0),
+      options: options.clone(),
+      path,
+      readonly,
+      started: Utc::now(),
+      reorgs: Mutex::new(VecDeque::new()),
+      unrecoverably_reorged: AtomicBool::new(false),
+    })
+  }
 
-    insert_table_info(&mut tables, &wtx, total_bytes, ORD_TX_TO_OPERATIONS);
-    insert_table_info(
-      &mut tables,
-      &wtx,
-      total_bytes,
-      COLLECTIONS_KEY_TO_INSCRIPTION_ID,
-    );
-    insert_multimap_table_info(
-      &mut tables,
-      &wtx,
-      total_bytes,
-      COLLECTIONS_INSCRIPTION_ID_TO_KINDS,
-    );
-    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_BALANCES);
-    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_TOKEN);
-    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_EVENTS);
-    insert_table_info(
-      &mut tables,
-      &wtx,
-      total_bytes,
-      BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
-    );
-    insert_multimap_table_info(
-      &mut tables,
-      &wtx,
-      total_bytes,
-      BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
-    );
+  /// Whether the index file has reached `--max-index-size-gb`, in which case
+  /// indexing is paused until the server is restarted with a larger limit.
+  pub(crate) fn is_at_capacity(&self) -> bool {
+    self.at_capacity.load(atomic::Ordering::Relaxed)
+  }
 
-    for table in wtx.list_tables()? {
-      assert!(tables.contains_key(table.name()));
-    }
+  /// Compare the size of the index file against `--max-index-size-gb`. Called
+  /// after each commit.
+  pub(crate) fn check_capacity(&self) -> Result {
+    let Some(max_index_size) = self.options.max_index_size() else {
+      return Ok(());
+    };
 
-    for table in wtx.list_multimap_tables()? {
-      assert!(tables.contains_key(table.name()));
+    let index_size = fs::metadata(&self.path)?.len();
+
+    match Capacity::of(index_size, max_index_size) {
+      Capacity::Available => {}
+      Capacity::NearlyFull => log::warn!(
+        "index file is {index_size} bytes, within 90% of the {max_index_size} byte limit"
+      ),
+      Capacity::Full => {
+        if !self.at_capacity.swap(true, atomic::Ordering::Relaxed) {
+          log::error!(
+            "index file is {index_size} bytes, exceeding the {max_index_size} byte limit, pausing indexing"
+          );
+        }
+      }
     }
 
-    let info = {
-      let statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
-      let sat_ranges = statistic_to_count
-        .get(&Statistic::SatRanges.key())?
-        .map(|x| x.value())
-        .unwrap_or(0);
-      let outputs_traversed = statistic_to_count
-        .get(&Statistic::OutputsTraversed.key())?
-        .map(|x| x.value())
-        .unwrap_or(0);
-      Info {
-        blocks_indexed: wtx
-          .open_table(HEIGHT_TO_BLOCK_HEADER)?
-          .range(0..)?
-          .next_back()
-          .and_then(|result| result.ok())
-          .map(|(height, _header)| height.value() + 1)
-          .unwrap_or(0),
-        branch_pages: stats.branch_pages(),
-        fragmented_bytes,
-        index_file_size: fs::metadata(&self.path)?.len(),
-        index_path: self.path.clone(),
-        leaf_pages: stats.leaf_pages(),
-        metadata_bytes,
-        outputs_traversed,
-        page_size: stats.page_size(),
-        sat_ranges,
-        stored_bytes,
-        tables,
-        total_bytes,
-        transactions: wtx
-          .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
-          .range(0..)?
-          .flat_map(|result| {
-            result.map(
-              |(starting_block_count, starting_timestamp)| TransactionInfo {
-                starting_block_count: starting_block_count.value(),
-                starting_timestamp: starting_timestamp.value(),
-              },
-            )
-          })
-          .collect(),
-        tree_height: stats.tree_height(),
-        utxos_indexed: wtx.open_table(OUTPOINT_TO_SAT_RANGES)?.len()?,
+    Ok(())
+  }
+
+  /// Subscribe to events for blocks committed from now on, received in
+  /// batches, one per commit.
+  pub(crate) fn subscribe(&self) -> broadcast::Receiver<Arc<Vec<Event>>> {
+    self.event_sender.subscribe()
+  }
+
+  pub(crate) fn get_chain(&self) -> Chain {
+    self.options.chain()
+  }
+
+  pub(crate) fn check_sync(&self, utxos: &BTreeMap<OutPoint, Amount>) -> Result<bool> {
+    let rtx = self.database.begin_read()?;
+    let outpoint_to_entry = rtx.open_table(OUTPOINT_TO_ENTRY)?;
+    for outpoint in utxos.keys() {
+      if outpoint_to_entry.get(&outpoint.store())?.is_none() {
+        return Err(anyhow!(
+          "output in Bitcoin Core wallet but not in ord index: {outpoint}"
+        ));
       }
-    };
+    }
 
-    Ok(info)
+    Ok(true)
   }
 
-  pub(crate) fn update(&self) -> Result {
-    let mut updater = Updater::new(self)?;
+  pub(crate) fn has_rune_index(&self) -> bool {
+    self.index_runes
+  }
 
-    loop {
-      match updater.update_index() {
-        Ok(ok) => return Ok(ok),
-        Err(err) => {
-          log::error!("{}", err.to_string());
+  pub(crate) fn has_transactions_index(&self) -> bool {
+    self.index_transactions
+  }
 
-          match err.downcast_ref() {
-            Some(&ReorgError::Recoverable { height, depth }) => {
-              Reorg::handle_reorg(self, height, depth)?;
+  pub(crate) fn has_sat_index(&self) -> bool {
+    self.index_sats
+  }
 
-              updater = Updater::new(self)?;
-            }
-            Some(&ReorgError::Unrecoverable) => {
-              self
-                .unrecoverably_reorged
-                .store(true, atomic::Ordering::Relaxed);
-              return Err(anyhow!(ReorgError::Unrecoverable));
-            }
-            _ => return Err(err),
-          };
-        }
+  pub(crate) fn status(&self) -> Result<StatusHtml> {
+    let rtx = self.database.begin_read()?;
+
+    let statistic_to_count = rtx.open_table(STATISTIC_TO_COUNT)?;
+
+    let statistic = |statistic: Statistic| -> Result<u64> {
+      Ok(
+        statistic_to_count
+          .get(statistic.key())?
+          .map(|guard| guard.value())
+          .unwrap_or_default(),
+      )
+    };
+
+    let height = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, _header)| height.value());
+
+    let next_height = height.map(|height| height + 1).unwrap_or(0);
+
+    let blessed_inscriptions = statistic(Statistic::BlessedInscriptions)?;
+    let cursed_inscriptions = statistic(Statistic::CursedInscriptions)?;
+
+    // Report the status even when bitcoind can't be reached, so that the
+    // endpoint stays usable for health checks.
+    let chain_block_count = match self.chain_block_count() {
+      Ok(chain_block_count) => Some(chain_block_count),
+      Err(err) => {
+        log::warn!("failed to get chain block count: {err}");
+        None
       }
+    };
+
+    let lag =
+      chain_block_count.map(|chain_block_count| chain_block_count.saturating_sub(next_height));
+
+    let uptime = (Utc::now() - self.started).to_std()?;
+
+    Ok(StatusHtml {
+      blessed_inscriptions,
+      chain: self.options.chain(),
+      chain_tip: chain_block_count.and_then(|chain_block_count| chain_block_count.checked_sub(1)),
+      cursed_inscriptions,
+      height,
+      inscriptions: blessed_inscriptions + cursed_inscriptions,
+      lag,
+      lost_sats: statistic(Statistic::LostSats)?,
+      minimum_rune_for_next_block: Rune::minimum_at_height(
+        self.options.chain(),
+        Height(next_height),
+      ),
+      readonly: self.readonly,
+      rune_index: statistic(Statistic::IndexRunes)? != 0,
+      route_groups: Vec::new(),
+      runes: statistic(Statistic::Runes)?,
+      sat_index: statistic(Statistic::IndexSats)? != 0,
+      shutting_down: SHUTTING_DOWN.load(atomic::Ordering::Relaxed),
+      started: self.started,
+      synced: lag.map_or(false, |lag| lag <= SYNCED_MAX_LAG),
+      transaction_index: statistic(Statistic::IndexTransactions)? != 0,
+      unrecoverably_reorged: self.unrecoverably_reorged.load(atomic::Ordering::Relaxed),
+      uptime,
+      uptime_secs: uptime.as_secs(),
+    })
+  }
+
+  fn record_reorg(&self, reorg: ReorgEvent) {
+    let mut reorgs = self.reorgs.lock().unwrap();
+
+    if reorgs.len() >= MAX_REORG_HISTORY {
+      reorgs.pop_front();
     }
+
+    reorgs.push_back(reorg.clone());
+
+    self
+      .event_sender
+      .send(Arc::new(vec![Event::Reorg(reorg)]))
+      .ok();
+  }
+
+  /// Recently recovered reorgs whose common ancestor is at or above `height`,
+  /// oldest first. Only the last `MAX_REORG_HISTORY` reorgs seen by this
+  /// process are retained.
+  pub(crate) fn reorgs_since(&self, height: u32) -> Vec<ReorgEvent> {
+    self
+      .reorgs
+      .lock()
+      .unwrap()
+      .iter()
+      .filter(|reorg| reorg.common_ancestor_height >= height)
+      .cloned()
+      .collect()
   }
 
   pub(crate) fn export(&self, filename: &String, include_addresses: bool) -> Result {
@@ -814,6 +1095,65 @@ impl Index {
     Ok(())
   }
 
+  /// Call `f` with the entry, satpoint, content type and content length of
+  /// every inscription revealed in blocks `from_height` up to but not
+  /// including `to_height`, in sequence number order, all read from the index
+  /// in a single read transaction. Returns the number of inscriptions visited.
+  pub(crate) fn for_each_inscription(
+    &self,
+    from_height: u32,
+    to_height: Option<u32>,
+    mut f: impl FnMut(InscriptionEntry, SatPoint, Option<&str>, Option<u64>) -> Result,
+  ) -> Result<u64> {
+    let rtx = self.begin_read()?;
+
+    let sequence_number_to_content = rtx.0.open_table(SEQUENCE_NUMBER_TO_CONTENT)?;
+    let sequence_number_to_satpoint = rtx.0.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+
+    let mut count = 0;
+
+    for result in rtx
+      .0
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .iter()?
+    {
+      let (sequence_number, entry) = result?;
+      let entry = InscriptionEntry::load(entry.value());
+
+      if entry.height < from_height {
+        continue;
+      }
+
+      // sequence numbers are assigned in block order
+      if to_height.map_or(false, |to_height| entry.height >= to_height) {
+        break;
+      }
+
+      let satpoint = SatPoint::load(
+        *sequence_number_to_satpoint
+          .get(sequence_number.value())?
+          .unwrap()
+          .value(),
+      );
+
+      let content = sequence_number_to_content.get(sequence_number.value())?;
+      let (content_type, content_length) = content
+        .as_ref()
+        .map(|content| content.value())
+        .unwrap_or_default();
+
+      f(entry, satpoint, content_type, content_length)?;
+
+      count += 1;
+
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+    }
+
+    Ok(count)
+  }
+
   pub(crate) fn begin_read(&self) -> Result<rtx::Rtx> {
     Ok(rtx::Rtx(self.database.begin_read()?))
   }
@@ -822,12 +1162,6 @@ impl Index {
     self.options.bitcoin_rpc_client(None)
   }
 
-  fn begin_write(&self) -> Result<WriteTransaction> {
-    let mut tx = self.database.begin_write()?;
-    tx.set_durability(self.durability);
-    Ok(tx)
-  }
-
   fn increment_statistic(wtx: &WriteTransaction, statistic: Statistic, n: u64) -> Result {
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
     let value = statistic_to_count
@@ -879,12 +1213,54 @@ impl Index {
     self.begin_read()?.block_count()
   }
 
-  pub(crate) fn block_height(&self) -> Result<Option<Height>> {
-    self.begin_read()?.block_height()
+  pub(crate) fn block_height(&self) -> Result<Option<Height>> {
+    self.begin_read()?.block_height()
+  }
+
+  pub(crate) fn block_hash(&self, height: Option<u32>) -> Result<Option<BlockHash>> {
+    self.begin_read()?.block_hash(height)
+  }
+
+  pub(crate) fn chain_block_count(&self) -> Result<u32> {
+    let block_count = u32::try_from(self.client.get_block_count()?)?.saturating_add(1);
+
+    Ok(match self.height_limit {
+      Some(height_limit) => block_count.min(height_limit),
+      None => block_count,
+    })
+  }
+
+  /// Query bitcoind for the chain block count, and remember it as the last
+  /// chain block count. Called by the updater before each update.
+  pub(crate) fn refresh_chain_block_count(&self) -> Result<u32> {
+    let chain_block_count = self.chain_block_count()?;
+
+    self
+      .last_chain_block_count
+      .store(chain_block_count, atomic::Ordering::Relaxed);
+
+    Ok(chain_block_count)
+  }
+
+  /// The chain block count last seen by the updater, without querying
+  /// bitcoind, or the block count of the index if the updater has not seen
+  /// the chain yet, for example when serving a `--readonly` index.
+  pub(crate) fn last_chain_block_count(&self) -> Result<u32> {
+    match self.last_chain_block_count.load(atomic::Ordering::Relaxed) {
+      0 => self.block_count(),
+      chain_block_count => Ok(chain_block_count),
+    }
   }
 
-  pub(crate) fn block_hash(&self, height: Option<u32>) -> Result<Option<BlockHash>> {
-    self.begin_read()?.block_hash(height)
+  /// Whether the index trails the chain tip last seen by the updater by no
+  /// more than `SYNCED_MAX_LAG` blocks.
+  pub(crate) fn is_synced(&self) -> Result<bool> {
+    Ok(
+      self
+        .last_chain_block_count()?
+        .saturating_sub(self.block_count()?)
+        <= SYNCED_MAX_LAG,
+    )
   }
 
   pub(crate) fn blocks(&self, take: usize) -> Result<Vec<(u32, BlockHash)>> {
@@ -981,6 +1357,35 @@ impl Index {
     Ok(Some((RuneId::load(id), entry, parent)))
   }
 
+  /// The transfers of the rune with `id` in blocks `from_height` up to but
+  /// not including `to_height`, in chain order, with the height of each.
+  pub(crate) fn get_rune_transfers(
+    &self,
+    id: RuneId,
+    from_height: u32,
+    to_height: u32,
+  ) -> Result<Vec<(u32, RuneTransferEntry)>> {
+    let mut transfers = Vec::new();
+
+    if from_height >= to_height {
+      return Ok(transfers);
+    }
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(RUNE_ID_TO_TRANSFER)?
+      .range((id.store(), from_height, 0)..(id.store(), to_height, 0))?
+    {
+      cancel::check()?;
+      let (key, transfer) = result?;
+      let (_, height, _) = key.value();
+      transfers.push((height, RuneTransferEntry::load(transfer.value().to_vec())));
+    }
+
+    Ok(transfers)
+  }
+
   pub(crate) fn runes(&self) -> Result<Vec<(RuneId, RuneEntry)>> {
     let mut entries = Vec::new();
 
@@ -990,6 +1395,7 @@ impl Index {
       .open_table(RUNE_ID_TO_RUNE_ENTRY)?
       .iter()?
     {
+      cancel::check()?;
       let (id, entry) = result?;
       entries.push((RuneId::load(id.value()), RuneEntry::load(entry.value())));
     }
@@ -1064,6 +1470,114 @@ impl Index {
     Ok(balances)
   }
 
+  pub(crate) fn get_rune_balances_for_script_pubkey(
+    &self,
+    script_pubkey: &Script,
+  ) -> Result<Vec<(RuneId, SpacedRune, Pile)>> {
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+
+    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let mut amounts: BTreeMap<u128, u128> = BTreeMap::new();
+
+    for outpoint in rtx
+      .open_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT)?
+      .get(script_pubkey.as_bytes())?
+    {
+      cancel::check()?;
+
+      let Some(balances) = outpoint_to_balances.get(outpoint?.value())? else {
+        continue;
+      };
+
+      let balances_buffer = balances.value();
+
+      let mut i = 0;
+      while i < balances_buffer.len() {
+        let (id, length) = runes::varint::decode(&balances_buffer[i..]);
+        i += length;
+        let (amount, length) = runes::varint::decode(&balances_buffer[i..]);
+        i += length;
+
+        *amounts.entry(id).or_default() += amount;
+      }
+    }
+
+    let mut balances = Vec::new();
+
+    for (id, amount) in amounts {
+      let id = RuneId::try_from(id).unwrap();
+
+      let entry = RuneEntry::load(id_to_rune_entries.get(id.store())?.unwrap().value());
+
+      balances.push((
+        id,
+        entry.spaced_rune(),
+        Pile {
+          amount,
+          divisibility: entry.divisibility,
+          symbol: entry.symbol,
+        },
+      ));
+    }
+
+    Ok(balances)
+  }
+
+  /// The unspent balance of the rune with `id` held by each script pubkey,
+  /// largest first. This scans every runic output, so callers should cache
+  /// the result.
+  pub(crate) fn get_rune_holders(&self, id: RuneId) -> Result<Vec<(ScriptBuf, u128)>> {
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+
+    let mut holders = Vec::new();
+
+    for result in rtx
+      .open_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT)?
+      .iter()?
+    {
+      cancel::check()?;
+
+      let (script_pubkey, outpoints) = result?;
+
+      let mut total = 0;
+
+      for outpoint in outpoints {
+        let Some(balances) = outpoint_to_balances.get(outpoint?.value())? else {
+          continue;
+        };
+
+        let balances_buffer = balances.value();
+
+        let mut i = 0;
+        while i < balances_buffer.len() {
+          let (balance_id, length) = runes::varint::decode(&balances_buffer[i..]);
+          i += length;
+          let (amount, length) = runes::varint::decode(&balances_buffer[i..]);
+          i += length;
+
+          if RuneId::try_from(balance_id)? == id {
+            total += amount;
+          }
+        }
+      }
+
+      if total > 0 {
+        holders.push((ScriptBuf::from(script_pubkey.value().to_vec()), total));
+      }
+    }
+
+    holders.sort_by(|(a_script, a_amount), (b_script, b_amount)| {
+      b_amount.cmp(a_amount).then_with(|| a_script.cmp(b_script))
+    });
+
+    Ok(holders)
+  }
+
   pub(crate) fn get_runic_outputs(&self, outpoints: &[OutPoint]) -> Result<BTreeSet<OutPoint>> {
     let rtx = self.database.begin_read()?;
 
@@ -1172,10 +1686,13 @@ impl Index {
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let mut collections = rtx
-      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
-      .iter()?
-      .skip(page_index.saturating_mul(page_size))
+    let sequence_number_to_children = rtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+
+    let mut parents = sequence_number_to_children.iter()?;
+
+    cancel::skip(&mut parents, page_index.saturating_mul(page_size))?;
+
+    let mut collections = parents
       .take(page_size.saturating_add(1))
       .map(|result| {
         result
@@ -1218,10 +1735,10 @@ impl Index {
   }
 
   #[cfg(test)]
-  pub(crate) fn get_parent_by_inscription_id(
+  pub(crate) fn get_parents_by_inscription_id(
     &self,
     inscription_id: InscriptionId,
-  ) -> InscriptionId {
+  ) -> Vec<InscriptionId> {
     let rtx = self.database.begin_read().unwrap();
 
     let sequence_number = rtx
@@ -1236,25 +1753,28 @@ impl Index {
       .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)
       .unwrap();
 
-    let parent_sequence_number = InscriptionEntry::load(
+    let parent_sequence_numbers = InscriptionEntry::load(
       sequence_number_to_inscription_entry
         .get(sequence_number)
         .unwrap()
         .unwrap()
         .value(),
     )
-    .parent
-    .unwrap();
-
-    let entry = InscriptionEntry::load(
-      sequence_number_to_inscription_entry
-        .get(parent_sequence_number)
-        .unwrap()
-        .unwrap()
-        .value(),
-    );
+    .parents;
 
-    entry.id
+    parent_sequence_numbers
+      .into_iter()
+      .map(|parent_sequence_number| {
+        InscriptionEntry::load(
+          sequence_number_to_inscription_entry
+            .get(parent_sequence_number)
+            .unwrap()
+            .unwrap()
+            .value(),
+        )
+        .id
+      })
+      .collect()
   }
 
   pub(crate) fn get_children_by_sequence_number_paginated(
@@ -1292,6 +1812,38 @@ impl Index {
     Ok((children, more))
   }
 
+  pub(crate) fn get_parents_by_sequence_number_paginated(
+    &self,
+    parent_sequence_numbers: &[u32],
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut parents = parent_sequence_numbers
+      .iter()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|sequence_number| {
+        Ok(
+          sequence_number_to_entry
+            .get(sequence_number)
+            .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)?,
+        )
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = parents.len() > page_size;
+
+    if more {
+      parents.pop();
+    }
+
+    Ok((parents, more))
+  }
+
   pub(crate) fn get_etching(&self, txid: Txid) -> Result<Option<SpacedRune>> {
     let rtx = self.database.begin_read()?;
 
@@ -1465,6 +2017,38 @@ impl Index {
     }))
   }
 
+  /// Inscriptions revealed by transactions in the node's mempool, which are
+  /// not yet indexed.
+  pub(crate) fn get_raw_mempool(&self) -> Result<Vec<Txid>> {
+    Ok(self.client.get_raw_mempool()?)
+  }
+
+  /// The inscriptions revealed by mempool transaction `txid`, or `None` if it
+  /// has been mined or evicted in the meantime.
+  pub(crate) fn get_mempool_transaction_inscriptions(
+    &self,
+    txid: Txid,
+  ) -> Result<Option<Vec<(InscriptionId, Inscription)>>> {
+    let Some(tx) = self.client.get_raw_transaction(&txid, None).into_option()? else {
+      return Ok(None);
+    };
+
+    ParsedEnvelope::from_transaction(&tx)
+      .into_iter()
+      .enumerate()
+      .map(|(index, envelope)| {
+        Ok((
+          InscriptionId {
+            txid,
+            index: index.try_into()?,
+          },
+          envelope.payload,
+        ))
+      })
+      .collect::<Result<Vec<(InscriptionId, Inscription)>>>()
+      .map(Some)
+  }
+
   pub(crate) fn inscription_count(&self, txid: Txid) -> Result<u32> {
     let start = InscriptionId { index: 0, txid };
 
@@ -1708,67 +2292,325 @@ impl Index {
       .map(|x| x.value())
       .unwrap_or(0);
 
-    let expected_blocks = height
-      .checked_sub(current)
-      .with_context(|| format!("current {current} height is greater than sat height {height}"))?;
+    let expected_blocks = height
+      .checked_sub(current)
+      .with_context(|| format!("current {current} height is greater than sat height {height}"))?;
+
+    Ok(Blocktime::Expected(
+      Utc::now()
+        .round_subsecs(0)
+        .checked_add_signed(chrono::Duration::seconds(
+          10 * 60 * i64::from(expected_blocks),
+        ))
+        .ok_or_else(|| anyhow!("block timestamp out of range"))?,
+    ))
+  }
+
+  pub(crate) fn get_inscriptions(
+    &self,
+    utxos: &BTreeMap<OutPoint, Amount>,
+  ) -> Result<BTreeMap<SatPoint, InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let mut result = BTreeMap::new();
+
+    let satpoint_to_sequence_number = rtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    for utxo in utxos.keys() {
+      result.extend(Self::inscriptions_on_output(
+        &satpoint_to_sequence_number,
+        &sequence_number_to_inscription_entry,
+        *utxo,
+      )?);
+    }
+
+    Ok(result)
+  }
+
+  pub(crate) fn get_inscriptions_paginated(
+    &self,
+    page_size: usize,
+    page_index: usize,
+    order: Order,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let entries = sequence_number_to_inscription_entry.iter()?;
+
+    let mut entries: Box<dyn Iterator<Item = _>> = match order {
+      Order::Asc => Box::new(entries),
+      Order::Desc => Box::new(entries.rev()),
+    };
+
+    cancel::skip(&mut entries, page_size.saturating_mul(page_index))?;
+
+    let mut inscriptions = entries
+      .take(page_size.saturating_add(1))
+      .flat_map(|result| result.map(|(_number, entry)| InscriptionEntry::load(entry.value()).id))
+      .collect::<Vec<InscriptionId>>();
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok((inscriptions, more))
+  }
+
+  /// Up to `page_size` inscriptions with sequence numbers after
+  /// `sequence_number`, oldest first, with their sequence numbers, and whether
+  /// there are more.
+  pub(crate) fn get_inscriptions_since(
+    &self,
+    page_size: usize,
+    sequence_number: u32,
+  ) -> Result<(Vec<(u32, InscriptionId)>, bool)> {
+    let Some(first) = sequence_number.checked_add(1) else {
+      return Ok((Vec::new(), false));
+    };
+
+    let rtx = self.database.begin_read()?;
+
+    let mut inscriptions = rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .range(first..)?
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        result
+          .map(|(sequence_number, entry)| {
+            (
+              sequence_number.value(),
+              InscriptionEntry::load(entry.value()).id,
+            )
+          })
+          .map_err(|err| anyhow!(err))
+      })
+      .collect::<Result<Vec<(u32, InscriptionId)>>>()?;
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok((inscriptions, more))
+  }
+
+  pub(crate) fn get_inscriptions_in_height_range_paginated(
+    &self,
+    from_height: u32,
+    to_height: u32,
+    page_size: usize,
+    page_index: usize,
+    order: Order,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let last_sequence_number_before = |height: u32| -> Result<u32> {
+      Ok(
+        height_to_last_sequence_number
+          .range(..height)?
+          .next_back()
+          .transpose()?
+          .map(|(_height, sequence_number)| sequence_number.value())
+          .unwrap_or(0),
+      )
+    };
+
+    let oldest_sequence_number = last_sequence_number_before(from_height)?;
+    let newest_sequence_number = last_sequence_number_before(to_height)?;
+
+    let entries =
+      sequence_number_to_inscription_entry.range(oldest_sequence_number..newest_sequence_number)?;
+
+    let mut entries: Box<dyn Iterator<Item = _>> = match order {
+      Order::Asc => Box::new(entries),
+      Order::Desc => Box::new(entries.rev()),
+    };
+
+    cancel::skip(&mut entries, page_size.saturating_mul(page_index))?;
+
+    let mut inscriptions = entries
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        result
+          .map(|(_number, entry)| InscriptionEntry::load(entry.value()).id)
+          .map_err(|err| anyhow!(err))
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
 
-    Ok(Blocktime::Expected(
-      Utc::now()
-        .round_subsecs(0)
-        .checked_add_signed(chrono::Duration::seconds(
-          10 * 60 * i64::from(expected_blocks),
-        ))
-        .ok_or_else(|| anyhow!("block timestamp out of range"))?,
-    ))
+    Ok((inscriptions, more))
   }
 
-  pub(crate) fn get_inscriptions(
+  /// List up to `page_size` inscriptions following the one with sequence
+  /// number `cursor` in `order`, optionally restricted to a height range.
+  /// Returns `None` if `cursor` is not an inscription in the listing.
+  pub(crate) fn get_inscriptions_after_cursor(
     &self,
-    utxos: &BTreeMap<OutPoint, Amount>,
-  ) -> Result<BTreeMap<SatPoint, InscriptionId>> {
+    page_size: usize,
+    cursor: u32,
+    order: Order,
+    height_range: Option<(u32, u32)>,
+  ) -> Result<Option<(Vec<(u32, InscriptionId)>, bool)>> {
     let rtx = self.database.begin_read()?;
 
-    let mut result = BTreeMap::new();
-
-    let satpoint_to_sequence_number = rtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
-    for utxo in utxos.keys() {
-      result.extend(Self::inscriptions_on_output(
-        &satpoint_to_sequence_number,
-        &sequence_number_to_inscription_entry,
-        *utxo,
-      )?);
+
+    let (oldest_sequence_number, newest_sequence_number) =
+      Self::sequence_number_range(&rtx, height_range)?;
+
+    if !(oldest_sequence_number..newest_sequence_number).contains(&cursor)
+      || sequence_number_to_inscription_entry.get(cursor)?.is_none()
+    {
+      return Ok(None);
     }
 
-    Ok(result)
+    let entries: Box<dyn Iterator<Item = _>> = match order {
+      Order::Asc => {
+        Box::new(sequence_number_to_inscription_entry.range(cursor + 1..newest_sequence_number)?)
+      }
+      Order::Desc => Box::new(
+        sequence_number_to_inscription_entry
+          .range(oldest_sequence_number..cursor)?
+          .rev(),
+      ),
+    };
+
+    let mut inscriptions = entries
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        result
+          .map(|(sequence_number, entry)| {
+            (
+              sequence_number.value(),
+              InscriptionEntry::load(entry.value()).id,
+            )
+          })
+          .map_err(|err| anyhow!(err))
+      })
+      .collect::<Result<Vec<(u32, InscriptionId)>>>()?;
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok(Some((inscriptions, more)))
   }
 
-  pub(crate) fn get_inscriptions_paginated(
+  /// Scans inscriptions after `cursor`, or from the first one in `order`
+  /// without a cursor, for at most `page_size` whose content type satisfies
+  /// `matches`, giving up after `scan_limit` rows. Also returns the sequence number of
+  /// the last row scanned if any rows remain, and whether the scan stopped
+  /// because of `scan_limit` before the page filled.
+  pub(crate) fn get_inscriptions_matching(
     &self,
     page_size: usize,
-    page_index: usize,
-  ) -> Result<(Vec<InscriptionId>, bool)> {
+    cursor: Option<u32>,
+    order: Order,
+    height_range: Option<(u32, u32)>,
+    scan_limit: usize,
+    mut matches: impl FnMut(Option<&str>) -> bool,
+  ) -> Result<Option<(Vec<(u32, InscriptionId)>, Option<u32>, bool)>> {
     let rtx = self.database.begin_read()?;
 
+    let sequence_number_to_content = rtx.open_table(SEQUENCE_NUMBER_TO_CONTENT)?;
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let mut inscriptions = sequence_number_to_inscription_entry
-      .iter()?
-      .rev()
-      .skip(page_size.saturating_mul(page_index))
-      .take(page_size.saturating_add(1))
-      .flat_map(|result| result.map(|(_number, entry)| InscriptionEntry::load(entry.value()).id))
-      .collect::<Vec<InscriptionId>>();
+    let (oldest_sequence_number, newest_sequence_number) =
+      Self::sequence_number_range(&rtx, height_range)?;
 
-    let more = inscriptions.len() > page_size;
+    if let Some(cursor) = cursor {
+      if !(oldest_sequence_number..newest_sequence_number).contains(&cursor)
+        || sequence_number_to_inscription_entry.get(cursor)?.is_none()
+      {
+        return Ok(None);
+      }
+    }
 
-    if more {
-      inscriptions.pop();
+    let contents: Box<dyn Iterator<Item = _>> = match order {
+      Order::Asc => Box::new(sequence_number_to_content.range(
+        cursor.map_or(oldest_sequence_number, |cursor| cursor + 1)..newest_sequence_number,
+      )?),
+      Order::Desc => Box::new(
+        sequence_number_to_content
+          .range(oldest_sequence_number..cursor.unwrap_or(newest_sequence_number))?
+          .rev(),
+      ),
+    };
+
+    let mut inscriptions = Vec::new();
+    let mut last_scanned = None;
+
+    for (scanned, result) in contents.enumerate() {
+      if inscriptions.len() == page_size || scanned == scan_limit {
+        let truncated = inscriptions.len() < page_size;
+        return Ok(Some((inscriptions, last_scanned, truncated)));
+      }
+
+      cancel::check()?;
+
+      let (sequence_number, content) = result?;
+      let sequence_number = sequence_number.value();
+      let (content_type, _content_length) = content.value();
+
+      last_scanned = Some(sequence_number);
+
+      if matches(content_type) {
+        let entry = sequence_number_to_inscription_entry
+          .get(sequence_number)?
+          .unwrap();
+        inscriptions.push((sequence_number, InscriptionEntry::load(entry.value()).id));
+      }
     }
 
-    Ok((inscriptions, more))
+    Ok(Some((inscriptions, None, false)))
+  }
+
+  /// The half-open range of sequence numbers of inscriptions created in
+  /// `height_range`, or all sequence numbers without one.
+  fn sequence_number_range(
+    rtx: &redb::ReadTransaction,
+    height_range: Option<(u32, u32)>,
+  ) -> Result<(u32, u32)> {
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+
+    let last_sequence_number_before = |height: u32| -> Result<u32> {
+      Ok(
+        height_to_last_sequence_number
+          .range(..height)?
+          .next_back()
+          .transpose()?
+          .map(|(_height, sequence_number)| sequence_number.value())
+          .unwrap_or(0),
+      )
+    };
+
+    Ok(match height_range {
+      Some((from_height, to_height)) => (
+        last_sequence_number_before(from_height)?,
+        last_sequence_number_before(to_height)?,
+      ),
+      None => (0, u32::MAX),
+    })
   }
 
   pub(crate) fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
@@ -1990,18 +2832,21 @@ impl Index {
       None
     };
 
-    let parent = match entry.parent {
-      Some(parent) => Some(
-        InscriptionEntry::load(
-          sequence_number_to_inscription_entry
-            .get(parent)?
-            .unwrap()
-            .value(),
+    let parents = entry
+      .parents
+      .iter()
+      .map(|parent| {
+        Ok(
+          InscriptionEntry::load(
+            sequence_number_to_inscription_entry
+              .get(parent)?
+              .unwrap()
+              .value(),
+          )
+          .id,
         )
-        .id,
-      ),
-      None => None,
-    };
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
 
     let mut charms = entry.charms;
 
@@ -2012,7 +2857,7 @@ impl Index {
     Ok(Some(InscriptionInfo {
       children,
       entry,
-      parent,
+      parents,
       output,
       satpoint,
       inscription,
@@ -2023,6 +2868,95 @@ impl Index {
     }))
   }
 
+  /// The content type and stored content length of each of `inscription_ids`,
+  /// read from the index rather than from the inscribing transactions.
+  pub(crate) fn get_inscriptions_content(
+    &self,
+    inscription_ids: &[InscriptionId],
+  ) -> Result<Vec<(Option<String>, Option<usize>)>> {
+    let rtx = self.database.begin_read()?;
+
+    let id_to_sequence_number = rtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_content = rtx.open_table(SEQUENCE_NUMBER_TO_CONTENT)?;
+
+    let mut content = Vec::with_capacity(inscription_ids.len());
+
+    for inscription_id in inscription_ids {
+      cancel::check()?;
+
+      let Some(sequence_number) = id_to_sequence_number
+        .get(&inscription_id.store())?
+        .map(|guard| guard.value())
+      else {
+        content.push((None, None));
+        continue;
+      };
+
+      content.push(match sequence_number_to_content.get(sequence_number)? {
+        Some(guard) => {
+          let (content_type, content_length) = guard.value();
+          (
+            content_type.map(str::to_string),
+            content_length.map(usize::try_from).transpose()?,
+          )
+        }
+        None => (None, None),
+      });
+    }
+
+    Ok(content)
+  }
+
+  /// The inscriptions with a `text/*` content type among the newest
+  /// `scan_limit` inscriptions, newest first, and whether there are older
+  /// inscriptions that were not visited.
+  pub(crate) fn get_text_inscriptions(
+    &self,
+    scan_limit: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut inscriptions = Vec::new();
+
+    for (scanned, result) in rtx
+      .open_table(SEQUENCE_NUMBER_TO_CONTENT)?
+      .iter()?
+      .rev()
+      .enumerate()
+    {
+      if scanned == scan_limit {
+        return Ok((inscriptions, true));
+      }
+
+      cancel::check()?;
+
+      let (sequence_number, content) = result?;
+
+      let (content_type, _content_length) = content.value();
+
+      if !content_type
+        .map(|content_type| content_type.starts_with("text/"))
+        .unwrap_or_default()
+      {
+        continue;
+      }
+
+      inscriptions.push(
+        InscriptionEntry::load(
+          sequence_number_to_entry
+            .get(sequence_number.value())?
+            .unwrap()
+            .value(),
+        )
+        .id,
+      );
+    }
+
+    Ok((inscriptions, false))
+  }
+
   pub(crate) fn get_inscription_entry(
     &self,
     inscription_id: InscriptionId,
@@ -2216,6 +3150,38 @@ mod tests {
     }
   }
 
+  #[test]
+  fn capacity() {
+    assert_eq!(Capacity::of(0, 100), Capacity::Available);
+    assert_eq!(Capacity::of(89, 100), Capacity::Available);
+    assert_eq!(Capacity::of(90, 100), Capacity::NearlyFull);
+    assert_eq!(Capacity::of(99, 100), Capacity::NearlyFull);
+    assert_eq!(Capacity::of(100, 100), Capacity::Full);
+    assert_eq!(Capacity::of(u64::MAX, u64::MAX - 1), Capacity::Full);
+  }
+
+  #[test]
+  fn max_index_size_pauses_indexing() {
+    {
+      let context = Context::builder().build();
+      context.mine_blocks(2);
+      assert!(!context.index.is_at_capacity());
+      assert_eq!(context.index.block_count().unwrap(), 3);
+    }
+
+    {
+      let context = Context::builder()
+        .args(["--max-index-size-gb", "0.000001"])
+        .build();
+      assert!(context.index.is_at_capacity());
+      assert_eq!(context.index.block_count().unwrap(), 1);
+
+      context.mine_blocks(2);
+      assert!(context.index.is_at_capacity());
+      assert_eq!(context.index.block_count().unwrap(), 1);
+    }
+  }
+
   #[test]
   fn inscriptions_below_first_inscription_height_are_skipped() {
     let inscription = inscription("text/plain;charset=utf-8", "hello");
@@ -3462,7 +4428,10 @@ mod tests {
 
       context.mine_blocks(1);
 
-      let (inscriptions, more) = context.index.get_inscriptions_paginated(100, 0).unwrap();
+      let (inscriptions, more) = context
+        .index
+        .get_inscriptions_paginated(100, 0, Order::Desc)
+        .unwrap();
       assert_eq!(inscriptions, &[inscription_id]);
       assert!(!more);
     }
@@ -4492,36 +5461,151 @@ mod tests {
         ..Default::default()
       });
 
-      let second = InscriptionId { txid, index: 0 };
+      let second = InscriptionId { txid, index: 0 };
+
+      context.mine_blocks(1);
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          3,
+          1,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        )],
+        ..Default::default()
+      });
+
+      let third = InscriptionId { txid, index: 0 };
+
+      context.mine_blocks(1);
+
+      let location = SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      };
+
+      assert_eq!(
+        vec![(location, first), (location, second), (location, third)],
+        context
+          .index
+          .get_inscriptions_on_output_with_satpoints(OutPoint { txid, vout: 0 })
+          .unwrap()
+      )
+    }
+  }
+
+  #[test]
+  fn inscription_outpoints_are_tracked_by_script_pubkey() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..Default::default()
+      });
+
+      context.mine_blocks(1);
+
+      // Test transactions pay to an empty script.
+      assert_eq!(
+        context
+          .index
+          .begin_read()
+          .unwrap()
+          .script_pubkey_to_inscription_outpoints(&ScriptBuf::new())
+          .unwrap(),
+        [OutPoint { txid, vout: 0 }],
+      );
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 1, 0, Default::default())],
+        outputs: 2,
+        ..Default::default()
+      });
+
+      context.mine_blocks(1);
+
+      assert_eq!(
+        context
+          .index
+          .begin_read()
+          .unwrap()
+          .script_pubkey_to_inscription_outpoints(&ScriptBuf::new())
+          .unwrap(),
+        [OutPoint { txid, vout: 0 }],
+      );
+
+      let rtx = context.index.database.begin_read().unwrap();
+
+      assert_eq!(
+        rtx
+          .open_table(INSCRIPTION_OUTPOINT_TO_SCRIPT_PUBKEY)
+          .unwrap()
+          .len()
+          .unwrap(),
+        1,
+      );
+    }
+  }
+
+  #[test]
+  fn get_inscriptions_matching_stops_at_scan_limit() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(5);
+
+    let mut ids = Vec::new();
+
+    for i in 0..5 {
+      let content_type = if i % 2 == 1 {
+        "image/png"
+      } else {
+        "text/plain"
+      };
+
+      ids.push(InscriptionId {
+        txid: context.rpc_server.broadcast_tx(TransactionTemplate {
+          inputs: &[(i + 1, 0, 0, inscription(content_type, "hello").to_witness())],
+          ..Default::default()
+        }),
+        index: 0,
+      });
+    }
+
+    context.mine_blocks(1);
 
-      context.mine_blocks(1);
-      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(
-          3,
-          1,
-          0,
-          inscription("text/plain;charset=utf-8", "hello").to_witness(),
-        )],
-        ..Default::default()
-      });
+    let odd = |content_type: Option<&str>| content_type == Some("image/png");
 
-      let third = InscriptionId { txid, index: 0 };
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_matching(10, None, Order::Asc, None, 3, odd)
+        .unwrap(),
+      Some((vec![(1, ids[1])], Some(2), true)),
+    );
 
-      context.mine_blocks(1);
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_matching(10, Some(2), Order::Asc, None, 3, odd)
+        .unwrap(),
+      Some((vec![(3, ids[3])], None, false)),
+    );
 
-      let location = SatPoint {
-        outpoint: OutPoint { txid, vout: 0 },
-        offset: 0,
-      };
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_matching(1, None, Order::Desc, None, 10, odd)
+        .unwrap(),
+      Some((vec![(3, ids[3])], Some(3), false)),
+    );
 
-      assert_eq!(
-        vec![(location, first), (location, second), (location, third)],
-        context
-          .index
-          .get_inscriptions_on_output_with_satpoints(OutPoint { txid, vout: 0 })
-          .unwrap()
-      )
-    }
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_matching(1, Some(10), Order::Desc, None, 10, odd)
+        .unwrap(),
+      None,
+    );
   }
 
   #[test]
@@ -4633,6 +5717,128 @@ mod tests {
     }
   }
 
+  #[test]
+  fn inscriptions_in_height_range() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let first = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let second = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let first = InscriptionId {
+      txid: first,
+      index: 0,
+    };
+    let second = InscriptionId {
+      txid: second,
+      index: 0,
+    };
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_in_height_range_paginated(0, 2, 100, 0, Order::Asc)
+        .unwrap(),
+      (Vec::new(), false)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_in_height_range_paginated(2, 4, 100, 0, Order::Asc)
+        .unwrap(),
+      (vec![first, second], false)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_in_height_range_paginated(2, 4, 1, 0, Order::Asc)
+        .unwrap(),
+      (vec![first], true)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_in_height_range_paginated(3, 10, 100, 0, Order::Asc)
+        .unwrap(),
+      (vec![second], false)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_in_height_range_paginated(10, 20, 100, 0, Order::Asc)
+        .unwrap(),
+      (Vec::new(), false)
+    );
+  }
+
+  #[test]
+  fn inscriptions_in_height_range_across_reorg() {
+    for mut context in Context::configurations() {
+      context.index.set_durability(redb::Durability::Immediate);
+
+      context.mine_blocks(1);
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..Default::default()
+      });
+      let first = InscriptionId { txid, index: 0 };
+
+      context.mine_blocks(6);
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..Default::default()
+      });
+      let second = InscriptionId { txid, index: 0 };
+
+      context.mine_blocks(1);
+
+      assert_eq!(
+        context
+          .index
+          .get_inscriptions_in_height_range_paginated(0, 10, 100, 0, Order::Asc)
+          .unwrap(),
+        (vec![first, second], false)
+      );
+
+      context.rpc_server.invalidate_tip();
+      context.mine_blocks(2);
+
+      assert_eq!(
+        context
+          .index
+          .get_inscriptions_in_height_range_paginated(0, 10, 100, 0, Order::Asc)
+          .unwrap(),
+        (vec![first], false)
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_inscriptions_in_height_range_paginated(8, 10, 100, 0, Order::Asc)
+          .unwrap(),
+        (Vec::new(), false)
+      );
+    }
+  }
+
   #[test]
   fn recover_from_3_block_deep_and_consecutive_reorg() {
     for mut context in Context::configurations() {
@@ -4757,6 +5963,154 @@ mod tests {
     }
   }
 
+  #[test]
+  fn reorgs_are_recorded_and_published() {
+    for mut context in Context::configurations() {
+      context.index.set_durability(redb::Durability::Immediate);
+
+      context.mine_blocks(1);
+
+      let orphaned_blocks = context
+        .mine_blocks(2)
+        .iter()
+        .map(|block| block.block_hash())
+        .collect::<Vec<BlockHash>>();
+
+      let mut receiver = context.index.subscribe();
+
+      context.rpc_server.invalidate_tip();
+      context.rpc_server.invalidate_tip();
+      context.mine_blocks(3);
+
+      let reorg = ReorgEvent {
+        common_ancestor_height: 1,
+        orphaned_blocks,
+      };
+
+      assert_eq!(context.index.reorgs_since(0), vec![reorg.clone()]);
+      assert_eq!(context.index.reorgs_since(1), vec![reorg.clone()]);
+      assert_eq!(context.index.reorgs_since(2), Vec::new());
+
+      let mut events = Vec::new();
+      while let Ok(batch) = receiver.try_recv() {
+        events.extend(batch.iter().cloned());
+      }
+
+      assert_eq!(events.first(), Some(&Event::Reorg(reorg)));
+      assert_eq!(
+        events.last(),
+        Some(&Event::BlockCommitted {
+          height: 4,
+          hash: context.index.block_hash(Some(4)).unwrap().unwrap(),
+        })
+      );
+    }
+  }
+
+  #[test]
+  fn events_of_blocks_larger_than_channel_capacity_are_not_lagged() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(EVENT_CHANNEL_CAPACITY as u64 + 1);
+
+    let mut receiver = context.index.subscribe();
+
+    for i in 0..=EVENT_CHANNEL_CAPACITY {
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/plain", "foo").to_witness())],
+        ..Default::default()
+      });
+    }
+
+    context.mine_blocks(1);
+
+    let events = receiver.try_recv().unwrap();
+
+    assert_eq!(events.len(), EVENT_CHANNEL_CAPACITY + 2);
+    assert!(events[..EVENT_CHANNEL_CAPACITY + 1]
+      .iter()
+      .all(|event| matches!(event, Event::InscriptionCreated { .. })));
+    assert!(matches!(
+      events.last(),
+      Some(Event::BlockCommitted { height, .. }) if *height == EVENT_CHANNEL_CAPACITY as u32 + 2
+    ));
+  }
+
+  #[test]
+  fn brc20_tick_receipts_are_stable_across_reorgs() {
+    let mut context = Context::builder().arg("--enable-index-brc20").build();
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(4);
+
+    let brc20 = |height, content: &str| {
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          height,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", content).to_witness(),
+        )],
+        ..Default::default()
+      });
+      context.mine_blocks(1);
+    };
+
+    brc20(
+      1,
+      r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"100"}"#,
+    );
+    brc20(2, r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"100"}"#);
+    brc20(3, r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"100"}"#);
+
+    context.rpc_server.invalidate_tip();
+
+    brc20(4, r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"50"}"#);
+    context.mine_blocks(1);
+
+    let receipts = Index::get_brc20_tick_receipts(
+      &brc20::Tick::from_str("ORDI").unwrap(),
+      0,
+      100,
+      &context.index.begin_read().unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+      receipts
+        .iter()
+        .map(|(height, _, receipt)| match &receipt.result {
+          Ok(brc20::Event::Deploy(_)) => (*height, 0),
+          Ok(brc20::Event::Mint(mint)) => (*height, mint.amount / 10u128.pow(18)),
+          event => panic!("unexpected event {event:?}"),
+        })
+        .collect::<Vec<(u32, u128)>>(),
+      [(5, 0), (6, 100), (7, 50)]
+    );
+
+    assert_eq!(
+      receipts.last().unwrap().1,
+      context.rpc_server.tx(7, 1).txid()
+    );
+  }
+
+  #[test]
+  fn reorg_history_is_bounded() {
+    let context = Context::builder().build();
+
+    for common_ancestor_height in 0..u32::try_from(MAX_REORG_HISTORY).unwrap() + 2 {
+      context.index.record_reorg(ReorgEvent {
+        common_ancestor_height,
+        orphaned_blocks: Vec::new(),
+      });
+    }
+
+    let reorgs = context.index.reorgs_since(0);
+
+    assert_eq!(reorgs.len(), MAX_REORG_HISTORY);
+    assert_eq!(reorgs[0].common_ancestor_height, 2);
+  }
+
   #[test]
   fn inscription_without_parent_tag_has_no_parent_entry() {
     for context in Context::configurations() {
@@ -4776,8 +6130,8 @@ mod tests {
         .get_inscription_entry(inscription_id)
         .unwrap()
         .unwrap()
-        .parent
-        .is_none());
+        .parents
+        .is_empty());
     }
   }
 
@@ -4806,7 +6160,7 @@ mod tests {
           Inscription {
             content_type: Some("text/plain".into()),
             body: Some("hello".into()),
-            parent: Some(parent_inscription_id.value()),
+            parents: vec![parent_inscription_id.value()],
             ..Default::default()
           }
           .to_witness(),
@@ -4823,8 +6177,8 @@ mod tests {
         .get_inscription_entry(inscription_id)
         .unwrap()
         .unwrap()
-        .parent
-        .is_none());
+        .parents
+        .is_empty());
     }
   }
 
@@ -4853,7 +6207,7 @@ mod tests {
           Inscription {
             content_type: Some("text/plain".into()),
             body: Some("hello".into()),
-            parent: Some(parent_inscription_id.value()),
+            parents: vec![parent_inscription_id.value()],
             ..Default::default()
           }
           .to_witness(),
@@ -4866,8 +6220,8 @@ mod tests {
       let inscription_id = InscriptionId { txid, index: 0 };
 
       assert_eq!(
-        context.index.get_parent_by_inscription_id(inscription_id),
-        parent_inscription_id
+        context.index.get_parents_by_inscription_id(inscription_id),
+        vec![parent_inscription_id]
       );
 
       assert_eq!(
@@ -4907,7 +6261,7 @@ mod tests {
             Inscription {
               content_type: Some("text/plain".into()),
               body: Some("hello".into()),
-              parent: Some(parent_inscription_id.value()),
+              parents: vec![parent_inscription_id.value()],
               ..Default::default()
             }
             .to_witness(),
@@ -4921,8 +6275,8 @@ mod tests {
       let inscription_id = InscriptionId { txid, index: 0 };
 
       assert_eq!(
-        context.index.get_parent_by_inscription_id(inscription_id),
-        parent_inscription_id
+        context.index.get_parents_by_inscription_id(inscription_id),
+        vec![parent_inscription_id]
       );
 
       assert_eq!(
@@ -4961,7 +6315,7 @@ mod tests {
             Inscription {
               content_type: Some("text/plain".into()),
               body: Some("hello".into()),
-              parent: Some(parent_inscription_id.value()),
+              parents: vec![parent_inscription_id.value()],
               ..Default::default()
             }
             .to_witness(),
@@ -4976,8 +6330,8 @@ mod tests {
       let inscription_id = InscriptionId { txid, index: 0 };
 
       assert_eq!(
-        context.index.get_parent_by_inscription_id(inscription_id),
-        parent_inscription_id
+        context.index.get_parents_by_inscription_id(inscription_id),
+        vec![parent_inscription_id]
       );
 
       assert_eq!(
@@ -5015,13 +6369,11 @@ mod tests {
           Inscription {
             content_type: Some("text/plain".into()),
             body: Some("hello".into()),
-            parent: Some(
-              parent_inscription_id
-                .value()
-                .into_iter()
-                .chain(iter::once(0))
-                .collect(),
-            ),
+            parents: vec![parent_inscription_id
+              .value()
+              .into_iter()
+              .chain(iter::once(0))
+              .collect()],
             ..Default::default()
           }
           .to_witness(),
@@ -5038,8 +6390,8 @@ mod tests {
         .get_inscription_entry(inscription_id)
         .unwrap()
         .unwrap()
-        .parent
-        .is_none());
+        .parents
+        .is_empty());
     }
   }
 
@@ -5202,7 +6554,7 @@ mod tests {
       let child_inscription = Inscription {
         content_type: Some("text/plain".into()),
         body: Some("pointer-child".into()),
-        parent: Some(parent_inscription_id.value()),
+        parents: vec![parent_inscription_id.value()],
         pointer: Some(0u64.to_le_bytes().to_vec()),
         ..Default::default()
       };
@@ -5247,8 +6599,8 @@ mod tests {
       assert_eq!(
         context
           .index
-          .get_parent_by_inscription_id(child_inscription_id),
-        parent_inscription_id
+          .get_parents_by_inscription_id(child_inscription_id),
+        vec![parent_inscription_id]
       );
 
       assert_eq!(
@@ -5893,4 +7245,72 @@ mod tests {
       assert_eq!(sat, entry.sat);
     }
   }
+
+  #[test]
+  fn readonly_index_reads_without_writing_the_index_file() {
+    let Context {
+      index,
+      options,
+      rpc_server,
+      tempdir: _tempdir,
+    } = Context::builder().build();
+
+    rpc_server.mine_blocks(1);
+    index.update().unwrap();
+    let path = index.path.clone();
+    drop(index);
+
+    let before = fs::read(&path).unwrap();
+
+    let index = Index::open_readonly(&options).unwrap();
+
+    assert_eq!(index.block_count().unwrap(), 2);
+
+    drop(index);
+
+    assert!(fs::read(&path).unwrap() == before);
+  }
+
+  #[test]
+  fn text_inscriptions_among_scanned_are_listed_newest_first() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let mut ids = Vec::new();
+
+    for (i, content_type) in ["text/plain", "image/png", "text/html", "text/css"]
+      .into_iter()
+      .enumerate()
+    {
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription(content_type, "foo").to_witness())],
+        ..Default::default()
+      });
+
+      ids.push(InscriptionId { txid, index: 0 });
+
+      context.mine_blocks(1);
+    }
+
+    assert_eq!(
+      context.index.get_text_inscriptions(10).unwrap(),
+      (vec![ids[3], ids[2], ids[0]], false)
+    );
+
+    assert_eq!(
+      context.index.get_text_inscriptions(2).unwrap(),
+      (vec![ids[3], ids[2]], true)
+    );
+
+    assert_eq!(
+      context.index.get_text_inscriptions(3).unwrap(),
+      (vec![ids[3], ids[2]], true)
+    );
+
+    assert_eq!(
+      context.index.get_text_inscriptions(4).unwrap(),
+      (vec![ids[3], ids[2], ids[0]], false)
+    );
+  }
 }
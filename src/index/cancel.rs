@@ -0,0 +1,79 @@
+use {super::*, std::cell::RefCell};
+
+thread_local! {
+  static CANCELLED: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// Restores the cancel flag a thread had before `scope`, even if `scope`
+/// unwinds.
+struct Restore(Option<Arc<AtomicBool>>);
+
+impl Drop for Restore {
+  fn drop(&mut self) {
+    CANCELLED.with(|cancelled| *cancelled.borrow_mut() = self.0.take());
+  }
+}
+
+/// Run `f` with `cancelled` as the cancel flag of the index queries it makes
+/// on this thread.
+pub(crate) fn scope<T>(cancelled: Option<Arc<AtomicBool>>, f: impl FnOnce() -> T) -> T {
+  let _restore = Restore(CANCELLED.with(|previous| previous.replace(cancelled)));
+  f()
+}
+
+/// The cancel flag of this thread, for work handed to other threads.
+pub(crate) fn current() -> Option<Arc<AtomicBool>> {
+  CANCELLED.with(|cancelled| cancelled.borrow().clone())
+}
+
+/// Fail if the cancel flag of this thread is set, because the request the
+/// query is made for has timed out. Long queries call this between rows, so
+/// that they stop early and release their read transaction.
+pub(crate) fn check() -> Result {
+  let cancelled = CANCELLED.with(|cancelled| {
+    cancelled
+      .borrow()
+      .as_ref()
+      .map_or(false, |cancelled| cancelled.load(atomic::Ordering::Relaxed))
+  });
+
+  if cancelled {
+    bail!("query cancelled");
+  }
+
+  Ok(())
+}
+
+/// Advance `iter` by `n` items, the rows before a page, checking the cancel
+/// flag before each.
+pub(crate) fn skip(iter: &mut impl Iterator, n: usize) -> Result {
+  for _ in 0..n {
+    check()?;
+
+    if iter.next().is_none() {
+      break;
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn queries_are_only_cancelled_in_scope() {
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    assert!(check().is_ok());
+
+    scope(Some(cancelled.clone()), || {
+      assert!(check().is_ok());
+      cancelled.store(true, atomic::Ordering::Relaxed);
+      assert_eq!(check().unwrap_err().to_string(), "query cancelled");
+    });
+
+    assert!(check().is_ok());
+  }
+}
@@ -30,16 +30,19 @@ impl Entry for Header {
 
 #[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub struct RuneEntry {
+  #[serde(with = "crate::json_amount")]
   pub burned: u128,
   pub deadline: Option<u32>,
   pub divisibility: u8,
   pub end: Option<u32>,
   pub etching: Txid,
+  #[serde(with = "crate::json_amount::option")]
   pub limit: Option<u128>,
   pub mints: u64,
   pub number: u64,
   pub rune: Rune,
   pub spacers: u32,
+  #[serde(with = "crate::json_amount")]
   pub supply: u128,
   pub symbol: Option<char>,
   pub timestamp: u32,
@@ -70,6 +73,14 @@ impl RuneEntry {
       spacers: self.spacers,
     }
   }
+
+  /// Whether a claim in a block at `height` with timestamp `timestamp` would
+  /// mint this rune, following the open etching checks of the rune updater.
+  pub(crate) fn mintable(&self, height: u32, timestamp: u32) -> bool {
+    self.limit.is_some()
+      && self.end.map_or(true, |end| height < end)
+      && self.deadline.map_or(true, |deadline| timestamp < deadline)
+  }
 }
 
 impl Default for RuneEntry {
@@ -181,6 +192,94 @@ impl Entry for RuneId {
   }
 }
 
+/// The movement of one rune in one transaction: the balances of the spent
+/// outputs that held it, the amount etched or minted, the balances assigned
+/// to the transaction's outputs, and the amount burned.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RuneTransferEntry {
+  pub(crate) burned: u128,
+  pub(crate) etching: bool,
+  pub(crate) inputs: Vec<(OutPoint, u128)>,
+  pub(crate) minted: u128,
+  pub(crate) outputs: Vec<(u32, u128)>,
+  pub(crate) txid: Txid,
+}
+
+impl Default for RuneTransferEntry {
+  fn default() -> Self {
+    Self {
+      burned: 0,
+      etching: false,
+      inputs: Vec::new(),
+      minted: 0,
+      outputs: Vec::new(),
+      txid: Txid::all_zeros(),
+    }
+  }
+}
+
+impl Entry for RuneTransferEntry {
+  type Value = Vec<u8>;
+
+  fn load(value: Self::Value) -> Self {
+    let mut i = 32;
+
+    let next = |i: &mut usize| {
+      let (n, len) = runes::varint::decode(&value[*i..]);
+      *i += len;
+      n
+    };
+
+    let burned = next(&mut i);
+    let etching = next(&mut i) != 0;
+    let minted = next(&mut i);
+
+    let mut inputs = Vec::new();
+    for _ in 0..next(&mut i) {
+      let outpoint = OutPoint::load(value[i..i + 36].try_into().unwrap());
+      i += 36;
+      inputs.push((outpoint, next(&mut i)));
+    }
+
+    let mut outputs = Vec::new();
+    for _ in 0..next(&mut i) {
+      let vout = u32::try_from(next(&mut i)).unwrap();
+      outputs.push((vout, next(&mut i)));
+    }
+
+    Self {
+      burned,
+      etching,
+      inputs,
+      minted,
+      outputs,
+      txid: Txid::from_byte_array(value[..32].try_into().unwrap()),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    let mut value = self.txid.to_byte_array().to_vec();
+
+    runes::varint::encode_to_vec(self.burned, &mut value);
+    runes::varint::encode_to_vec(self.etching.into(), &mut value);
+    runes::varint::encode_to_vec(self.minted, &mut value);
+
+    runes::varint::encode_to_vec(self.inputs.len().try_into().unwrap(), &mut value);
+    for (outpoint, amount) in self.inputs {
+      value.extend_from_slice(&outpoint.store());
+      runes::varint::encode_to_vec(amount, &mut value);
+    }
+
+    runes::varint::encode_to_vec(self.outputs.len().try_into().unwrap(), &mut value);
+    for (vout, amount) in self.outputs {
+      runes::varint::encode_to_vec(vout.into(), &mut value);
+      runes::varint::encode_to_vec(amount, &mut value);
+    }
+
+    value
+  }
+}
+
 #[derive(Debug)]
 pub(crate) struct InscriptionEntry {
   pub(crate) charms: u16,
@@ -188,7 +287,7 @@ pub(crate) struct InscriptionEntry {
   pub(crate) height: u32,
   pub(crate) id: InscriptionId,
   pub(crate) inscription_number: i32,
-  pub(crate) parent: Option<u32>,
+  pub(crate) parents: Vec<u32>,
   pub(crate) sat: Option<Sat>,
   pub(crate) sequence_number: u32,
   pub(crate) timestamp: u32,
@@ -200,7 +299,7 @@ pub(crate) type InscriptionEntryValue = (
   u32,                // height
   InscriptionIdValue, // inscription id
   i32,                // inscription number
-  Option<u32>,        // parent
+  Vec<u32>,           // parents
   Option<u64>,        // sat
   u32,                // sequence number
   u32,                // timestamp
@@ -217,7 +316,7 @@ impl Entry for InscriptionEntry {
       height,
       id,
       inscription_number,
-      parent,
+      parents,
       sat,
       sequence_number,
       timestamp,
@@ -229,7 +328,7 @@ impl Entry for InscriptionEntry {
       height,
       id: InscriptionId::load(id),
       inscription_number,
-      parent,
+      parents,
       sat: sat.map(Sat),
       sequence_number,
       timestamp,
@@ -243,7 +342,7 @@ impl Entry for InscriptionEntry {
       self.height,
       self.id.store(),
       self.inscription_number,
-      self.parent,
+      self.parents,
       self.sat.map(Sat::n),
       self.sequence_number,
       self.timestamp,
@@ -430,6 +529,25 @@ mod tests {
     assert_eq!(InscriptionId::load((0, 0, 256)), inscription_id);
   }
 
+  #[test]
+  fn rune_transfer_entry() {
+    let entry = RuneTransferEntry {
+      burned: 1,
+      etching: true,
+      inputs: vec![(outpoint(1), 2), (outpoint(2), u128::MAX)],
+      minted: 3,
+      outputs: vec![(0, 4), (5, 6)],
+      txid: txid(7),
+    };
+
+    assert_eq!(RuneTransferEntry::load(entry.clone().store()), entry);
+
+    assert_eq!(
+      RuneTransferEntry::load(RuneTransferEntry::default().store()),
+      RuneTransferEntry::default()
+    );
+  }
+
   #[test]
   fn rune_entry() {
     let entry = RuneEntry {
@@ -0,0 +1,28 @@
+use super::*;
+
+/// Events published to subscribers once the block that produced them has
+/// been committed to the index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum Event {
+  InscriptionCreated {
+    id: InscriptionId,
+    number: i32,
+    height: u32,
+    content_type: Option<String>,
+  },
+  BlockCommitted {
+    height: u32,
+    hash: BlockHash,
+  },
+  Reorg(ReorgEvent),
+}
+
+/// A reorg the index recovered from, identified by the height of the last
+/// block shared by both branches and the hashes of the blocks that were
+/// rolled back, in ascending height order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ReorgEvent {
+  pub(crate) common_ancestor_height: u32,
+  pub(crate) orphaned_blocks: Vec<BlockHash>,
+}
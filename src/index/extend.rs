@@ -68,7 +68,10 @@ impl Index {
     client: &Client,
   ) -> Result<Option<Vec<ord::InscriptionOp>>> {
     let Some(operations) = rtx.ord_transaction_id_to_inscription_operations(txid)? else {
-      let raw_tx = client.get_raw_transaction_info(&txid, None)?;
+      let Some(raw_tx) = client.get_raw_transaction_info(&txid, None).into_option()? else {
+        // Unknown transaction.
+        return Ok(None);
+      };
 
       match raw_tx.blockhash {
         Some(tx_blockhash) => {
@@ -101,41 +104,6 @@ impl Index {
     Ok(Some(operations))
   }
 
-  pub(crate) fn get_ord_block_inscription_operations(
-    block_hash: BlockHash,
-    rtx: &Rtx,
-    client: &Client,
-  ) -> Result<Vec<(bitcoin::Txid, Vec<ord::InscriptionOp>)>> {
-    // get block from btc client.
-    let blockinfo = client.get_block_info(&block_hash)?;
-
-    // get blockhash from redb.
-    let Some(block_hash) = rtx.block_hash(Some(u32::try_from(blockinfo.height).unwrap()))? else {
-      return Err(anyhow!(
-        "Can't retrieve block: {} from the database.",
-        blockinfo.height
-      ));
-    };
-
-    // check of conflicting block.
-    if blockinfo.hash != block_hash {
-      return Err(anyhow!(
-        "Conflict with block hash in the database. {} != {}",
-        block_hash,
-        blockinfo.hash
-      ));
-    }
-
-    let mut result = Vec::new();
-    for txid in blockinfo.tx {
-      let Some(inscriptions) = rtx.ord_transaction_id_to_inscription_operations(txid)? else {
-        continue;
-      };
-      result.push((txid, inscriptions));
-    }
-    Ok(result)
-  }
-
   pub(crate) fn get_brc20_balance_by_tick_and_address(
     tick: brc20::Tick,
     script_key: ScriptKey,
@@ -243,6 +211,97 @@ impl Index {
     Ok(result)
   }
 
+  /// The successful BRC20 events of `tick` in blocks `from_height..to_height`
+  /// of the indexed chain, in block and transaction order, along with the
+  /// height and txid they occurred in. Only heights and transactions recorded
+  /// for the ticker by the indexer are read, so neither empty blocks nor
+  /// transactions with events of other tickers are visited.
+  pub(crate) fn get_brc20_tick_receipts(
+    tick: &brc20::Tick,
+    from_height: u32,
+    to_height: u32,
+    rtx: &Rtx,
+  ) -> Result<Vec<(u32, Txid, brc20::Receipt)>> {
+    let lower = tick.to_lowercase();
+
+    let mut result = Vec::new();
+    for (height, txids) in rtx.brc20_get_tick_transaction_ids(tick, from_height..to_height)? {
+      for txid in txids {
+        cancel::check()?;
+
+        let Some(receipts) = rtx.brc20_transaction_id_to_transaction_receipt(txid)? else {
+          continue;
+        };
+
+        for receipt in receipts {
+          let Ok(event) = &receipt.result else {
+            continue;
+          };
+
+          if event.tick().to_lowercase() == lower {
+            result.push((height, txid, receipt));
+          }
+        }
+      }
+    }
+    Ok(result)
+  }
+
+  /// The balance of `script_key` as it was after block `height`, found by
+  /// undoing the successful events of the ticker in the blocks since, newest
+  /// first, from the current balance.
+  pub(crate) fn get_brc20_balance_at_height(
+    tick: brc20::Tick,
+    script_key: ScriptKey,
+    height: u32,
+    rtx: &Rtx,
+  ) -> Result<Option<brc20::Balance>> {
+    let Some(mut balance) =
+      Self::get_brc20_balance_by_tick_and_address(tick.clone(), script_key.clone(), rtx)?
+    else {
+      return Ok(None);
+    };
+
+    let undo = |balance: u128, delta: i128| -> Result<u128> {
+      balance.checked_add_signed(delta).ok_or_else(|| {
+        anyhow!("balance of {script_key} in {tick} before height {height} is out of range")
+      })
+    };
+
+    let end = rtx.block_count()?;
+
+    for (_, _, receipt) in Self::get_brc20_tick_receipts(&tick, height + 1, end, rtx)?
+      .into_iter()
+      .rev()
+    {
+      let (overall, transferable) = match receipt.result {
+        Ok(brc20::Event::Mint(event)) if receipt.to == script_key => {
+          (-i128::try_from(event.amount)?, 0)
+        }
+        Ok(brc20::Event::InscribeTransfer(event)) if receipt.to == script_key => {
+          (0, -i128::try_from(event.amount)?)
+        }
+        Ok(brc20::Event::Transfer(event)) => {
+          let amount = i128::try_from(event.amount)?;
+          let mut delta = (0, 0);
+          if receipt.from == script_key {
+            delta = (amount, amount);
+          }
+          if receipt.to == script_key {
+            delta.0 -= amount;
+          }
+          delta
+        }
+        _ => continue,
+      };
+
+      balance.overall_balance = undo(balance.overall_balance, overall)?;
+      balance.transferable_balance = undo(balance.transferable_balance, transferable)?;
+    }
+
+    Ok(Some(balance))
+  }
+
   // Assume these are helper functions defined elsewhere in the module.
   pub(crate) fn fetch_vout(
     rtx: &Rtx,
@@ -257,18 +316,54 @@ impl Index {
     } else {
       // Try to get the txout from the transaction table or the RPC request.
       Ok(
-        Self::get_transaction_with_rtx(outpoint.txid, rtx, client, chain, index_transactions)?.map(
-          |tx| {
+        Self::get_transaction_with_rtx(outpoint.txid, rtx, client, chain, index_transactions)?
+          .and_then(|tx| {
             tx.output
               .get(usize::try_from(outpoint.vout).unwrap())
-              .unwrap()
-              .to_owned()
-          },
-        ),
+              .cloned()
+          }),
       )
     }
   }
 
+  /// The confirmations of an unspent output, counted against the index tip
+  /// rather than the tip of the node `tx_out` came from, so that they agree
+  /// with the other heights the index serves. Works without `-txindex`.
+  pub(crate) fn get_output_confirmations(
+    rtx: &Rtx,
+    client: &Client,
+    tx_out: &GetTxOutResult,
+  ) -> Result<u32> {
+    let Some(index_height) = rtx.block_height()? else {
+      return Ok(0);
+    };
+
+    if tx_out.confirmations == 0 {
+      return Ok(0);
+    }
+
+    let node_height = if rtx.block_hash(None)? == Some(tx_out.bestblock) {
+      index_height.n()
+    } else {
+      match client
+        .get_block_header_info(&tx_out.bestblock)
+        .into_option()?
+      {
+        Some(info) => u32::try_from(info.height).unwrap(),
+        None => return Ok(0),
+      }
+    };
+
+    let height = (node_height + 1).saturating_sub(tx_out.confirmations);
+
+    Ok(
+      index_height
+        .n()
+        .checked_sub(height)
+        .map_or(0, |depth| depth + 1),
+    )
+  }
+
   pub(crate) fn list_sat_range(
     rtx: &Rtx,
     outpoint: OutPoint,
@@ -0,0 +1,506 @@
+use {
+  super::*,
+  crate::okx::datastore::brc20::{
+    redb::table::{get_tokens_info, set_tick_transaction_ids},
+    Tick,
+  },
+};
+
+/// A forward-only migration of the index from schema `from` to `from + 1`.
+pub(crate) struct Migration {
+  pub(crate) from: u64,
+  pub(crate) description: &'static str,
+  pub(crate) run: fn(&Database, &Client) -> Result,
+}
+
+/// Migrations of older index schemas, by the schema they migrate from. Schema
+/// changes that need data only a full reindex can produce have none, so
+/// indices built before them have to be deleted and rebuilt.
+const MIGRATIONS: &[Migration] = &[Migration {
+  from: 21,
+  description: "index BRC20 transactions by ticker and height",
+  run: index_brc20_tick_transactions,
+}];
+
+/// Blocks whose transactions `index_brc20_tick_transactions` commits together.
+const BRC20_TICK_TRANSACTIONS_BATCH: u32 = 1000;
+
+/// Record the transactions with successful BRC20 events of each ticker by
+/// height, fetching each block since the first BRC20 deploy with the Bitcoin
+/// Core RPC client for the order of its transactions.
+///
+/// Blocks are committed in batches, recording the next height to migrate, so
+/// that an interrupted migration resumes after the last batch. Each block's
+/// transactions are written whole, so migrating a block again is harmless.
+fn index_brc20_tick_transactions(database: &Database, client: &Client) -> Result {
+  let rtx = database.begin_read()?;
+
+  // Successful events of a ticker all follow its deploy, so earlier blocks
+  // have none.
+  let Some(first_deploy) = get_tokens_info(&rtx.open_table(BRC20_TOKEN)?)?
+    .iter()
+    .map(|token_info| token_info.deployed_number)
+    .min()
+  else {
+    return Ok(());
+  };
+
+  let Some(tip) = rtx
+    .open_table(HEIGHT_TO_BLOCK_HEADER)?
+    .range(0..)?
+    .next_back()
+    .transpose()?
+    .map(|(height, _header)| height.value())
+  else {
+    return Ok(());
+  };
+
+  let mut next = rtx
+    .open_table(STATISTIC_TO_COUNT)?
+    .get(&Statistic::MigratedHeight.key())?
+    .map(|height| u32::try_from(height.value()).unwrap())
+    .unwrap_or_default()
+    .max(first_deploy);
+
+  drop(rtx);
+
+  while next <= tip {
+    let end = next
+      .saturating_add(BRC20_TICK_TRANSACTIONS_BATCH)
+      .min(tip + 1);
+
+    let wtx = database.begin_write()?;
+
+    {
+      let events = wtx.open_table(BRC20_EVENTS)?;
+      let mut tick_height_to_txids = wtx.open_table(BRC20_TICK_HEIGHT_TO_TXIDS)?;
+
+      for result in wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?.range(next..end)? {
+        let (height, header) = result?;
+
+        let block = client.get_block(&Header::load(*header.value()).block_hash())?;
+
+        let mut tick_txids = BTreeMap::<Tick, Vec<Txid>>::new();
+
+        for txid in block.txdata.iter().map(Transaction::txid) {
+          let Some(receipts) = get_transaction_receipts(&events, &txid)? else {
+            continue;
+          };
+
+          for event in receipts
+            .iter()
+            .filter_map(|receipt| receipt.result.as_ref().ok())
+          {
+            let txids = tick_txids.entry(event.tick().clone()).or_default();
+
+            if txids.last() != Some(&txid) {
+              txids.push(txid);
+            }
+          }
+        }
+
+        for (tick, txids) in tick_txids {
+          set_tick_transaction_ids(&mut tick_height_to_txids, &tick, height.value(), &txids)?;
+        }
+      }
+
+      Index::set_statistic(
+        &mut wtx.open_table(STATISTIC_TO_COUNT)?,
+        Statistic::MigratedHeight,
+        end.into(),
+      )?;
+    }
+
+    wtx.commit()?;
+
+    log::info!(
+      "Indexed BRC20 ticker transactions of blocks {first_deploy} to {} of {tip}",
+      end - 1
+    );
+
+    next = end;
+  }
+
+  let wtx = database.begin_write()?;
+  wtx
+    .open_table(STATISTIC_TO_COUNT)?
+    .remove(&Statistic::MigratedHeight.key())?;
+  wtx.commit()?;
+
+  Ok(())
+}
+
+/// Whether `MIGRATIONS` can migrate an index of schema `from` to
+/// `SCHEMA_VERSION`.
+pub(crate) fn can_migrate(from: u64) -> bool {
+  (from..SCHEMA_VERSION).all(|schema| MIGRATIONS.iter().any(|migration| migration.from == schema))
+}
+
+impl Index {
+  /// Migrate the index to `SCHEMA_VERSION`, returning the schema it was
+  /// migrated from.
+  pub(crate) fn migrate(options: &Options) -> Result<u64> {
+    let path = Self::path(options);
+
+    if !path.is_file() {
+      bail!("index `{}` does not exist", path.display());
+    }
+
+    let database = Database::builder()
+      .open(&path)
+      .map_err(|err| anyhow!("failed to open index: {err}"))?;
+
+    migrate(
+      &database,
+      &path,
+      MIGRATIONS,
+      SCHEMA_VERSION,
+      &options.bitcoin_rpc_client(None)?,
+    )
+  }
+}
+
+/// Migrate `database` to schema `target` with `migrations`, committing the
+/// schema each migration reaches as soon as it is done, so that an
+/// interrupted run resumes with the migration it was in.
+fn migrate(
+  database: &Database,
+  path: &Path,
+  migrations: &[Migration],
+  target: u64,
+  client: &Client,
+) -> Result<u64> {
+  let from = database
+    .begin_read()?
+    .open_table(STATISTIC_TO_COUNT)?
+    .get(&Statistic::Schema.key())?
+    .map(|schema| schema.value())
+    .unwrap_or(0);
+
+  if from > target {
+    bail!(
+      "index at `{}` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {from}, ord schema {target}",
+      path.display()
+    );
+  }
+
+  let mut steps = Vec::new();
+
+  for schema in from..target {
+    let Some(migration) = migrations.iter().find(|migration| migration.from == schema) else {
+      bail!(
+        "index at `{}` cannot be migrated from schema {schema} to schema {}, consider deleting and rebuilding the index: index schema {from}, ord schema {target}",
+        path.display(),
+        schema + 1,
+      );
+    };
+
+    steps.push(migration);
+  }
+
+  for migration in steps {
+    log::info!(
+      "Migrating index from schema {} to schema {}: {}",
+      migration.from,
+      migration.from + 1,
+      migration.description
+    );
+
+    (migration.run)(database, client)?;
+
+    let wtx = database.begin_write()?;
+
+    Index::set_statistic(
+      &mut wtx.open_table(STATISTIC_TO_COUNT)?,
+      Statistic::Schema,
+      migration.from + 1,
+    )?;
+
+    wtx.commit()?;
+  }
+
+  Ok(from)
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  const TABLE: TableDefinition<u64, u64> = TableDefinition::new("MIGRATED");
+
+  fn context_with_schema(schema: u64) -> Context {
+    let context = Context::builder().build();
+
+    let wtx = context.index.database.begin_write().unwrap();
+
+    wtx
+      .open_table(STATISTIC_TO_COUNT)
+      .unwrap()
+      .insert(&Statistic::Schema.key(), &schema)
+      .unwrap();
+
+    wtx.commit().unwrap();
+
+    context
+  }
+
+  fn schema(database: &Database) -> u64 {
+    database
+      .begin_read()
+      .unwrap()
+      .open_table(STATISTIC_TO_COUNT)
+      .unwrap()
+      .get(&Statistic::Schema.key())
+      .unwrap()
+      .unwrap()
+      .value()
+  }
+
+  fn create_table(database: &Database, _client: &Client) -> Result {
+    let wtx = database.begin_write()?;
+    wtx.open_table(TABLE)?.insert(0, 1)?;
+    wtx.commit()?;
+    Ok(())
+  }
+
+  const MIGRATIONS: &[Migration] = &[
+    Migration {
+      from: SCHEMA_VERSION - 2,
+      description: "create table",
+      run: create_table,
+    },
+    Migration {
+      from: SCHEMA_VERSION - 1,
+      description: "nothing",
+      run: |_, _| Ok(()),
+    },
+  ];
+
+  #[test]
+  fn migrations_are_applied_in_order() {
+    let context = context_with_schema(SCHEMA_VERSION - 2);
+    let database = &context.index.database;
+
+    assert_eq!(
+      migrate(
+        database,
+        Path::new("index.redb"),
+        MIGRATIONS,
+        SCHEMA_VERSION,
+        &context.index.client,
+      )
+      .unwrap(),
+      SCHEMA_VERSION - 2
+    );
+
+    assert_eq!(schema(database), SCHEMA_VERSION);
+
+    assert_eq!(
+      database
+        .begin_read()
+        .unwrap()
+        .open_table(TABLE)
+        .unwrap()
+        .get(0)
+        .unwrap()
+        .unwrap()
+        .value(),
+      1
+    );
+  }
+
+  #[test]
+  fn current_schema_needs_no_migration() {
+    let context = Context::builder().build();
+
+    assert_eq!(
+      migrate(
+        &context.index.database,
+        Path::new("index.redb"),
+        &[],
+        SCHEMA_VERSION,
+        &context.index.client,
+      )
+      .unwrap(),
+      SCHEMA_VERSION
+    );
+  }
+
+  #[test]
+  fn schema_without_migration_must_be_rebuilt() {
+    let context = context_with_schema(SCHEMA_VERSION - 3);
+    let database = &context.index.database;
+
+    assert_eq!(
+      migrate(
+        database,
+        Path::new("index.redb"),
+        MIGRATIONS,
+        SCHEMA_VERSION,
+        &context.index.client,
+      )
+      .unwrap_err()
+        .to_string(),
+      format!(
+        "index at `index.redb` cannot be migrated from schema {} to schema {}, consider deleting and rebuilding the index: index schema {}, ord schema {SCHEMA_VERSION}",
+        SCHEMA_VERSION - 3,
+        SCHEMA_VERSION - 2,
+        SCHEMA_VERSION - 3,
+      )
+    );
+
+    assert_eq!(schema(database), SCHEMA_VERSION - 3);
+  }
+
+  #[test]
+  fn newer_schema_cannot_be_migrated() {
+    let context = context_with_schema(SCHEMA_VERSION + 1);
+
+    assert!(migrate(
+      &context.index.database,
+      Path::new("index.redb"),
+      MIGRATIONS,
+      SCHEMA_VERSION,
+      &context.index.client,
+    )
+    .unwrap_err()
+    .to_string()
+    .contains("newer, incompatible version of ord"));
+  }
+
+  #[test]
+  fn previous_schema_must_be_migrated_before_opening() {
+    let (options, _tempdir, _rpc_server) = {
+      let context = context_with_schema(SCHEMA_VERSION - 1);
+      (context.options, context.tempdir, context.rpc_server)
+    };
+
+    assert_eq!(
+      Index::open(&options).err().unwrap().to_string(),
+      format!(
+        "index at `{}` was built with an older version of ord, run `ord migrate-index` to migrate it: index schema {}, ord schema {SCHEMA_VERSION}",
+        Index::path(&options).display(),
+        SCHEMA_VERSION - 1,
+      )
+    );
+
+    assert_eq!(Index::migrate(&options).unwrap(), SCHEMA_VERSION - 1);
+
+    Index::open(&options).unwrap();
+  }
+
+  #[test]
+  fn unmigratable_schema_must_be_rebuilt_before_opening() {
+    let (options, tempdir, _rpc_server) = {
+      let context = context_with_schema(SCHEMA_VERSION - 2);
+      (context.options, context.tempdir, context.rpc_server)
+    };
+
+    let path = Index::path(&options);
+
+    assert_eq!(
+      Index::migrate(&options).unwrap_err().to_string(),
+      format!(
+        "index at `{}` cannot be migrated from schema {} to schema {}, consider deleting and rebuilding the index: index schema {}, ord schema {SCHEMA_VERSION}",
+        path.display(),
+        SCHEMA_VERSION - 2,
+        SCHEMA_VERSION - 1,
+        SCHEMA_VERSION - 2,
+      )
+    );
+
+    assert_eq!(
+      Context::builder()
+        .tempdir(tempdir)
+        .try_build()
+        .err()
+        .unwrap()
+        .to_string(),
+      format!(
+        "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {}, ord schema {SCHEMA_VERSION}",
+        path.display(),
+        SCHEMA_VERSION - 2,
+      )
+    );
+  }
+
+  #[test]
+  fn brc20_tick_transactions_are_indexed_by_migration() {
+    let context = Context::builder().arg("--enable-index-brc20").build();
+    let database = &context.index.database;
+
+    context.mine_blocks(1);
+
+    for (height, content) in [
+      (
+        1,
+        r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"100"}"#,
+      ),
+      (2, r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"100"}"#),
+    ] {
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          height,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", content).to_witness(),
+        )],
+        ..Default::default()
+      });
+      context.mine_blocks(1);
+    }
+
+    let receipts = || {
+      Index::get_brc20_tick_receipts(
+        &brc20::Tick::from_str("ordi").unwrap(),
+        0,
+        100,
+        &context.index.begin_read().unwrap(),
+      )
+      .unwrap()
+    };
+
+    let expected = receipts();
+    assert_eq!(expected.len(), 2);
+
+    let wtx = database.begin_write().unwrap();
+    wtx.delete_table(BRC20_TICK_HEIGHT_TO_TXIDS).unwrap();
+    wtx.open_table(BRC20_TICK_HEIGHT_TO_TXIDS).unwrap();
+    wtx
+      .open_table(STATISTIC_TO_COUNT)
+      .unwrap()
+      .insert(&Statistic::Schema.key(), &21)
+      .unwrap();
+    wtx.commit().unwrap();
+
+    assert!(receipts().is_empty());
+
+    assert_eq!(
+      migrate(
+        database,
+        Path::new("index.redb"),
+        super::MIGRATIONS,
+        SCHEMA_VERSION,
+        &context.index.client,
+      )
+      .unwrap(),
+      21
+    );
+
+    assert_eq!(schema(database), SCHEMA_VERSION);
+    assert_eq!(receipts(), expected);
+
+    index_brc20_tick_transactions(database, &context.index.client).unwrap();
+
+    assert_eq!(receipts(), expected);
+
+    assert!(database
+      .begin_read()
+      .unwrap()
+      .open_table(STATISTIC_TO_COUNT)
+      .unwrap()
+      .get(&Statistic::MigratedHeight.key())
+      .unwrap()
+      .is_none());
+  }
+}
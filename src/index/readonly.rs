@@ -0,0 +1,217 @@
+use {
+  super::*,
+  redb::StorageBackend,
+  std::io::{Read, Seek, SeekFrom},
+};
+
+const BLOCK_SIZE: u64 = 4096;
+
+/// A redb storage backend that reads an index file without ever writing to
+/// it. redb writes to the file even when only reading, for example to mark
+/// the database as open, so writes are applied to copies of the affected
+/// blocks kept in memory, and then read back from there.
+#[derive(Debug)]
+pub(super) struct ReadOnlyBackend {
+  file: Mutex<File>,
+  state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+  /// Blocks that have been written to, by block number.
+  blocks: HashMap<u64, Vec<u8>>,
+  /// Bytes of the file past this offset have been truncated away, and read
+  /// as zeros.
+  file_len: u64,
+  len: u64,
+}
+
+impl ReadOnlyBackend {
+  pub(super) fn open(path: &Path) -> io::Result<Self> {
+    let file = File::open(path)?;
+
+    let len = file.metadata()?.len();
+
+    if len == 0 {
+      return Err(io::ErrorKind::InvalidData.into());
+    }
+
+    Ok(Self {
+      file: Mutex::new(file),
+      state: Mutex::new(State {
+        blocks: HashMap::new(),
+        file_len: len,
+        len,
+      }),
+    })
+  }
+
+  #[allow(clippy::cast_possible_truncation)]
+  fn block(&self, state: &State, number: u64) -> io::Result<Vec<u8>> {
+    if let Some(block) = state.blocks.get(&number) {
+      return Ok(block.clone());
+    }
+
+    let start = number * BLOCK_SIZE;
+    let mut block = vec![0; BLOCK_SIZE as usize];
+    let len = state.file_len.saturating_sub(start).min(BLOCK_SIZE);
+
+    if len > 0 {
+      let mut file = self.file.lock().unwrap();
+      file.seek(SeekFrom::Start(start))?;
+      file.read_exact(&mut block[..len as usize])?;
+    }
+
+    Ok(block)
+  }
+}
+
+// Offsets within a block, and within the data of a single read or write,
+// always fit in a `usize`.
+#[allow(clippy::cast_possible_truncation)]
+impl StorageBackend for ReadOnlyBackend {
+  fn len(&self) -> io::Result<u64> {
+    Ok(self.state.lock().unwrap().len)
+  }
+
+  fn read(&self, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+    let state = self.state.lock().unwrap();
+
+    let end = offset + len as u64;
+
+    if end > state.len {
+      return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    let mut buffer = Vec::with_capacity(len);
+    let mut position = offset;
+
+    while position < end {
+      let number = position / BLOCK_SIZE;
+      let start = (position % BLOCK_SIZE) as usize;
+      let stop = (end - number * BLOCK_SIZE).min(BLOCK_SIZE) as usize;
+      buffer.extend_from_slice(&self.block(&state, number)?[start..stop]);
+      position = (number + 1) * BLOCK_SIZE;
+    }
+
+    Ok(buffer)
+  }
+
+  fn set_len(&self, len: u64) -> io::Result<()> {
+    let mut state = self.state.lock().unwrap();
+
+    if len < state.len {
+      state.file_len = state.file_len.min(len);
+
+      let last = len / BLOCK_SIZE;
+      state.blocks.retain(|number, _| *number <= last);
+
+      if let Some(block) = state.blocks.get_mut(&last) {
+        block[(len % BLOCK_SIZE) as usize..].fill(0);
+      }
+    }
+
+    state.len = len;
+
+    Ok(())
+  }
+
+  fn sync_data(&self, _eventual: bool) -> io::Result<()> {
+    Ok(())
+  }
+
+  fn write(&self, offset: u64, data: &[u8]) -> io::Result<()> {
+    let mut state = self.state.lock().unwrap();
+
+    let end = offset + data.len() as u64;
+    let mut position = offset;
+
+    while position < end {
+      let number = position / BLOCK_SIZE;
+      let start = (position % BLOCK_SIZE) as usize;
+      let stop = (end - number * BLOCK_SIZE).min(BLOCK_SIZE) as usize;
+      let source = (position - offset) as usize;
+
+      let mut block = self.block(&state, number)?;
+      block[start..stop].copy_from_slice(&data[source..source + stop - start]);
+      state.blocks.insert(number, block);
+
+      position = (number + 1) * BLOCK_SIZE;
+    }
+
+    state.len = state.len.max(end);
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn backend(contents: &[u8]) -> (TempDir, ReadOnlyBackend) {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("index.redb");
+    fs::write(&path, contents).unwrap();
+    (tempdir, ReadOnlyBackend::open(&path).unwrap())
+  }
+
+  #[test]
+  fn writes_are_read_back_without_touching_the_file() {
+    let contents = (0..BLOCK_SIZE * 2 + 10)
+      .map(|i| (i % 251).try_into().unwrap())
+      .collect::<Vec<u8>>();
+
+    let (tempdir, backend) = backend(&contents);
+
+    backend.write(BLOCK_SIZE - 2, &[1, 2, 3, 4]).unwrap();
+
+    let mut expected = contents.clone();
+    let block = usize::try_from(BLOCK_SIZE).unwrap();
+    expected[block - 2..block + 2].copy_from_slice(&[1, 2, 3, 4]);
+
+    assert_eq!(
+      backend.read(0, expected.len()).unwrap(),
+      expected,
+      "reads see writes"
+    );
+
+    assert_eq!(
+      fs::read(tempdir.path().join("index.redb")).unwrap(),
+      contents
+    );
+  }
+
+  #[test]
+  fn truncated_bytes_read_as_zeros_when_extended() {
+    let (_tempdir, backend) = backend(&[7; 100]);
+
+    backend.set_len(10).unwrap();
+    assert!(backend.read(0, 11).is_err());
+
+    backend.set_len(20).unwrap();
+
+    let mut expected = vec![7; 10];
+    expected.extend([0; 10]);
+    assert_eq!(backend.read(0, 20).unwrap(), expected);
+  }
+
+  #[test]
+  fn writes_past_the_end_extend_the_storage() {
+    let (_tempdir, backend) = backend(&[7; 10]);
+
+    backend.write(BLOCK_SIZE * 3, &[1]).unwrap();
+
+    assert_eq!(backend.len().unwrap(), BLOCK_SIZE * 3 + 1);
+    assert_eq!(backend.read(BLOCK_SIZE * 3 - 1, 2).unwrap(), [0, 1]);
+    assert_eq!(backend.read(9, 2).unwrap(), [7, 0]);
+  }
+
+  #[test]
+  fn empty_files_are_rejected() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("index.redb");
+    fs::write(&path, []).unwrap();
+    assert!(ReadOnlyBackend::open(&path).is_err());
+  }
+}
@@ -0,0 +1,360 @@
+use {
+  super::{updater::BlockData, verify::RUNE_BALANCES, *},
+  updater::index_block_runes,
+};
+
+/// The result of rebuilding the rune tables.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReindexedRunes {
+  pub blocks: u32,
+  pub runes: u64,
+}
+
+impl WritableIndex {
+  /// Rebuild the rune tables by replaying the runestones in every indexed
+  /// block from the first rune height, leaving all other tables untouched.
+  /// Everything is done in a single write transaction, so an interrupted
+  /// reindex leaves the rune tables as they were.
+  pub(crate) fn reindex_runes(&self) -> Result<ReindexedRunes> {
+    if !self.index_runes {
+      bail!("index at `{}` does not index runes", self.path.display());
+    }
+
+    let violations = self
+      .verify()?
+      .into_iter()
+      .filter(|invariant| invariant.name != RUNE_BALANCES)
+      .filter_map(|invariant| invariant.violation)
+      .collect::<Vec<String>>();
+
+    if !violations.is_empty() {
+      bail!(
+        "refusing to reindex runes of an index with inconsistent tables, consider deleting and rebuilding the index: {}",
+        violations.join(", ")
+      );
+    }
+
+    let wtx = self.begin_write()?;
+
+    wtx.delete_table(OUTPOINT_TO_RUNE_BALANCES)?;
+    wtx.delete_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    wtx.delete_table(RUNE_ID_TO_TRANSFER)?;
+    wtx.delete_table(RUNE_OUTPOINT_TO_SCRIPT_PUBKEY)?;
+    wtx.delete_table(RUNE_TO_RUNE_ID)?;
+    wtx.delete_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+    wtx.delete_table(TRANSACTION_ID_TO_RUNE)?;
+    wtx.delete_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT)?;
+
+    let mut blocks = 0;
+
+    {
+      let height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+      let inscription_id_to_sequence_number = wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+      let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+
+      Index::set_statistic(&mut statistic_to_count, Statistic::ReservedRunes, 0)?;
+      Index::set_statistic(&mut statistic_to_count, Statistic::Runes, 0)?;
+
+      for result in height_to_block_header.range(self.options.first_rune_height()..)? {
+        if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+          bail!("interrupted, rune tables were not changed");
+        }
+
+        let (height, header) = result?;
+        let height = height.value();
+        let hash = Header::load(*header.value()).block_hash();
+
+        let block = BlockData::from(self.client.get_block(&hash)?);
+
+        log::info!("Replaying runestones in block {height}");
+
+        index_block_runes(
+          self,
+          &wtx,
+          height,
+          &block,
+          &inscription_id_to_sequence_number,
+          &mut statistic_to_count,
+        )?;
+
+        blocks += 1;
+      }
+    }
+
+    let runes = wtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::Runes.key())?
+      .map(|runes| runes.value())
+      .unwrap_or_default();
+
+    wtx.commit()?;
+
+    Ok(ReindexedRunes { blocks, runes })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::index::{testing::Context, verify::Invariant},
+  };
+
+  const RUNE: u128 = 99246114928149462;
+
+  type Rows = Vec<(Vec<u8>, Vec<u8>)>;
+
+  fn rows<K: RedbKey + 'static, V: RedbValue + 'static>(
+    context: &Context,
+    definition: TableDefinition<K, V>,
+  ) -> Rows {
+    let mut rows = Vec::new();
+
+    for result in context
+      .index
+      .database
+      .begin_read()
+      .unwrap()
+      .open_table(definition)
+      .unwrap()
+      .iter()
+      .unwrap()
+    {
+      let (key, value) = result.unwrap();
+      let (key, value) = (key.value(), value.value());
+      rows.push((
+        K::as_bytes(&key).as_ref().to_vec(),
+        V::as_bytes(&value).as_ref().to_vec(),
+      ));
+    }
+
+    rows
+  }
+
+  fn multimap_rows<K: RedbKey + 'static, V: RedbKey + 'static>(
+    context: &Context,
+    definition: MultimapTableDefinition<K, V>,
+  ) -> Rows {
+    let mut rows = Vec::new();
+
+    for result in context
+      .index
+      .database
+      .begin_read()
+      .unwrap()
+      .open_multimap_table(definition)
+      .unwrap()
+      .iter()
+      .unwrap()
+    {
+      let (key, values) = result.unwrap();
+      let key = key.value();
+      for value in values {
+        let value = value.unwrap();
+        let value = value.value();
+        rows.push((
+          K::as_bytes(&key).as_ref().to_vec(),
+          V::as_bytes(&value).as_ref().to_vec(),
+        ));
+      }
+    }
+
+    rows
+  }
+
+  fn rune_tables(context: &Context) -> Vec<Rows> {
+    vec![
+      rows(context, OUTPOINT_TO_RUNE_BALANCES),
+      rows(context, RUNE_ID_TO_RUNE_ENTRY),
+      rows(context, RUNE_ID_TO_TRANSFER),
+      rows(context, RUNE_OUTPOINT_TO_SCRIPT_PUBKEY),
+      rows(context, RUNE_TO_RUNE_ID),
+      rows(context, SEQUENCE_NUMBER_TO_RUNE_ID),
+      rows(context, TRANSACTION_ID_TO_RUNE),
+      multimap_rows(context, SCRIPT_PUBKEY_TO_RUNE_OUTPOINT),
+    ]
+  }
+
+  fn other_tables(context: &Context) -> Vec<Rows> {
+    vec![
+      rows(context, HEIGHT_TO_BLOCK_HEADER),
+      rows(context, HEIGHT_TO_LAST_SEQUENCE_NUMBER),
+      rows(context, HOME_INSCRIPTIONS),
+      rows(context, INSCRIPTION_ID_TO_SEQUENCE_NUMBER),
+      rows(context, INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER),
+      rows(context, INSCRIPTION_OUTPOINT_TO_SCRIPT_PUBKEY),
+      rows(context, OUTPOINT_TO_ENTRY),
+      rows(context, SEQUENCE_NUMBER_TO_CONTENT),
+      rows(context, SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY),
+      rows(context, SEQUENCE_NUMBER_TO_SATPOINT),
+      rows(context, STATISTIC_TO_COUNT),
+      rows(context, ORD_TX_TO_OPERATIONS),
+      multimap_rows(context, SATPOINT_TO_SEQUENCE_NUMBER),
+      multimap_rows(context, SCRIPT_PUBKEY_TO_INSCRIPTION_OUTPOINT),
+      multimap_rows(context, SEQUENCE_NUMBER_TO_CHILDREN),
+    ]
+  }
+
+  fn index_runes_and_inscriptions(context: &Context) -> InscriptionId {
+    context.mine_blocks(1);
+
+    let etching = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      outputs: 2,
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: RuneId {
+              height: 2,
+              index: 1,
+            }
+            .into(),
+            amount: 1000,
+            output: 1,
+          }],
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: 1,
+            output: 0,
+          }],
+          etching: Some(Etching::default()),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.runes().unwrap().len(), 2);
+    assert_eq!(rows(context, SEQUENCE_NUMBER_TO_RUNE_ID).len(), 1);
+
+    InscriptionId {
+      txid: etching,
+      index: 0,
+    }
+  }
+
+  #[test]
+  fn rune_tables_are_rebuilt_and_other_tables_are_untouched() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    index_runes_and_inscriptions(&context);
+
+    let runes = rune_tables(&context);
+    let others = other_tables(&context);
+
+    let wtx = context.index.database.begin_write().unwrap();
+    wtx
+      .open_table(RUNE_ID_TO_RUNE_ENTRY)
+      .unwrap()
+      .remove(
+        RuneId {
+          height: 2,
+          index: 1,
+        }
+        .store(),
+      )
+      .unwrap();
+    wtx
+      .open_table(RUNE_TO_RUNE_ID)
+      .unwrap()
+      .insert(
+        RUNE + 1,
+        RuneId {
+          height: 9,
+          index: 9,
+        }
+        .store(),
+      )
+      .unwrap();
+    wtx.commit().unwrap();
+
+    assert_ne!(rune_tables(&context), runes);
+
+    assert_eq!(
+      context.index.reindex_runes().unwrap(),
+      ReindexedRunes {
+        blocks: context.index.block_count().unwrap(),
+        runes: 2,
+      }
+    );
+
+    assert_eq!(rune_tables(&context), runes);
+    assert_eq!(other_tables(&context), others);
+
+    assert!(context.index.verify().unwrap().iter().all(Invariant::holds));
+  }
+
+  #[test]
+  fn inconsistent_index_is_not_reindexed() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    let id = index_runes_and_inscriptions(&context);
+
+    let wtx = context.index.database.begin_write().unwrap();
+    wtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
+      .unwrap()
+      .remove(&id.store())
+      .unwrap();
+    wtx.commit().unwrap();
+
+    let runes = rune_tables(&context);
+
+    assert_eq!(
+      context.index.reindex_runes().unwrap_err().to_string(),
+      "refusing to reindex runes of an index with inconsistent tables, consider deleting and rebuilding the index: INSCRIPTION_ID_TO_SEQUENCE_NUMBER has 0 rows, but SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY has 1"
+    );
+
+    assert_eq!(rune_tables(&context), runes);
+  }
+
+  #[test]
+  fn index_without_runes_is_not_reindexed() {
+    let context = Context::builder().build();
+
+    assert!(context
+      .index
+      .reindex_runes()
+      .unwrap_err()
+      .to_string()
+      .ends_with("does not index runes"));
+  }
+}
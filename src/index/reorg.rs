@@ -22,6 +22,7 @@ impl std::error::Error for ReorgError {}
 const MAX_SAVEPOINTS: u32 = 2;
 const SAVEPOINT_INTERVAL: u32 = 10;
 const CHAIN_TIP_DISTANCE: u32 = 21;
+pub(crate) const MAX_REORG_HISTORY: usize = 100;
 
 pub(crate) struct Reorg {}
 
@@ -53,7 +54,25 @@ impl Reorg {
     }
   }
 
-  pub(crate) fn handle_reorg(index: &Index, height: u32, depth: u32) -> Result {
+  /// The reorg detected while indexing the block at `height`, whose branch
+  /// diverges from the index `depth` blocks back.
+  pub(crate) fn orphaned(index: &Index, height: u32, depth: u32) -> Result<ReorgEvent> {
+    let common_ancestor_height = height.saturating_sub(depth);
+
+    let mut orphaned_blocks = Vec::new();
+    for orphaned_height in common_ancestor_height + 1..height {
+      if let Some(hash) = index.block_hash(Some(orphaned_height))? {
+        orphaned_blocks.push(hash);
+      }
+    }
+
+    Ok(ReorgEvent {
+      common_ancestor_height,
+      orphaned_blocks,
+    })
+  }
+
+  pub(crate) fn handle_reorg(index: &WritableIndex, height: u32, depth: u32) -> Result {
     log::info!("rolling back database after reorg of depth {depth} at height {height}");
 
     if let redb::Durability::None = index.durability {
@@ -78,7 +97,7 @@ impl Reorg {
     Ok(())
   }
 
-  pub(crate) fn update_savepoints(index: &Index, height: u32) -> Result {
+  pub(crate) fn update_savepoints(index: &WritableIndex, height: u32) -> Result {
     if let redb::Durability::None = index.durability {
       return Ok(());
     }
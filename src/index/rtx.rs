@@ -115,6 +115,36 @@ impl Rtx<'_> {
     )
   }
 
+  /// The inscriptions on the sat at `satpoint`, oldest first.
+  pub(crate) fn inscriptions_at_satpoint(&self, satpoint: SatPoint) -> Result<Vec<InscriptionId>> {
+    let sequence_number_to_inscription_entry =
+      self.0.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    self
+      .0
+      .open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?
+      .get(&satpoint.store())?
+      .map(|sequence_number| {
+        let entry = sequence_number_to_inscription_entry
+          .get(sequence_number?.value())?
+          .unwrap();
+        Ok(InscriptionEntry::load(entry.value()).id)
+      })
+      .collect()
+  }
+
+  pub(crate) fn script_pubkey_to_inscription_outpoints(
+    &self,
+    script_pubkey: &Script,
+  ) -> Result<Vec<OutPoint>> {
+    self
+      .0
+      .open_multimap_table(SCRIPT_PUBKEY_TO_INSCRIPTION_OUTPOINT)?
+      .get(script_pubkey.as_bytes())?
+      .map(|outpoint| Ok(Entry::load(*outpoint?.value())))
+      .collect()
+  }
+
   pub(crate) fn sequence_number_to_inscription_entry(
     &self,
     sequence_number: u32,
@@ -217,6 +247,15 @@ impl Rtx<'_> {
     get_transaction_receipts(&table, &txid)
   }
 
+  pub(crate) fn brc20_get_tick_transaction_ids(
+    &self,
+    tick: &brc20::Tick,
+    heights: std::ops::Range<u32>,
+  ) -> Result<Vec<(u32, Vec<Txid>)>> {
+    let table = self.0.open_table(BRC20_TICK_HEIGHT_TO_TXIDS)?;
+    get_tick_transaction_ids(&table, tick, heights)
+  }
+
   pub(crate) fn brc20_get_tick_transferable_by_address(
     &self,
     tick: &brc20::Tick,
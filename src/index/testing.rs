@@ -69,7 +69,7 @@ pub(crate) struct Context {
   pub(crate) rpc_server: test_bitcoincore_rpc::Handle,
   #[allow(unused)]
   pub(crate) tempdir: TempDir,
-  pub(crate) index: Index,
+  pub(crate) index: WritableIndex,
 }
 
 impl Context {
@@ -120,6 +120,8 @@ impl Context {
 
     assert_eq!(balances, self.index.get_rune_balances().unwrap());
 
+    let runic_outputs = u64::try_from(balances.len()).unwrap();
+
     let mut outstanding: HashMap<RuneId, u128> = HashMap::new();
 
     for (_, balances) in balances {
@@ -134,5 +136,38 @@ impl Context {
         entry.supply - entry.burned
       );
     }
+
+    // Test transactions pay to an empty script, so every unspent rune balance
+    // belongs to it.
+    assert_eq!(
+      self
+        .index
+        .get_rune_balances_for_script_pubkey(&ScriptBuf::new())
+        .unwrap()
+        .into_iter()
+        .map(|(id, _rune, pile)| (id, pile.amount))
+        .collect::<HashMap<RuneId, u128>>(),
+      outstanding,
+    );
+
+    let rtx = self.index.database.begin_read().unwrap();
+
+    assert_eq!(
+      rtx
+        .open_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT)
+        .unwrap()
+        .len()
+        .unwrap(),
+      runic_outputs,
+    );
+
+    assert_eq!(
+      rtx
+        .open_table(RUNE_OUTPOINT_TO_SCRIPT_PUBKEY)
+        .unwrap()
+        .len()
+        .unwrap(),
+      runic_outputs,
+    );
   }
 }
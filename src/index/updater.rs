@@ -1,4 +1,7 @@
-use crate::okx::protocol::{context::Context, ChainContext, ProtocolConfig, ProtocolManager};
+use crate::okx::{
+  datastore::ord::operation::Action,
+  protocol::{context::Context, ChainContext, ProtocolConfig, ProtocolManager},
+};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use {
   self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
@@ -35,9 +38,10 @@ impl From<Block> for BlockData {
 }
 
 pub(crate) struct Updater<'index> {
+  events: Vec<Event>,
   range_cache: HashMap<OutPointValue, Vec<u8>>,
   height: u32,
-  index: &'index Index,
+  index: &'index WritableIndex,
   sat_ranges_since_flush: u64,
   outputs_cached: u64,
   outputs_inserted_since_flush: u64,
@@ -45,8 +49,9 @@ pub(crate) struct Updater<'index> {
 }
 
 impl<'index> Updater<'_> {
-  pub(crate) fn new(index: &'index Index) -> Result<Updater<'index>> {
+  pub(crate) fn new(index: &'index WritableIndex) -> Result<Updater<'index>> {
     Ok(Updater {
+      events: Vec::new(),
       range_cache: HashMap::new(),
       height: index.block_count()?,
       index,
@@ -58,8 +63,12 @@ impl<'index> Updater<'_> {
   }
 
   pub(crate) fn update_index(&mut self) -> Result {
+    if self.index.is_at_capacity() {
+      return Ok(());
+    }
+
     let mut wtx = self.index.begin_write()?;
-    let starting_height = u32::try_from(self.index.client.get_block_count()?).unwrap() + 1;
+    let starting_height = self.index.refresh_chain_block_count()?;
 
     wtx
       .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
@@ -150,7 +159,7 @@ impl<'index> Updater<'_> {
           // write transaction
           break;
         }
-        if should_break {
+        if should_break || self.index.is_at_capacity() {
           break;
         }
         wtx
@@ -343,6 +352,16 @@ impl<'index> Updater<'_> {
   ) -> Result<()> {
     Reorg::detect_reorg(&block, self.height, self.index)?;
 
+    let height = self.height;
+
+    let span = tracing::info_span!(
+      "index_block",
+      height,
+      inscriptions = tracing::field::Empty,
+      duration_ms = tracing::field::Empty,
+    );
+    let _entered = span.enter();
+
     let start = Instant::now();
     let mut sat_ranges_written = 0;
     let mut outputs_in_block = 0;
@@ -429,9 +448,14 @@ impl<'index> Updater<'_> {
       wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
     let mut inscription_number_to_sequence_number =
       wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+    let mut inscription_outpoint_to_script_pubkey =
+      wtx.open_table(INSCRIPTION_OUTPOINT_TO_SCRIPT_PUBKEY)?;
     let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
     let mut satpoint_to_sequence_number = wtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+    let mut script_pubkey_to_inscription_outpoint =
+      wtx.open_multimap_table(SCRIPT_PUBKEY_TO_INSCRIPTION_OUTPOINT)?;
     let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+    let mut sequence_number_to_content = wtx.open_table(SEQUENCE_NUMBER_TO_CONTENT)?;
     let mut sequence_number_to_inscription_entry =
       wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
     let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
@@ -476,13 +500,16 @@ impl<'index> Updater<'_> {
       &mut inscription_id_to_sequence_number,
       self.index.index_transactions,
       &mut inscription_number_to_sequence_number,
+      &mut inscription_outpoint_to_script_pubkey,
       next_sequence_number,
       lost_sats,
       &mut outpoint_to_entry,
       &mut transaction_id_to_transaction,
       &mut sat_to_sequence_number,
       &mut satpoint_to_sequence_number,
+      &mut script_pubkey_to_inscription_outpoint,
       &mut sequence_number_to_children,
+      &mut sequence_number_to_content,
       &mut sequence_number_to_inscription_entry,
       &mut sequence_number_to_satpoint,
       block.header.time,
@@ -620,6 +647,41 @@ impl<'index> Updater<'_> {
 
     inscription_updater.flush_cache()?;
 
+    let inscriptions_added = operations
+      .values()
+      .flatten()
+      .filter(|op| matches!(op.action, Action::New { .. }))
+      .count();
+
+    // Only buffer events when someone is listening, so that syncing a fresh
+    // index doesn't accumulate events between commits.
+    let publish_events = self.index.event_sender.receiver_count() > 0;
+
+    if publish_events {
+      let mut created = operations
+        .values()
+        .flatten()
+        .filter_map(|op| match &op.action {
+          Action::New { inscription, .. } => Some((
+            op.sequence_number,
+            Event::InscriptionCreated {
+              id: op.inscription_id,
+              number: op.inscription_number?,
+              height: self.height,
+              content_type: inscription.content_type().map(str::to_string),
+            },
+          )),
+          Action::Transfer => None,
+        })
+        .collect::<Vec<(u32, Event)>>();
+
+      created.sort_by_key(|(sequence_number, _event)| *sequence_number);
+
+      self
+        .events
+        .extend(created.into_iter().map(|(_sequence_number, event)| event));
+    }
+
     let mut context = Context {
       chain_conf: ChainContext {
         chain: self.index.options.chain(),
@@ -638,6 +700,7 @@ impl<'index> Updater<'_> {
       BRC20_BALANCES: &mut wtx.open_table(BRC20_BALANCES)?,
       BRC20_TOKEN: &mut wtx.open_table(BRC20_TOKEN)?,
       BRC20_EVENTS: &mut wtx.open_table(BRC20_EVENTS)?,
+      BRC20_TICK_HEIGHT_TO_TXIDS: &mut wtx.open_table(BRC20_TICK_HEIGHT_TO_TXIDS)?,
       BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS: &mut wtx
         .open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?,
       BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS: &mut wtx
@@ -649,54 +712,25 @@ impl<'index> Updater<'_> {
     ProtocolManager::new(config).index_block(&mut context, &block, operations)?;
 
     if index.index_runes && self.height >= self.index.options.first_rune_height() {
-      let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
-      let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-      let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
-      let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
-      let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
-
-      let runes = statistic_to_count
-        .get(&Statistic::Runes.into())?
-        .map(|x| x.value())
-        .unwrap_or(0);
-
-      let mut rune_updater = RuneUpdater {
-        height: self.height,
-        id_to_entry: &mut rune_id_to_rune_entry,
-        inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
-        minimum: Rune::minimum_at_height(self.index.options.chain(), Height(self.height)),
-        outpoint_to_balances: &mut outpoint_to_rune_balances,
-        rune_to_id: &mut rune_to_rune_id,
-        runes,
-        sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
-        statistic_to_count: &mut statistic_to_count,
-        timestamp: block.header.time,
-        transaction_id_to_rune: &mut transaction_id_to_rune,
-        updates: HashMap::new(),
-      };
-
-      for (i, (tx, txid)) in block.txdata.iter().enumerate() {
-        rune_updater.index_runes(i, tx, *txid)?;
-      }
-
-      for (rune_id, update) in rune_updater.updates {
-        let mut entry = RuneEntry::load(
-          rune_id_to_rune_entry
-            .get(&rune_id.store())?
-            .unwrap()
-            .value(),
-        );
-
-        entry.burned += update.burned;
-        entry.mints += update.mints;
-        entry.supply += update.supply;
-
-        rune_id_to_rune_entry.insert(&rune_id.store(), entry.store())?;
-      }
+      index_block_runes(
+        index,
+        wtx,
+        self.height,
+        &block,
+        &inscription_id_to_sequence_number,
+        &mut statistic_to_count,
+      )?;
     }
 
     height_to_block_header.insert(&self.height, &block.header.store())?;
 
+    if publish_events {
+      self.events.push(Event::BlockCommitted {
+        height: self.height,
+        hash: block.header.block_hash(),
+      });
+    }
+
     self.height += 1;
     self.outputs_traversed += outputs_in_block;
 
@@ -708,6 +742,18 @@ impl<'index> Updater<'_> {
       context.miss,
     );
 
+    let duration_ms = start.elapsed().as_millis();
+
+    span.record("inscriptions", inscriptions_added);
+    span.record("duration_ms", duration_ms);
+
+    tracing::debug!(
+      height,
+      inscriptions = inscriptions_added,
+      duration_ms,
+      "indexed block"
+    );
+
     Ok(())
   }
 
@@ -810,12 +856,88 @@ impl<'index> Updater<'_> {
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
 
+    self.index.check_capacity()?;
+
+    if !self.events.is_empty() {
+      // Sending only fails when there are no subscribers.
+      self
+        .index
+        .event_sender
+        .send(Arc::new(mem::take(&mut self.events)))
+        .ok();
+    }
+
     Reorg::update_savepoints(self.index, self.height)?;
 
     Ok(())
   }
 }
 
+/// Index the runestones in `block`, at `height`. `Index::reindex_runes` replays
+/// blocks through this as well, so that rebuilt rune tables match.
+pub(super) fn index_block_runes<'db, 'tx>(
+  index: &Index,
+  wtx: &'tx WriteTransaction<'db>,
+  height: u32,
+  block: &BlockData,
+  inscription_id_to_sequence_number: &Table<'db, 'tx, InscriptionIdValue, u32>,
+  statistic_to_count: &mut Table<'db, 'tx, u64, u64>,
+) -> Result {
+  let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+  let mut rune_outpoint_to_script_pubkey = wtx.open_table(RUNE_OUTPOINT_TO_SCRIPT_PUBKEY)?;
+  let mut script_pubkey_to_rune_outpoint =
+    wtx.open_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT)?;
+  let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+  let mut rune_id_to_transfer = wtx.open_table(RUNE_ID_TO_TRANSFER)?;
+  let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
+  let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+  let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
+
+  let runes = statistic_to_count
+    .get(&Statistic::Runes.into())?
+    .map(|x| x.value())
+    .unwrap_or(0);
+
+  let mut rune_updater = RuneUpdater {
+    height,
+    id_to_entry: &mut rune_id_to_rune_entry,
+    id_to_transfer: &mut rune_id_to_transfer,
+    inscription_id_to_sequence_number,
+    minimum: Rune::minimum_at_height(index.options.chain(), Height(height)),
+    outpoint_to_balances: &mut outpoint_to_rune_balances,
+    outpoint_to_script_pubkey: &mut rune_outpoint_to_script_pubkey,
+    rune_to_id: &mut rune_to_rune_id,
+    runes,
+    script_pubkey_to_outpoint: &mut script_pubkey_to_rune_outpoint,
+    sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
+    statistic_to_count,
+    timestamp: block.header.time,
+    transaction_id_to_rune: &mut transaction_id_to_rune,
+    updates: HashMap::new(),
+  };
+
+  for (i, (tx, txid)) in block.txdata.iter().enumerate() {
+    rune_updater.index_runes(i, tx, *txid)?;
+  }
+
+  for (rune_id, update) in rune_updater.updates {
+    let mut entry = RuneEntry::load(
+      rune_id_to_rune_entry
+        .get(&rune_id.store())?
+        .unwrap()
+        .value(),
+    );
+
+    entry.burned += update.burned;
+    entry.mints += update.mints;
+    entry.supply += update.supply;
+
+    rune_id_to_rune_entry.insert(&rune_id.store(), entry.store())?;
+  }
+
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
   use rayon::prelude::*;
@@ -30,7 +30,7 @@ enum Origin {
     cursed: bool,
     fee: u64,
     hidden: bool,
-    parent: Option<InscriptionId>,
+    parents: Vec<InscriptionId>,
     pointer: Option<u64>,
     reinscription: bool,
     unbound: bool,
@@ -52,6 +52,8 @@ pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
   pub(super) id_to_sequence_number: &'a mut Table<'db, 'tx, InscriptionIdValue, u32>,
   pub(super) index_transactions: bool,
   pub(super) inscription_number_to_sequence_number: &'a mut Table<'db, 'tx, i32, u32>,
+  pub(super) inscription_outpoint_to_script_pubkey:
+    &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
   pub(super) next_sequence_number: u32,
   pub(super) lost_sats: u64,
   pub(super) outpoint_to_entry: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
@@ -62,7 +64,11 @@ pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
   pub(super) sat_to_sequence_number: &'a mut MultimapTable<'db, 'tx, u64, u32>,
   pub(super) satpoint_to_sequence_number:
     &'a mut MultimapTable<'db, 'tx, &'static SatPointValue, u32>,
+  pub(super) script_pubkey_to_inscription_outpoint:
+    &'a mut MultimapTable<'db, 'tx, &'static [u8], &'static OutPointValue>,
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'db, 'tx, u32, u32>,
+  pub(super) sequence_number_to_content:
+    &'a mut Table<'db, 'tx, u32, (Option<&'static str>, Option<u64>)>,
   pub(super) sequence_number_to_entry: &'a mut Table<'db, 'tx, u32, InscriptionEntryValue>,
   pub(super) sequence_number_to_satpoint: &'a mut Table<'db, 'tx, u32, &'static SatPointValue>,
   pub(super) timestamp: u32,
@@ -83,13 +89,26 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     id_to_sequence_number: &'a mut Table<'db, 'tx, InscriptionIdValue, u32>,
     index_transactions: bool,
     inscription_number_to_sequence_number: &'a mut Table<'db, 'tx, i32, u32>,
+    inscription_outpoint_to_script_pubkey: &'a mut Table<
+      'db,
+      'tx,
+      &'static OutPointValue,
+      &'static [u8],
+    >,
     next_sequence_number: u32,
     lost_sats: u64,
     outpoint_to_entry: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
     transaction_id_to_transaction: &'a mut Table<'db, 'tx, &'static TxidValue, &'static [u8]>,
     sat_to_sequence_number: &'a mut MultimapTable<'db, 'tx, u64, u32>,
     satpoint_to_sequence_number: &'a mut MultimapTable<'db, 'tx, &'static SatPointValue, u32>,
+    script_pubkey_to_inscription_outpoint: &'a mut MultimapTable<
+      'db,
+      'tx,
+      &'static [u8],
+      &'static OutPointValue,
+    >,
     sequence_number_to_children: &'a mut MultimapTable<'db, 'tx, u32, u32>,
+    sequence_number_to_content: &'a mut Table<'db, 'tx, u32, (Option<&'static str>, Option<u64>)>,
     sequence_number_to_entry: &'a mut Table<'db, 'tx, u32, InscriptionEntryValue>,
     sequence_number_to_satpoint: &'a mut Table<'db, 'tx, u32, &'static SatPointValue>,
     timestamp: u32,
@@ -109,6 +128,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       id_to_sequence_number,
       index_transactions,
       inscription_number_to_sequence_number,
+      inscription_outpoint_to_script_pubkey,
       next_sequence_number,
       lost_sats,
       outpoint_to_entry,
@@ -117,7 +137,9 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       transaction_id_to_transaction,
       sat_to_sequence_number,
       satpoint_to_sequence_number,
+      script_pubkey_to_inscription_outpoint,
       sequence_number_to_children,
+      sequence_number_to_content,
       sequence_number_to_entry,
       sequence_number_to_satpoint,
       timestamp,
@@ -172,6 +194,15 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           .1 += 1;
       }
 
+      if let Some(script_pubkey) = self
+        .inscription_outpoint_to_script_pubkey
+        .remove(&tx_in.previous_output.store())?
+      {
+        self
+          .script_pubkey_to_inscription_outpoint
+          .remove(script_pubkey.value(), &tx_in.previous_output.store())?;
+      }
+
       let offset = total_input_value;
 
       // multi-level cache for UTXO set to get to the input amount
@@ -273,7 +304,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
             cursed: curse.is_some() && !jubilant,
             fee: 0,
             hidden: inscription.payload.hidden(),
-            parent: inscription.payload.parent(),
+            parents: inscription.payload.parents(),
             pointer: inscription.payload.pointer(),
             reinscription: inscribed_offsets.get(&offset).is_some(),
             unbound,
@@ -310,15 +341,11 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
 
     for flotsam in &mut floating_inscriptions {
       if let Flotsam {
-        origin: Origin::New { parent, .. },
+        origin: Origin::New { parents, .. },
         ..
       } = flotsam
       {
-        if let Some(purported_parent) = parent {
-          if !potential_parents.contains(purported_parent) {
-            *parent = None;
-          }
-        }
+        parents.retain(|parent| potential_parents.contains(parent));
       }
     }
 
@@ -408,7 +435,15 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         _ => new_satpoint,
       };
 
-      self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+      let script_pubkey =
+        &tx.output[usize::try_from(new_satpoint.outpoint.vout).unwrap()].script_pubkey;
+
+      self.update_inscription_location(
+        input_sat_ranges,
+        flotsam,
+        new_satpoint,
+        Some(script_pubkey),
+      )?;
     }
 
     if is_coinbase {
@@ -417,7 +452,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           outpoint: OutPoint::null(),
           offset: self.lost_sats + flotsam.offset - output_value,
         };
-        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint, None)?;
       }
       self.lost_sats += self.reward - output_value;
       Ok(())
@@ -477,6 +512,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
     flotsam: Flotsam,
     new_satpoint: SatPoint,
+    script_pubkey: Option<&Script>,
   ) -> Result {
     let inscription_id = flotsam.inscription_id;
     let (unbound, sequence_number) = match flotsam.origin {
@@ -498,11 +534,11 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         cursed,
         fee,
         hidden,
-        parent,
+        ref parents,
         pointer: _,
         reinscription,
         unbound,
-        inscription: _,
+        ref inscription,
         vindicated,
       } => {
         let inscription_number = if cursed {
@@ -575,21 +611,32 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           self.sat_to_sequence_number.insert(&n, &sequence_number)?;
         }
 
-        let parent = match parent {
-          Some(parent_id) => {
+        let parents = parents
+          .iter()
+          .map(|parent_id| {
             let parent_sequence_number = self
               .id_to_sequence_number
               .get(&parent_id.store())?
               .unwrap()
               .value();
+
             self
               .sequence_number_to_children
               .insert(parent_sequence_number, sequence_number)?;
 
-            Some(parent_sequence_number)
-          }
-          None => None,
-        };
+            Ok(parent_sequence_number)
+          })
+          .collect::<Result<Vec<u32>>>()?;
+
+        self.sequence_number_to_content.insert(
+          sequence_number,
+          (
+            inscription.content_type(),
+            inscription
+              .content_length()
+              .map(|content_length| content_length.try_into().unwrap()),
+          ),
+        )?;
 
         self.sequence_number_to_entry.insert(
           sequence_number,
@@ -599,7 +646,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
             height: self.height,
             id: inscription_id,
             inscription_number,
-            parent,
+            parents,
             sat,
             sequence_number,
             timestamp: self.timestamp,
@@ -635,6 +682,16 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       self.unbound_inscriptions += 1;
       new_unbound_satpoint.store()
     } else {
+      if let Some(script_pubkey) = script_pubkey {
+        let outpoint = new_satpoint.outpoint.store();
+        self
+          .inscription_outpoint_to_script_pubkey
+          .insert(&outpoint, script_pubkey.as_bytes())?;
+        self
+          .script_pubkey_to_inscription_outpoint
+          .insert(script_pubkey.as_bytes(), &outpoint)?;
+      }
+
       new_satpoint.store()
     };
 
@@ -659,14 +716,14 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
             pointer: _,
             reinscription: _,
             unbound,
-            parent,
+            parents,
             inscription,
             vindicated,
           } => Action::New {
             cursed,
             unbound,
             vindicated,
-            parent,
+            parent: parents.first().copied(),
             inscription,
           },
         },
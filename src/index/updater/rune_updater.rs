@@ -29,11 +29,16 @@ pub(crate) struct RuneUpdate {
 pub(super) struct RuneUpdater<'a, 'db, 'tx> {
   pub(super) height: u32,
   pub(super) id_to_entry: &'a mut Table<'db, 'tx, RuneIdValue, RuneEntryValue>,
+  pub(super) id_to_transfer: &'a mut Table<'db, 'tx, (RuneIdValue, u32, u32), &'static [u8]>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'db, 'tx, InscriptionIdValue, u32>,
   pub(super) minimum: Rune,
   pub(super) outpoint_to_balances: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) outpoint_to_script_pubkey:
+    &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
   pub(super) rune_to_id: &'a mut Table<'db, 'tx, u128, RuneIdValue>,
   pub(super) runes: u64,
+  pub(super) script_pubkey_to_outpoint:
+    &'a mut MultimapTable<'db, 'tx, &'static [u8], &'static OutPointValue>,
   pub(super) sequence_number_to_rune_id: &'a mut Table<'db, 'tx, u32, RuneIdValue>,
   pub(super) statistic_to_count: &'a mut Table<'db, 'tx, u64, u64>,
   pub(super) timestamp: u32,
@@ -48,6 +53,9 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
     // A mapping of rune ID to un-allocated balance of that rune
     let mut unallocated: HashMap<u128, u128> = HashMap::new();
 
+    // A mapping of rune ID to the transfer recorded for it in this transaction
+    let mut transfers: BTreeMap<u128, RuneTransferEntry> = BTreeMap::new();
+
     // Increment unallocated runes with the runes in this transaction's inputs
     for input in &tx.input {
       if let Some(guard) = self
@@ -62,8 +70,22 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
           let (balance, len) = varint::decode(&buffer[i..]);
           i += len;
           *unallocated.entry(id).or_default() += balance;
+          transfers
+            .entry(id)
+            .or_default()
+            .inputs
+            .push((input.previous_output, balance));
         }
       }
+
+      if let Some(script_pubkey) = self
+        .outpoint_to_script_pubkey
+        .remove(&input.previous_output.store())?
+      {
+        self
+          .script_pubkey_to_outpoint
+          .remove(script_pubkey.value(), &input.previous_output.store())?;
+      }
     }
 
     let burn = runestone
@@ -149,6 +171,8 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
         None => None,
       };
 
+      let issuance = allocation.as_ref().map(|allocation| allocation.balance);
+
       if !burn {
         let mut mintable: HashMap<u128, u128> = HashMap::new();
 
@@ -266,6 +290,7 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
         for (id, amount) in mintable {
           let minted = limits[&id] - amount;
           if minted > 0 {
+            transfers.entry(id).or_default().minted += minted;
             let update = self
               .updates
               .entry(RuneId::try_from(id).unwrap())
@@ -288,6 +313,10 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
         symbol,
       }) = allocation
       {
+        let transfer = transfers.entry(id).or_default();
+        transfer.etching = true;
+        transfer.minted += issuance.unwrap() - balance;
+
         let id = RuneId::try_from(id).unwrap();
         self.rune_to_id.insert(rune.0, id.store())?;
         self.transaction_id_to_rune.insert(&txid.store(), rune.0)?;
@@ -396,20 +425,35 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
       for (id, balance) in balances {
         varint::encode_to_vec(id, &mut buffer);
         varint::encode_to_vec(balance, &mut buffer);
+        transfers
+          .entry(id)
+          .or_default()
+          .outputs
+          .push((vout.try_into().unwrap(), balance));
       }
 
-      self.outpoint_to_balances.insert(
-        &OutPoint {
-          txid,
-          vout: vout.try_into().unwrap(),
-        }
-        .store(),
-        buffer.as_slice(),
-      )?;
+      let outpoint = OutPoint {
+        txid,
+        vout: vout.try_into().unwrap(),
+      }
+      .store();
+
+      let script_pubkey = tx.output[vout].script_pubkey.as_bytes();
+
+      self
+        .outpoint_to_balances
+        .insert(&outpoint, buffer.as_slice())?;
+      self
+        .outpoint_to_script_pubkey
+        .insert(&outpoint, script_pubkey)?;
+      self
+        .script_pubkey_to_outpoint
+        .insert(script_pubkey, &outpoint)?;
     }
 
     // increment entries with burned runes
     for (id, amount) in burned {
+      transfers.entry(id).or_default().burned += amount;
       self
         .updates
         .entry(RuneId::try_from(id).unwrap())
@@ -417,6 +461,19 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
         .burned += amount;
     }
 
+    // record the transfer of every rune this transaction moved, minted or burned
+    for (id, mut transfer) in transfers {
+      transfer.txid = txid;
+      self.id_to_transfer.insert(
+        &(
+          RuneId::try_from(id).unwrap().store(),
+          self.height,
+          u32::try_from(index).unwrap(),
+        ),
+        transfer.store().as_slice(),
+      )?;
+    }
+
     Ok(())
   }
 }
@@ -0,0 +1,385 @@
+use super::*;
+
+/// An invariant of the index and, if it does not hold, what was found instead.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Invariant {
+  pub name: String,
+  pub violation: Option<String>,
+}
+
+impl Invariant {
+  fn new(name: &str, violation: Option<String>) -> Self {
+    Self {
+      name: name.into(),
+      violation,
+    }
+  }
+
+  pub fn holds(&self) -> bool {
+    self.violation.is_none()
+  }
+}
+
+/// The name of the only invariant that depends on the rune tables.
+pub(super) const RUNE_BALANCES: &str = "rune balances sum to supply less burned";
+
+impl Index {
+  /// Check the tables of the index against each other, without writing to it.
+  pub(crate) fn verify(&self) -> Result<Vec<Invariant>> {
+    let rtx = self.database.begin_read()?;
+
+    let inscriptions = rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .len()?;
+
+    let mut invariants = Vec::new();
+
+    invariants.push(Invariant::new(
+      "inscription tables have one row per sequence number",
+      [
+        rtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?.len()?,
+        rtx
+          .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
+          .len()?,
+        rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?.len()?,
+      ]
+      .into_iter()
+      .zip([
+        INSCRIPTION_ID_TO_SEQUENCE_NUMBER.name(),
+        INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER.name(),
+        SEQUENCE_NUMBER_TO_SATPOINT.name(),
+      ])
+      .find(|(len, _)| *len != inscriptions)
+      .map(|(len, name)| {
+        format!(
+          "{} has {len} rows, but {} has {inscriptions}",
+          name,
+          SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY.name()
+        )
+      }),
+    ));
+
+    let statistic_to_count = rtx.open_table(STATISTIC_TO_COUNT)?;
+    let statistic = |statistic: Statistic| -> Result<u64> {
+      Ok(
+        statistic_to_count
+          .get(&statistic.key())?
+          .map(|count| count.value())
+          .unwrap_or_default(),
+      )
+    };
+
+    let blessed = statistic(Statistic::BlessedInscriptions)?;
+    let cursed = statistic(Statistic::CursedInscriptions)?;
+
+    invariants.push(Invariant::new(
+      "blessed and cursed inscriptions sum to the inscription count",
+      (blessed + cursed != inscriptions).then(|| {
+        format!("{blessed} blessed and {cursed} cursed inscriptions, but {inscriptions} in total")
+      }),
+    ));
+
+    if !self.options.no_index_inscriptions {
+      let mut violation = None;
+      let mut previous = (0, 0);
+
+      for result in rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?.iter()? {
+        let (height, next_sequence_number) = result?;
+        let (height, next_sequence_number) = (height.value(), next_sequence_number.value());
+
+        if next_sequence_number < previous.1 {
+          violation = Some(format!(
+            "height {height} ends at sequence number {next_sequence_number}, before height {} at {}",
+            previous.0, previous.1
+          ));
+          break;
+        }
+
+        previous = (height, next_sequence_number);
+      }
+
+      if violation.is_none() && u64::from(previous.1) != inscriptions {
+        violation = Some(format!(
+          "per-height inscription counts sum to {}, but there are {inscriptions} inscriptions",
+          previous.1
+        ));
+      }
+
+      invariants.push(Invariant::new(
+        "per-height inscription counts sum to the inscription count",
+        violation,
+      ));
+    }
+
+    if self.index_runes {
+      let mut outstanding = BTreeMap::<RuneId, u128>::new();
+
+      for result in rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?.iter()? {
+        let (_, balances) = result?;
+        let balances = balances.value();
+
+        let mut i = 0;
+        while i < balances.len() {
+          let (id, length) = runes::varint::decode(&balances[i..]);
+          i += length;
+          let (amount, length) = runes::varint::decode(&balances[i..]);
+          i += length;
+
+          let balance = outstanding
+            .entry(RuneId::try_from(id).unwrap())
+            .or_default();
+
+          *balance = balance.saturating_add(amount);
+        }
+      }
+
+      let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+      let mut violation = None;
+
+      for (id, balance) in &outstanding {
+        if rune_id_to_rune_entry.get(&id.store())?.is_none() {
+          violation = Some(format!("outputs hold {balance} of unknown rune {id}"));
+          break;
+        }
+      }
+
+      if violation.is_none() {
+        for result in rune_id_to_rune_entry.iter()? {
+          let (id, entry) = result?;
+          let id = RuneId::load(id.value());
+          let entry = RuneEntry::load(entry.value());
+          let balance = outstanding.get(&id).copied().unwrap_or_default();
+
+          violation = match entry.supply.checked_sub(entry.burned) {
+            None => Some(format!(
+              "rune {id} has {} burned, more than its supply of {}",
+              entry.burned, entry.supply
+            )),
+            Some(outstanding) if outstanding != balance => Some(format!(
+              "rune {id} has {balance} in outputs, but {outstanding} outstanding"
+            )),
+            Some(_) => None,
+          };
+
+          if violation.is_some() {
+            break;
+          }
+        }
+      }
+
+      invariants.push(Invariant::new(RUNE_BALANCES, violation));
+    }
+
+    Ok(invariants)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  const RUNE: u128 = 99246114928149462;
+
+  fn violations(context: &Context) -> Vec<String> {
+    context
+      .index
+      .verify()
+      .unwrap()
+      .into_iter()
+      .filter_map(|invariant| invariant.violation)
+      .collect()
+  }
+
+  fn inscribe(context: &Context) -> InscriptionId {
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    InscriptionId { txid, index: 0 }
+  }
+
+  fn etch(context: &Context) -> RuneId {
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    RuneId {
+      height: 2,
+      index: 1,
+    }
+  }
+
+  #[test]
+  fn fresh_index_is_consistent() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    etch(&context);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.statistic(Statistic::BlessedInscriptions), 1);
+
+    let invariants = context.index.verify().unwrap();
+
+    assert_eq!(invariants.len(), 4);
+    assert!(invariants.iter().all(Invariant::holds));
+  }
+
+  #[test]
+  fn missing_inscription_id_row_is_reported() {
+    let context = Context::builder().build();
+
+    let id = inscribe(&context);
+
+    let wtx = context.index.database.begin_write().unwrap();
+    wtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
+      .unwrap()
+      .remove(&id.store())
+      .unwrap();
+    wtx.commit().unwrap();
+
+    assert_eq!(
+      violations(&context),
+      ["INSCRIPTION_ID_TO_SEQUENCE_NUMBER has 0 rows, but SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY has 1"]
+    );
+  }
+
+  #[test]
+  fn inconsistent_statistics_are_reported() {
+    let context = Context::builder().build();
+
+    inscribe(&context);
+
+    let wtx = context.index.database.begin_write().unwrap();
+    wtx
+      .open_table(STATISTIC_TO_COUNT)
+      .unwrap()
+      .insert(&Statistic::CursedInscriptions.key(), &1)
+      .unwrap();
+    wtx.commit().unwrap();
+
+    assert_eq!(
+      violations(&context),
+      ["1 blessed and 1 cursed inscriptions, but 1 in total"]
+    );
+  }
+
+  #[test]
+  fn decreasing_height_sequence_numbers_are_reported() {
+    let context = Context::builder().build();
+
+    inscribe(&context);
+
+    let wtx = context.index.database.begin_write().unwrap();
+    wtx
+      .open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)
+      .unwrap()
+      .insert(5, 0)
+      .unwrap();
+    wtx.commit().unwrap();
+
+    assert_eq!(
+      violations(&context),
+      ["height 5 ends at sequence number 0, before height 2 at 1"]
+    );
+  }
+
+  #[test]
+  fn missing_height_sequence_numbers_are_reported() {
+    let context = Context::builder().build();
+
+    inscribe(&context);
+
+    let wtx = context.index.database.begin_write().unwrap();
+    wtx
+      .open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)
+      .unwrap()
+      .remove(2)
+      .unwrap();
+    wtx.commit().unwrap();
+
+    assert_eq!(
+      violations(&context),
+      ["per-height inscription counts sum to 0, but there are 1 inscriptions"]
+    );
+  }
+
+  #[test]
+  fn negative_rune_supply_is_reported() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    let id = etch(&context);
+
+    let wtx = context.index.database.begin_write().unwrap();
+    {
+      let mut table = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY).unwrap();
+      let mut entry = RuneEntry::load(table.get(&id.store()).unwrap().unwrap().value());
+      entry.supply = 1;
+      entry.burned = 2;
+      table.insert(&id.store(), entry.store()).unwrap();
+    }
+    wtx.commit().unwrap();
+
+    assert_eq!(
+      violations(&context),
+      [format!("rune {id} has 2 burned, more than its supply of 1")]
+    );
+  }
+
+  #[test]
+  fn mismatched_rune_balances_are_reported() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    let id = etch(&context);
+
+    let wtx = context.index.database.begin_write().unwrap();
+    {
+      let mut table = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY).unwrap();
+      let mut entry = RuneEntry::load(table.get(&id.store()).unwrap().unwrap().value());
+      entry.burned = 1;
+      table.insert(&id.store(), entry.store()).unwrap();
+    }
+    wtx.commit().unwrap();
+
+    assert_eq!(
+      violations(&context),
+      [format!(
+        "rune {id} has {} in outputs, but {} outstanding",
+        u128::MAX,
+        u128::MAX - 1
+      )]
+    );
+  }
+}
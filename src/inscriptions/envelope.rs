@@ -53,7 +53,7 @@ impl From<RawEnvelope> for ParsedEnvelope {
     let delegate = Tag::Delegate.remove_field(&mut fields);
     let metadata = Tag::Metadata.remove_field(&mut fields);
     let metaprotocol = Tag::Metaprotocol.remove_field(&mut fields);
-    let parent = Tag::Parent.remove_field(&mut fields);
+    let parents = Tag::Parent.remove_array(&mut fields);
     let pointer = Tag::Pointer.remove_field(&mut fields);
 
     let unrecognized_even_field = fields
@@ -76,7 +76,7 @@ impl From<RawEnvelope> for ParsedEnvelope {
         incomplete_field,
         metadata,
         metaprotocol,
-        parent,
+        parents,
         pointer,
         unrecognized_even_field,
       },
@@ -821,6 +821,52 @@ mod tests {
     );
   }
 
+  #[test]
+  fn all_parent_fields_are_recognized() {
+    assert_eq!(
+      parse(&[envelope(&[
+        &PROTOCOL_ID,
+        Tag::Parent.bytes(),
+        &[1; 32],
+        Tag::Parent.bytes(),
+        &[2; 32]
+      ])]),
+      vec![ParsedEnvelope {
+        payload: Inscription {
+          parents: vec![vec![1; 32], vec![2; 32]],
+          duplicate_field: true,
+          ..Default::default()
+        },
+        ..Default::default()
+      }],
+    );
+  }
+
+  #[test]
+  fn round_trip_with_multiple_parents() {
+    let inscription = Inscription {
+      parents: vec![vec![1; 32], vec![2; 33]],
+      ..Default::default()
+    };
+
+    let mut witness = Witness::new();
+
+    witness.push(inscription.append_reveal_script(script::Builder::new()));
+
+    witness.push([]);
+
+    assert_eq!(
+      parse(&[witness]),
+      vec![ParsedEnvelope {
+        payload: Inscription {
+          duplicate_field: true,
+          ..inscription
+        },
+        ..Default::default()
+      }],
+    );
+  }
+
   #[test]
   fn tag_66_makes_inscriptions_unbound() {
     assert_eq!(
@@ -24,7 +24,7 @@ pub struct Inscription {
   pub incomplete_field: bool,
   pub metadata: Option<Vec<u8>>,
   pub metaprotocol: Option<Vec<u8>>,
-  pub parent: Option<Vec<u8>>,
+  pub parents: Vec<Vec<u8>>,
   pub pointer: Option<Vec<u8>>,
   pub unrecognized_even_field: bool,
 }
@@ -103,7 +103,7 @@ impl Inscription {
       content_encoding,
       metadata,
       metaprotocol: metaprotocol.map(|metaprotocol| metaprotocol.into_bytes()),
-      parent: parent.map(|id| id.value()),
+      parents: parent.into_iter().map(|id| id.value()).collect(),
       pointer: pointer.map(Self::pointer_value),
       ..Default::default()
     })
@@ -131,7 +131,7 @@ impl Inscription {
     Tag::ContentType.encode(&mut builder, &self.content_type);
     Tag::ContentEncoding.encode(&mut builder, &self.content_encoding);
     Tag::Metaprotocol.encode(&mut builder, &self.metaprotocol);
-    Tag::Parent.encode(&mut builder, &self.parent);
+    Tag::Parent.encode_array(&mut builder, &self.parents);
     Tag::Delegate.encode(&mut builder, &self.delegate);
     Tag::Pointer.encode(&mut builder, &self.pointer);
     Tag::Metadata.encode(&mut builder, &self.metadata);
@@ -169,8 +169,8 @@ impl Inscription {
     Inscription::append_batch_reveal_script_to_builder(inscriptions, builder).into_script()
   }
 
-  fn inscription_id_field(field: &Option<Vec<u8>>) -> Option<InscriptionId> {
-    let value = field.as_ref()?;
+  fn inscription_id_field(field: Option<&[u8]>) -> Option<InscriptionId> {
+    let value = field?;
 
     if value.len() < Txid::LEN {
       return None;
@@ -237,7 +237,7 @@ impl Inscription {
   }
 
   pub(crate) fn delegate(&self) -> Option<InscriptionId> {
-    Self::inscription_id_field(&self.delegate)
+    Self::inscription_id_field(self.delegate.as_deref())
   }
 
   pub(crate) fn metadata(&self) -> Option<Value> {
@@ -249,7 +249,17 @@ impl Inscription {
   }
 
   pub(crate) fn parent(&self) -> Option<InscriptionId> {
-    Self::inscription_id_field(&self.parent)
+    self.parents().first().copied()
+  }
+
+  /// The parents of the inscription, in the order their tags appear in the
+  /// envelope. Parent fields which are not valid inscription ids are skipped.
+  pub(crate) fn parents(&self) -> Vec<InscriptionId> {
+    self
+      .parents
+      .iter()
+      .filter_map(|parent| Self::inscription_id_field(Some(parent)))
+      .collect()
   }
 
   pub(crate) fn pointer(&self) -> Option<u64> {
@@ -430,7 +440,7 @@ mod tests {
   #[test]
   fn inscription_with_no_parent_field_has_no_parent() {
     assert!(Inscription {
-      parent: None,
+      parents: Vec::new(),
       ..Default::default()
     }
     .parent()
@@ -440,7 +450,7 @@ mod tests {
   #[test]
   fn inscription_with_parent_field_shorter_than_txid_length_has_no_parent() {
     assert!(Inscription {
-      parent: Some(vec![]),
+      parents: vec![vec![]],
       ..Default::default()
     }
     .parent()
@@ -450,7 +460,7 @@ mod tests {
   #[test]
   fn inscription_with_parent_field_longer_than_txid_and_index_has_no_parent() {
     assert!(Inscription {
-      parent: Some(vec![1; 37]),
+      parents: vec![vec![1; 37]],
       ..Default::default()
     }
     .parent()
@@ -464,7 +474,7 @@ mod tests {
     parent[35] = 0;
 
     assert!(Inscription {
-      parent: Some(parent),
+      parents: vec![parent],
       ..Default::default()
     }
     .parent()
@@ -478,13 +488,39 @@ mod tests {
     parent[34] = 0;
 
     assert!(Inscription {
-      parent: Some(parent),
+      parents: vec![parent],
       ..Default::default()
     }
     .parent()
     .is_none());
   }
 
+  #[test]
+  fn inscription_with_multiple_parent_fields_has_multiple_parents() {
+    let parents = vec![vec![1; 32], vec![], vec![2; 33]];
+
+    let inscription = Inscription {
+      parents,
+      ..Default::default()
+    };
+
+    assert_eq!(
+      inscription.parents(),
+      [
+        InscriptionId {
+          txid: Txid::from_slice(&[1; 32]).unwrap(),
+          index: 0,
+        },
+        InscriptionId {
+          txid: Txid::from_slice(&[2; 32]).unwrap(),
+          index: 2,
+        },
+      ]
+    );
+
+    assert_eq!(inscription.parent(), inscription.parents().first().copied());
+  }
+
   #[test]
   fn inscription_delegate_txid_is_deserialized_correctly() {
     assert_eq!(
@@ -509,11 +545,11 @@ mod tests {
   fn inscription_parent_txid_is_deserialized_correctly() {
     assert_eq!(
       Inscription {
-        parent: Some(vec![
+        parents: vec![vec![
           0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
           0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d,
           0x1e, 0x1f,
-        ]),
+        ]],
         ..Default::default()
       }
       .parent()
@@ -529,7 +565,7 @@ mod tests {
   fn inscription_parent_with_zero_byte_index_field_is_deserialized_correctly() {
     assert_eq!(
       Inscription {
-        parent: Some(vec![1; 32]),
+        parents: vec![vec![1; 32]],
         ..Default::default()
       }
       .parent()
@@ -543,11 +579,11 @@ mod tests {
   fn inscription_parent_with_one_byte_index_field_is_deserialized_correctly() {
     assert_eq!(
       Inscription {
-        parent: Some(vec![
+        parents: vec![vec![
           0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
           0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
           0xff, 0xff, 0x01
-        ]),
+        ]],
         ..Default::default()
       }
       .parent()
@@ -561,11 +597,11 @@ mod tests {
   fn inscription_parent_with_two_byte_index_field_is_deserialized_correctly() {
     assert_eq!(
       Inscription {
-        parent: Some(vec![
+        parents: vec![vec![
           0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
           0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
           0xff, 0xff, 0x01, 0x02
-        ]),
+        ]],
         ..Default::default()
       }
       .parent()
@@ -579,11 +615,11 @@ mod tests {
   fn inscription_parent_with_three_byte_index_field_is_deserialized_correctly() {
     assert_eq!(
       Inscription {
-        parent: Some(vec![
+        parents: vec![vec![
           0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
           0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
           0xff, 0xff, 0x01, 0x02, 0x03
-        ]),
+        ]],
         ..Default::default()
       }
       .parent()
@@ -597,11 +633,11 @@ mod tests {
   fn inscription_parent_with_four_byte_index_field_is_deserialized_correctly() {
     assert_eq!(
       Inscription {
-        parent: Some(vec![
+        parents: vec![vec![
           0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
           0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
           0xff, 0xff, 0x01, 0x02, 0x03, 0x04,
-        ]),
+        ]],
         ..Default::default()
       }
       .parent()
@@ -5,6 +5,7 @@ use {
   },
   mp4::{MediaType, Mp4Reader, TrackType},
   std::{fs::File, io::BufReader},
+  utoipa::ToSchema,
 };
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -22,6 +23,61 @@ pub(crate) enum Media {
   Video,
 }
 
+/// The kind of content of a media type, for clients that choose how to
+/// display content by kind rather than by exact type.
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaCategory {
+  Image,
+  Video,
+  Audio,
+  Text,
+  Model,
+  Other,
+}
+
+impl MediaCategory {
+  pub(crate) fn for_media_type(media_type: &str) -> Self {
+    match media_type.split_once('/').map(|(top, _)| top) {
+      Some("image") => Self::Image,
+      Some("video") => Self::Video,
+      Some("audio") => Self::Audio,
+      Some("text") => Self::Text,
+      Some("model") => Self::Model,
+      _ => match media_type.parse() {
+        Ok(Media::Code(_) | Media::Markdown | Media::Text) => Self::Text,
+        _ => Self::Other,
+      },
+    }
+  }
+}
+
+impl Display for MediaCategory {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Image => "image",
+        Self::Video => "video",
+        Self::Audio => "audio",
+        Self::Text => "text",
+        Self::Model => "model",
+        Self::Other => "other",
+      }
+    )
+  }
+}
+
+/// The media type of `content_type`: its type and subtype in lowercase,
+/// without parameters. `None` if `content_type` is not a valid media type.
+pub(crate) fn media_type(content_type: &str) -> Option<String> {
+  content_type
+    .parse::<mime::Mime>()
+    .ok()
+    .map(|mime| mime.essence_str().to_lowercase())
+}
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub(crate) enum Language {
   Css,
@@ -122,6 +178,20 @@ impl Media {
     ))
   }
 
+  /// The file extension of content of `content_type`, or `bin` if it is not
+  /// a known media type.
+  pub(crate) fn extension_for_content_type(content_type: &str) -> &'static str {
+    let Some(essence) = media_type(content_type) else {
+      return "bin";
+    };
+
+    Self::TABLE
+      .iter()
+      .filter(|(table_type, ..)| media_type(table_type).as_deref() == Some(essence.as_str()))
+      .find_map(|(.., extensions)| extensions.first().copied())
+      .unwrap_or("bin")
+  }
+
   pub(crate) fn check_mp4_codec(path: &Path) -> Result<(), Error> {
     let f = File::open(path)?;
     let size = f.metadata()?.len();
@@ -189,6 +259,20 @@ mod tests {
     );
   }
 
+  #[test]
+  fn extension_for_content_type() {
+    assert_eq!(Media::extension_for_content_type("image/png"), "png");
+    assert_eq!(Media::extension_for_content_type("IMAGE/JPEG"), "jpg");
+    assert_eq!(Media::extension_for_content_type("text/html"), "html");
+    assert_eq!(
+      Media::extension_for_content_type("text/plain; charset=utf-8"),
+      "txt"
+    );
+    assert_eq!(Media::extension_for_content_type("image/avif"), "bin");
+    assert_eq!(Media::extension_for_content_type("foo/bar"), "bin");
+    assert_eq!(Media::extension_for_content_type("foo"), "bin");
+  }
+
   #[test]
   fn h264_in_mp4_is_allowed() {
     assert!(Media::check_mp4_codec(Path::new("examples/h264.mp4")).is_ok(),);
@@ -199,6 +283,46 @@ mod tests {
     assert!(Media::check_mp4_codec(Path::new("examples/av1.mp4")).is_err(),);
   }
 
+  #[test]
+  fn media_types_are_normalized() {
+    assert_eq!(
+      media_type("text/html;charset=utf-8").as_deref(),
+      Some("text/html")
+    );
+    assert_eq!(
+      media_type("Image/SVG+XML; charset=UTF-8").as_deref(),
+      Some("image/svg+xml")
+    );
+    assert_eq!(
+      media_type("model/gltf+json").as_deref(),
+      Some("model/gltf+json")
+    );
+    assert_eq!(media_type("not a media type"), None);
+    assert_eq!(media_type(""), None);
+  }
+
+  #[test]
+  fn media_categories() {
+    for (media_type, category) in [
+      ("text/html", MediaCategory::Text),
+      ("image/svg+xml", MediaCategory::Image),
+      ("model/gltf+json", MediaCategory::Model),
+      ("model/gltf-binary", MediaCategory::Model),
+      ("image/png", MediaCategory::Image),
+      ("video/mp4", MediaCategory::Video),
+      ("audio/mpeg", MediaCategory::Audio),
+      ("application/json", MediaCategory::Text),
+      ("application/x-unknown", MediaCategory::Other),
+      ("application/octet-stream", MediaCategory::Other),
+    ] {
+      assert_eq!(
+        MediaCategory::for_media_type(media_type),
+        category,
+        "{media_type}"
+      );
+    }
+  }
+
   #[test]
   fn no_duplicate_extensions() {
     let mut set = HashSet::new();
@@ -57,6 +57,12 @@ impl Tag {
     }
   }
 
+  pub(crate) fn encode_array(self, builder: &mut script::Builder, values: &[Vec<u8>]) {
+    for value in values {
+      self.encode(builder, &Some(value.clone()));
+    }
+  }
+
   pub(crate) fn remove_field(self, fields: &mut BTreeMap<&[u8], Vec<&[u8]>>) -> Option<Vec<u8>> {
     if self.is_chunked() {
       let value = fields.remove(self.bytes())?;
@@ -82,4 +88,13 @@ impl Tag {
       }
     }
   }
+
+  pub(crate) fn remove_array(self, fields: &mut BTreeMap<&[u8], Vec<&[u8]>>) -> Vec<Vec<u8>> {
+    fields
+      .remove(self.bytes())
+      .unwrap_or_default()
+      .into_iter()
+      .map(|value| value.to_vec())
+      .collect()
+  }
 }
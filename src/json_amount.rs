@@ -0,0 +1,189 @@
+//! Serialization of rune amounts in JSON.
+//!
+//! Amounts are `u128`s, which JavaScript clients can't parse as numbers
+//! without losing precision, so they are serialized as decimal strings. The
+//! server can opt back into numbers with `--legacy-number-amounts`.
+//!
+//! Use with `#[serde(with = "json_amount")]`, or the `option` and `map`
+//! submodules for `Option<u128>` and `BTreeMap<K, u128>` fields.
+
+use {super::*, serde::de::Visitor, std::future::Future};
+
+tokio::task_local! {
+  static LEGACY_NUMBER_AMOUNTS: bool;
+}
+
+/// Run `future`, serializing amounts as JSON numbers if `legacy` is set.
+pub(crate) async fn scope<F: Future>(legacy: bool, future: F) -> F::Output {
+  LEGACY_NUMBER_AMOUNTS.scope(legacy, future).await
+}
+
+struct Amount(u128);
+
+impl Serialize for Amount {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    if LEGACY_NUMBER_AMOUNTS
+      .try_with(|legacy| *legacy)
+      .unwrap_or_default()
+    {
+      serializer.serialize_u128(self.0)
+    } else {
+      serializer.collect_str(&self.0)
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    struct AmountVisitor;
+
+    impl<'de> Visitor<'de> for AmountVisitor {
+      type Value = Amount;
+
+      fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("an amount as a decimal string or an integer")
+      }
+
+      fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Amount, E> {
+        Ok(Amount(value.into()))
+      }
+
+      fn visit_u128<E: serde::de::Error>(self, value: u128) -> Result<Amount, E> {
+        Ok(Amount(value))
+      }
+
+      fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Amount, E> {
+        value.parse().map(Amount).map_err(E::custom)
+      }
+    }
+
+    deserializer.deserialize_any(AmountVisitor)
+  }
+}
+
+pub(crate) fn serialize<S>(amount: &u128, serializer: S) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  Amount(*amount).serialize(serializer)
+}
+
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<u128, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Ok(Amount::deserialize(deserializer)?.0)
+}
+
+pub(crate) mod option {
+  use super::*;
+
+  pub(crate) fn serialize<S>(amount: &Option<u128>, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    amount.map(Amount).serialize(serializer)
+  }
+
+  pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Option<u128>, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    Ok(Option::<Amount>::deserialize(deserializer)?.map(|amount| amount.0))
+  }
+}
+
+pub(crate) mod map {
+  use super::*;
+
+  pub(crate) fn serialize<K, S>(
+    amounts: &BTreeMap<K, u128>,
+    serializer: S,
+  ) -> Result<S::Ok, S::Error>
+  where
+    K: Serialize,
+    S: Serializer,
+  {
+    serializer.collect_map(amounts.iter().map(|(key, amount)| (key, Amount(*amount))))
+  }
+
+  pub(crate) fn deserialize<'de, K, D>(deserializer: D) -> Result<BTreeMap<K, u128>, D::Error>
+  where
+    K: Deserialize<'de> + Ord,
+    D: Deserializer<'de>,
+  {
+    Ok(
+      BTreeMap::<K, Amount>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(key, amount)| (key, amount.0))
+        .collect(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Debug, PartialEq, Serialize, Deserialize)]
+  struct Amounts {
+    #[serde(with = "crate::json_amount")]
+    amount: u128,
+    #[serde(with = "crate::json_amount::option")]
+    limit: Option<u128>,
+    #[serde(with = "crate::json_amount::map")]
+    balances: BTreeMap<String, u128>,
+  }
+
+  fn amounts() -> Amounts {
+    Amounts {
+      amount: 10u128.pow(20),
+      limit: Some(u128::max_value()),
+      balances: vec![("foo".into(), 10u128.pow(20))].into_iter().collect(),
+    }
+  }
+
+  #[test]
+  fn amounts_round_trip_as_strings() {
+    let json = serde_json::to_string(&amounts()).unwrap();
+
+    assert_eq!(
+      json,
+      r#"{"amount":"100000000000000000000","limit":"340282366920938463463374607431768211455","balances":{"foo":"100000000000000000000"}}"#
+    );
+
+    assert_eq!(serde_json::from_str::<Amounts>(&json).unwrap(), amounts());
+  }
+
+  #[test]
+  fn legacy_amounts_are_numbers() {
+    let json = tokio::runtime::Runtime::new()
+      .unwrap()
+      .block_on(scope(true, async {
+        serde_json::to_string(&amounts()).unwrap()
+      }));
+
+    assert_eq!(
+      json,
+      r#"{"amount":100000000000000000000,"limit":340282366920938463463374607431768211455,"balances":{"foo":100000000000000000000}}"#
+    );
+  }
+
+  #[test]
+  fn small_numbers_are_accepted() {
+    assert_eq!(
+      serde_json::from_str::<Amounts>(r#"{"amount":1,"limit":null,"balances":{"foo":2}}"#).unwrap(),
+      Amounts {
+        amount: 1,
+        limit: None,
+        balances: vec![("foo".into(), 2)].into_iter().collect(),
+      }
+    );
+  }
+}
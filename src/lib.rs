@@ -23,6 +23,7 @@ use {
     height::Height,
     index::List,
     inscriptions::{media, teleburn, Charm, Media, ParsedEnvelope},
+    logger::LogFormat,
     outgoing::Outgoing,
     representation::Representation,
     runes::{Etching, Pile, SpacedRune},
@@ -66,7 +67,7 @@ use {
     io::{self, Cursor},
     mem,
     net::{TcpListener, ToSocketAddrs},
-    ops::{Add, AddAssign, Sub},
+    ops::{Add, AddAssign, Deref, Sub},
     path::{Path, PathBuf},
     process::{self, Command},
     str::FromStr,
@@ -85,7 +86,7 @@ use {
 pub use self::{
   chain::Chain,
   fee_rate::FeeRate,
-  index::{Index, RuneEntry},
+  index::{Index, RuneEntry, WritableIndex},
   inscriptions::{Envelope, Inscription, InscriptionId},
   object::Object,
   options::Options,
@@ -126,6 +127,7 @@ mod fee_rate;
 mod height;
 mod index;
 mod inscriptions;
+mod json_amount;
 mod logger;
 mod object;
 mod okx;
@@ -146,7 +148,7 @@ type Result<T = (), E = Error> = std::result::Result<T, E>;
 const CYCLE_EPOCHS: u32 = 6;
 
 static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
-static LISTENERS: Mutex<Vec<axum_server::Handle>> = Mutex::new(Vec::new());
+static LISTENERS: Mutex<Vec<(axum_server::Handle, Duration)>> = Mutex::new(Vec::new());
 static INDEXER: Mutex<Option<thread::JoinHandle<()>>> = Mutex::new(Option::None);
 
 const TARGET_POSTAGE: Amount = Amount::from_sat(10_000);
@@ -217,7 +219,8 @@ fn gracefully_shutdown_indexer() {
 pub fn main() {
   let args = Arguments::parse();
   let log_dir = args.options.log_dir();
-  logger::init(args.options.log_level(), log_dir).expect("initialize logger error:");
+  logger::init(args.options.log_level(), args.options.log_format, log_dir)
+    .expect("initialize logger error:");
 
   ctrlc::set_handler(move || {
     if SHUTTING_DOWN.fetch_or(true, atomic::Ordering::Relaxed) {
@@ -230,7 +233,7 @@ pub fn main() {
       .lock()
       .unwrap()
       .iter()
-      .for_each(|handle| handle.graceful_shutdown(Some(Duration::from_millis(100))));
+      .for_each(|(handle, grace)| handle.graceful_shutdown(Some(*grace)));
   })
   .expect("Error setting <CTRL-C> handler");
 
@@ -8,22 +8,131 @@ use log4rs::{
       },
       RollingFileAppender,
     },
+    Append,
   },
-  config::{Appender, Logger, Root},
   encode::pattern::PatternEncoder,
-  Config,
 };
 use std::fs;
+use std::io;
 use std::path::Path;
+use std::sync::Arc;
+use tracing::Subscriber;
+use tracing_subscriber::{
+  filter::{LevelFilter, Targets},
+  fmt::{self, MakeWriter},
+  layer::SubscriberExt,
+  util::SubscriberInitExt,
+  Layer,
+};
+
+#[derive(Default, clap::ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub enum LogFormat {
+  Json,
+  #[default]
+  Text,
+}
+
+/// Lines formatted by the tracing subscriber are handed to log4rs appenders
+/// verbatim, which write them to the console and roll the log file.
+#[derive(Clone)]
+struct Appenders(Arc<Vec<Box<dyn Append>>>);
+
+impl<'a> MakeWriter<'a> for Appenders {
+  type Writer = Line;
+
+  fn make_writer(&'a self) -> Self::Writer {
+    Line {
+      appenders: self.0.clone(),
+      buffer: Vec::new(),
+    }
+  }
+}
+
+/// A single formatted line, appended to every appender when dropped.
+struct Line {
+  appenders: Arc<Vec<Box<dyn Append>>>,
+  buffer: Vec<u8>,
+}
+
+impl io::Write for Line {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.buffer.extend_from_slice(buf);
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+impl Drop for Line {
+  fn drop(&mut self) {
+    if self.buffer.is_empty() {
+      return;
+    }
+
+    let line = String::from_utf8_lossy(&self.buffer);
+
+    for appender in self.appenders.iter() {
+      if let Err(err) =
+        appender.append(&log::Record::builder().args(format_args!("{line}")).build())
+      {
+        eprintln!("failed to write log line: {err}");
+      }
+    }
+  }
+}
+
+fn level_filter(level: log::LevelFilter) -> LevelFilter {
+  match level {
+    log::LevelFilter::Off => LevelFilter::OFF,
+    log::LevelFilter::Error => LevelFilter::ERROR,
+    log::LevelFilter::Warn => LevelFilter::WARN,
+    log::LevelFilter::Info => LevelFilter::INFO,
+    log::LevelFilter::Debug => LevelFilter::DEBUG,
+    log::LevelFilter::Trace => LevelFilter::TRACE,
+  }
+}
+
+/// A subscriber writing events with their fields and the fields of their
+/// spans as `format` lines to `appenders`.
+fn subscriber(
+  level: log::LevelFilter,
+  format: LogFormat,
+  appenders: Appenders,
+) -> impl Subscriber + Send + Sync {
+  let layer = match format {
+    LogFormat::Json => fmt::layer()
+      .json()
+      .with_current_span(true)
+      .with_span_list(true)
+      .with_writer(appenders)
+      .boxed(),
+    LogFormat::Text => fmt::layer().with_ansi(false).with_writer(appenders).boxed(),
+  };
 
-pub fn init<P: AsRef<Path>>(level: log::LevelFilter, log_dir: P) -> anyhow::Result<log4rs::Handle> {
+  tracing_subscriber::registry().with(layer).with(
+    Targets::new()
+      .with_default(level_filter(level))
+      .with_target("mio", LevelFilter::ERROR),
+  )
+}
+
+/// Install a tracing subscriber writing to the console and to a rolling
+/// `ord.log` in `log_dir`. Records of the `log` crate are bridged to it, so
+/// they are written the same way.
+pub fn init<P: AsRef<Path>>(
+  level: log::LevelFilter,
+  format: LogFormat,
+  log_dir: P,
+) -> anyhow::Result<()> {
   fs::create_dir_all(&log_dir)?;
   let log_file = log_dir.as_ref().join("ord.log");
 
-  let stdout = ConsoleAppender::builder().build();
+  let stdout = ConsoleAppender::builder()
+    .encoder(Box::new(PatternEncoder::new("{m}")))
+    .build();
 
-  // using default encoder for now, change it as needed.
-  let encoder = PatternEncoder::default();
   let trigger = SizeTrigger::new(1024 * 1024 * 20);
   let roller = FixedWindowRoller::builder()
     .build(
@@ -38,21 +147,85 @@ pub fn init<P: AsRef<Path>>(level: log::LevelFilter, log_dir: P) -> anyhow::Resu
   let policy = CompoundPolicy::new(Box::new(trigger), Box::new(roller));
   let rfile = RollingFileAppender::builder()
     .append(true)
-    .encoder(Box::new(encoder))
+    .encoder(Box::new(PatternEncoder::new("{m}")))
     .build(&log_file, Box::new(policy))
     .with_context(|| format!("Failed to create rolling file {}", log_file.display()))?;
 
-  let cfg = Config::builder()
-    .appender(Appender::builder().build("stdout", Box::new(stdout)))
-    .appender(Appender::builder().build("rfile", Box::new(rfile)))
-    .logger(Logger::builder().build("mio", log::LevelFilter::Error))
-    .build(
-      Root::builder()
-        .appender("stdout")
-        .appender("rfile")
-        .build(level),
-    )
-    .context("build log config failed")?;
+  subscriber(
+    level,
+    format,
+    Appenders(Arc::new(vec![Box::new(stdout), Box::new(rfile)])),
+  )
+  .try_init()
+  .context("tracing subscriber init error")
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::sync::Mutex};
+
+  #[derive(Debug, Clone, Default)]
+  struct Lines(Arc<Mutex<Vec<String>>>);
+
+  impl Append for Lines {
+    fn append(&self, record: &log::Record) -> anyhow::Result<()> {
+      self.0.lock().unwrap().push(record.args().to_string());
+      Ok(())
+    }
+
+    fn flush(&self) {}
+  }
+
+  fn lines(format: LogFormat, f: impl FnOnce()) -> Vec<String> {
+    let lines = Lines::default();
+
+    tracing::subscriber::with_default(
+      subscriber(
+        log::LevelFilter::Info,
+        format,
+        Appenders(Arc::new(vec![Box::new(lines.clone())])),
+      ),
+      f,
+    );
+
+    let lines = lines.0.lock().unwrap().clone();
+    lines
+  }
+
+  fn emit() {
+    let span = tracing::info_span!("request", route = "/status");
+    let _entered = span.enter();
+    tracing::error!(error = "boom", "error serving request");
+    tracing::debug!("filtered");
+  }
+
+  #[test]
+  fn json_lines_carry_fields_and_spans() {
+    let lines = lines(LogFormat::Json, emit);
+
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].ends_with('\n'));
+
+    let line: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+
+    assert_eq!(line["level"], "ERROR");
+    assert_eq!(line["fields"]["message"], "error serving request");
+    assert_eq!(line["fields"]["error"], "boom");
+    assert_eq!(line["span"]["name"], "request");
+    assert_eq!(line["span"]["route"], "/status");
+  }
+
+  #[test]
+  fn text_lines_carry_fields_and_spans() {
+    let lines = lines(LogFormat::Text, emit);
 
-  log4rs::init_config(cfg).context("log4rs init config error")
+    assert_eq!(lines.len(), 1);
+    assert!(
+      lines[0].contains(
+        "request{route=\"/status\"}: ord::logger::tests: error serving request error=\"boom\""
+      ),
+      "{}",
+      lines[0]
+    );
+  }
 }
@@ -30,6 +30,17 @@ pub enum Event {
   Transfer(TransferEvent),
 }
 
+impl Event {
+  pub fn tick(&self) -> &Tick {
+    match self {
+      Self::Deploy(event) => &event.tick,
+      Self::Mint(event) => &event.tick,
+      Self::InscribeTransfer(event) => &event.tick,
+      Self::Transfer(event) => &event.tick,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct DeployEvent {
   pub supply: u128,
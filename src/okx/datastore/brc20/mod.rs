@@ -13,7 +13,10 @@ pub use self::{
 use super::ScriptKey;
 use crate::{Result, SatPoint};
 use bitcoin::{OutPoint, Txid};
-use std::fmt::{Debug, Display};
+use std::{
+  fmt::{Debug, Display},
+  ops::Range,
+};
 
 pub trait Brc20Reader {
   type Error: Debug + Display;
@@ -30,6 +33,12 @@ pub trait Brc20Reader {
 
   fn get_transaction_receipts(&self, txid: &Txid) -> Result<Option<Vec<Receipt>>, Self::Error>;
 
+  fn get_tick_transaction_ids(
+    &self,
+    tick: &Tick,
+    heights: Range<u32>,
+  ) -> Result<Vec<(u32, Vec<Txid>)>, Self::Error>;
+
   fn get_transferable_assets_by_satpoint(
     &self,
     satpoint: &SatPoint,
@@ -73,6 +82,13 @@ pub trait Brc20ReaderWriter: Brc20Reader {
     receipt: &[Receipt],
   ) -> Result<(), Self::Error>;
 
+  fn add_tick_transaction_id(
+    &mut self,
+    tick: &Tick,
+    height: u32,
+    txid: &Txid,
+  ) -> Result<(), Self::Error>;
+
   fn insert_transferable_asset(
     &mut self,
     satpoint: SatPoint,
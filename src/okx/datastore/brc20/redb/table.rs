@@ -17,6 +17,7 @@ use crate::{
 };
 use bitcoin::{OutPoint, Txid};
 use redb::{MultimapTable, ReadableMultimapTable, ReadableTable, Table};
+use std::ops::Range;
 
 // BRC20_BALANCES
 pub fn get_balances<T>(table: &T, script_key: &ScriptKey) -> Result<Vec<Balance>>
@@ -84,6 +85,32 @@ where
   )
 }
 
+// BRC20_TICK_HEIGHT_TO_TXIDS
+pub fn get_tick_transaction_ids<T>(
+  table: &T,
+  tick: &Tick,
+  heights: Range<u32>,
+) -> Result<Vec<(u32, Vec<Txid>)>>
+where
+  T: ReadableTable<(&'static str, u32), &'static [u8]>,
+{
+  let tick = tick.to_lowercase().hex();
+
+  Ok(
+    table
+      .range((tick.as_str(), heights.start)..(tick.as_str(), heights.end))?
+      .flat_map(|result| {
+        result.map(|(key, data)| {
+          (
+            key.value().1,
+            rmp_serde::from_slice::<Vec<Txid>>(data.value()).unwrap(),
+          )
+        })
+      })
+      .collect(),
+  )
+}
+
 // BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS
 // BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS
 pub fn get_transferable_assets_by_account<T, S>(
@@ -264,6 +291,44 @@ pub fn save_transaction_receipts(
   Ok(())
 }
 
+// BRC20_TICK_HEIGHT_TO_TXIDS
+pub fn add_tick_transaction_id(
+  table: &mut Table<'_, '_, (&'static str, u32), &'static [u8]>,
+  tick: &Tick,
+  height: u32,
+  txid: &Txid,
+) -> Result<()> {
+  let tick = tick.to_lowercase().hex();
+
+  let mut txids = table
+    .get((tick.as_str(), height))?
+    .map(|v| rmp_serde::from_slice::<Vec<Txid>>(v.value()).unwrap())
+    .unwrap_or_default();
+
+  if txids.last() != Some(txid) {
+    txids.push(*txid);
+    table.insert(
+      (tick.as_str(), height),
+      rmp_serde::to_vec(&txids).unwrap().as_slice(),
+    )?;
+  }
+  Ok(())
+}
+
+// BRC20_TICK_HEIGHT_TO_TXIDS
+pub fn set_tick_transaction_ids(
+  table: &mut Table<'_, '_, (&'static str, u32), &'static [u8]>,
+  tick: &Tick,
+  height: u32,
+  txids: &[Txid],
+) -> Result<()> {
+  table.insert(
+    (tick.to_lowercase().hex().as_str(), height),
+    rmp_serde::to_vec(txids).unwrap().as_slice(),
+  )?;
+  Ok(())
+}
+
 // BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS
 // BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS
 pub fn insert_transferable_asset(
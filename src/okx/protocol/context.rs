@@ -7,7 +7,8 @@ use crate::{
     datastore::{
       brc20::{
         redb::table::{
-          get_balance, get_balances, get_token_info, get_tokens_info, get_transaction_receipts,
+          add_tick_transaction_id, get_balance, get_balances, get_tick_transaction_ids,
+          get_token_info, get_tokens_info, get_transaction_receipts,
           get_transferable_assets_by_account, get_transferable_assets_by_account_ticker,
           get_transferable_assets_by_outpoint, get_transferable_assets_by_satpoint,
           insert_token_info, insert_transferable_asset, remove_transferable_asset,
@@ -36,6 +37,7 @@ use crate::{
 use anyhow::anyhow;
 use bitcoin::{OutPoint, TxOut, Txid};
 use redb::{MultimapTable, Table};
+use std::ops::Range;
 
 #[allow(non_snake_case)]
 pub struct Context<'a, 'db, 'txn> {
@@ -58,6 +60,8 @@ pub struct Context<'a, 'db, 'txn> {
   pub(crate) BRC20_BALANCES: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
   pub(crate) BRC20_TOKEN: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
   pub(crate) BRC20_EVENTS: &'a mut Table<'db, 'txn, &'static TxidValue, &'static [u8]>,
+  pub(crate) BRC20_TICK_HEIGHT_TO_TXIDS:
+    &'a mut Table<'db, 'txn, (&'static str, u32), &'static [u8]>,
   pub(crate) BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS:
     &'a mut Table<'db, 'txn, &'static SatPointValue, &'static [u8]>,
   pub(crate) BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS:
@@ -184,6 +188,14 @@ impl<'a, 'db, 'txn> Brc20Reader for Context<'a, 'db, 'txn> {
     get_transaction_receipts(self.BRC20_EVENTS, txid)
   }
 
+  fn get_tick_transaction_ids(
+    &self,
+    tick: &Tick,
+    heights: Range<u32>,
+  ) -> crate::Result<Vec<(u32, Vec<Txid>)>, Self::Error> {
+    get_tick_transaction_ids(self.BRC20_TICK_HEIGHT_TO_TXIDS, tick, heights)
+  }
+
   fn get_transferable_assets_by_account(
     &self,
     script: &ScriptKey,
@@ -265,6 +277,15 @@ impl<'a, 'db, 'txn> Brc20ReaderWriter for Context<'a, 'db, 'txn> {
     save_transaction_receipts(self.BRC20_EVENTS, txid, receipt)
   }
 
+  fn add_tick_transaction_id(
+    &mut self,
+    tick: &Tick,
+    height: u32,
+    txid: &Txid,
+  ) -> crate::Result<(), Self::Error> {
+    add_tick_transaction_id(self.BRC20_TICK_HEIGHT_TO_TXIDS, tick, height, txid)
+  }
+
   fn insert_transferable_asset(
     &mut self,
     satpoint: SatPoint,
@@ -41,6 +41,15 @@ impl CallManager {
       .save_transaction_receipts(txid, &receipts)
       .map_err(|e| anyhow!("failed to add transaction receipt to state! error: {e}"))?;
 
+    for event in receipts
+      .iter()
+      .filter_map(|receipt| receipt.result.as_ref().ok())
+    {
+      context
+        .add_tick_transaction_id(event.tick(), context.chain_conf.blockheight, txid)
+        .map_err(|e| anyhow!("failed to add tick transaction to state! error: {e}"))?;
+    }
+
     let brc20_inscriptions = receipts
       .into_iter()
       .map(|receipt| receipt.inscription_id)
@@ -10,6 +10,13 @@ use {super::*, bitcoincore_rpc::Auth};
 pub struct Options {
   #[arg(long, default_value_t=LogLevel::default(), help = "log level")]
   pub(crate) log_level: LogLevel,
+  #[arg(
+    long,
+    value_enum,
+    default_value_t,
+    help = "Write logs as <LOG_FORMAT>."
+  )]
+  pub(crate) log_format: LogFormat,
   #[arg(long, help = "write log in directory <LOG_DIR>")]
   pub(crate) log_dir: Option<PathBuf>,
   #[arg(long, help = "Load Bitcoin Core data dir from <BITCOIN_DATA_DIR>.")]
@@ -44,6 +51,11 @@ pub struct Options {
     help = "Set lru cache to <LRU_SIZE>. By default 10000000"
   )]
   pub(crate) lru_size: usize,
+  #[arg(
+    long,
+    help = "Pause indexing once the index file reaches <MAX_INDEX_SIZE_GB> gigabytes."
+  )]
+  pub(crate) max_index_size_gb: Option<f64>,
   #[arg(
     long,
     help = "Don't look for inscriptions below <FIRST_INSCRIPTION_HEIGHT>."
@@ -164,6 +176,13 @@ impl Options {
     }
   }
 
+  pub(crate) fn max_index_size(&self) -> Option<u64> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    self
+      .max_index_size_gb
+      .map(|gigabytes| (gigabytes * 1e9) as u64)
+  }
+
   pub(crate) fn index_runes(&self) -> bool {
     self.index_runes && self.chain() != Chain::Mainnet
   }
@@ -862,6 +881,25 @@ mod tests {
     assert_eq!(arguments.options.db_cache_size, Some(16000000000));
   }
 
+  #[test]
+  fn max_index_size_is_in_gigabytes() {
+    assert_eq!(
+      Arguments::try_parse_from(["ord", "index", "update"])
+        .unwrap()
+        .options
+        .max_index_size(),
+      None
+    );
+
+    assert_eq!(
+      Arguments::try_parse_from(["ord", "--max-index-size-gb", "1.5", "index", "update"])
+        .unwrap()
+        .options
+        .max_index_size(),
+      Some(1_500_000_000)
+    );
+  }
+
   #[test]
   fn index_runes_only_returns_true_if_index_runes_flag_is_passed_and_not_on_mainnnet() {
     assert!(Arguments::try_parse_from([
@@ -1,11 +1,18 @@
-use super::*;
+use {super::*, http::Uri};
 
 #[derive(Default)]
 pub(crate) struct ServerConfig {
   pub(crate) chain: Chain,
+  pub(crate) content_csp: Option<String>,
+  pub(crate) content_origin: Option<Uri>,
   pub(crate) csp_origin: Option<String>,
   pub(crate) decompress: bool,
+  pub(crate) decompress_brotli_limit: u64,
+  pub(crate) default_page_size: usize,
   pub(crate) domain: Option<String>,
   pub(crate) index_sats: bool,
   pub(crate) is_json_api_enabled: bool,
+  pub(crate) max_page_size: usize,
+  pub(crate) route_groups: Vec<String>,
+  pub(crate) scan_mempool: bool,
 }
@@ -2,18 +2,23 @@ use super::*;
 
 pub mod balances;
 pub mod decode;
+pub mod dump_openapi;
 pub mod epochs;
+pub mod export_inscriptions;
 pub mod find;
 pub mod index;
 pub mod list;
+pub mod migrate_index;
 pub mod parse;
 mod preview;
+pub mod reindex_runes;
 pub mod runes;
 pub(crate) mod server;
 pub mod subsidy;
 pub mod supply;
 pub mod teleburn;
 pub mod traits;
+pub mod verify_index;
 pub mod wallet;
 
 #[derive(Debug, Parser)]
@@ -22,18 +27,29 @@ pub(crate) enum Subcommand {
   Balances,
   #[command(about = "Decode a transaction")]
   Decode(decode::Decode),
+  #[command(about = "Print the OpenAPI document of the JSON API")]
+  DumpOpenapi(dump_openapi::DumpOpenapi),
   #[command(about = "List the first satoshis of each reward epoch")]
   Epochs,
+  #[command(about = "Write inscriptions as newline-delimited JSON")]
+  ExportInscriptions(export_inscriptions::ExportInscriptions),
   #[command(about = "Find a satoshi's current location")]
   Find(find::Find),
   #[command(subcommand, about = "Index commands")]
   Index(index::IndexSubcommand),
   #[command(about = "List the satoshis in an output")]
   List(list::List),
+  #[command(
+    about = "Migrate the index to the current schema version",
+    long_about = "Migrate the index to the current schema version.\n\nMigrating from schema 21 fetches every block since the first BRC20 deploy from Bitcoin Core to index BRC20 transactions by ticker, which takes hours on mainnet. Progress is committed every 1000 blocks, so an interrupted migration resumes where it stopped."
+  )]
+  MigrateIndex,
   #[command(about = "Parse a satoshi from ordinal notation")]
   Parse(parse::Parse),
   #[command(about = "Run an explorer server populated with inscriptions")]
   Preview(preview::Preview),
+  #[command(about = "Rebuild the rune tables of the index, leaving all other tables untouched")]
+  ReindexRunes,
   #[command(about = "List all runes")]
   Runes,
   #[command(about = "Run the explorer server")]
@@ -46,6 +62,8 @@ pub(crate) enum Subcommand {
   Teleburn(teleburn::Teleburn),
   #[command(about = "Display satoshi traits")]
   Traits(traits::Traits),
+  #[command(about = "Check the index for inconsistencies without updating it")]
+  VerifyIndex,
   #[command(about = "Wallet commands")]
   Wallet(wallet::Wallet),
 }
@@ -55,23 +73,36 @@ impl Subcommand {
     match self {
       Self::Balances => balances::run(options),
       Self::Decode(decode) => decode.run(options),
+      Self::DumpOpenapi(dump_openapi) => dump_openapi.run(),
       Self::Epochs => epochs::run(),
+      Self::ExportInscriptions(export_inscriptions) => export_inscriptions.run(options),
       Self::Find(find) => find.run(options),
       Self::Index(index) => index.run(options),
       Self::List(list) => list.run(options),
+      Self::MigrateIndex => migrate_index::run(options),
       Self::Parse(parse) => parse.run(),
       Self::Preview(preview) => preview.run(),
+      Self::ReindexRunes => reindex_runes::run(options),
       Self::Runes => runes::run(options),
       Self::Server(server) => {
-        let index = Arc::new(Index::open(&options)?);
+        let (index, writer) = if server.readonly {
+          (Arc::new(Index::open_readonly(&options)?), None)
+        } else {
+          let writer = Index::open(&options)?;
+          (writer.reader(), Some(writer))
+        };
         let handle = axum_server::Handle::new();
-        LISTENERS.lock().unwrap().push(handle.clone());
-        server.run(options, index, handle)
+        LISTENERS
+          .lock()
+          .unwrap()
+          .push((handle.clone(), server.shutdown_grace()));
+        server.run(options, index, writer, handle)
       }
       Self::Subsidy(subsidy) => subsidy.run(),
       Self::Supply => supply::run(),
       Self::Teleburn(teleburn) => teleburn.run(),
       Self::Traits(traits) => traits.run(),
+      Self::VerifyIndex => verify_index::run(options),
       Self::Wallet(wallet) => wallet.run(options),
     }
   }
@@ -94,4 +125,13 @@ where
   }
 }
 
+/// Output printed as is rather than serialized to JSON.
+pub(crate) struct Text(pub(crate) String);
+
+impl Output for Text {
+  fn print_json(&self) {
+    print!("{}", self.0);
+  }
+}
+
 pub(crate) type SubcommandResult = Result<Box<dyn Output>>;
@@ -0,0 +1,30 @@
+use {super::*, clap::ValueEnum, utoipa::OpenApi};
+
+#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Format {
+  #[default]
+  Json,
+  Yaml,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct DumpOpenapi {
+  #[arg(
+    long,
+    value_enum,
+    default_value_t,
+    help = "Print the document as <FORMAT>."
+  )]
+  format: Format,
+}
+
+impl DumpOpenapi {
+  pub(crate) fn run(self) -> SubcommandResult {
+    let openapi = server::ApiDoc::openapi();
+
+    Ok(match self.format {
+      Format::Json => Box::new(openapi),
+      Format::Yaml => Box::new(Text(serde_yaml::to_string(&openapi)?)),
+    })
+  }
+}
@@ -0,0 +1,84 @@
+use {
+  super::*,
+  std::io::{BufWriter, Write},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct ExportInscriptions {
+  #[arg(long, help = "Write inscriptions to <OUTPUT> instead of stdout.")]
+  output: Option<PathBuf>,
+  #[arg(
+    long,
+    default_value_t = 0,
+    help = "Only export inscriptions revealed at or above <FROM_HEIGHT>."
+  )]
+  from_height: u32,
+  #[arg(long, help = "Only export inscriptions revealed below <TO_HEIGHT>.")]
+  to_height: Option<u32>,
+}
+
+/// One line of the export.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportedInscription {
+  pub id: InscriptionId,
+  pub number: i32,
+  pub height: u32,
+  pub content_type: Option<String>,
+  pub content_length: Option<u64>,
+  pub sat: Option<Sat>,
+  pub satpoint: SatPoint,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub inscriptions: u64,
+}
+
+impl ExportInscriptions {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    if let Some(to_height) = self.to_height {
+      if to_height <= self.from_height {
+        bail!("--to-height must be greater than --from-height");
+      }
+    }
+
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let mut writer: Box<dyn Write> = match &self.output {
+      Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+      None => Box::new(BufWriter::new(io::stdout())),
+    };
+
+    let inscriptions = index.for_each_inscription(
+      self.from_height,
+      self.to_height,
+      |entry, satpoint, content_type, content_length| {
+        serde_json::to_writer(
+          &mut writer,
+          &ExportedInscription {
+            id: entry.id,
+            number: entry.inscription_number,
+            height: entry.height,
+            content_type: content_type.map(str::to_string),
+            content_length,
+            sat: entry.sat,
+            satpoint,
+          },
+        )?;
+        writeln!(writer)?;
+        Ok(())
+      },
+    )?;
+
+    writer.flush()?;
+
+    // the inscriptions themselves are written to stdout
+    if self.output.is_none() {
+      return Ok(Box::new(Text(String::new())));
+    }
+
+    Ok(Box::new(Output { inscriptions }))
+  }
+}
@@ -0,0 +1,16 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub from_schema: u64,
+  pub to_schema: u64,
+}
+
+pub(crate) fn run(options: Options) -> SubcommandResult {
+  let from_schema = Index::migrate(&options)?;
+
+  Ok(Box::new(Output {
+    from_schema,
+    to_schema: crate::index::SCHEMA_VERSION,
+  }))
+}
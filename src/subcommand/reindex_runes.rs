@@ -0,0 +1,9 @@
+pub use crate::index::reindex::ReindexedRunes as Output;
+
+use super::*;
+
+pub(crate) fn run(options: Options) -> SubcommandResult {
+  let index = Index::open(&options)?;
+
+  Ok(Box::new(index.reindex_runes()?))
+}
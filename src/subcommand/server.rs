@@ -2,33 +2,45 @@ use {
   self::{
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
+    byte_range::{ByteRange, RangeHeader},
+    charset::Charset,
+    content_cache::{CachedContent, ContentCache},
     deserialize_from_str::DeserializeFromStr,
-    error::{ApiError, OptionExt, ServerError, ServerResult},
+    error::{ApiError, ErrorFormat, OptionExt, ServerError, ServerResult},
+    fields::{FieldsQuery, Selected},
+    protobuf::{
+      AcceptProtobuf, ApiEncoded, ApiEncodedResult, InscriptionResponse, OutPointResultResponse,
+    },
+    transform::TransformPool,
   },
   super::*,
   crate::{
+    index::{cancel, Order, ReorgEvent},
     server_config::ServerConfig,
     templates::{
-      BlockHtml, BlockJson, BlocksHtml, ChildrenHtml, ChildrenJson, ClockSvg, CollectionsHtml,
-      HomeHtml, InputHtml, InscriptionHtml, InscriptionJson, InscriptionsBlockHtml,
+      BlockHtml, BlockJson, BlocksHtml, ChildInscriptionJson, ChildInscriptionsJson, ChildrenHtml,
+      ChildrenJson, ClockSvg, CollectionsHtml, HomeHtml, InputHtml, InscriptionHtml,
+      InscriptionItemJson, InscriptionJson, InscriptionParentJson, InscriptionsBlockHtml,
       InscriptionsHtml, InscriptionsJson, OutputHtml, OutputJson, PageContent, PageHtml,
-      PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
-      PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
-      RangeHtml, RareTxt, RuneHtml, RuneJson, RunesHtml, RunesJson, SatHtml, SatInscriptionJson,
-      SatInscriptionsJson, SatJson, TransactionHtml,
+      ParentsJson, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
+      PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
+      PreviewVideoHtml, RangeHtml, RareTxt, RuneHtml, RuneJson, RuneMintJson, RunesHtml, RunesJson,
+      SatHtml, SatInscriptionJson, SatInscriptionsJson, SatJson, TransactionHtml,
     },
   },
   axum::{
-    body,
-    extract::{Extension, Json, Path, Query},
-    headers::UserAgent,
-    http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
+    body::{self, Full},
+    extract::{DefaultBodyLimit, Extension, Host, Json, Path, Query},
+    headers::{ETag, HeaderMapExt, IfModifiedSince, IfNoneMatch, LastModified, UserAgent},
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode, Uri},
     response::{IntoResponse, Redirect, Response},
-    routing::get,
+    routing::{get, post},
     Router, TypedHeader,
   },
   axum_server::Handle,
+  base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine},
   brotli::Decompressor,
+  clap::ValueEnum,
   rust_embed::RustEmbed,
   rustls_acme::{
     acme::{LETS_ENCRYPT_PRODUCTION_DIRECTORY, LETS_ENCRYPT_STAGING_DIRECTORY},
@@ -36,30 +48,57 @@ use {
     caches::DirCache,
     AcmeConfig,
   },
-  std::{cmp::Ordering, io::Read, str, sync::Arc},
+  std::{cmp::Ordering, io::Read, net::SocketAddr, str, sync::Arc},
   tokio_stream::StreamExt,
   tower_http::{
-    compression::CompressionLayer,
+    compression::{
+      predicate::{NotForContentType, Predicate, SizeAbove},
+      CompressionLayer,
+    },
     cors::{Any, CorsLayer},
     set_header::SetResponseHeaderLayer,
   },
-  utoipa::OpenApi,
+  utoipa::{IntoParams, OpenApi},
 };
 
 mod accept_encoding;
 mod accept_json;
 mod api;
+mod auth;
 mod brc20;
+mod byte_range;
+mod charset;
+mod concurrency;
+mod content_cache;
 mod error;
+mod fields;
 mod info;
+mod metrics;
 mod ord;
+mod params;
+mod protobuf;
+mod request_id;
 mod response;
+mod rpc;
+mod runes;
 mod sat;
+mod sniff;
+mod timeout;
+mod trace;
+mod transform;
 mod types;
 mod utils;
+mod websocket;
 
-use self::api::*;
 use self::response::ApiResponse;
+use self::{
+  api::*,
+  params::{Param, PathParams},
+};
+
+/// Blocks with at least this many confirmations are considered safe from
+/// reorgs and their recursive endpoint responses are cacheable.
+const BURIED_CONFIRMATIONS: u32 = 6;
 
 #[derive(Copy, Clone)]
 pub(crate) enum InscriptionQuery {
@@ -116,6 +155,258 @@ struct Search {
   query: String,
 }
 
+#[derive(Deserialize)]
+struct PageQuery {
+  page: Option<u64>,
+}
+
+#[derive(Default, Deserialize, IntoParams)]
+struct IncludeQuery {
+  /// Comma-separated optional fields to add to the response.
+  include: Option<String>,
+}
+
+impl IncludeQuery {
+  fn includes(&self, field: &str, known: &[&str]) -> ServerResult<bool> {
+    let mut included = false;
+
+    for value in self.include.iter().flat_map(|include| include.split(',')) {
+      if !known.contains(&value) {
+        return Err(ServerError::BadRequest(format!(
+          "unknown include `{value}`"
+        )));
+      }
+
+      included |= value == field;
+    }
+
+    Ok(included)
+  }
+}
+
+#[derive(Deserialize)]
+struct OrderQuery {
+  order: Option<String>,
+}
+
+impl OrderQuery {
+  fn order(&self) -> ServerResult<Option<Order>> {
+    self
+      .order
+      .as_deref()
+      .map(str::parse)
+      .transpose()
+      .map_err(|err: Error| ServerError::BadRequest(err.to_string()))
+  }
+}
+
+#[derive(Deserialize)]
+struct CursorQuery {
+  cursor: Option<String>,
+}
+
+impl CursorQuery {
+  /// Cursors are the URL-safe base64 of the big-endian sequence number of
+  /// the last inscription on the previous page.
+  fn encode(sequence_number: u32) -> String {
+    URL_SAFE_NO_PAD.encode(sequence_number.to_be_bytes())
+  }
+
+  fn cursor(&self) -> ServerResult<Option<u32>> {
+    let Some(cursor) = &self.cursor else {
+      return Ok(None);
+    };
+
+    URL_SAFE_NO_PAD
+      .decode(cursor)
+      .ok()
+      .and_then(|bytes| <[u8; 4]>::try_from(bytes).ok())
+      .map(|bytes| Some(u32::from_be_bytes(bytes)))
+      .ok_or_else(|| ServerError::BadRequest(format!("malformed cursor `{cursor}`")))
+  }
+}
+
+#[derive(Deserialize)]
+struct LimitQuery {
+  limit: Option<usize>,
+}
+
+impl LimitQuery {
+  fn limit(&self, server_config: &ServerConfig) -> ServerResult<usize> {
+    page_size(server_config, self.limit)
+      .map_err(|err| ServerError::BadRequest(err.message().into()))
+  }
+}
+
+#[derive(Deserialize)]
+struct SinceQuery {
+  since: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct PollQuery {
+  since_sequence: Option<u32>,
+  wait: Option<u64>,
+}
+
+impl PollQuery {
+  /// Requests wait at most this long for new inscriptions, however long they
+  /// ask for.
+  const MAX_WAIT: Duration = Duration::from_secs(60);
+
+  /// How long to wait for an inscription after `since_sequence` to be
+  /// indexed, if polling.
+  fn poll(&self) -> ServerResult<Option<(u32, Duration)>> {
+    match (self.since_sequence, self.wait) {
+      (Some(since_sequence), wait) => Ok(Some((
+        since_sequence,
+        Duration::from_secs(wait.unwrap_or_default()).min(Self::MAX_WAIT),
+      ))),
+      (None, Some(_)) => Err(ServerError::BadRequest(
+        "wait requires since_sequence".into(),
+      )),
+      (None, None) => Ok(None),
+    }
+  }
+}
+
+#[derive(Deserialize)]
+struct HeightRangeQuery {
+  from_height: Option<u32>,
+  to_height: Option<u32>,
+}
+
+impl HeightRangeQuery {
+  const MAX_SPAN: u32 = 100_000;
+
+  fn range(&self) -> ServerResult<Option<(u32, u32)>> {
+    match (self.from_height, self.to_height) {
+      (None, None) => Ok(None),
+      (Some(from_height), Some(to_height)) => {
+        if from_height >= to_height {
+          Err(ServerError::BadRequest(
+            "from_height must be less than to_height".into(),
+          ))
+        } else if to_height - from_height > Self::MAX_SPAN {
+          Err(ServerError::BadRequest(format!(
+            "height range may span at most {} blocks",
+            Self::MAX_SPAN
+          )))
+        } else {
+          Ok(Some((from_height, to_height)))
+        }
+      }
+      _ => Err(ServerError::BadRequest(
+        "from_height and to_height must be given together".into(),
+      )),
+    }
+  }
+}
+
+#[derive(Deserialize)]
+struct HexQuery {
+  #[serde(default)]
+  hex: bool,
+}
+
+impl HexQuery {
+  /// Largest stored body served as hex, which doubles its size.
+  const MAX_BYTES: usize = 2 * 1024 * 1024;
+
+  fn encode(bytes: &[u8]) -> ServerResult<String> {
+    if bytes.len() > Self::MAX_BYTES {
+      return Err(ServerError::PayloadTooLarge(format!(
+        "hex output is limited to {} stored bytes",
+        Self::MAX_BYTES
+      )));
+    }
+
+    Ok(hex::encode(bytes))
+  }
+}
+
+#[derive(Deserialize)]
+struct ContentQuery {
+  #[serde(default)]
+  hex: bool,
+  #[serde(default, deserialize_with = "ContentQuery::flag")]
+  download: bool,
+}
+
+impl ContentQuery {
+  /// `download=1` reads better in links than `download=true`, so accept both.
+  fn flag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    match String::deserialize(deserializer)?.as_str() {
+      "1" | "true" => Ok(true),
+      "0" | "false" => Ok(false),
+      flag => Err(serde::de::Error::custom(format!("invalid flag `{flag}`"))),
+    }
+  }
+}
+
+#[derive(Deserialize)]
+struct ContentTypeQuery {
+  content_type: Option<String>,
+}
+
+impl ContentTypeQuery {
+  /// Inscriptions examined per request when filtering, since inscriptions
+  /// are not indexed by content type.
+  const SCAN_LIMIT: usize = 1_000;
+
+  fn filter(&self) -> ServerResult<Option<ContentTypeFilter>> {
+    let Some(filter) = &self.content_type else {
+      return Ok(None);
+    };
+
+    let token = |part: &str| {
+      !part.is_empty()
+        && part
+          .chars()
+          .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+    };
+
+    match filter.split_once('/') {
+      None if token(filter) => Ok(Some(ContentTypeFilter::Family(filter.clone()))),
+      Some((family, subtype)) if token(family) && token(subtype) => {
+        Ok(Some(ContentTypeFilter::Exact(filter.clone())))
+      }
+      _ => Err(ServerError::BadRequest(format!(
+        "invalid content type filter `{filter}`"
+      ))),
+    }
+  }
+}
+
+/// Matches content types without regard to parameters like `charset`, either
+/// by family, like `image`, or exactly, like `text/plain`.
+enum ContentTypeFilter {
+  Family(String),
+  Exact(String),
+}
+
+impl ContentTypeFilter {
+  fn matches(&self, content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+
+    match self {
+      Self::Family(family) => media_type
+        .split('/')
+        .next()
+        .unwrap_or_default()
+        .eq_ignore_ascii_case(family),
+      Self::Exact(exact) => media_type.eq_ignore_ascii_case(exact),
+    }
+  }
+}
+
+/// The body of a content response, which is the stored content unless it has
+/// to be decompressed for a client that does not accept its encoding.
+enum ContentBody {
+  Stored,
+  Brotli,
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -137,6 +428,51 @@ impl Display for StaticHtml {
   }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Compression {
+  Br,
+  Gzip,
+}
+
+impl Compression {
+  fn encoding(self) -> &'static str {
+    match self {
+      Self::Br => "br",
+      Self::Gzip => "gzip",
+    }
+  }
+}
+
+/// Groups of routes that can be left unmounted with `--disable-routes`.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum RouteGroup {
+  Html,
+  Search,
+  Listings,
+  Ws,
+  Brc20,
+}
+
+impl RouteGroup {
+  const ALL: [Self; 5] = [
+    Self::Html,
+    Self::Search,
+    Self::Listings,
+    Self::Ws,
+    Self::Brc20,
+  ];
+
+  fn name(self) -> &'static str {
+    match self {
+      Self::Html => "html",
+      Self::Search => "search",
+      Self::Listings => "listings",
+      Self::Ws => "ws",
+      Self::Brc20 => "brc20",
+    }
+  }
+}
+
 #[derive(Debug, Parser)]
 pub(crate) struct Server {
   #[arg(
@@ -149,11 +485,38 @@ pub(crate) struct Server {
     help = "Request ACME TLS certificate for <ACME_DOMAIN>. This ord instance must be reachable at <ACME_DOMAIN>:443 to respond to Let's Encrypt ACME challenges."
   )]
   acme_domain: Vec<String>,
+  #[arg(
+    long,
+    conflicts_with_all = ["address", "http", "http_port", "https", "https_port"],
+    help = "Listen on <BIND>, an address and port like `0.0.0.0:80` or `[::1]:8080`, for incoming HTTP requests, instead of on --address and --http-port. May be given more than once. Every address serves the same routes from the same index."
+  )]
+  bind: Vec<SocketAddr>,
   #[arg(
     long,
     help = "Use <CSP_ORIGIN> in Content-Security-Policy header. Set this to the public-facing URL of your ord instance."
   )]
   csp_origin: Option<String>,
+  #[arg(
+    long,
+    help = "Serve inscription content with Content-Security-Policy <CONTENT_CSP> instead of the default sandboxed policy."
+  )]
+  content_csp: Option<String>,
+  #[arg(
+    long,
+    help = "Redirect requests for inscription content and previews to <CONTENT_ORIGIN>, e.g. `https://content.example.com`, unless they were made to that host."
+  )]
+  content_origin: Option<String>,
+  #[arg(
+    long,
+    help = "Allow cross-origin requests from <CORS_ORIGIN>, e.g. `https://example.com`. May be given more than once. By default requests from any origin are allowed."
+  )]
+  cors_origin: Vec<String>,
+  #[arg(
+    long,
+    requires = "cors_origin",
+    help = "Allow cross-origin requests from origins given with --cors-origin to include credentials."
+  )]
+  cors_allow_credentials: bool,
   #[arg(
     long,
     help = "Listen on <HTTP_PORT> for incoming HTTP requests. [default: 80]"
@@ -179,113 +542,372 @@ pub(crate) struct Server {
   pub(crate) enable_json_api: bool,
   #[arg(
     long,
-    help = "Decompress encoded content. Currently only supports brotli. Be careful using this on production instances. A decompressed inscription may be arbitrarily large, making decompression a DoS vector."
+    help = "Require `Authorization: Bearer <API_KEY>` on /api/v1 routes."
+  )]
+  api_key: Option<String>,
+  #[arg(
+    long,
+    help = "Decompress encoded content. Currently only supports brotli. Be careful using this on production instances. A decompressed inscription may be arbitrarily large, making decompression a DoS vector. See --decompress-brotli-limit."
   )]
   pub(crate) decompress: bool,
+  #[arg(
+    long,
+    default_value_t = 100 * 1024 * 1024,
+    help = "Refuse to serve decompressed content larger than <DECOMPRESS_BROTLI_LIMIT> bytes."
+  )]
+  pub(crate) decompress_brotli_limit: u64,
+  #[arg(
+    long,
+    help = "List inscriptions revealed by transactions in the mempool of the Bitcoin Core node at /api/v1/ord/mempool/inscriptions."
+  )]
+  scan_mempool: bool,
+  #[arg(
+    long,
+    default_value_t = 1024 * 1024,
+    help = "Reject requests with bodies larger than <MAX_REQUEST_BODY_BYTES> bytes."
+  )]
+  max_request_body_bytes: usize,
+  #[arg(
+    long,
+    default_value_t = 0,
+    help = "Keep up to <CONTENT_CACHE_BYTES> bytes of recently served inscription content in memory, along with its compressed representations. Disabled when 0."
+  )]
+  content_cache_bytes: usize,
+  #[arg(
+    long,
+    default_value_t = 10,
+    help = "Fail requests not served within <QUERY_TIMEOUT_SECS> seconds with 504 Gateway Timeout."
+  )]
+  query_timeout_secs: u64,
+  #[arg(
+    long = "route-timeout",
+    value_name = "ROUTE=SECS",
+    help = "Fail requests to route <ROUTE> not served within <SECS> seconds, instead of --query-timeout-secs. <ROUTE> is a route template, for example `/api/v1/inscriptions/batch` or `/inscription/:inscription_query`. May be given more than once. No routes are overridden by default."
+  )]
+  route_timeouts: Vec<timeout::RouteTimeout>,
+  #[arg(
+    long,
+    help = "Reject requests from a client IP that has <MAX_CONCURRENT_PER_IP> requests in flight with 429 Too Many Requests."
+  )]
+  max_concurrent_per_ip: Option<usize>,
+  #[arg(
+    long,
+    help = "Decompress and hash inscription content on <TRANSFORM_THREADS> threads, rejecting requests with 429 Too Many Requests when 16 transforms per thread are queued. Defaults to the number of available CPUs."
+  )]
+  transform_threads: Option<usize>,
+  #[arg(
+    long,
+    help = "Identify clients by the first address in `X-Forwarded-For`. Only set this behind a proxy that sets the header."
+  )]
+  trust_proxy: bool,
+  #[arg(
+    long,
+    help = "Serialize rune amounts in JSON responses as numbers instead of decimal strings. JavaScript clients lose precision on numbers above 2^53."
+  )]
+  legacy_number_amounts: bool,
+  #[arg(
+    long,
+    value_delimiter = ',',
+    default_values = ["br", "gzip"],
+    help = "Compress responses with <COMPRESSION>, a comma-separated list of algorithms. Content that is stored compressed is never recompressed."
+  )]
+  compression: Vec<Compression>,
+  #[arg(
+    long,
+    default_value = "1024",
+    help = "Only compress responses larger than <COMPRESSION_MIN_SIZE> bytes."
+  )]
+  compression_min_size: u16,
+  #[arg(
+    long,
+    value_delimiter = ',',
+    help = "Do not serve the routes of <DISABLE_ROUTES>, a comma-separated list of route groups, which then return 404 Not Found. `html` is the explorer, `search` its search pages and /api/v1/inscriptions/search, `listings` its listing pages and /api/v1/runes, `ws` is /ws/inscriptions and `brc20` is /api/v1/brc20."
+  )]
+  disable_routes: Vec<RouteGroup>,
+  #[arg(
+    long,
+    value_enum,
+    default_value_t = ErrorFormat::Code,
+    help = "Render JSON error bodies as <ERROR_FORMAT>: `code` for `{code, msg}`, or `problem` for RFC 9457 problem details served as `application/problem+json`."
+  )]
+  error_format: ErrorFormat,
+  #[arg(
+    long,
+    default_value = "100",
+    help = "Return <DEFAULT_PAGE_SIZE> items per page from paginated JSON API endpoints unless a `limit` is requested."
+  )]
+  default_page_size: usize,
+  #[arg(
+    long,
+    default_value = "1000",
+    help = "Return at most <MAX_PAGE_SIZE> items per page from paginated JSON API endpoints. Larger `limit`s are reduced to <MAX_PAGE_SIZE>."
+  )]
+  max_page_size: usize,
+  #[arg(
+    long,
+    default_value = "30",
+    help = "Wait up to <SHUTDOWN_GRACE_SECS> seconds for in-flight requests to finish when shutting down."
+  )]
+  shutdown_grace_secs: u64,
+  #[arg(
+    long,
+    help = "Save the cumulative request and API error counters of /metrics to <METRICS_FILE> on shutdown, and resume them from it on startup."
+  )]
+  metrics_file: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Serve an existing index without ever writing to it. The index is not updated."
+  )]
+  pub(crate) readonly: bool,
 }
 
+#[derive(OpenApi)]
+#[openapi(
+paths(
+brc20::brc20_balance,
+brc20::brc20_balance_at_height,
+brc20::brc20_all_balance,
+brc20::brc20_tick_info,
+brc20::brc20_all_tick_info,
+brc20::brc20_tx_events,
+brc20::brc20_block_events,
+brc20::brc20_tick_events,
+brc20::brc20_transferable,
+brc20::brc20_all_transferable,
+brc20::brc20_outpoint,
+
+ord::ord_inscription_id,
+ord::ord_inscription_number,
+ord::ord_inscription,
+ord::ord_inscriptions_batch,
+ord::ord_inscription_metadata,
+ord::ord_outpoint,
+ord::ord_outpoint_data,
+ord::ord_outpoint_offset,
+ord::ord_outputs_batch,
+ord::ord_address_inscriptions,
+ord::ord_txid_inscriptions,
+ord::ord_block,
+ord::ord_mempool_inscriptions,
+ord::ord_inscriptions_search,
+ord::ord_sat,
+ord::ord_sat_satpoint,
+ord::ord_sat_ranges,
+ord::ord_special_sats,
+
+runes::runes_list,
+runes::runes_balances,
+runes::runes_transfers,
+runes::runes_holders,
+
+sat::sat_range_by_outpoint,
+sat::sat_range_with_rarity_by_outpoint,
+sat::sat_inscriptions,
+sat::sat_inscriptions_paginated,
+
+info::node_info,
+),
+components(schemas(
+// BRC20 schemas
+brc20::ApiTickInfo,
+brc20::ApiTickInfos,
+brc20::ApiBalance,
+brc20::ApiBalances,
+brc20::ApiTxEvent,
+brc20::ApiDeployEvent,
+brc20::ApiMintEvent,
+brc20::ApiInscribeTransferEvent,
+brc20::ApiTransferEvent,
+brc20::ApiErrorEvent,
+brc20::ApiTxEvents,
+brc20::ApiBlockEvents,
+brc20::ApiTickEvent,
+brc20::ApiTickEvents,
+brc20::ApiTransferableAsset,
+brc20::ApiTransferableAssets,
+brc20::ApiOutPointResult,
+
+// BRC20 responses schemas
+response::ApiBRC20Tick,
+response::ApiBRC20AllTick,
+response::ApiBRC20Balance,
+response::ApiBRC20AllBalance,
+response::ApiBRC20TxEvents,
+response::ApiBRC20BlockEvents,
+response::ApiBRC20TickEvents,
+response::ApiBRC20Transferable,
+response::ApiBRC20OutPointResult,
+
+// Ord schemas
+ord::ApiInscription,
+ord::ApiContentEncoding,
+ord::ApiContentHash,
+media::MediaCategory,
+ord::ApiInscriptionDigest,
+ord::ApiInscriptionsBatchRequest,
+ord::ApiInscriptionsBatch,
+ord::ApiInscriptionMetadata,
+ord::ApiOutpointInscriptions,
+ord::ApiOutPointResult,
+ord::ApiOutpoint,
+ord::ApiSatPointInscriptions,
+ord::ApiOutputsBatchRequest,
+ord::ApiOutputsBatch,
+ord::ApiAddressInscription,
+ord::ApiAddressInscriptions,
+ord::ApiInscriptionAction,
+ord::ApiTxInscription,
+ord::ApiTxTransfer,
+ord::ApiTxInscriptions,
+ord::ApiBlockMint,
+ord::ApiBlockMints,
+ord::ApiMempoolInscription,
+ord::ApiMempoolInscriptions,
+ord::ApiSearchMatch,
+ord::ApiSearchMatches,
+ord::ApiSat,
+ord::ApiSatPoint,
+ord::ApiSatRangesRequest,
+ord::ApiCoalescedSatRanges,
+ord::ApiSpecialSat,
+ord::ApiSpecialSats,
+
+// Ord responses schemas
+response::ApiOrdInscription,
+response::ApiOrdInscriptionsBatch,
+response::ApiOrdInscriptionMetadata,
+response::ApiOrdTxInscriptions,
+response::ApiOrdBlock,
+response::ApiOrdMempoolInscriptions,
+response::ApiOrdSearchMatches,
+response::ApiOrdOutPointResult,
+response::ApiOrdOutpoint,
+response::ApiOrdSatPointInscriptions,
+response::ApiOrdOutputsBatch,
+response::ApiOrdAddressInscriptions,
+response::ApiOrdSat,
+response::ApiOrdSatPoint,
+response::ApiOrdCoalescedSatRanges,
+response::ApiOrdSpecialSats,
+
+// Runes schemas
+runes::ApiRune,
+runes::ApiRunes,
+runes::RuneSort,
+runes::ApiRuneBalance,
+runes::ApiRuneBalances,
+runes::ApiRuneAllocation,
+runes::ApiRuneTransfer,
+runes::ApiRuneTransfers,
+runes::ApiRuneHolder,
+runes::ApiRuneHolders,
+
+// Runes responses schemas
+response::ApiRunesList,
+response::ApiRunesBalances,
+response::ApiRunesTransfers,
+response::ApiRunesHolders,
+
+// Sat schemas
+sat::ApiOutPointResult,
+sat::ApiSatRanges,
+sat::ApiSatRange,
+sat::RaritySat,
+
+// Sat responses schemas
+response::ApiSatOutPointResult,
+
+// Node Info schemas
+info::NodeInfo,
+info::ChainInfo,
+types::AddressType,
+types::ScriptPubkey,
+response::Node,
+
+// Recursive endpoint schemas
+ChildInscriptionJson,
+ChildInscriptionsJson,
+SatInscriptionsJson,
+
+ApiError
+))
+)]
+pub(crate) struct ApiDoc;
+
 impl Server {
-  pub(crate) fn run(self, options: Options, index: Arc<Index>, handle: Handle) -> SubcommandResult {
+  /// Serve `index`, and keep it updated through `writer` unless serving a
+  /// read-only index, which has no writer.
+  pub(crate) fn run(
+    self,
+    options: Options,
+    index: Arc<Index>,
+    writer: Option<WritableIndex>,
+    handle: Handle,
+  ) -> SubcommandResult {
     Runtime::new()?.block_on(async {
-      let index_clone = index.clone();
+      if let Some(writer) = writer {
+        let index_thread = thread::spawn(move || loop {
+          if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            break;
+          }
+          if let Err(error) = writer.update() {
+            log::warn!("Updating index: {error}");
+          }
+          thread::sleep(Duration::from_millis(5000));
+        });
+        INDEXER.lock().unwrap().replace(index_thread);
+      }
 
-      let index_thread = thread::spawn(move || loop {
-        if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
-          break;
-        }
-        if let Err(error) = index_clone.update() {
-          log::warn!("Updating index: {error}");
-        }
-        thread::sleep(Duration::from_millis(5000));
-      });
-      INDEXER.lock().unwrap().replace(index_thread);
-
-      #[derive(OpenApi)]
-      #[openapi(
-      paths(
-      brc20::brc20_balance,
-      brc20::brc20_all_balance,
-      brc20::brc20_tick_info,
-      brc20::brc20_all_tick_info,
-      brc20::brc20_tx_events,
-      brc20::brc20_block_events,
-      brc20::brc20_transferable,
-      brc20::brc20_all_transferable,
-
-      ord::ord_inscription_id,
-      ord::ord_inscription_number,
-      ord::ord_outpoint,
-      ord::ord_txid_inscriptions,
-      ord::ord_block_inscriptions,
-
-      info::node_info,
-      ),
-      components(schemas(
-      // BRC20 schemas
-      brc20::ApiTickInfo,
-      brc20::ApiTickInfos,
-      brc20::ApiBalance,
-      brc20::ApiBalances,
-      brc20::ApiTxEvent,
-      brc20::ApiDeployEvent,
-      brc20::ApiMintEvent,
-      brc20::ApiInscribeTransferEvent,
-      brc20::ApiTransferEvent,
-      brc20::ApiErrorEvent,
-      brc20::ApiTxEvents,
-      brc20::ApiBlockEvents,
-      brc20::ApiTransferableAsset,
-      brc20::ApiTransferableAssets,
-
-      // BRC20 responses schemas
-      response::ApiBRC20Tick,
-      response::ApiBRC20AllTick,
-      response::ApiBRC20Balance,
-      response::ApiBRC20AllBalance,
-      response::ApiBRC20TxEvents,
-      response::ApiBRC20BlockEvents,
-      response::ApiBRC20Transferable,
-
-      // Ord schemas
-      ord::ApiInscription,
-      ord::ApiContentEncoding,
-      ord::ApiInscriptionDigest,
-      ord::ApiOutpointInscriptions,
-      ord::ApiOutPointResult,
-      ord::ApiInscriptionAction,
-      ord::ApiTxInscription,
-      ord::ApiTxInscriptions,
-      ord::ApiBlockInscriptions,
-
-      // Ord responses schemas
-      response::ApiOrdInscription,
-      response::ApiOrdTxInscriptions,
-      response::ApiOrdBlockInscriptions,
-      response::ApiOrdOutPointResult,
-
-      // Node Info schemas
-      info::NodeInfo,
-      info::ChainInfo,
-      types::ScriptPubkey,
-      response::Node,
-      ApiError
-      ))
-      )]
-      struct ApiDoc;
+      if let Some(path) = &self.metrics_file {
+        metrics::load(path);
+      }
 
       let config = Arc::new(options.load_config()?);
+      let legacy_number_amounts = self.legacy_number_amounts;
+      let error_format = self.error_format;
+      let timeouts = Arc::new(timeout::Timeouts::new(
+        Duration::from_secs(self.query_timeout_secs),
+        &self.route_timeouts,
+      ));
+      let max_request_body_bytes = self.max_request_body_bytes;
+      let concurrency_limit = self
+        .max_concurrent_per_ip
+        .map(|max| Arc::new(concurrency::ConcurrencyLimit::new(max, self.trust_proxy)));
+      let transforms = Arc::new(TransformPool::new(self.transform_threads.unwrap_or_else(
+        || {
+          thread::available_parallelism()
+            .map(|threads| threads.get())
+            .unwrap_or(1)
+        },
+      ))?);
       let acme_domains = self.acme_domains()?;
 
+      ensure!(
+        self.default_page_size > 0 && self.default_page_size <= self.max_page_size,
+        "--default-page-size must be greater than 0 and not greater than --max-page-size",
+      );
+
       let server_config = Arc::new(ServerConfig {
         chain: options.chain(),
+        content_csp: self.content_csp.clone(),
+        content_origin: self.content_origin()?,
         csp_origin: self.csp_origin.clone(),
         domain: acme_domains.first().cloned(),
         index_sats: index.has_sat_index(),
         is_json_api_enabled: self.enable_json_api,
+        scan_mempool: self.scan_mempool,
         decompress: self.decompress,
+        decompress_brotli_limit: self.decompress_brotli_limit,
+        default_page_size: self.default_page_size,
+        max_page_size: self.max_page_size,
+        route_groups: RouteGroup::ALL
+          .into_iter()
+          .filter(|group| self.routes_enabled(&[*group]))
+          .map(|group| group.name().into())
+          .collect(),
       });
 
-      let api_v1_router = Router::new()
+      let mut api_v1_router = Router::new()
         .route(
           "/api-docs/openapi.json",
           get(|| async { ApiDoc::openapi().to_pretty_json().unwrap() }),
@@ -296,46 +918,43 @@ impl Server {
           "/ord/number/:number/inscription",
           get(ord::ord_inscription_number),
         )
-        .route("/ord/outpoint/:outpoint/info", get(ord::ord_outpoint))
-        .route(
-          "/ord/tx/:txid/inscriptions",
-          get(ord::ord_txid_inscriptions),
-        )
-        .route(
-          "/ord/block/:blockhash/inscriptions",
-          get(ord::ord_block_inscriptions),
-        )
-        .route(
-          "/ord/debug/bitmap/district/:number",
-          get(ord::ord_debug_bitmap_district),
-        )
-        .route("/brc20/tick/:tick", get(brc20::brc20_tick_info))
-        .route("/brc20/tick", get(brc20::brc20_all_tick_info))
+        .route("/inscription/:id", get(ord::ord_inscription))
+        .route("/inscriptions/batch", post(ord::ord_inscriptions_batch))
         .route(
-          "/brc20/tick/:tick/address/:address/balance",
-          get(brc20::brc20_balance),
+          "/inscription/:id/metadata",
+          get(ord::ord_inscription_metadata),
         )
+        .route("/ord/outpoint/:outpoint", get(ord::ord_outpoint_data))
+        .route("/ord/outpoint/:outpoint/info", get(ord::ord_outpoint))
         .route(
-          "/brc20/address/:address/balance",
-          get(brc20::brc20_all_balance),
+          "/ord/outpoint/:outpoint/offset/:offset",
+          get(ord::ord_outpoint_offset),
         )
+        .route("/ord/outputs", post(ord::ord_outputs_batch))
         .route(
-          "/brc20/tick/:tick/address/:address/transferable",
-          get(brc20::brc20_transferable),
+          "/ord/address/:address/inscriptions",
+          get(ord::ord_address_inscriptions),
         )
         .route(
-          "/brc20/address/:address/transferable",
-          get(brc20::brc20_all_transferable),
+          "/ord/tx/:txid/inscriptions",
+          get(ord::ord_txid_inscriptions),
         )
+        .route("/ord/block/:block/inscriptions", get(ord::ord_block))
         .route(
-          "/brc20/outpoint/:outpoint/transferable",
-          get(brc20::brc20_outpoint),
+          "/ord/mempool/inscriptions",
+          get(ord::ord_mempool_inscriptions),
         )
-        .route("/brc20/tx/:txid/events", get(brc20::brc20_tx_events))
+        .route("/ord/sat/:sat", get(ord::ord_sat))
+        .route("/ord/sat/:sat/satpoint", get(ord::ord_sat_satpoint))
+        .route("/ord/sat-ranges", post(ord::ord_sat_ranges))
+        .route("/ord/sats/special", get(ord::ord_special_sats))
         .route(
-          "/brc20/block/:block_hash/events",
-          get(brc20::brc20_block_events),
+          "/ord/debug/bitmap/district/:number",
+          get(ord::ord_debug_bitmap_district),
         )
+        .route("/runes/balances/:address", get(runes::runes_balances))
+        .route("/runes/:rune/transfers", get(runes::runes_transfers))
+        .route("/runes/:rune/holders", get(runes::runes_holders))
         .route(
           "/sat/outpoint/:outpoint/info",
           get(sat::sat_range_by_outpoint),
@@ -345,46 +964,76 @@ impl Server {
           get(sat::sat_range_with_rarity_by_outpoint),
         );
 
-      let api_router = Router::new().nest("/v1", api_v1_router);
+      if self.routes_enabled(&[RouteGroup::Brc20]) {
+        api_v1_router = api_v1_router
+          .route("/brc20/tick/:tick", get(brc20::brc20_tick_info))
+          .route("/brc20/tick/:tick/events", get(brc20::brc20_tick_events))
+          .route("/brc20/tick", get(brc20::brc20_all_tick_info))
+          .route(
+            "/brc20/tick/:tick/address/:address/balance",
+            get(brc20::brc20_balance),
+          )
+          .route(
+            "/brc20/tick/:tick/balance/:address",
+            get(brc20::brc20_balance_at_height),
+          )
+          .route(
+            "/brc20/address/:address/balance",
+            get(brc20::brc20_all_balance),
+          )
+          .route(
+            "/brc20/tick/:tick/address/:address/transferable",
+            get(brc20::brc20_transferable),
+          )
+          .route(
+            "/brc20/address/:address/transferable",
+            get(brc20::brc20_all_transferable),
+          )
+          .route(
+            "/brc20/outpoint/:outpoint/transferable",
+            get(brc20::brc20_outpoint),
+          )
+          .route("/brc20/tx/:txid/events", get(brc20::brc20_tx_events))
+          .route(
+            "/brc20/block/:block_hash/events",
+            get(brc20::brc20_block_events),
+          );
+      }
+
+      if self.routes_enabled(&[RouteGroup::Listings]) {
+        api_v1_router = api_v1_router.route("/runes", get(runes::runes_list));
+      }
+
+      if self.routes_enabled(&[RouteGroup::Search]) {
+        api_v1_router =
+          api_v1_router.route("/inscriptions/search", get(ord::ord_inscriptions_search));
+      }
+
+      let require_api_key = |router: Router<Arc<ServerConfig>>| match self.api_key.as_deref() {
+        Some(api_key) => {
+          let api_key = Arc::<str>::from(api_key);
+          router.layer(axum::middleware::from_fn(
+            move |request: http::Request<axum::body::Body>, next: axum::middleware::Next<_>| {
+              auth::require_api_key(api_key.clone(), request, next)
+            },
+          ))
+        }
+        None => router,
+      };
+
+      let api_router = Router::new().nest("/v1", require_api_key(api_v1_router));
 
-      let router = Router::new()
-        .route("/", get(Self::home))
-        .route("/block/:query", get(Self::block))
+      let rpc_router = require_api_key(Router::new().route("/rpc", post(rpc::rpc)));
+
+      let mut router = Router::new()
         .route("/blockcount", get(Self::block_count))
         .route("/blockhash", get(Self::block_hash))
         .route("/blockhash/:height", get(Self::block_hash_from_height))
         .route("/blockheight", get(Self::block_height))
-        .route("/blocks", get(Self::blocks))
         .route("/blocktime", get(Self::block_time))
-        .route("/bounties", get(Self::bounties))
-        .route("/children/:inscription_id", get(Self::children))
-        .route(
-          "/children/:inscription_id/:page",
-          get(Self::children_paginated),
-        )
-        .route("/clock", get(Self::clock))
-        .route("/collections", get(Self::collections))
-        .route("/collections/:page", get(Self::collections_paginated))
         .route("/content/:inscription_id", get(Self::content))
-        .route("/faq", get(Self::faq))
-        .route("/favicon.ico", get(Self::favicon))
-        .route("/feed.xml", get(Self::feed))
-        .route("/input/:block/:transaction/:input", get(Self::input))
-        .route("/inscription/:inscription_query", get(Self::inscription))
-        .route("/inscriptions", get(Self::inscriptions))
-        .route("/inscriptions/:page", get(Self::inscriptions_paginated))
-        .route(
-          "/inscriptions/block/:height",
-          get(Self::inscriptions_in_block),
-        )
-        .route(
-          "/inscriptions/block/:height/:page",
-          get(Self::inscriptions_in_block_paginated),
-        )
         .route("/install.sh", get(Self::install_script))
-        .route("/ordinal/:sat", get(Self::ordinal))
-        .route("/output/:output", get(Self::output))
-        .route("/preview/:inscription_id", get(Self::preview))
+        .route("/metrics", get(metrics::metrics))
         .route("/r/blockhash", get(Self::block_hash_json))
         .route(
           "/r/blockhash/:height",
@@ -392,51 +1041,198 @@ impl Server {
         )
         .route("/r/blockheight", get(Self::block_height))
         .route("/r/blocktime", get(Self::block_time))
+        .route(
+          "/r/blocktime/:height",
+          get(Self::block_time_from_height_json),
+        )
         .route("/r/children/:inscription_id", get(Self::children_recursive))
         .route(
           "/r/children/:inscription_id/:page",
           get(Self::children_recursive_paginated),
         )
+        .route(
+          "/r/children/:inscription_id/inscriptions",
+          get(Self::child_inscriptions_recursive),
+        )
+        .route(
+          "/r/children/:inscription_id/inscriptions/:page",
+          get(Self::child_inscriptions_recursive_paginated),
+        )
         .route("/r/metadata/:inscription_id", get(Self::metadata))
-        .route("/r/sat/:sat_number", get(Self::sat_inscriptions))
+        .route("/r/parents/:inscription_id", get(Self::parents_recursive))
+        .route(
+          "/r/parents/:inscription_id/:page",
+          get(Self::parents_recursive_paginated),
+        )
+        .route("/r/reorgs", get(Self::reorgs))
+        .route("/r/sat/:sat_number", get(sat::sat_inscriptions))
         .route(
           "/r/sat/:sat_number/:page",
-          get(Self::sat_inscriptions_paginated),
+          get(sat::sat_inscriptions_paginated),
         )
         .route(
           "/r/sat/:sat_number/at/:index",
           get(Self::sat_inscription_at_index),
         )
-        .route("/range/:start/:end", get(Self::range))
-        .route("/rare.txt", get(Self::rare_txt))
-        .route("/rune/:rune", get(Self::rune))
-        .route("/runes", get(Self::runes))
-        .route("/sat/:sat", get(Self::sat))
-        .route("/search", get(Self::search_by_query))
-        .route("/search/*query", get(Self::search_by_path))
-        .route("/static/*path", get(Self::static_asset))
+        .route(
+          "/r/undelegated-content/:inscription_id",
+          get(Self::content_undelegated),
+        )
         .route("/status", get(Self::status))
-        .route("/tx/:txid", get(Self::transaction))
-        .nest("/api", api_router)
-        .layer(Extension(index))
-        .layer(Extension(server_config.clone()))
-        .layer(Extension(config))
+        .route("/version", get(info::version));
+
+      if self.routes_enabled(&[RouteGroup::Html]) {
+        router = router
+          .route("/", get(Self::home))
+          .route("/block/:query", get(Self::block))
+          .route("/bounties", get(Self::bounties))
+          .route("/children/:inscription_id", get(Self::children))
+          .route(
+            "/children/:inscription_id/:page",
+            get(Self::children_paginated),
+          )
+          .route("/clock", get(Self::clock))
+          .route("/faq", get(Self::faq))
+          .route("/favicon.ico", get(Self::favicon))
+          .route("/input/:block/:transaction/:input", get(Self::input))
+          .route("/inscription/:inscription_query", get(Self::inscription))
+          .route("/ordinal/:sat", get(Self::ordinal))
+          .route("/output/:output", get(Self::output))
+          .route("/preview/:inscription_id", get(Self::preview))
+          .route("/range/:start/:end", get(Self::range))
+          .route("/rare.txt", get(Self::rare_txt))
+          .route("/rune/:rune", get(Self::rune))
+          .route("/sat/:sat", get(Self::sat))
+          .route("/static/*path", get(Self::static_asset))
+          .route("/tx/:txid", get(Self::transaction));
+      }
+
+      if self.routes_enabled(&[RouteGroup::Html, RouteGroup::Listings]) {
+        router = router
+          .route("/blocks", get(Self::blocks))
+          .route("/collections", get(Self::collections))
+          .route("/collections/:page", get(Self::collections_paginated))
+          .route("/feed.xml", get(Self::feed))
+          .route("/inscriptions", get(Self::inscriptions))
+          .route("/inscriptions/:page", get(Self::inscriptions_paginated))
+          .route(
+            "/inscriptions/block/:height",
+            get(Self::inscriptions_in_block),
+          )
+          .route(
+            "/inscriptions/block/:height/:page",
+            get(Self::inscriptions_in_block_paginated),
+          )
+          .route("/runes", get(Self::runes));
+      }
+
+      if self.routes_enabled(&[RouteGroup::Html, RouteGroup::Search]) {
+        router = router
+          .route("/search", get(Self::search_by_query))
+          .route("/search/*query", get(Self::search_by_path));
+      }
+
+      if self.routes_enabled(&[RouteGroup::Ws]) {
+        router = router.route("/ws/inscriptions", get(websocket::inscriptions));
+      }
+
+      let router = router
         .layer(SetResponseHeaderLayer::if_not_present(
           header::CONTENT_SECURITY_POLICY,
           HeaderValue::from_static("default-src 'self'"),
         ))
+        .nest("/api", api_router)
+        .merge(rpc_router)
+        .layer(axum::middleware::from_fn(accept_json::vary_accept))
+        .layer(axum::middleware::from_fn(
+          move |request: http::Request<axum::body::Body>, next: axum::middleware::Next<_>| {
+            json_amount::scope(legacy_number_amounts, next.run(request))
+          },
+        ))
+        .layer(axum::middleware::from_fn(move |request, next| {
+          timeout::timeout_requests(timeouts.clone(), request, next)
+        }))
+        .layer(DefaultBodyLimit::max(max_request_body_bytes))
+        .layer(axum::middleware::from_fn(move |request, next| {
+          error::json_body_limit_rejections(max_request_body_bytes, request, next)
+        }))
+        .layer(axum::middleware::from_fn(error::json_server_errors))
+        .layer(axum::middleware::from_fn(move |request, next| {
+          concurrency::limit_concurrency(concurrency_limit.clone(), request, next)
+        }))
+        // probes are mounted outside of the concurrency limit and timeouts, so
+        // that a busy server is not taken for a dead one
+        .route("/live", get(Self::live))
+        .route("/ready", get(Self::ready))
+        .layer(axum::middleware::from_fn(metrics::track_requests))
+        .layer(axum::middleware::from_fn(trace::trace_requests))
+        .layer(axum::middleware::from_fn(
+          move |request: http::Request<axum::body::Body>, next: axum::middleware::Next<_>| {
+            error_format.scope(next.run(request))
+          },
+        ))
+        .layer(axum::middleware::from_fn(request_id::request_id))
+        .layer(Extension(index))
+        .layer(Extension(Arc::new(ContentCache::new(
+          self.content_cache_bytes,
+          self.compression.clone(),
+          self.compression_min_size.into(),
+        ))))
+        .layer(Extension(Arc::new(ord::ContentHashCache::default())))
+        .layer(Extension(Arc::new(ord::MempoolInscriptionsCache::default())))
+        .layer(Extension(transforms))
+        .layer(Extension(Arc::new(runes::RuneHoldersCache::default())))
+        .layer(Extension(server_config.clone()))
+        .layer(Extension(config))
         .layer(SetResponseHeaderLayer::overriding(
           header::STRICT_TRANSPORT_SECURITY,
           HeaderValue::from_static("max-age=31536000; includeSubDomains; preload"),
         ))
+        .layer(SetResponseHeaderLayer::overriding(
+          HeaderName::from_static(info::X_ORD_VERSION),
+          HeaderValue::from_str(&info::ord_version())?,
+        ))
+        .layer(self.cors()?)
+        // `CorsLayer` would replace the `Vary` headers of responses, so its
+        // own are appended here instead
+        .layer(SetResponseHeaderLayer::appending(
+          header::VARY,
+          HeaderValue::from_static(
+            "origin, access-control-request-method, access-control-request-headers",
+          ),
+        ))
         .layer(
-          CorsLayer::new()
-            .allow_methods([http::Method::GET])
-            .allow_origin(Any),
+          CompressionLayer::new()
+            .br(self.compression.contains(&Compression::Br))
+            .gzip(self.compression.contains(&Compression::Gzip))
+            .compress_when(
+              SizeAbove::new(self.compression_min_size)
+                .and(NotForContentType::GRPC)
+                .and(NotForContentType::IMAGES)
+                // a compressed range would not be a range of the content
+                .and(|_, _, headers: &HeaderMap, _: &http::Extensions| {
+                  !headers.contains_key(header::CONTENT_RANGE)
+                }),
+            ),
         )
-        .layer(CompressionLayer::new())
         .with_state(server_config);
 
+      if !self.bind.is_empty() {
+        let servers = self
+          .listeners()?
+          .into_iter()
+          .map(|listener| Self::serve(router.clone(), handle.clone(), listener, SpawnConfig::Http))
+          .collect::<Result<Vec<_>>>()?;
+
+        for result in futures::future::join_all(servers).await {
+          result??;
+        }
+
+        self.save_metrics();
+
+        return Ok(Box::new(Empty {}) as Box<dyn Output>);
+      }
+
       match (self.http_port(), self.https_port()) {
         (Some(http_port), None) => {
           self
@@ -478,10 +1274,72 @@ impl Server {
         (None, None) => unreachable!(),
       }
 
+      self.save_metrics();
+
       Ok(Box::new(Empty {}) as Box<dyn Output>)
     })
   }
 
+  fn cors(&self) -> Result<CorsLayer> {
+    // only headers that some response actually sets
+    let exposed_headers = [
+      header::ETAG,
+      HeaderName::from_static(request_id::X_REQUEST_ID),
+      HeaderName::from_static(info::X_ORD_VERSION),
+      HeaderName::from_static(sniff::X_ORIGINAL_CONTENT_TYPE),
+      HeaderName::from_static(charset::X_INVALID_UTF8),
+    ];
+
+    // authorization carries the `--api-key` bearer token
+    let allowed_headers = [
+      header::ACCEPT,
+      header::AUTHORIZATION,
+      header::CONTENT_TYPE,
+      HeaderName::from_static(request_id::X_REQUEST_ID),
+    ];
+
+    if self.cors_origin.is_empty() {
+      return Ok(
+        CorsLayer::new()
+          .allow_methods([http::Method::GET])
+          .allow_origin(Any)
+          .allow_headers(allowed_headers)
+          .expose_headers(exposed_headers)
+          .vary([]),
+      );
+    }
+
+    let origins = self
+      .cors_origin
+      .iter()
+      .map(|origin| {
+        origin
+          .parse::<HeaderValue>()
+          .with_context(|| format!("invalid CORS origin `{origin}`"))
+      })
+      .collect::<Result<Vec<HeaderValue>>>()?;
+
+    Ok(
+      CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods([http::Method::GET, http::Method::POST])
+        .allow_headers(allowed_headers)
+        .expose_headers(exposed_headers)
+        .allow_credentials(self.cors_allow_credentials)
+        .vary([]),
+    )
+  }
+
+  fn save_metrics(&self) {
+    if let Some(path) = &self.metrics_file {
+      metrics::save(path);
+    }
+  }
+
+  pub(crate) fn shutdown_grace(&self) -> Duration {
+    Duration::from_secs(self.shutdown_grace_secs)
+  }
+
   fn spawn(
     &self,
     router: Router,
@@ -505,27 +1363,52 @@ impl Server {
       .next()
       .ok_or_else(|| anyhow!("failed to get socket addrs"))?;
 
+    Self::serve(router, handle, Self::listen(addr)?, config)
+  }
+
+  /// Bind every `--bind` address, failing if any of them cannot be bound, so
+  /// that no server is started unless all of them can be.
+  fn listeners(&self) -> Result<Vec<std::net::TcpListener>> {
+    self.bind.iter().copied().map(Self::listen).collect()
+  }
+
+  fn listen(addr: SocketAddr) -> Result<std::net::TcpListener> {
+    let listener =
+      std::net::TcpListener::bind(addr).with_context(|| format!("failed to bind to {addr}"))?;
+
+    listener.set_nonblocking(true)?;
+
+    Ok(listener)
+  }
+
+  fn serve(
+    router: Router,
+    handle: Handle,
+    listener: std::net::TcpListener,
+    config: SpawnConfig,
+  ) -> Result<task::JoinHandle<io::Result<()>>> {
     if !integration_test() {
       eprintln!(
-        "Listening on {}://{addr}",
+        "Listening on {}://{}",
         match config {
           SpawnConfig::Https(_) => "https",
           _ => "http",
-        }
+        },
+        listener.local_addr()?,
       );
     }
 
     Ok(tokio::spawn(async move {
       match config {
         SpawnConfig::Https(acceptor) => {
-          axum_server::Server::bind(addr)
+          axum_server::from_tcp(listener)
             .handle(handle)
             .acceptor(acceptor)
-            .serve(router.into_make_service())
+            .serve(router.into_make_service_with_connect_info::<SocketAddr>())
             .await
         }
         SpawnConfig::Redirect(destination) => {
-          axum_server::Server::bind(addr)
+          axum_server::from_tcp(listener)
             .handle(handle)
             .serve(
               Router::new()
@@ -536,21 +1419,43 @@ impl Server {
             .await
         }
         SpawnConfig::Http => {
-          axum_server::Server::bind(addr)
+          axum_server::from_tcp(listener)
             .handle(handle)
-            .serve(router.into_make_service())
+            .serve(router.into_make_service_with_connect_info::<SocketAddr>())
             .await
         }
       }
     }))
   }
 
+  /// Whether routes that belong to all of `groups` are served.
+  fn routes_enabled(&self, groups: &[RouteGroup]) -> bool {
+    groups
+      .iter()
+      .all(|group| !self.disable_routes.contains(group))
+  }
+
   fn acme_cache(acme_cache: Option<&PathBuf>, options: &Options) -> PathBuf {
     acme_cache
       .unwrap_or(&options.data_dir().join("acme-cache"))
       .to_path_buf()
   }
 
+  fn content_origin(&self) -> Result<Option<Uri>> {
+    let Some(content_origin) = &self.content_origin else {
+      return Ok(None);
+    };
+
+    let uri = content_origin.parse::<Uri>()?;
+
+    ensure!(
+      uri.scheme().is_some() && uri.authority().is_some() && uri.path() == "/",
+      "--content-origin must be a scheme and host, e.g. `https://content.example.com`"
+    );
+
+    Ok(Some(uri))
+  }
+
   fn acme_domains(&self) -> Result<Vec<String>> {
     if !self.acme_domain.is_empty() {
       Ok(self.acme_domain.clone())
@@ -616,6 +1521,25 @@ impl Server {
     index.block_height()?.ok_or_not_found(|| "genesis block")
   }
 
+  /// Fail with 503 while the index trails the chain tip last seen by the
+  /// updater, so that tip endpoints never make bitcoind requests.
+  fn index_synced(index: &Index) -> ServerResult<()> {
+    if index.is_at_capacity() {
+      return Err(ServerError::InsufficientStorage("index at capacity".into()));
+    }
+
+    let block_count = index.block_count()?;
+    let chain_block_count = index.last_chain_block_count()?;
+
+    if block_count < chain_block_count {
+      return Err(ServerError::ServiceUnavailable(format!(
+        "index is syncing: {block_count} of {chain_block_count} blocks indexed"
+      )));
+    }
+
+    Ok(())
+  }
+
   async fn clock(Extension(index): Extension<Arc<Index>>) -> ServerResult<Response> {
     Ok(
       (
@@ -632,7 +1556,8 @@ impl Server {
   async fn sat(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path(DeserializeFromStr(sat)): Path<DeserializeFromStr<Sat>>,
+    PathParams(Param(sat)): PathParams<Param<Sat>>,
+    Query(fields): Query<FieldsQuery>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
     let inscriptions = index.get_inscription_ids_by_sat(sat)?;
@@ -646,7 +1571,7 @@ impl Server {
     });
     let blocktime = index.block_time(sat.height())?;
     Ok(if accept_json {
-      Json(SatJson {
+      Json(fields.select(SatJson {
         number: sat.0,
         decimal: sat.decimal().to_string(),
         degree: sat.degree().to_string(),
@@ -661,7 +1586,7 @@ impl Server {
         satpoint,
         timestamp: blocktime.timestamp().timestamp(),
         inscriptions,
-      })
+      })?)
       .into_response()
     } else {
       SatHtml {
@@ -682,7 +1607,8 @@ impl Server {
   async fn output(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path(outpoint): Path<OutPoint>,
+    PathParams(Param(outpoint)): PathParams<Param<OutPoint>>,
+    Query(fields): Query<FieldsQuery>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
     let list = index.list(outpoint)?;
@@ -715,17 +1641,19 @@ impl Server {
     let runes = index.get_rune_balances_for_outpoint(outpoint)?;
 
     Ok(if accept_json {
-      Json(OutputJson::new(
-        outpoint,
-        list,
-        server_config.chain,
-        output,
-        inscriptions,
-        runes
-          .into_iter()
-          .map(|(spaced_rune, pile)| (spaced_rune.rune, pile.amount))
-          .collect(),
-      ))
+      Json(
+        fields.select(OutputJson::new(
+          outpoint,
+          list,
+          server_config.chain,
+          output,
+          inscriptions,
+          runes
+            .into_iter()
+            .map(|(spaced_rune, pile)| (spaced_rune.rune, pile.amount))
+            .collect(),
+        ))?,
+      )
       .into_response()
     } else {
       OutputHtml {
@@ -743,10 +1671,7 @@ impl Server {
 
   async fn range(
     Extension(server_config): Extension<Arc<ServerConfig>>,
-    Path((DeserializeFromStr(start), DeserializeFromStr(end))): Path<(
-      DeserializeFromStr<Sat>,
-      DeserializeFromStr<Sat>,
-    )>,
+    PathParams((Param(start), Param(end))): PathParams<(Param<Sat>, Param<Sat>)>,
   ) -> ServerResult<PageHtml<RangeHtml>> {
     match start.cmp(&end) {
       Ordering::Equal => Err(ServerError::BadRequest("empty range".to_string())),
@@ -778,7 +1703,21 @@ impl Server {
       .ok_or_not_found(|| format!("rune {spaced_rune}"))?;
 
     Ok(if accept_json {
-      Json(RuneJson { entry, id, parent }).into_response()
+      let (height, timestamp) = match index.block_height()? {
+        Some(height) => (
+          height.n(),
+          u32::try_from(index.block_time(height)?.unix_timestamp()).unwrap(),
+        ),
+        None => (0, 0),
+      };
+
+      Json(RuneJson {
+        mint: RuneMintJson::new(&entry, id, height, timestamp),
+        entry,
+        id,
+        parent,
+      })
+      .into_response()
     } else {
       RuneHtml { entry, id, parent }
         .page(server_config)
@@ -841,6 +1780,7 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(DeserializeFromStr(query)): Path<DeserializeFromStr<BlockQuery>>,
+    Query(fields): Query<FieldsQuery>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
     let (block, height) = match query {
@@ -866,12 +1806,12 @@ impl Server {
 
     Ok(if accept_json {
       let inscriptions = index.get_inscriptions_in_block(height)?;
-      Json(BlockJson::new(
+      Json(fields.select(BlockJson::new(
         block,
         Height(height),
         Self::index_height(&index)?,
         inscriptions,
-      ))
+      ))?)
       .into_response()
     } else {
       let (featured_inscriptions, total_num) =
@@ -914,17 +1854,52 @@ impl Server {
     )
   }
 
+  async fn reorgs(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<SinceQuery>,
+  ) -> Json<Vec<ReorgEvent>> {
+    Json(index.reorgs_since(query.since.unwrap_or_default()))
+  }
+
   async fn metadata(
     Extension(index): Extension<Arc<Index>>,
-    Path(inscription_id): Path<InscriptionId>,
-  ) -> ServerResult<Json<String>> {
+    PathParams(Param(inscription_id)): PathParams<Param<InscriptionId>>,
+    Query(query): Query<HexQuery>,
+  ) -> ServerResult<Response> {
     let metadata = index
       .get_inscription_by_id(inscription_id)?
       .ok_or_not_found(|| format!("inscription {inscription_id}"))?
       .metadata
       .ok_or_not_found(|| format!("inscription {inscription_id} metadata"))?;
 
-    Ok(Json(hex::encode(metadata)))
+    let metadata = HexQuery::encode(&metadata)?;
+
+    Ok(if query.hex {
+      metadata.into_response()
+    } else {
+      Json(metadata).into_response()
+    })
+  }
+
+  /// Liveness probe, which succeeds as long as the server is running.
+  async fn live() -> StatusCode {
+    StatusCode::OK
+  }
+
+  /// Readiness probe, which succeeds only while the index is synced with the
+  /// chain tip and the server is not shutting down.
+  async fn ready(Extension(index): Extension<Arc<Index>>) -> ServerResult<StatusCode> {
+    if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      return Err(ServerError::ServiceUnavailable("shutting down".into()));
+    }
+
+    match index.is_synced() {
+      Ok(true) => Ok(StatusCode::OK),
+      Ok(false) => Err(ServerError::ServiceUnavailable("index is syncing".into())),
+      Err(err) => Err(ServerError::ServiceUnavailable(format!(
+        "failed to get sync state: {err}"
+      ))),
+    }
   }
 
   async fn status(
@@ -932,10 +1907,14 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
+    let mut status = index.status()?;
+
+    status.route_groups = server_config.route_groups.clone();
+
     Ok(if accept_json {
-      Json(index.status()?).into_response()
+      Json(status).into_response()
     } else {
-      index.status()?.page(server_config).into_response()
+      status.page(server_config).into_response()
     })
   }
 
@@ -1085,6 +2064,8 @@ impl Server {
   }
 
   async fn block_height(Extension(index): Extension<Arc<Index>>) -> ServerResult<String> {
+    Self::index_synced(&index)?;
+
     Ok(
       index
         .block_height()?
@@ -1094,6 +2075,8 @@ impl Server {
   }
 
   async fn block_hash(Extension(index): Extension<Arc<Index>>) -> ServerResult<String> {
+    Self::index_synced(&index)?;
+
     Ok(
       index
         .block_hash(None)?
@@ -1103,6 +2086,8 @@ impl Server {
   }
 
   async fn block_hash_json(Extension(index): Extension<Arc<Index>>) -> ServerResult<Json<String>> {
+    Self::index_synced(&index)?;
+
     Ok(Json(
       index
         .block_hash(None)?
@@ -1126,16 +2111,100 @@ impl Server {
   async fn block_hash_from_height_json(
     Extension(index): Extension<Arc<Index>>,
     Path(height): Path<u32>,
-  ) -> ServerResult<Json<String>> {
-    Ok(Json(
-      index
-        .block_hash(Some(height))?
-        .ok_or_not_found(|| "blockhash")?
-        .to_string(),
-    ))
+    request_headers: HeaderMap,
+  ) -> ServerResult<Response> {
+    let block_hash = index
+      .block_hash(Some(height))?
+      .ok_or_not_found(|| "blockhash")?;
+
+    Self::block_response(
+      &index,
+      height,
+      block_hash,
+      &request_headers,
+      Json(block_hash.to_string()),
+    )
+  }
+
+  async fn block_time_from_height_json(
+    Extension(index): Extension<Arc<Index>>,
+    Path(height): Path<u32>,
+    request_headers: HeaderMap,
+  ) -> ServerResult<Response> {
+    let block_hash = index
+      .block_hash(Some(height))?
+      .ok_or_not_found(|| "blocktime")?;
+
+    Self::block_response(
+      &index,
+      height,
+      block_hash,
+      &request_headers,
+      Json(index.block_time(Height(height))?.unix_timestamp()),
+    )
+  }
+
+  /// A response with `body`, which depends only on the block at `height`.
+  fn block_response(
+    index: &Index,
+    height: u32,
+    block_hash: BlockHash,
+    request_headers: &HeaderMap,
+    body: impl IntoResponse,
+  ) -> ServerResult<Response> {
+    let mut headers = HeaderMap::new();
+
+    // Blocks near the tip may still change in a reorg, so only responses
+    // about buried blocks are safe to cache.
+    if Self::index_height(index)?.n().saturating_sub(height) < BURIED_CONFIRMATIONS {
+      headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+      return Ok((headers, body).into_response());
+    }
+
+    let etag = format!("\"{block_hash}\"").parse::<ETag>().unwrap();
+
+    let last_modified = SystemTime::UNIX_EPOCH
+      + Duration::from_secs(
+        index
+          .block_time(Height(height))?
+          .unix_timestamp()
+          .try_into()
+          .unwrap_or_default(),
+      );
+
+    headers.insert(
+      header::CACHE_CONTROL,
+      HeaderValue::from_static("public, max-age=31536000, immutable"),
+    );
+    headers.typed_insert(etag.clone());
+    headers.typed_insert(LastModified::from(last_modified));
+
+    // An absent `If-None-Match` header decodes as an empty tag list, so check
+    // for its presence before falling back to `If-Modified-Since`.
+    let not_modified = if request_headers.contains_key(header::IF_NONE_MATCH) {
+      request_headers
+        .typed_get::<IfNoneMatch>()
+        .map_or(false, |if_none_match| {
+          !if_none_match.precondition_passes(&etag)
+        })
+    } else {
+      request_headers
+        .typed_get::<IfModifiedSince>()
+        .map_or(false, |if_modified_since| {
+          !if_modified_since.is_modified(last_modified)
+        })
+    };
+
+    if not_modified {
+      return Ok((StatusCode::NOT_MODIFIED, headers).into_response());
+    }
+
+    Ok((headers, body).into_response())
   }
 
   async fn block_time(Extension(index): Extension<Arc<Index>>) -> ServerResult<String> {
+    Self::index_synced(&index)?;
+
     Ok(
       index
         .block_time(index.block_height()?.ok_or_not_found(|| "blocktime")?)?
@@ -1182,123 +2251,459 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     Extension(config): Extension<Arc<Config>>,
     Extension(server_config): Extension<Arc<ServerConfig>>,
-    Path(inscription_id): Path<InscriptionId>,
+    Extension(transforms): Extension<Arc<TransformPool>>,
+    Extension(content_cache): Extension<Arc<ContentCache>>,
+    PathParams(Param(inscription_id)): PathParams<Param<InscriptionId>>,
+    Query(query): Query<ContentQuery>,
+    uri: Uri,
+    host: Option<Host>,
     accept_encoding: AcceptEncoding,
+    range: RangeHeader,
+    if_none_match: Option<TypedHeader<IfNoneMatch>>,
   ) -> ServerResult<Response> {
+    let path = match uri.query() {
+      Some(query) => format!("/content/{inscription_id}?{query}"),
+      None => format!("/content/{inscription_id}"),
+    };
+
+    if let Some(redirect) = Self::content_origin_redirect(&server_config, host, &path) {
+      return Ok(redirect.into_response());
+    }
+
     if config.is_hidden(inscription_id) {
       return Ok(PreviewUnknownHtml.into_response());
     }
 
-    let mut inscription = index
-      .get_inscription_by_id(inscription_id)?
-      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+    let content = match content_cache.get(inscription_id) {
+      Some(content) => content,
+      None => {
+        let entry = index
+          .get_inscription_entry(inscription_id)?
+          .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
-    if let Some(delegate) = inscription.delegate() {
-      inscription = index
-        .get_inscription_by_id(delegate)?
-        .ok_or_not_found(|| format!("delegate {inscription_id}"))?
-    }
+        let mut content_id = inscription_id;
 
-    Ok(
-      Self::content_response(inscription, accept_encoding, &server_config)?
-        .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
-        .into_response(),
-    )
-  }
+        let mut inscription = index
+          .get_inscription_by_id(inscription_id)?
+          .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
-  fn content_response(
-    inscription: Inscription,
-    accept_encoding: AcceptEncoding,
-    server_config: &ServerConfig,
-  ) -> ServerResult<Option<(HeaderMap, Vec<u8>)>> {
-    let mut headers = HeaderMap::new();
+        if let Some(delegate) = inscription.delegate() {
+          content_id = delegate;
+          inscription = index
+            .get_inscription_by_id(delegate)?
+            .ok_or_not_found(|| format!("delegate {delegate}"))?
+        }
 
-    match &server_config.csp_origin {
-      None => {
-        headers.insert(
-          header::CONTENT_SECURITY_POLICY,
-          HeaderValue::from_static("default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:"),
-        );
-        headers.append(
-          header::CONTENT_SECURITY_POLICY,
-          HeaderValue::from_static("default-src *:*/content/ *:*/blockheight *:*/blockhash *:*/blockhash/ *:*/blocktime *:*/r/ 'unsafe-eval' 'unsafe-inline' data: blob:"),
-        );
+        let content = Arc::new(CachedContent::new(content_id, inscription, entry.timestamp));
+
+        content_cache.insert(inscription_id, content.clone());
+
+        content
       }
-      Some(origin) => {
-        let csp = format!("default-src {origin}/content/ {origin}/blockheight {origin}/blockhash {origin}/blockhash/ {origin}/blocktime {origin}/r/ 'unsafe-eval' 'unsafe-inline' data: blob:");
-        headers.insert(
-          header::CONTENT_SECURITY_POLICY,
-          HeaderValue::from_str(&csp).map_err(|err| ServerError::Internal(Error::from(err)))?,
-        );
+    };
+
+    // the body, if it is not the stored content, which is served as is, and
+    // is the only representation ranges may be requested of
+    let (mut headers, body) = if query.hex {
+      let stored = content
+        .inscription
+        .body()
+        .ok_or_not_found(|| format!("inscription {inscription_id} content"))?;
+
+      let mut headers = HeaderMap::new();
+      headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=31536000, immutable"),
+      );
+      headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; charset=utf-8"),
+      );
+
+      (headers, Some(HexQuery::encode(stored)?.into_bytes()))
+    } else {
+      let (headers, body) = Self::content_headers(
+        &content.inscription,
+        accept_encoding.clone(),
+        &server_config,
+      )?
+      .ok_or_not_found(|| format!("inscription {inscription_id} content"))?;
+
+      match body {
+        ContentBody::Stored => (headers, None),
+        ContentBody::Brotli => (
+          headers,
+          Some(
+            Self::decompress_brotli(
+              content.inscription.body().unwrap_or_default().to_vec(),
+              &server_config,
+              &transforms,
+            )
+            .await?,
+          ),
+        ),
       }
-    }
+    };
 
-    headers.insert(
-      header::CACHE_CONTROL,
-      HeaderValue::from_static("public, max-age=31536000, immutable"),
-    );
+    if query.download {
+      let extension = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map(Media::extension_for_content_type)
+        .unwrap_or("bin");
+
+      headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!(
+          "attachment; filename=\"{inscription_id}.{extension}\""
+        ))
+        .unwrap(),
+      );
+    }
 
-    headers.insert(
-      header::CONTENT_TYPE,
-      inscription
-        .content_type()
-        .and_then(|content_type| content_type.parse().ok())
-        .unwrap_or(HeaderValue::from_static("application/octet-stream")),
-    );
+    // stored content is compressed here rather than by the compression layer,
+    // so that the compressed representation can be cached, but never when a
+    // range is requested, since ranges are of the stored content
+    let compressed = if body.is_none()
+      && range.resolve(content.inscription.body().unwrap_or_default().len()) == ByteRange::Whole
+    {
+      content_cache
+        .compressed(inscription_id, &content, &accept_encoding, &transforms)
+        .await?
+    } else {
+      None
+    };
 
-    if let Some(content_encoding) = inscription.content_encoding() {
-      if accept_encoding.is_acceptable(&content_encoding) {
-        headers.insert(header::CONTENT_ENCODING, content_encoding);
-      } else if server_config.decompress && content_encoding == "br" {
-        let Some(body) = inscription.into_body() else {
-          return Ok(None);
-        };
+    if let Some((compression, _)) = compressed {
+      headers.insert(header::VARY, HeaderValue::from_static("Accept-Encoding"));
+      headers.insert(
+        header::CONTENT_ENCODING,
+        HeaderValue::from_static(compression.encoding()),
+      );
+    }
 
-        let mut decompressed = Vec::new();
+    let etag = Self::content_etag(content.id, &headers, query.hex);
 
-        Decompressor::new(body.as_slice(), 4096)
-          .read_to_end(&mut decompressed)
-          .map_err(|err| ServerError::Internal(err.into()))?;
+    headers.typed_insert(etag.clone());
+    headers.typed_insert(LastModified::from(
+      SystemTime::UNIX_EPOCH + Duration::from_secs(content.timestamp.into()),
+    ));
 
-        return Ok(Some((headers, decompressed)));
-      } else {
-        return Err(ServerError::NotAcceptable {
-          accept_encoding,
-          content_encoding,
-        });
+    if let Some(TypedHeader(if_none_match)) = if_none_match {
+      if !if_none_match.precondition_passes(&etag) {
+        return Ok((StatusCode::NOT_MODIFIED, headers).into_response());
       }
     }
 
-    let Some(body) = inscription.into_body() else {
-      return Ok(None);
+    if let Some((_, compressed)) = compressed {
+      headers.insert(header::CONTENT_LENGTH, compressed.len().into());
+      return Ok((headers, compressed).into_response());
+    }
+
+    let Some(body) = body else {
+      let length = content.inscription.body().unwrap_or_default().len();
+
+      headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+
+      return Ok(match range.resolve(length) {
+        ByteRange::Whole => {
+          headers.insert(header::CONTENT_LENGTH, length.into());
+          (headers, content.stream(0..length)).into_response()
+        }
+        ByteRange::Slice(slice) => {
+          headers.insert(
+            header::CONTENT_RANGE,
+            HeaderValue::from_str(&format!("bytes {}-{}/{length}", slice.start, slice.end - 1))
+              .unwrap(),
+          );
+          headers.insert(header::CONTENT_LENGTH, slice.len().into());
+          (StatusCode::PARTIAL_CONTENT, headers, content.stream(slice)).into_response()
+        }
+        ByteRange::Unsatisfiable => {
+          headers.remove(header::CONTENT_TYPE);
+          headers.insert(
+            header::CONTENT_RANGE,
+            HeaderValue::from_str(&format!("bytes */{length}")).unwrap(),
+          );
+          (StatusCode::RANGE_NOT_SATISFIABLE, headers).into_response()
+        }
+      });
     };
 
-    Ok(Some((headers, body)))
+    headers.insert(header::CONTENT_LENGTH, body.len().into());
+
+    Ok((headers, body).into_response())
   }
 
-  async fn preview(
+  /// The content of an inscription as stored, without following its
+  /// delegate, if any.
+  async fn content_undelegated(
     Extension(index): Extension<Arc<Index>>,
     Extension(config): Extension<Arc<Config>>,
     Extension(server_config): Extension<Arc<ServerConfig>>,
-    Path(inscription_id): Path<InscriptionId>,
+    Extension(transforms): Extension<Arc<TransformPool>>,
+    PathParams(Param(inscription_id)): PathParams<Param<InscriptionId>>,
     accept_encoding: AcceptEncoding,
   ) -> ServerResult<Response> {
     if config.is_hidden(inscription_id) {
       return Ok(PreviewUnknownHtml.into_response());
     }
 
-    let mut inscription = index
+    let inscription = index
       .get_inscription_by_id(inscription_id)?
       .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
-    if let Some(delegate) = inscription.delegate() {
-      inscription = index
-        .get_inscription_by_id(delegate)?
-        .ok_or_not_found(|| format!("delegate {inscription_id}"))?
+    Ok(
+      Self::content_response(inscription, accept_encoding, &server_config, &transforms)
+        .await?
+        .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
+        .into_response(),
+    )
+  }
+
+  /// Content is immutable, so the inscription id identifies it, but the same
+  /// content may be served as hex, or with or without its content encoding
+  /// applied.
+  fn content_etag(inscription_id: InscriptionId, headers: &HeaderMap, hex: bool) -> ETag {
+    let etag = match headers
+      .get(header::CONTENT_ENCODING)
+      .and_then(|encoding| encoding.to_str().ok())
+    {
+      _ if hex => format!("\"{inscription_id}-hex\""),
+      Some(encoding) => format!("\"{inscription_id}-{encoding}\""),
+      None => format!("\"{inscription_id}\""),
+    };
+
+    etag.parse().unwrap()
+  }
+
+  /// Inscription content is served only from `--content-origin`, if given, so
+  /// that it cannot script this origin.
+  fn content_origin_redirect(
+    server_config: &ServerConfig,
+    host: Option<Host>,
+    path: &str,
+  ) -> Option<Redirect> {
+    let origin = server_config.content_origin.as_ref()?;
+
+    if host.is_some_and(|Host(host)| {
+      origin
+        .authority()
+        .is_some_and(|authority| host.eq_ignore_ascii_case(authority.as_str()))
+    }) {
+      return None;
     }
 
-    match inscription.media() {
-      Media::Audio => Ok(PreviewAudioHtml { inscription_id }.into_response()),
+    Some(Redirect::temporary(&format!(
+      "{}{path}",
+      origin.to_string().trim_end_matches('/')
+    )))
+  }
+
+  async fn content_response(
+    inscription: Inscription,
+    accept_encoding: AcceptEncoding,
+    server_config: &ServerConfig,
+    transforms: &TransformPool,
+  ) -> ServerResult<Option<(HeaderMap, Vec<u8>)>> {
+    let Some((headers, body_kind)) =
+      Self::content_headers(&inscription, accept_encoding, server_config)?
+    else {
+      return Ok(None);
+    };
+
+    let body = inscription.into_body().unwrap_or_default();
+
+    let body = match body_kind {
+      ContentBody::Stored => body,
+      ContentBody::Brotli => Self::decompress_brotli(body, server_config, transforms).await?,
+    };
+
+    Ok(Some((headers, body)))
+  }
+
+  /// Decompress brotli compressed content on the transform pool, failing if
+  /// it decompresses to more than `--decompress-brotli-limit` bytes.
+  async fn decompress_brotli(
+    body: Vec<u8>,
+    server_config: &ServerConfig,
+    transforms: &TransformPool,
+  ) -> ServerResult<Vec<u8>> {
+    let limit = server_config.decompress_brotli_limit;
+
+    let decompressed = transforms
+      .run(move || {
+        let mut decompressed = Vec::new();
+
+        Decompressor::new(body.as_slice(), 4096)
+          .take(limit.saturating_add(1))
+          .read_to_end(&mut decompressed)
+          .map(|_| decompressed)
+      })
+      .await?
+      .map_err(|err| ServerError::Internal(err.into()))?;
+
+    if u64::try_from(decompressed.len()).unwrap() > limit {
+      return Err(ServerError::PayloadTooLarge(format!(
+        "decompressed content exceeds limit of {limit} bytes"
+      )));
+    }
+
+    Ok(decompressed)
+  }
+
+  /// The headers of a content response and whether its body is the stored
+  /// content or has to be decompressed, or `None` if there is no content.
+  fn content_headers(
+    inscription: &Inscription,
+    accept_encoding: AcceptEncoding,
+    server_config: &ServerConfig,
+  ) -> ServerResult<Option<(HeaderMap, ContentBody)>> {
+    let mut headers = HeaderMap::new();
+
+    match (&server_config.content_csp, &server_config.csp_origin) {
+      (Some(csp), _) => {
+        headers.insert(
+          header::CONTENT_SECURITY_POLICY,
+          HeaderValue::from_str(csp).map_err(|err| ServerError::Internal(Error::from(err)))?,
+        );
+      }
+      (None, None) => {
+        headers.insert(
+          header::CONTENT_SECURITY_POLICY,
+          HeaderValue::from_static(
+            "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:; sandbox allow-scripts",
+          ),
+        );
+        headers.append(
+          header::CONTENT_SECURITY_POLICY,
+          HeaderValue::from_static("default-src *:*/content/ *:*/blockheight *:*/blockhash *:*/blockhash/ *:*/blocktime *:*/r/ 'unsafe-eval' 'unsafe-inline' data: blob:"),
+        );
+      }
+      (None, Some(origin)) => {
+        let csp = format!("default-src {origin}/content/ {origin}/blockheight {origin}/blockhash {origin}/blockhash/ {origin}/blocktime {origin}/r/ 'unsafe-eval' 'unsafe-inline' data: blob:; sandbox allow-scripts");
+        headers.insert(
+          header::CONTENT_SECURITY_POLICY,
+          HeaderValue::from_str(&csp).map_err(|err| ServerError::Internal(Error::from(err)))?,
+        );
+      }
+    }
+
+    headers.insert(
+      header::CACHE_CONTROL,
+      HeaderValue::from_static("public, max-age=31536000, immutable"),
+    );
+
+    let sniffed = inscription
+      .body()
+      .filter(|_| {
+        inscription.content_encoding().is_none()
+          && sniff::needs_sniffing(inscription.content_type())
+      })
+      .and_then(sniff::sniff);
+
+    match sniffed {
+      Some(sniffed) => {
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(sniffed));
+
+        if let Some(original) = inscription
+          .content_type()
+          .and_then(|content_type| content_type.parse().ok())
+        {
+          headers.insert(
+            HeaderName::from_static(sniff::X_ORIGINAL_CONTENT_TYPE),
+            original,
+          );
+        }
+      }
+      None => {
+        let charset = match (inscription.content_type(), inscription.body()) {
+          (Some(content_type), Some(body)) if inscription.content_encoding().is_none() => {
+            charset::charset(content_type, body)
+          }
+          _ => Charset::Unchanged,
+        };
+
+        let content_type = match &charset {
+          Charset::Utf8(content_type) => Some(content_type.clone()),
+          Charset::Unchanged | Charset::InvalidUtf8 => {
+            inscription.content_type().map(str::to_string)
+          }
+        };
+
+        headers.insert(
+          header::CONTENT_TYPE,
+          content_type
+            .and_then(|content_type| content_type.parse().ok())
+            .unwrap_or(HeaderValue::from_static("application/octet-stream")),
+        );
+
+        if charset == Charset::InvalidUtf8 {
+          headers.insert(
+            HeaderName::from_static(charset::X_INVALID_UTF8),
+            HeaderValue::from_static("true"),
+          );
+        }
+      }
+    }
+
+    if let Some(content_encoding) = inscription.content_encoding() {
+      headers.insert(header::VARY, HeaderValue::from_static("Accept-Encoding"));
+
+      if accept_encoding.is_acceptable(&content_encoding) {
+        headers.insert(header::CONTENT_ENCODING, content_encoding);
+      } else if server_config.decompress && content_encoding == "br" {
+        if inscription.body().is_none() {
+          return Ok(None);
+        }
+
+        return Ok(Some((headers, ContentBody::Brotli)));
+      } else {
+        return Err(ServerError::NotAcceptable {
+          accept_encoding,
+          content_encoding,
+        });
+      }
+    }
+
+    if inscription.body().is_none() {
+      return Ok(None);
+    }
+
+    Ok(Some((headers, ContentBody::Stored)))
+  }
+
+  async fn preview(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(transforms): Extension<Arc<TransformPool>>,
+    PathParams(Param(inscription_id)): PathParams<Param<InscriptionId>>,
+    host: Option<Host>,
+    accept_encoding: AcceptEncoding,
+  ) -> ServerResult<Response> {
+    if let Some(redirect) =
+      Self::content_origin_redirect(&server_config, host, &format!("/preview/{inscription_id}"))
+    {
+      return Ok(redirect.into_response());
+    }
+
+    if config.is_hidden(inscription_id) {
+      return Ok(PreviewUnknownHtml.into_response());
+    }
+
+    let mut inscription = index
+      .get_inscription_by_id(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+    if let Some(delegate) = inscription.delegate() {
+      inscription = index
+        .get_inscription_by_id(delegate)?
+        .ok_or_not_found(|| format!("delegate {delegate}"))?
+    }
+
+    match inscription.media() {
+      Media::Audio => Ok(PreviewAudioHtml { inscription_id }.into_response()),
       Media::Code(language) => Ok(
         (
           [(
@@ -1323,7 +2728,8 @@ impl Server {
           .into_response(),
       ),
       Media::Iframe => Ok(
-        Self::content_response(inscription, accept_encoding, &server_config)?
+        Self::content_response(inscription, accept_encoding, &server_config, &transforms)
+          .await?
           .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
           .into_response(),
       ),
@@ -1377,44 +2783,79 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(DeserializeFromStr(query)): Path<DeserializeFromStr<InscriptionQuery>>,
+    Query(include): Query<IncludeQuery>,
+    Query(fields): Query<FieldsQuery>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
+    let include_parent = include.includes("parent", &["parent"])?;
+
     let info =
       Index::inscription_info(&index, query)?.ok_or_not_found(|| format!("inscription {query}"))?;
 
-    Ok(if accept_json {
-      Json(InscriptionJson {
-        inscription_id: info.entry.id,
-        charms: Charm::ALL
+    let parent_details = if include_parent {
+      Some(
+        info
+          .parents
           .iter()
-          .filter(|charm| charm.is_set(info.charms))
-          .map(|charm| charm.title().into())
-          .collect(),
-        children: info.children,
-        inscription_number: info.entry.inscription_number,
-        genesis_height: info.entry.height,
-        parent: info.parent,
-        genesis_fee: info.entry.fee,
-        output_value: info.output.as_ref().map(|o| o.value),
-        address: info
-          .output
-          .as_ref()
-          .and_then(|o| {
-            server_config
-              .chain
-              .address_from_script(&o.script_pubkey)
-              .ok()
+          .copied()
+          .map(|parent| {
+            let entry = index
+              .get_inscription_entry(parent)?
+              .ok_or_not_found(|| format!("inscription {parent}"))?;
+
+            let inscription = index
+              .get_inscription_by_id(parent)?
+              .ok_or_not_found(|| format!("inscription {parent}"))?;
+
+            Ok(InscriptionParentJson {
+              id: parent,
+              number: entry.inscription_number,
+              content_type: inscription.content_type().map(str::to_string),
+            })
           })
-          .map(|address| address.to_string()),
-        sat: info.entry.sat,
-        satpoint: info.satpoint,
-        content_type: info.inscription.content_type().map(|s| s.to_string()),
-        content_length: info.inscription.content_length(),
-        timestamp: timestamp(info.entry.timestamp).timestamp(),
-        previous: info.previous,
-        next: info.next,
-        rune: info.rune,
-      })
+          .collect::<ServerResult<Vec<InscriptionParentJson>>>()?,
+      )
+    } else {
+      None
+    };
+
+    Ok(if accept_json {
+      Json(
+        fields.select(InscriptionJson {
+          inscription_id: info.entry.id,
+          charms: Charm::ALL
+            .iter()
+            .filter(|charm| charm.is_set(info.charms))
+            .map(|charm| charm.title().into())
+            .collect(),
+          children: info.children,
+          inscription_number: info.entry.inscription_number,
+          genesis_height: info.entry.height,
+          parent: info.parents.first().copied(),
+          parent_details,
+          parents: info.parents,
+          genesis_fee: info.entry.fee,
+          output_value: info.output.as_ref().map(|o| o.value),
+          address: info
+            .output
+            .as_ref()
+            .and_then(|o| {
+              server_config
+                .chain
+                .address_from_script(&o.script_pubkey)
+                .ok()
+            })
+            .map(|address| address.to_string()),
+          sat: info.entry.sat,
+          satpoint: info.satpoint,
+          content_type: info.inscription.content_type().map(|s| s.to_string()),
+          content_length: info.inscription.content_length(),
+          timestamp: timestamp(info.entry.timestamp).timestamp(),
+          previous: info.previous,
+          next: info.next,
+          rune: info.rune,
+        })?,
+      )
       .into_response()
     } else {
       InscriptionHtml {
@@ -1428,7 +2869,8 @@ impl Server {
         inscription_number: info.entry.inscription_number,
         next: info.next,
         output: info.output,
-        parent: info.parent,
+        parent_details,
+        parents: info.parents,
         previous: info.previous,
         rune: info.rune,
         sat: info.entry.sat,
@@ -1472,12 +2914,12 @@ impl Server {
   async fn children(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path(inscription_id): Path<InscriptionId>,
+    PathParams(Param(inscription_id)): PathParams<Param<InscriptionId>>,
   ) -> ServerResult<Response> {
     Self::children_paginated(
       Extension(server_config),
       Extension(index),
-      Path((inscription_id, 0)),
+      PathParams((Param(inscription_id), 0)),
     )
     .await
   }
@@ -1485,7 +2927,7 @@ impl Server {
   async fn children_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path((parent, page)): Path<(InscriptionId, usize)>,
+    PathParams((Param(parent), page)): PathParams<(Param<InscriptionId>, usize)>,
   ) -> ServerResult<Response> {
     let entry = index
       .get_inscription_entry(parent)?
@@ -1515,15 +2957,104 @@ impl Server {
 
   async fn children_recursive(
     Extension(index): Extension<Arc<Index>>,
-    Path(inscription_id): Path<InscriptionId>,
+    PathParams(Param(inscription_id)): PathParams<Param<InscriptionId>>,
   ) -> ServerResult<Response> {
-    Self::children_recursive_paginated(Extension(index), Path((inscription_id, 0))).await
+    Self::children_recursive_paginated(Extension(index), PathParams((Param(inscription_id), 0)))
+      .await
   }
 
   async fn children_recursive_paginated(
     Extension(index): Extension<Arc<Index>>,
-    Path((parent, page)): Path<(InscriptionId, usize)>,
+    PathParams((Param(parent), page)): PathParams<(Param<InscriptionId>, usize)>,
+  ) -> ServerResult<Response> {
+    let parent_sequence_number = index
+      .get_inscription_entry(parent)?
+      .ok_or_not_found(|| format!("inscription {parent}"))?
+      .sequence_number;
+
+    let (ids, more) =
+      index.get_children_by_sequence_number_paginated(parent_sequence_number, 100, page)?;
+
+    let items = Self::inscription_items(&index, &ids)?;
+
+    Ok(
+      Json(ChildrenJson {
+        ids,
+        more,
+        page,
+        items,
+      })
+      .into_response(),
+    )
+  }
+
+  async fn parents_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    PathParams(Param(inscription_id)): PathParams<Param<InscriptionId>>,
+  ) -> ServerResult<Response> {
+    Self::parents_recursive_paginated(Extension(index), PathParams((Param(inscription_id), 0)))
+      .await
+  }
+
+  async fn parents_recursive_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    PathParams((Param(child), page)): PathParams<(Param<InscriptionId>, usize)>,
   ) -> ServerResult<Response> {
+    let parent_sequence_numbers = index
+      .get_inscription_entry(child)?
+      .ok_or_not_found(|| format!("inscription {child}"))?
+      .parents;
+
+    let (ids, more) =
+      index.get_parents_by_sequence_number_paginated(&parent_sequence_numbers, 100, page)?;
+
+    let items = Self::inscription_items(&index, &ids)?;
+
+    Ok(
+      Json(ParentsJson {
+        ids,
+        more,
+        page,
+        items,
+      })
+      .into_response(),
+    )
+  }
+
+  /// Listing items of `inscription_ids`, with their content type and length
+  /// as stored in the index.
+  fn inscription_items(
+    index: &Index,
+    inscription_ids: &[InscriptionId],
+  ) -> ServerResult<Vec<InscriptionItemJson>> {
+    Ok(
+      inscription_ids
+        .iter()
+        .zip(index.get_inscriptions_content(inscription_ids)?)
+        .map(|(id, (content_type, content_length))| InscriptionItemJson {
+          id: *id,
+          content_type,
+          content_length,
+        })
+        .collect(),
+    )
+  }
+
+  async fn child_inscriptions_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    PathParams(Param(inscription_id)): PathParams<Param<InscriptionId>>,
+  ) -> ServerResult<Json<ChildInscriptionsJson>> {
+    Self::child_inscriptions_recursive_paginated(
+      Extension(index),
+      PathParams((Param(inscription_id), 0)),
+    )
+    .await
+  }
+
+  async fn child_inscriptions_recursive_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    PathParams((Param(parent), page)): PathParams<(Param<InscriptionId>, usize)>,
+  ) -> ServerResult<Json<ChildInscriptionsJson>> {
     let parent_sequence_number = index
       .get_inscription_entry(parent)?
       .ok_or_not_found(|| format!("inscription {parent}"))?
@@ -1532,18 +3063,94 @@ impl Server {
     let (ids, more) =
       index.get_children_by_sequence_number_paginated(parent_sequence_number, 100, page)?;
 
-    Ok(Json(ChildrenJson { ids, more, page }).into_response())
+    let content = index.get_inscriptions_content(&ids)?;
+
+    let children = ids
+      .into_iter()
+      .zip(content)
+      .map(|(id, (content_type, content_length))| {
+        let entry = index
+          .get_inscription_entry(id)?
+          .ok_or_not_found(|| format!("inscription {id}"))?;
+
+        let satpoint = index
+          .get_inscription_satpoint_by_id(id)?
+          .ok_or_not_found(|| format!("inscription {id} satpoint"))?;
+
+        Ok(ChildInscriptionJson {
+          id,
+          number: entry.inscription_number,
+          content_type,
+          content_length,
+          satpoint,
+        })
+      })
+      .collect::<ServerResult<Vec<ChildInscriptionJson>>>()?;
+
+    Ok(Json(ChildInscriptionsJson {
+      children,
+      more,
+      page,
+    }))
   }
 
   async fn inscriptions(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    query: Query<HeightRangeQuery>,
+    order: Query<OrderQuery>,
+    cursor: Query<CursorQuery>,
+    content_type: Query<ContentTypeQuery>,
+    limit: Query<LimitQuery>,
+    Query(poll): Query<PollQuery>,
+    deadline: Option<Extension<timeout::Deadline>>,
     accept_json: AcceptJson,
   ) -> ServerResult<Response> {
+    if let Some((since_sequence, wait)) = poll.poll()? {
+      if query.range()?.is_some()
+        || order.order()?.is_some()
+        || cursor.cursor.is_some()
+        || content_type.content_type.is_some()
+      {
+        return Err(ServerError::BadRequest(
+          "since_sequence cannot be combined with other listing parameters".into(),
+        ));
+      }
+
+      // answer before the request times out, leaving time to list what was
+      // indexed in the meantime
+      let mut deadline_at = tokio::time::Instant::now() + wait;
+
+      if let Some(Extension(timeout::Deadline(timeout_at))) = deadline {
+        deadline_at = deadline_at.min(
+          timeout_at
+            .checked_sub(Duration::from_secs(1))
+            .unwrap_or(timeout_at),
+        );
+      }
+
+      let limit = limit.limit(&server_config)?;
+
+      return Self::poll_inscriptions(
+        server_config,
+        index,
+        since_sequence,
+        limit,
+        deadline_at,
+        accept_json,
+      )
+      .await;
+    }
+
     Self::inscriptions_paginated(
       Extension(server_config),
       Extension(index),
       Path(0),
+      query,
+      order,
+      cursor,
+      content_type,
+      limit,
       accept_json,
     )
     .await
@@ -1553,25 +3160,142 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(page_index): Path<usize>,
+    Query(query): Query<HeightRangeQuery>,
+    Query(order): Query<OrderQuery>,
+    Query(cursor): Query<CursorQuery>,
+    Query(content_type): Query<ContentTypeQuery>,
+    Query(limit): Query<LimitQuery>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
-    let (inscriptions, more_inscriptions) = index.get_inscriptions_paginated(100, page_index)?;
+    let order = order.order()?;
+    let cursor = cursor.cursor()?;
+    let range = query.range()?;
+    let content_type = content_type.filter()?;
+    let filtered = content_type.is_some();
+    let limit = limit.limit(&server_config)?;
+
+    // Height ranges have always been listed oldest first, everything else
+    // newest first.
+    let order_or_default = order.unwrap_or(if range.is_some() {
+      Order::Asc
+    } else {
+      Order::Desc
+    });
 
-    let prev = page_index.checked_sub(1);
+    let (inscriptions, more_inscriptions, last_sequence_number, truncated) =
+      match (content_type, cursor) {
+        (Some(content_type), cursor) => {
+          if page_index != 0 {
+            return Err(ServerError::BadRequest(
+              "content_type and page cannot be combined".into(),
+            ));
+          }
+
+          let (inscriptions, last_scanned, truncated) = index
+            .get_inscriptions_matching(
+              limit,
+              cursor,
+              order_or_default,
+              range,
+              ContentTypeQuery::SCAN_LIMIT,
+              |inscription_content_type| {
+                inscription_content_type
+                  .map(|inscription_content_type| content_type.matches(inscription_content_type))
+                  .unwrap_or_default()
+              },
+            )?
+            .ok_or_else(|| ServerError::BadRequest("cursor out of range".into()))?;
+
+          (
+            inscriptions
+              .into_iter()
+              .map(|(_sequence_number, id)| id)
+              .collect(),
+            last_scanned.is_some(),
+            last_scanned,
+            truncated,
+          )
+        }
+        (None, Some(cursor)) => {
+          if page_index != 0 {
+            return Err(ServerError::BadRequest(
+              "cursor and page cannot be combined".into(),
+            ));
+          }
+
+          let (inscriptions, more) = index
+            .get_inscriptions_after_cursor(limit, cursor, order_or_default, range)?
+            .ok_or_else(|| ServerError::BadRequest("cursor out of range".into()))?;
+
+          let last_sequence_number = inscriptions
+            .last()
+            .map(|(sequence_number, _id)| *sequence_number);
+
+          (
+            inscriptions
+              .into_iter()
+              .map(|(_sequence_number, id)| id)
+              .collect(),
+            more,
+            last_sequence_number,
+            false,
+          )
+        }
+        (None, None) => {
+          let (inscriptions, more) = match range {
+            Some((from_height, to_height)) => index.get_inscriptions_in_height_range_paginated(
+              from_height,
+              to_height,
+              limit,
+              page_index,
+              order_or_default,
+            )?,
+            None => index.get_inscriptions_paginated(limit, page_index, order_or_default)?,
+          };
+
+          let last_sequence_number = match inscriptions.last() {
+            Some(id) if more => index
+              .get_inscription_entry(*id)?
+              .map(|entry| entry.sequence_number),
+            _ => None,
+          };
+
+          (inscriptions, more, last_sequence_number, false)
+        }
+      };
+
+    let next_cursor = last_sequence_number
+      .filter(|_| more_inscriptions)
+      .map(CursorQuery::encode);
+
+    // Cursor and filtered pages have no page number to link to.
+    let paged = cursor.is_none() && !filtered;
+
+    let prev = page_index.checked_sub(1).filter(|_| paged);
 
-    let next = more_inscriptions.then_some(page_index + 1);
+    let next = more_inscriptions
+      .then_some(page_index + 1)
+      .filter(|_| paged);
 
     Ok(if accept_json {
+      let items = Self::inscription_items(&index, &inscriptions)?;
+
       Json(InscriptionsJson {
         inscriptions,
         page_index,
+        limit,
         more: more_inscriptions,
+        next_cursor,
+        truncated,
+        items,
+        last_sequence: None,
       })
       .into_response()
     } else {
       InscriptionsHtml {
         inscriptions,
         next,
+        order,
         prev,
       }
       .page(server_config)
@@ -1579,47 +3303,121 @@ impl Server {
     })
   }
 
-  async fn inscriptions_in_block(
-    Extension(server_config): Extension<Arc<ServerConfig>>,
-    Extension(index): Extension<Arc<Index>>,
-    Path(block_height): Path<u32>,
+  /// The inscriptions indexed after `since_sequence`, oldest first, waiting
+  /// until `deadline` for one to be indexed if there are none yet.
+  async fn poll_inscriptions(
+    server_config: Arc<ServerConfig>,
+    index: Arc<Index>,
+    since_sequence: u32,
+    limit: usize,
+    deadline: tokio::time::Instant,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
-    Self::inscriptions_in_block_paginated(
-      Extension(server_config),
-      Extension(index),
-      Path((block_height, 0)),
-      AcceptJson(accept_json),
-    )
-    .await
-  }
+    use tokio::sync::broadcast::error::RecvError;
 
-  async fn inscriptions_in_block_paginated(
-    Extension(server_config): Extension<Arc<ServerConfig>>,
-    Extension(index): Extension<Arc<Index>>,
-    Path((block_height, page_index)): Path<(u32, usize)>,
-    AcceptJson(accept_json): AcceptJson,
-  ) -> ServerResult<Response> {
-    let page_size = 100;
+    // subscribe before listing, so that inscriptions indexed in between are
+    // not missed
+    let mut events = index.subscribe();
 
-    let mut inscriptions = index
-      .get_inscriptions_in_block(block_height)?
-      .into_iter()
-      .skip(page_index.saturating_mul(page_size))
-      .take(page_size.saturating_add(1))
-      .collect::<Vec<InscriptionId>>();
+    let (inscriptions, more) = loop {
+      let (inscriptions, more) = index.get_inscriptions_since(limit, since_sequence)?;
 
-    let more = inscriptions.len() > page_size;
+      if !inscriptions.is_empty() {
+        break (inscriptions, more);
+      }
+
+      match tokio::time::timeout_at(deadline, events.recv()).await {
+        Ok(Ok(_) | Err(RecvError::Lagged(_))) => continue,
+        Ok(Err(RecvError::Closed)) | Err(_) => break (inscriptions, more),
+      }
+    };
+
+    let last_sequence = inscriptions
+      .last()
+      .map(|(sequence_number, _id)| *sequence_number);
+
+    let inscriptions = inscriptions
+      .into_iter()
+      .map(|(_sequence_number, id)| id)
+      .collect::<Vec<InscriptionId>>();
+
+    Ok(if accept_json {
+      let items = Self::inscription_items(&index, &inscriptions)?;
+
+      Json(InscriptionsJson {
+        inscriptions,
+        page_index: 0,
+        limit,
+        more,
+        next_cursor: None,
+        truncated: false,
+        items,
+        last_sequence,
+      })
+      .into_response()
+    } else {
+      InscriptionsHtml {
+        inscriptions,
+        next: None,
+        order: None,
+        prev: None,
+      }
+      .page(server_config)
+      .into_response()
+    })
+  }
+
+  async fn inscriptions_in_block(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(block_height): Path<u32>,
+    limit: Query<LimitQuery>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    Self::inscriptions_in_block_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((block_height, 0)),
+      limit,
+      AcceptJson(accept_json),
+    )
+    .await
+  }
+
+  async fn inscriptions_in_block_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((block_height, page_index)): Path<(u32, usize)>,
+    Query(limit): Query<LimitQuery>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    let page_size = limit.limit(&server_config)?;
+
+    let mut inscriptions = index
+      .get_inscriptions_in_block(block_height)?
+      .into_iter()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .collect::<Vec<InscriptionId>>();
+
+    let more = inscriptions.len() > page_size;
 
     if more {
       inscriptions.pop();
     }
 
     Ok(if accept_json {
+      let items = Self::inscription_items(&index, &inscriptions)?;
+
       Json(InscriptionsJson {
         inscriptions,
         page_index,
+        limit: page_size,
         more,
+        next_cursor: None,
+        truncated: false,
+        items,
+        last_sequence: None,
       })
       .into_response()
     } else {
@@ -1635,31 +3433,9 @@ impl Server {
     })
   }
 
-  async fn sat_inscriptions(
-    Extension(index): Extension<Arc<Index>>,
-    Path(sat): Path<u64>,
-  ) -> ServerResult<Json<SatInscriptionsJson>> {
-    Self::sat_inscriptions_paginated(Extension(index), Path((sat, 0))).await
-  }
-
-  async fn sat_inscriptions_paginated(
-    Extension(index): Extension<Arc<Index>>,
-    Path((sat, page)): Path<(u64, u64)>,
-  ) -> ServerResult<Json<SatInscriptionsJson>> {
-    if !index.has_sat_index() {
-      return Err(ServerError::NotFound(
-        "this server has no sat index".to_string(),
-      ));
-    }
-
-    let (ids, more) = index.get_inscription_ids_by_sat_paginated(Sat(sat), 100, page)?;
-
-    Ok(Json(SatInscriptionsJson { ids, more, page }))
-  }
-
   async fn sat_inscription_at_index(
     Extension(index): Extension<Arc<Index>>,
-    Path((DeserializeFromStr(sat), inscription_index)): Path<(DeserializeFromStr<Sat>, isize)>,
+    PathParams((Param(sat), inscription_index)): PathParams<(Param<Sat>, isize)>,
   ) -> ServerResult<Json<SatInscriptionJson>> {
     if !index.has_sat_index() {
       return Err(ServerError::NotFound(
@@ -1667,9 +3443,11 @@ impl Server {
       ));
     }
 
-    let id = index.get_inscription_id_by_sat_indexed(sat, inscription_index)?;
+    let id = index
+      .get_inscription_id_by_sat_indexed(sat, inscription_index)?
+      .ok_or_not_found(|| format!("inscription at index {inscription_index} on sat {sat}"))?;
 
-    Ok(Json(SatInscriptionJson { id }))
+    Ok(Json(SatInscriptionJson { id: Some(id) }))
   }
 
   async fn redirect_http_to_https(
@@ -1688,17 +3466,17 @@ impl Server {
 mod tests {
   use {
     super::*,
-    crate::runes::{Edict, Etching, Rune, Runestone},
+    crate::runes::{Edict, Etching, Rune, Runestone, CLAIM_BIT},
     reqwest::Url,
     serde::de::DeserializeOwned,
-    std::net::TcpListener,
+    std::{io::Write, net::TcpListener},
   };
 
   const RUNE: u128 = 99246114928149462;
 
   struct TestServer {
     bitcoin_rpc_server: test_bitcoincore_rpc::Handle,
-    index: Arc<Index>,
+    index: WritableIndex,
     ord_server_handle: Handle,
     url: Url,
     #[allow(unused)]
@@ -1808,13 +3586,18 @@ mod tests {
         server_args.join(" "),
       ));
 
-      let index = Arc::new(Index::open(&options).unwrap());
+      let index = Index::open(&options).unwrap();
       let ord_server_handle = Handle::new();
 
       {
-        let index = index.clone();
+        let reader = index.reader();
+        let writer = index.clone();
         let ord_server_handle = ord_server_handle.clone();
-        thread::spawn(|| server.run(options, index, ord_server_handle).unwrap());
+        thread::spawn(|| {
+          server
+            .run(options, reader, Some(writer), ord_server_handle)
+            .unwrap()
+        });
       }
 
       while index.statistic(crate::index::Statistic::Commits) == 0 {
@@ -1947,6 +3730,20 @@ mod tests {
     }
   }
 
+  async fn content_response(
+    inscription: Inscription,
+    accept_encoding: AcceptEncoding,
+    server_config: &ServerConfig,
+  ) -> ServerResult<Option<(HeaderMap, Vec<u8>)>> {
+    Server::content_response(
+      inscription,
+      accept_encoding,
+      server_config,
+      &TransformPool::new(1).unwrap(),
+    )
+    .await
+  }
+
   fn parse_server_args(args: &str) -> (Options, Server) {
     match Arguments::try_parse_from(args.split_whitespace()) {
       Ok(arguments) => match arguments.subcommand {
@@ -2112,6 +3909,20 @@ mod tests {
     );
   }
 
+  #[test]
+  fn content_origin_must_be_scheme_and_host() {
+    let (_, server) = parse_server_args("ord server --content-origin https://content.example.com");
+    assert_eq!(
+      server.content_origin().unwrap().unwrap(),
+      "https://content.example.com/"
+    );
+
+    for content_origin in ["content.example.com", "https://content.example.com/content"] {
+      let (_, server) = parse_server_args(&format!("ord server --content-origin {content_origin}"));
+      assert!(server.content_origin().is_err(), "{content_origin}");
+    }
+  }
+
   #[test]
   fn acme_domain_flag_is_respected() {
     let (_, server) = parse_server_args("ord server --acme-domain example.com");
@@ -2602,6 +4413,178 @@ mod tests {
     );
   }
 
+  #[test]
+  fn rune_amounts_are_strings_unless_legacy_number_amounts_is_set() {
+    for (server_args, supply) in [
+      (
+        &["--enable-json-api"][..],
+        r#""340282366920938463463374607431768211455""#,
+      ),
+      (
+        &["--enable-json-api", "--legacy-number-amounts"][..],
+        "340282366920938463463374607431768211455",
+      ),
+    ] {
+      let server = TestServer::new_server(
+        test_bitcoincore_rpc::builder()
+          .network(bitcoin::Network::Regtest)
+          .build(),
+        None,
+        &["--chain", "regtest", "--index-runes"],
+        server_args,
+      );
+
+      server.mine_blocks(1);
+
+      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, Default::default())],
+        op_return: Some(
+          Runestone {
+            edicts: vec![Edict {
+              id: 0,
+              amount: u128::max_value(),
+              output: 0,
+            }],
+            etching: Some(Etching {
+              rune: Some(Rune(RUNE)),
+              ..Default::default()
+            }),
+            ..Default::default()
+          }
+          .encipher(),
+        ),
+        ..Default::default()
+      });
+
+      server.mine_blocks(1);
+
+      let get_json_text = |path: String| {
+        reqwest::blocking::Client::new()
+          .get(server.join_url(&path))
+          .header(reqwest::header::ACCEPT, "application/json")
+          .send()
+          .unwrap()
+          .text()
+          .unwrap()
+      };
+
+      assert!(get_json_text(format!("/output/{txid}:0"))
+        .contains(&format!(r#""runes":{{"{}":{supply}}}"#, Rune(RUNE))));
+
+      assert!(
+        get_json_text(format!("/rune/{}", Rune(RUNE))).contains(&format!(r#""supply":{supply}"#))
+      );
+    }
+  }
+
+  #[test]
+  fn rune_json_reports_mint_eligibility() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--index-runes"],
+      &["--enable-json-api"],
+    );
+
+    server.mine_blocks(4);
+
+    let etchings = [
+      (RUNE, Some(100), Some(10), None),
+      (RUNE + 1, Some(100), Some(2), None),
+      (RUNE + 2, Some(100), None, Some(5)),
+      (RUNE + 3, None, None, None),
+    ];
+
+    for (i, (rune, limit, term, deadline)) in etchings.into_iter().enumerate() {
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, Default::default())],
+        op_return: Some(
+          Runestone {
+            etching: Some(Etching {
+              rune: Some(Rune(rune)),
+              limit,
+              term,
+              deadline,
+              ..Default::default()
+            }),
+            ..Default::default()
+          }
+          .encipher(),
+        ),
+        ..Default::default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(5, 0, 0, Default::default())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: u128::from(RuneId {
+              height: 5,
+              index: 1,
+            }) | CLAIM_BIT,
+            amount: 100,
+            output: 0,
+          }],
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let mint = |rune: u128| {
+      let response = reqwest::blocking::Client::new()
+        .get(server.join_url(&format!("/rune/{}", Rune(rune))))
+        .header(reqwest::header::ACCEPT, "application/json")
+        .send()
+        .unwrap();
+
+      assert_eq!(response.status(), StatusCode::OK);
+
+      response.json::<serde_json::Value>().unwrap()["mint"].clone()
+    };
+
+    pretty_assert_eq!(
+      mint(RUNE),
+      serde_json::json!({
+        "mintable": true,
+        "mints": 1,
+        "cap": null,
+        "amount_per_mint": "100",
+        "start_height": 5,
+        "end_height": 15,
+      })
+    );
+
+    pretty_assert_eq!(
+      mint(RUNE + 1),
+      serde_json::json!({
+        "mintable": false,
+        "mints": 0,
+        "cap": null,
+        "amount_per_mint": "100",
+        "start_height": 5,
+        "end_height": 7,
+      })
+    );
+
+    let past_deadline = mint(RUNE + 2);
+    assert_eq!(past_deadline["mintable"], false);
+    assert_eq!(past_deadline["end_height"], serde_json::Value::Null);
+
+    let closed = mint(RUNE + 3);
+    assert_eq!(closed["mintable"], false);
+    assert_eq!(closed["amount_per_mint"], serde_json::Value::Null);
+  }
+
   #[test]
   fn runes_are_displayed_on_output_page() {
     let server = TestServer::new_with_regtest_with_index_runes();
@@ -2714,6 +4697,22 @@ mod tests {
       .assert_redirect("/", &format!("https://{}/", System::host_name().unwrap()));
   }
 
+  #[test]
+  fn disabled_route_groups_are_not_served() {
+    let server = TestServer::new_with_args(
+      &[],
+      &["--enable-json-api", "--disable-routes", "html,search"],
+    );
+
+    server.assert_response("/", StatusCode::NOT_FOUND, "");
+    server.assert_response("/search?query=0", StatusCode::NOT_FOUND, "");
+    server.assert_response("/inscriptions", StatusCode::NOT_FOUND, "");
+    server.assert_response("/blockheight", StatusCode::OK, "0");
+
+    let status: crate::templates::StatusHtml = server.get_json("/status");
+    assert_eq!(status.route_groups, ["listings", "ws", "brc20"]);
+  }
+
   #[test]
   fn status() {
     let test_server = TestServer::new();
@@ -2727,6 +4726,12 @@ mod tests {
   <dd>mainnet</dd>
   <dt>height</dt>
   <dd>0</dd>
+  <dt>chain tip</dt>
+  <dd>0</dd>
+  <dt>lag</dt>
+  <dd>0</dd>
+  <dt>synced</dt>
+  <dd>true</dd>
   <dt>inscriptions</dt>
   <dd>0</dd>
   <dt>blessed inscriptions</dt>
@@ -2747,12 +4752,18 @@ mod tests {
   <dd>.*</dd>
   <dt>unrecoverably reorged</dt>
   <dd>false</dd>
+  <dt>shutting down</dt>
+  <dd>false</dd>
   <dt>rune index</dt>
   <dd>false</dd>
   <dt>sat index</dt>
   <dd>false</dd>
   <dt>transaction index</dt>
   <dd>false</dd>
+  <dt>readonly</dt>
+  <dd>false</dd>
+  <dt>route groups</dt>
+  <dd>html, search, listings, ws, brc20</dd>
   <dt>git branch</dt>
   <dd>.*</dd>
   <dt>git commit</dt>
@@ -2837,970 +4848,1157 @@ mod tests {
   }
 
   #[test]
-  fn range_end_before_range_start_returns_400() {
-    TestServer::new().assert_response(
-      "/range/1/0",
-      StatusCode::BAD_REQUEST,
-      "range start greater than range end",
-    );
-  }
+  fn status_reports_lag_while_syncing() {
+    let test_server = TestServer::new_with_args(&[], &["--enable-json-api"]);
 
-  #[test]
-  fn invalid_range_start_returns_400() {
-    TestServer::new().assert_response(
-      "/range/=/0",
-      StatusCode::BAD_REQUEST,
-      "Invalid URL: invalid digit found in string",
-    );
-  }
+    test_server.bitcoin_rpc_server.mine_blocks(3);
 
-  #[test]
-  fn invalid_range_end_returns_400() {
-    TestServer::new().assert_response(
-      "/range/0/=",
-      StatusCode::BAD_REQUEST,
-      "Invalid URL: invalid digit found in string",
-    );
-  }
+    let response = reqwest::blocking::Client::new()
+      .get(test_server.join_url("/status"))
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
 
-  #[test]
-  fn empty_range_returns_400() {
-    TestServer::new().assert_response("/range/0/0", StatusCode::BAD_REQUEST, "empty range");
-  }
+    assert_eq!(response.status(), StatusCode::OK);
 
-  #[test]
-  fn range() {
-    TestServer::new().assert_response_regex(
-      "/range/0/1",
-      StatusCode::OK,
-      r".*<title>Sat range 0–1</title>.*<h1>Sat range 0–1</h1>
-<dl>
-  <dt>value</dt><dd>1</dd>
-  <dt>first</dt><dd><a href=/sat/0 class=mythic>0</a></dd>
-</dl>.*",
-    );
+    let status: crate::templates::StatusHtml = response.json().unwrap();
+
+    assert_eq!(status.height, Some(0));
+    assert_eq!(status.chain_tip, Some(3));
+    assert_eq!(status.lag, Some(3));
+    assert!(!status.synced);
+
+    let status: crate::templates::StatusHtml = test_server.get_json("/status");
+
+    assert_eq!(status.height, Some(3));
+    assert_eq!(status.lag, Some(0));
+    assert!(status.synced);
   }
+
   #[test]
-  fn sat_number() {
-    TestServer::new().assert_response_regex("/sat/0", StatusCode::OK, ".*<h1>Sat 0</h1>.*");
+  fn live_is_always_ok() {
+    let test_server = TestServer::new();
+
+    test_server.bitcoin_rpc_server.mine_blocks(3);
+
+    let response = reqwest::blocking::get(test_server.join_url("/live")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), "");
+
+    test_server.assert_response("/live", StatusCode::OK, "");
   }
 
   #[test]
-  fn sat_decimal() {
-    TestServer::new().assert_response_regex("/sat/0.0", StatusCode::OK, ".*<h1>Sat 0</h1>.*");
+  fn ready_only_while_synced() {
+    let test_server = TestServer::new();
+
+    test_server.assert_response("/ready", StatusCode::OK, "");
+
+    test_server.bitcoin_rpc_server.mine_blocks(3);
+
+    test_server.index.refresh_chain_block_count().unwrap();
+
+    let response = reqwest::blocking::get(test_server.join_url("/ready")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.text().unwrap(), "index is syncing");
+
+    test_server.assert_response("/ready", StatusCode::OK, "");
   }
 
   #[test]
-  fn sat_degree() {
-    TestServer::new().assert_response_regex("/sat/0°0′0″0‴", StatusCode::OK, ".*<h1>Sat 0</h1>.*");
+  fn route_timeouts_apply_to_synchronous_reads() {
+    let server = TestServer::new_with_args(&[], &["--route-timeout", "/tx/:txid=1"]);
+
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.tx(1, 0).txid();
+
+    let start = Instant::now();
+
+    let response = {
+      let _paused = server.bitcoin_rpc_server.pause();
+      reqwest::blocking::get(server.join_url(&format!("/tx/{txid}"))).unwrap()
+    };
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    server.assert_response_regex(format!("/tx/{txid}"), StatusCode::OK, ".*");
   }
 
   #[test]
-  fn sat_name() {
-    TestServer::new().assert_response_regex(
-      "/sat/nvtdijuwxlp",
-      StatusCode::OK,
-      ".*<h1>Sat 0</h1>.*",
-    );
+  fn probes_are_not_concurrency_limited() {
+    let test_server = TestServer::new_with_args(&[], &["--max-concurrent-per-ip", "0"]);
+
+    let response = reqwest::blocking::get(test_server.join_url("/status")).unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    test_server.assert_response("/live", StatusCode::OK, "");
+    test_server.assert_response("/ready", StatusCode::OK, "");
   }
 
   #[test]
-  fn sat() {
-    TestServer::new().assert_response_regex(
-      "/sat/0",
-      StatusCode::OK,
-      ".*<title>Sat 0</title>.*<h1>Sat 0</h1>.*",
-    );
-  }
+  fn recursive_block_hash_of_buried_block_is_cacheable() {
+    let test_server = TestServer::new();
 
-  #[test]
-  fn block() {
-    TestServer::new().assert_response_regex(
-      "/block/0",
-      StatusCode::OK,
-      ".*<title>Block 0</title>.*<h1>Block 0</h1>.*",
+    test_server.mine_blocks(6);
+
+    let response = test_server.get("/r/blockhash/0");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "public, max-age=31536000, immutable"
     );
-  }
 
-  #[test]
-  fn sat_out_of_range() {
-    TestServer::new().assert_response(
-      "/sat/2099999997690000",
-      StatusCode::BAD_REQUEST,
-      "Invalid URL: invalid sat",
+    let etag = response.headers().get(header::ETAG).unwrap().clone();
+    let last_modified = response
+      .headers()
+      .get(header::LAST_MODIFIED)
+      .unwrap()
+      .clone();
+
+    assert_eq!(
+      etag,
+      format!(
+        "\"{}\"",
+        test_server.index.block_hash(Some(0)).unwrap().unwrap()
+      )
     );
+    assert_eq!(last_modified, "Sat, 03 Jan 2009 18:15:05 GMT");
+
+    let client = reqwest::blocking::Client::new();
+
+    let response = client
+      .get(test_server.join_url("/r/blockhash/0"))
+      .header(header::IF_NONE_MATCH, etag)
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    let response = client
+      .get(test_server.join_url("/r/blockhash/0"))
+      .header(header::IF_MODIFIED_SINCE, last_modified)
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    let response = client
+      .get(test_server.join_url("/r/blockhash/0"))
+      .header(header::IF_NONE_MATCH, "\"foo\"")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
   }
 
   #[test]
-  fn invalid_outpoint_hash_returns_400() {
-    TestServer::new().assert_response(
-      "/output/foo:0",
-      StatusCode::BAD_REQUEST,
-      "Invalid URL: error parsing TXID",
+  fn recursive_block_time_from_height() {
+    let test_server = TestServer::new();
+
+    let block = test_server.mine_blocks(6)[0].clone();
+
+    assert_eq!(test_server.get_json::<u32>("/r/blocktime/0"), 1231006505);
+    assert_eq!(
+      test_server.get_json::<u32>("/r/blocktime/1"),
+      block.header.time
     );
-  }
 
-  #[test]
-  fn output_with_sat_index() {
-    let txid = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
-    TestServer::new_with_sat_index().assert_response_regex(
-      format!("/output/{txid}:0"),
-      StatusCode::OK,
-      format!(
-        ".*<title>Output {txid}:0</title>.*<h1>Output <span class=monospace>{txid}:0</span></h1>
-<dl>
-  <dt>value</dt><dd>5000000000</dd>
-  <dt>script pubkey</dt><dd class=monospace>OP_PUSHBYTES_65 [[:xdigit:]]{{130}} OP_CHECKSIG</dd>
-  <dt>transaction</dt><dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
-</dl>
-<h2>1 Sat Range</h2>
-<ul class=monospace>
-  <li><a href=/range/0/5000000000 class=mythic>0–5000000000</a></li>
-</ul>.*"
-      ),
+    let response = test_server.get("/r/blocktime/0");
+
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "public, max-age=31536000, immutable"
+    );
+    assert_eq!(
+      response.headers().get(header::LAST_MODIFIED).unwrap(),
+      "Sat, 03 Jan 2009 18:15:05 GMT"
     );
-  }
 
-  #[test]
-  fn output_without_sat_index() {
-    let txid = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
-    TestServer::new().assert_response_regex(
-      format!("/output/{txid}:0"),
-      StatusCode::OK,
-      format!(
-        ".*<title>Output {txid}:0</title>.*<h1>Output <span class=monospace>{txid}:0</span></h1>
-<dl>
-  <dt>value</dt><dd>5000000000</dd>
-  <dt>script pubkey</dt><dd class=monospace>OP_PUSHBYTES_65 [[:xdigit:]]{{130}} OP_CHECKSIG</dd>
-  <dt>transaction</dt><dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
-</dl>.*"
-      ),
+    let response = test_server.get("/r/blocktime/1");
+
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "no-store"
     );
-  }
 
-  #[test]
-  fn null_output_is_initially_empty() {
-    let txid = "0000000000000000000000000000000000000000000000000000000000000000";
-    TestServer::new_with_sat_index().assert_response_regex(
-      format!("/output/{txid}:4294967295"),
-      StatusCode::OK,
-      format!(
-        ".*<title>Output {txid}:4294967295</title>.*<h1>Output <span class=monospace>{txid}:4294967295</span></h1>
-<dl>
-  <dt>value</dt><dd>0</dd>
-  <dt>script pubkey</dt><dd class=monospace></dd>
-  <dt>transaction</dt><dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
-</dl>
-<h2>0 Sat Ranges</h2>
-<ul class=monospace>
-</ul>.*"
-      ),
+    assert_eq!(
+      test_server.get("/r/blocktime/7").status(),
+      StatusCode::NOT_FOUND
     );
   }
 
   #[test]
-  fn null_output_receives_lost_sats() {
-    let server = TestServer::new_with_sat_index();
+  fn recursive_block_hash_near_tip_is_not_cached() {
+    let test_server = TestServer::new();
 
-    server.mine_blocks_with_subsidy(1, 0);
+    test_server.mine_blocks(6);
 
-    let txid = "0000000000000000000000000000000000000000000000000000000000000000";
+    let response = test_server.get("/r/blockhash/1");
 
-    server.assert_response_regex(
-      format!("/output/{txid}:4294967295"),
-      StatusCode::OK,
-      format!(
-        ".*<title>Output {txid}:4294967295</title>.*<h1>Output <span class=monospace>{txid}:4294967295</span></h1>
-<dl>
-  <dt>value</dt><dd>5000000000</dd>
-  <dt>script pubkey</dt><dd class=monospace></dd>
-  <dt>transaction</dt><dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
-</dl>
-<h2>1 Sat Range</h2>
-<ul class=monospace>
-  <li><a href=/range/5000000000/10000000000 class=uncommon>5000000000–10000000000</a></li>
-</ul>.*"
-      ),
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "no-store"
     );
+    assert!(response.headers().get(header::ETAG).is_none());
   }
 
   #[test]
-  fn unbound_output_receives_unbound_inscriptions() {
-    let server = TestServer::new_with_regtest_with_index_sats();
-
-    server.mine_blocks(1);
-
-    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, Default::default())],
-      fee: 50 * 100_000_000,
-      ..Default::default()
-    });
+  fn tip_endpoints_return_service_unavailable_while_syncing() {
+    let test_server = TestServer::new();
 
-    server.mine_blocks(1);
+    test_server.bitcoin_rpc_server.mine_blocks(1);
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        2,
-        1,
-        0,
-        inscription("text/plain;charset=utf-8", "hello").to_witness(),
-      )],
-      ..Default::default()
-    });
+    test_server.index.refresh_chain_block_count().unwrap();
 
-    server.mine_blocks(1);
+    for path in ["/blockheight", "/blockhash", "/r/blockhash", "/blocktime"] {
+      let response = reqwest::blocking::get(test_server.join_url(path)).unwrap();
+      assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+      assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "5");
+      assert_eq!(
+        response.text().unwrap(),
+        "index is syncing: 1 of 2 blocks indexed"
+      );
+    }
 
-    let inscription_id = InscriptionId { txid, index: 0 };
+    test_server.assert_response("/blockheight", StatusCode::OK, "1");
+  }
 
-    server.assert_response_regex(
-      format!("/inscription/{}", inscription_id),
-      StatusCode::OK,
-      format!(
-        ".*<dl>
-  <dt>id</dt>
-  <dd class=monospace>{inscription_id}</dd>.*<dt>output</dt>
-  <dd><a class=monospace href=/output/0000000000000000000000000000000000000000000000000000000000000000:0>0000000000000000000000000000000000000000000000000000000000000000:0</a></dd>.*"
-      ),
+  #[test]
+  fn range_end_before_range_start_returns_400() {
+    TestServer::new().assert_response(
+      "/range/1/0",
+      StatusCode::BAD_REQUEST,
+      "range start greater than range end",
     );
+  }
 
-    server.assert_response_regex(
-      "/output/0000000000000000000000000000000000000000000000000000000000000000:0",
-      StatusCode::OK,
-      ".*<h1>Output <span class=monospace>0000000000000000000000000000000000000000000000000000000000000000:0</span></h1>
-<dl>
-  <dt>inscriptions</dt>
-  <dd class=thumbnails>
-    <a href=/inscription/.*><iframe sandbox=allow-scripts scrolling=no loading=lazy src=/preview/.*></iframe></a>
-  </dd>.*",
-    );
+  #[test]
+  fn invalid_range_start_returns_400() {
+    TestServer::new().assert_response("/range/=/0", StatusCode::BAD_REQUEST, "invalid sat");
   }
 
   #[test]
-  fn unbound_output_returns_200() {
-    TestServer::new().assert_response_regex(
-      "/output/0000000000000000000000000000000000000000000000000000000000000000:0",
-      StatusCode::OK,
-      ".*",
-    );
+  fn invalid_range_end_returns_400() {
+    TestServer::new().assert_response("/range/0/=", StatusCode::BAD_REQUEST, "invalid sat");
   }
 
   #[test]
-  fn invalid_output_returns_400() {
-    TestServer::new().assert_response(
-      "/output/foo:0",
-      StatusCode::BAD_REQUEST,
-      "Invalid URL: error parsing TXID",
-    );
+  fn empty_range_returns_400() {
+    TestServer::new().assert_response("/range/0/0", StatusCode::BAD_REQUEST, "empty range");
   }
 
   #[test]
-  fn home() {
-    let server = TestServer::new_with_regtest();
+  fn api_key_gates_json_api() {
+    let server = TestServer::new_with_args(&[], &["--enable-json-api", "--api-key", "foo"]);
 
-    server.mine_blocks(1);
+    let client = reqwest::blocking::Client::new();
 
-    let mut ids = Vec::new();
+    let get = |authorization: Option<&str>| {
+      let mut request = client.get(server.join_url("/api/v1/node/info"));
+      if let Some(authorization) = authorization {
+        request = request.header(header::AUTHORIZATION, authorization);
+      }
+      request.send().unwrap()
+    };
 
-    for i in 0..101 {
-      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(i + 1, 0, 0, inscription("image/png", "hello").to_witness())],
-        ..Default::default()
-      });
-      ids.push(InscriptionId { txid, index: 0 });
-      server.mine_blocks(1);
+    for authorization in [None, Some("Bearer bar"), Some("Bearer "), Some("foo")] {
+      let response = get(authorization);
+      assert_eq!(
+        response.status(),
+        StatusCode::UNAUTHORIZED,
+        "{authorization:?}"
+      );
+      assert_eq!(
+        response.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+        "Bearer"
+      );
+      let json = response.json::<serde_json::Value>().unwrap();
+      assert_eq!(json["code"], 5);
+      assert_eq!(json["msg"], "missing or invalid API key");
     }
 
-    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "{}").to_witness())],
-      ..Default::default()
-    });
+    assert_eq!(get(Some("Bearer foo")).status(), StatusCode::OK);
 
-    server.mine_blocks(1);
+    assert_eq!(server.get("/blockcount").status(), StatusCode::OK);
+    assert_eq!(server.get("/r/blockheight").status(), StatusCode::OK);
+  }
 
-    server.assert_response_regex(
-      "/",
-      StatusCode::OK,
-      format!(
-        r".*<title>Ordinals</title>.*
-<h1>Latest Inscriptions</h1>
-<div class=thumbnails>
-  <a href=/inscription/{}>.*</a>
-  (<a href=/inscription/[[:xdigit:]]{{64}}i0>.*</a>\s*){{99}}
-</div>
-.*
-",
-        ids[100]
-      ),
-    );
+  #[test]
+  fn json_api_is_open_without_api_key() {
+    let server = TestServer::new_with_args(&[], &["--enable-json-api"]);
+
+    assert_eq!(server.get("/api/v1/node/info").status(), StatusCode::OK);
   }
 
   #[test]
-  fn blocks() {
+  fn errors_are_json_when_request_prefers_json() {
     let test_server = TestServer::new();
 
-    test_server.mine_blocks(1);
+    let client = reqwest::blocking::Client::new();
 
-    test_server.assert_response_regex(
-      "/blocks",
-      StatusCode::OK,
-      ".*<title>Blocks</title>.*
-<h1>Blocks</h1>
-<div class=block>
-  <h2><a href=/block/1>Block 1</a></h2>
-  <div class=thumbnails>
-  </div>
-</div>
-<div class=block>
-  <h2><a href=/block/0>Block 0</a></h2>
-  <div class=thumbnails>
-  </div>
-</div>
-</ol>.*",
+    let response = client
+      .get(test_server.join_url("/blockhash/999"))
+      .header(header::ACCEPT, "application/json")
+      .header(request_id::X_REQUEST_ID, "foo")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "application/json"
+    );
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "no-store"
+    );
+    assert_eq!(
+      response.text().unwrap(),
+      r#"{"code":3,"msg":"blockhash not found","request_id":"foo"}"#
     );
-  }
 
-  #[test]
-  fn nav_displays_chain() {
-    TestServer::new_with_regtest().assert_response_regex(
-      "/",
-      StatusCode::OK,
-      ".*<a href=/ title=home>Ordinals<sup>regtest</sup></a>.*",
+    let response = client
+      .get(test_server.join_url("/range/1/1"))
+      .header(header::ACCEPT, "text/html;q=0.5, application/json")
+      .header(request_id::X_REQUEST_ID, "bar")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      r#"{"code":2,"msg":"empty range","request_id":"bar"}"#
     );
   }
 
   #[test]
-  fn blocks_block_limit() {
-    let test_server = TestServer::new();
+  fn api_errors_are_problem_details_with_error_format_problem() {
+    let error = |server_args: &[&str]| {
+      let response = reqwest::blocking::Client::new()
+        .get(TestServer::new_with_args(&[], server_args).join_url("/api/v1/ord/id/foo/inscription"))
+        .header(request_id::X_REQUEST_ID, "foo")
+        .send()
+        .unwrap();
 
-    test_server.mine_blocks(101);
+      assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
-    test_server.assert_response_regex(
-      "/blocks",
-      StatusCode::OK,
-      ".*<ol start=96 reversed class=block-list>\n(  <li><a href=/block/[[:xdigit:]]{64}>[[:xdigit:]]{64}</a></li>\n){95}</ol>.*"
-    );
-  }
+      (
+        response.headers()[header::CONTENT_TYPE].clone(),
+        response.json::<serde_json::Value>().unwrap(),
+      )
+    };
 
-  #[test]
-  fn block_not_found() {
-    TestServer::new().assert_response(
-      "/block/467a86f0642b1d284376d13a98ef58310caa49502b0f9a560ee222e0a122fe16",
-      StatusCode::NOT_FOUND,
-      "block 467a86f0642b1d284376d13a98ef58310caa49502b0f9a560ee222e0a122fe16 not found",
-    );
-  }
+    let (content_type, code) = error(&[]);
 
-  #[test]
-  fn unmined_sat() {
-    TestServer::new().assert_response_regex(
-      "/sat/0",
-      StatusCode::OK,
-      ".*<dt>timestamp</dt><dd><time>2009-01-03 18:15:05 UTC</time></dd>.*",
+    assert_eq!(content_type, "application/json");
+    assert_eq!(code["code"], 2);
+    assert_eq!(code["request_id"], "foo");
+
+    let (content_type, problem) = error(&["--error-format", "problem"]);
+
+    assert_eq!(content_type, "application/problem+json");
+    assert_eq!(
+      problem,
+      serde_json::json!({
+        "type": "about:blank",
+        "title": "Bad Request",
+        "status": 400,
+        "detail": code["msg"],
+        "request_id": "foo",
+      })
     );
-  }
 
-  #[test]
-  fn mined_sat() {
-    TestServer::new().assert_response_regex(
-      "/sat/5000000000",
-      StatusCode::OK,
-      ".*<dt>timestamp</dt><dd><time>.*</time> \\(expected\\)</dd>.*",
+    let response = reqwest::blocking::Client::new()
+      .get(
+        TestServer::new_with_args(&[], &["--error-format", "problem"]).join_url("/blockhash/999"),
+      )
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+      response.headers()[header::CONTENT_TYPE],
+      "application/problem+json"
+    );
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["detail"],
+      "blockhash not found"
     );
   }
 
   #[test]
-  fn static_asset() {
-    TestServer::new().assert_response_regex(
-      "/static/index.css",
-      StatusCode::OK,
-      r".*\.rare \{
-  background-color: var\(--rare\);
-}.*",
+  fn unknown_inscriptions_are_problem_details_with_error_format_problem() {
+    let server = TestServer::new_with_args(&[], &["--error-format", "problem"]);
+
+    let response = server.get(format!("/api/v1/ord/id/{}/inscription", inscription_id(1)));
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+      response.headers()[header::CONTENT_TYPE],
+      "application/problem+json"
     );
-  }
 
-  #[test]
-  fn favicon() {
-    TestServer::new().assert_response_regex("/favicon.ico", StatusCode::OK, r".*");
-  }
+    let problem = response.json::<serde_json::Value>().unwrap();
 
-  #[test]
-  fn clock_updates() {
-    let test_server = TestServer::new();
-    test_server.assert_response_regex("/clock", StatusCode::OK, ".*<text.*>0</text>.*");
-    test_server.mine_blocks(1);
-    test_server.assert_response_regex("/clock", StatusCode::OK, ".*<text.*>1</text>.*");
+    assert_eq!(problem["title"], "Not Found");
+    assert_eq!(problem["status"], 404);
   }
 
   #[test]
-  fn block_by_hash() {
+  fn api_errors_include_request_id() {
     let test_server = TestServer::new();
 
-    test_server.mine_blocks(1);
-    let transaction = TransactionTemplate {
-      inputs: &[(1, 0, 0, Default::default())],
-      fee: 0,
-      ..Default::default()
-    };
-    test_server.bitcoin_rpc_server.broadcast_tx(transaction);
-    let block_hash = test_server.mine_blocks(1)[0].block_hash();
+    let response = reqwest::blocking::Client::new()
+      .get(test_server.join_url("/api/v1/ord/id/foo/inscription"))
+      .header(request_id::X_REQUEST_ID, "foo-bar")
+      .send()
+      .unwrap();
 
-    test_server.assert_response_regex(
-      format!("/block/{block_hash}"),
-      StatusCode::OK,
-      ".*<h1>Block 2</h1>.*",
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.headers().get(request_id::X_REQUEST_ID).unwrap(),
+      "foo-bar"
+    );
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["request_id"],
+      "foo-bar"
     );
-  }
 
-  #[test]
-  fn block_by_height() {
-    let test_server = TestServer::new();
+    let response = test_server.get("/blockhash/999");
 
-    test_server.assert_response_regex("/block/0", StatusCode::OK, ".*<h1>Block 0</h1>.*");
+    assert_eq!(
+      response
+        .headers()
+        .get(request_id::X_REQUEST_ID)
+        .unwrap()
+        .len(),
+      36
+    );
   }
 
   #[test]
-  fn transaction() {
+  fn brc20_tick_list_page_envelope() {
     let test_server = TestServer::new();
 
-    let coinbase_tx = test_server.mine_blocks(1)[0].txdata[0].clone();
-    let txid = coinbase_tx.txid();
+    assert_eq!(
+      test_server.get_json::<serde_json::Value>("/api/v1/brc20/tick"),
+      serde_json::json!({"code": 0, "msg": "ok", "data": {"tokens": []}})
+    );
 
-    test_server.assert_response_regex(
-      format!("/tx/{txid}"),
-      StatusCode::OK,
-      format!(
-        ".*<title>Transaction {txid}</title>.*<h1>Transaction <span class=monospace>{txid}</span></h1>
-<dl>
-</dl>
-<h2>1 Input</h2>
-<ul>
-  <li><a class=monospace href=/output/0000000000000000000000000000000000000000000000000000000000000000:4294967295>0000000000000000000000000000000000000000000000000000000000000000:4294967295</a></li>
-</ul>
-<h2>1 Output</h2>
-<ul class=monospace>
-  <li>
-    <a href=/output/84aca0d43f45ac753d4744f40b2f54edec3a496b298951735d450e601386089d:0 class=monospace>
-      84aca0d43f45ac753d4744f40b2f54edec3a496b298951735d450e601386089d:0
-    </a>
-    <dl>
-      <dt>value</dt><dd>5000000000</dd>
-      <dt>script pubkey</dt><dd class=monospace></dd>
-    </dl>
-  </li>
-</ul>.*"
-      ),
+    assert_eq!(
+      test_server.get_json::<serde_json::Value>("/api/v1/brc20/tick?v=2&page=1&limit=10"),
+      serde_json::json!({
+        "code": 0,
+        "msg": "ok",
+        "data": {"data": [], "page_index": 1, "limit": 10, "more": false}
+      })
+    );
+
+    assert_eq!(
+      test_server.get("/api/v1/brc20/tick?v=3").status(),
+      StatusCode::BAD_REQUEST
     );
   }
 
   #[test]
-  fn detect_unrecoverable_reorg() {
-    let test_server = TestServer::new();
+  fn brc20_tick_info_reports_mint_progress() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-brc20"],
+      &["--enable-json-api"],
+    );
 
-    test_server.mine_blocks(21);
+    server.mine_blocks(3);
 
-    test_server.assert_response_regex(
-      "/status",
-      StatusCode::OK,
-      ".*<dt>unrecoverably reorged</dt>\n  <dd>false</dd>.*",
-    );
+    let brc20 = |content: &str| inscription("text/plain;charset=utf-8", content).to_witness();
 
-    for _ in 0..15 {
-      test_server.bitcoin_rpc_server.invalidate_tip();
+    let deploy = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        brc20(r#"{"p":"brc-20","op":"deploy","tick":"OrDi","max":"1000","lim":"100"}"#),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    for i in 2..=3 {
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          i,
+          0,
+          0,
+          brc20(r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"100"}"#),
+        )],
+        ..Default::default()
+      });
+
+      server.mine_blocks(1);
     }
 
-    test_server.bitcoin_rpc_server.mine_blocks(21);
+    let tick = server.get_json::<serde_json::Value>("/api/v1/brc20/tick/ORDI")["data"].clone();
 
-    test_server.assert_response_regex(
-      "/status",
-      StatusCode::OK,
-      ".*<dt>unrecoverably reorged</dt>\n  <dd>true</dd>.*",
+    assert_eq!(tick["tick"], "OrDi");
+    assert_eq!(tick["inscriptionId"], format!("{deploy}i0"));
+    assert_eq!(tick["supply"], "1000000000000000000000");
+    assert_eq!(tick["limitPerMint"], "100000000000000000000");
+    assert_eq!(tick["minted"], "200000000000000000000");
+    assert_eq!(tick["remainingMintable"], "800000000000000000000");
+    assert_eq!(tick["mintedPercent"], 20.0);
+    assert_eq!(tick["decimal"], 18);
+
+    assert_eq!(
+      server.get("/api/v1/brc20/tick/sats").status(),
+      StatusCode::NOT_FOUND
     );
   }
 
   #[test]
-  fn rare_with_sat_index() {
-    TestServer::new_with_sat_index().assert_response(
-      "/rare.txt",
-      StatusCode::OK,
-      "sat\tsatpoint
-0\t4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0
-",
+  fn brc20_tick_events() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-brc20"],
+      &["--enable-json-api"],
     );
-  }
 
-  #[test]
-  fn rare_without_sat_index() {
-    TestServer::new().assert_response(
-      "/rare.txt",
-      StatusCode::OK,
-      "sat\tsatpoint
-",
+    server.mine_blocks(3);
+
+    let brc20 = |content: &str| inscription("text/plain;charset=utf-8", content).to_witness();
+
+    for (height, content) in [
+      (
+        1,
+        r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"100"}"#,
+      ),
+      (2, r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"100"}"#),
+      (
+        3,
+        r#"{"p":"brc-20","op":"transfer","tick":"ordi","amt":"40"}"#,
+      ),
+    ] {
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(height, 0, 0, brc20(content))],
+        ..Default::default()
+      });
+
+      server.mine_blocks(1);
+    }
+
+    let transfer = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(6, 1, 0, Witness::new())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let events = server
+      .get_json::<serde_json::Value>("/api/v1/brc20/tick/ORDI/events?from_height=0&to_height=100")
+      ["data"]
+      .clone();
+
+    assert_eq!(events["more"], false);
+
+    let events = events["events"].as_array().unwrap();
+
+    assert_eq!(
+      events
+        .iter()
+        .map(|event| (
+          event["type"].as_str().unwrap(),
+          event["height"].as_u64().unwrap(),
+          event["amount"].as_str().unwrap_or_default(),
+        ))
+        .collect::<Vec<(&str, u64, &str)>>(),
+      [
+        ("deploy", 4, ""),
+        ("mint", 5, "100000000000000000000"),
+        ("inscribeTransfer", 6, "40000000000000000000"),
+        ("transfer", 7, "40000000000000000000"),
+      ]
     );
-  }
 
-  #[test]
-  fn show_rare_txt_in_header_with_sat_index() {
-    TestServer::new_with_sat_index().assert_response_regex(
-      "/",
-      StatusCode::OK,
-      ".*
-      <a href=/clock title=clock>.*</a>
-      <a href=/rare.txt title=rare>.*</a>.*",
+    assert_eq!(events[3]["txid"], transfer.to_string());
+    assert_eq!(events[3]["inscriptionId"], events[2]["inscriptionId"]);
+
+    assert_eq!(
+      server
+        .get_json::<serde_json::Value>("/api/v1/brc20/tick/ordi/events?from_height=5&to_height=6")
+        ["data"]["events"]
+        .as_array()
+        .unwrap()
+        .len(),
+      1
     );
-  }
 
-  #[test]
-  fn rare_sat_location() {
-    TestServer::new_with_sat_index().assert_response_regex(
-      "/sat/0",
-      StatusCode::OK,
-      ".*>4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0<.*",
+    for (query, status) in [
+      ("", StatusCode::BAD_REQUEST),
+      ("?from_height=6&to_height=5", StatusCode::BAD_REQUEST),
+      ("?from_height=0&to_height=1001", StatusCode::BAD_REQUEST),
+    ] {
+      assert_eq!(
+        server
+          .get(format!("/api/v1/brc20/tick/ordi/events{query}"))
+          .status(),
+        status,
+        "{query}"
+      );
+    }
+
+    assert_eq!(
+      server
+        .get("/api/v1/brc20/tick/sats/events?from_height=0&to_height=1")
+        .status(),
+      StatusCode::NOT_FOUND
     );
   }
 
   #[test]
-  fn dont_show_rare_txt_in_header_without_sat_index() {
-    TestServer::new().assert_response_regex(
-      "/",
-      StatusCode::OK,
-      ".*
-      <a href=/clock title=clock>.*</a>
-      <a href=https://docs.ordinals.com/.*",
-    );
-  }
-
-  #[test]
-  fn input() {
-    TestServer::new().assert_response_regex(
-      "/input/0/0/0",
-      StatusCode::OK,
-      ".*<title>Input /0/0/0</title>.*<h1>Input /0/0/0</h1>.*<dt>text</dt><dd>.*The Times 03/Jan/2009 Chancellor on brink of second bailout for banks</dd>.*",
+  fn brc20_balance_at_height() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-brc20"],
+      &["--enable-json-api"],
     );
-  }
-
-  #[test]
-  fn input_missing() {
-    TestServer::new().assert_response(
-      "/input/1/1/1",
-      StatusCode::NOT_FOUND,
-      "input /1/1/1 not found",
-    );
-  }
-
-  #[test]
-  fn commits_are_tracked() {
-    let server = TestServer::new();
 
-    thread::sleep(Duration::from_millis(100));
-    assert_eq!(server.index.statistic(crate::index::Statistic::Commits), 1);
+    server.mine_blocks(3);
 
-    let info = server.index.info().unwrap();
-    assert_eq!(info.transactions.len(), 1);
-    assert_eq!(info.transactions[0].starting_block_count, 0);
+    let brc20 = |content: &str| inscription("text/plain;charset=utf-8", content).to_witness();
 
-    server.index.update().unwrap();
+    for (height, content) in [
+      (
+        1,
+        r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"100"}"#,
+      ),
+      (2, r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"100"}"#),
+      (
+        3,
+        r#"{"p":"brc-20","op":"transfer","tick":"ordi","amt":"40"}"#,
+      ),
+    ] {
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(height, 0, 0, brc20(content))],
+        ..Default::default()
+      });
 
-    assert_eq!(server.index.statistic(crate::index::Statistic::Commits), 1);
+      server.mine_blocks(1);
+    }
 
-    let info = server.index.info().unwrap();
-    assert_eq!(info.transactions.len(), 1);
-    assert_eq!(info.transactions[0].starting_block_count, 0);
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(6, 1, 0, Witness::new())],
+      ..Default::default()
+    });
 
     server.mine_blocks(1);
 
-    thread::sleep(Duration::from_millis(10));
-    server.index.update().unwrap();
+    let address = ScriptBuf::new().script_hash();
 
-    assert_eq!(server.index.statistic(crate::index::Statistic::Commits), 2);
+    let balance = |query: &str| {
+      let balance = server
+        .get_json::<serde_json::Value>(format!("/api/v1/brc20/tick/ordi/balance/{address}{query}"))
+        ["data"]
+        .clone();
 
-    let info = server.index.info().unwrap();
-    assert_eq!(info.transactions.len(), 2);
-    assert_eq!(info.transactions[0].starting_block_count, 0);
-    assert_eq!(info.transactions[1].starting_block_count, 1);
-    assert!(
-      info.transactions[1].starting_timestamp - info.transactions[0].starting_timestamp >= 10
-    );
-  }
+      (
+        balance["overallBalance"].as_str().unwrap().to_owned(),
+        balance["transferableBalance"].as_str().unwrap().to_owned(),
+      )
+    };
 
-  #[test]
-  fn outputs_traversed_are_tracked() {
-    let server = TestServer::new_with_sat_index();
+    let current = balance("");
 
+    assert_eq!(current, ("100000000000000000000".into(), "0".into()));
+    assert_eq!(balance("?height=7"), current);
     assert_eq!(
-      server
-        .index
-        .statistic(crate::index::Statistic::OutputsTraversed),
-      1
+      balance("?height=6"),
+      (
+        "100000000000000000000".into(),
+        "40000000000000000000".into()
+      )
     );
-
-    server.index.update().unwrap();
+    assert_eq!(balance("?height=4"), ("0".into(), "0".into()));
 
     assert_eq!(
       server
-        .index
-        .statistic(crate::index::Statistic::OutputsTraversed),
-      1
+        .get(format!(
+          "/api/v1/brc20/tick/ordi/balance/{address}?height=8"
+        ))
+        .status(),
+      StatusCode::BAD_REQUEST
     );
 
-    server.mine_blocks(2);
-
-    server.index.update().unwrap();
-
     assert_eq!(
       server
-        .index
-        .statistic(crate::index::Statistic::OutputsTraversed),
-      3
+        .get(format!(
+          "/api/v1/brc20/tick/sats/balance/{address}?height=4"
+        ))
+        .status(),
+      StatusCode::NOT_FOUND
     );
   }
 
   #[test]
-  fn coinbase_sat_ranges_are_tracked() {
-    let server = TestServer::new_with_sat_index();
-
-    assert_eq!(
-      server.index.statistic(crate::index::Statistic::SatRanges),
-      1
+  fn brc20_transferable_inscriptions() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-brc20"],
+      &["--enable-json-api"],
     );
 
-    server.mine_blocks(1);
+    server.mine_blocks(3);
 
-    assert_eq!(
-      server.index.statistic(crate::index::Statistic::SatRanges),
-      2
-    );
+    let brc20 = |content: &str| inscription("text/plain;charset=utf-8", content).to_witness();
+
+    let address = ScriptBuf::new().script_hash();
+
+    let transferable = || {
+      server.get_json::<serde_json::Value>(format!(
+        "/api/v1/brc20/tick/ordi/address/{address}/transferable"
+      ))["data"]["inscriptions"]
+        .clone()
+    };
+
+    for (height, content) in [
+      (
+        1,
+        r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"100"}"#,
+      ),
+      (2, r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"100"}"#),
+    ] {
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(height, 0, 0, brc20(content))],
+        ..Default::default()
+      });
+
+      server.mine_blocks(1);
+    }
+
+    assert_eq!(transferable(), serde_json::json!([]));
+
+    let inscribe_transfer = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        3,
+        0,
+        0,
+        brc20(r#"{"p":"brc-20","op":"transfer","tick":"ordi","amt":"40"}"#),
+      )],
+      ..Default::default()
+    });
 
     server.mine_blocks(1);
 
-    assert_eq!(
-      server.index.statistic(crate::index::Statistic::SatRanges),
-      3
-    );
-  }
+    let inscription_id = InscriptionId {
+      txid: inscribe_transfer,
+      index: 0,
+    };
 
-  #[test]
-  fn split_sat_ranges_are_tracked() {
-    let server = TestServer::new_with_sat_index();
+    let inscriptions = transferable();
 
+    assert_eq!(inscriptions.as_array().unwrap().len(), 1);
+    assert_eq!(inscriptions[0]["inscriptionId"], inscription_id.to_string());
+    assert_eq!(inscriptions[0]["amount"], "40000000000000000000");
+    assert_eq!(inscriptions[0]["tick"], "ordi");
     assert_eq!(
-      server.index.statistic(crate::index::Statistic::SatRanges),
-      1
+      inscriptions[0]["location"],
+      SatPoint {
+        outpoint: OutPoint {
+          txid: inscribe_transfer,
+          vout: 0,
+        },
+        offset: 0,
+      }
+      .to_string()
     );
+    assert_eq!(inscriptions[0]["spendable"], true);
 
-    server.mine_blocks(1);
     server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, Default::default())],
-      outputs: 2,
-      fee: 0,
+      inputs: &[(6, 1, 0, Witness::new())],
       ..Default::default()
     });
+
+    assert_eq!(transferable()[0]["spendable"], false);
+
     server.mine_blocks(1);
 
-    assert_eq!(
-      server.index.statistic(crate::index::Statistic::SatRanges),
-      4,
-    );
+    assert_eq!(transferable(), serde_json::json!([]));
   }
 
   #[test]
-  fn fee_sat_ranges_are_tracked() {
-    let server = TestServer::new_with_sat_index();
+  fn brc20_transferable_inscriptions_of_unknown_ticker() {
+    let test_server = TestServer::new();
 
-    assert_eq!(
-      server.index.statistic(crate::index::Statistic::SatRanges),
-      1
+    let response = test_server.get(
+      "/api/v1/brc20/tick/ordi/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4/transferable",
+    );
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = test_server.get(
+      "/api/v1/brc20/tick/foo/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4/transferable",
     );
 
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+  }
+
+  #[test]
+  fn inscriptions_height_range_filter() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
     server.mine_blocks(1);
-    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, Default::default())],
-      outputs: 2,
-      fee: 2,
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..Default::default()
     });
+
     server.mine_blocks(1);
 
+    let inscription_id = InscriptionId { txid, index: 0 };
+
     assert_eq!(
-      server.index.statistic(crate::index::Statistic::SatRanges),
-      5,
+      server.get_json::<InscriptionsJson>("/inscriptions?from_height=2&to_height=3"),
+      InscriptionsJson {
+        inscriptions: vec![inscription_id],
+        page_index: 0,
+        limit: 100,
+        more: false,
+        next_cursor: None,
+        truncated: false,
+        items: vec![InscriptionItemJson {
+          id: inscription_id,
+          content_type: Some("text/foo".into()),
+          content_length: Some(5),
+        }],
+        last_sequence: None,
+      }
     );
-  }
 
-  #[test]
-  fn content_response_no_content() {
     assert_eq!(
-      Server::content_response(
-        Inscription::new(Some("text/plain".as_bytes().to_vec()), None),
-        AcceptEncoding::default(),
-        &ServerConfig::default(),
-      )
-      .unwrap(),
-      None
+      server.get_json::<InscriptionsJson>("/inscriptions?from_height=0&to_height=2"),
+      InscriptionsJson {
+        inscriptions: Vec::new(),
+        page_index: 0,
+        limit: 100,
+        more: false,
+        next_cursor: None,
+        truncated: false,
+        items: Vec::new(),
+        last_sequence: None,
+      }
     );
-  }
-
-  #[test]
-  fn content_response_with_content() {
-    let (headers, body) = Server::content_response(
-      Inscription::new(Some("text/plain".as_bytes().to_vec()), Some(vec![1, 2, 3])),
-      AcceptEncoding::default(),
-      &ServerConfig::default(),
-    )
-    .unwrap()
-    .unwrap();
 
-    assert_eq!(headers["content-type"], "text/plain");
-    assert_eq!(body, vec![1, 2, 3]);
-  }
+    server.assert_response(
+      "/inscriptions?from_height=3&to_height=3",
+      StatusCode::BAD_REQUEST,
+      "from_height must be less than to_height",
+    );
 
-  #[test]
-  fn content_security_policy_no_origin() {
-    let (headers, _) = Server::content_response(
-      Inscription::new(Some("text/plain".as_bytes().to_vec()), Some(vec![1, 2, 3])),
-      AcceptEncoding::default(),
-      &ServerConfig::default(),
-    )
-    .unwrap()
-    .unwrap();
+    server.assert_response(
+      "/inscriptions?from_height=0&to_height=100001",
+      StatusCode::BAD_REQUEST,
+      "height range may span at most 100000 blocks",
+    );
 
-    assert_eq!(
-      headers["content-security-policy"],
-      HeaderValue::from_static("default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:")
+    server.assert_response(
+      "/inscriptions/1?from_height=0",
+      StatusCode::BAD_REQUEST,
+      "from_height and to_height must be given together",
     );
   }
 
   #[test]
-  fn content_security_policy_with_origin() {
-    let (headers, _) = Server::content_response(
-      Inscription::new(Some("text/plain".as_bytes().to_vec()), Some(vec![1, 2, 3])),
-      AcceptEncoding::default(),
-      &ServerConfig {
-        csp_origin: Some("https://ordinals.com".into()),
-        ..Default::default()
-      },
-    )
-    .unwrap()
-    .unwrap();
-
-    assert_eq!(headers["content-security-policy"], HeaderValue::from_static("default-src https://ordinals.com/content/ https://ordinals.com/blockheight https://ordinals.com/blockhash https://ordinals.com/blockhash/ https://ordinals.com/blocktime https://ordinals.com/r/ 'unsafe-eval' 'unsafe-inline' data: blob:"));
-  }
+  fn inscriptions_batch_reports_missing_ids() {
+    let server = TestServer::new_with_regtest_with_json_api();
 
-  #[test]
-  fn code_preview() {
-    let server = TestServer::new_with_regtest();
     server.mine_blocks(1);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        inscription("text/javascript", "hello").to_witness(),
-      )],
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      format!(r".*<html lang=en data-inscription={inscription_id} data-language=javascript>.*"),
+    let inscription_id = InscriptionId { txid, index: 0 };
+    let missing = InscriptionId { txid, index: 1 };
+
+    let client = reqwest::blocking::Client::new();
+
+    let response = client
+      .post(server.join_url("/api/v1/inscriptions/batch"))
+      .json(&serde_json::json!({
+        "ids": [inscription_id.to_string(), "foo", missing.to_string()]
+      }))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json = response.json::<serde_json::Value>().unwrap();
+
+    assert_eq!(
+      json["data"]["inscriptions"][inscription_id.to_string()]["id"],
+      inscription_id.to_string()
+    );
+    assert_eq!(json["data"]["inscriptions"].as_object().unwrap().len(), 1);
+    assert_eq!(
+      json["data"]["not_found"],
+      serde_json::json!(["foo", missing.to_string()])
     );
+
+    let response = client
+      .post(server.join_url("/api/v1/inscriptions/batch"))
+      .json(&serde_json::json!({
+        "ids": vec![inscription_id.to_string(); 101]
+      }))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
   }
 
   #[test]
-  fn content_response_no_content_type() {
-    let (headers, body) = Server::content_response(
-      Inscription::new(None, Some(Vec::new())),
-      AcceptEncoding::default(),
-      &ServerConfig::default(),
-    )
-    .unwrap()
-    .unwrap();
+  fn oversized_request_bodies_are_rejected_with_json() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest"],
+      &["--enable-json-api", "--max-request-body-bytes", "64"],
+    );
 
-    assert_eq!(headers["content-type"], "application/octet-stream");
-    assert!(body.is_empty());
-  }
+    let client = reqwest::blocking::Client::new();
 
-  #[test]
-  fn content_response_bad_content_type() {
-    let (headers, body) = Server::content_response(
-      Inscription::new(Some("\n".as_bytes().to_vec()), Some(Vec::new())),
-      AcceptEncoding::default(),
-      &ServerConfig::default(),
-    )
-    .unwrap()
-    .unwrap();
+    for path in ["/api/v1/inscriptions/batch", "/rpc"] {
+      let response = client
+        .post(server.join_url(path))
+        .json(&serde_json::json!({ "ids": vec![inscription_id(1).to_string(); 2] }))
+        .send()
+        .unwrap();
 
-    assert_eq!(headers["content-type"], "application/octet-stream");
-    assert!(body.is_empty());
-  }
+      assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE, "{path}");
+      assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/json",
+        "{path}"
+      );
 
-  #[test]
-  fn text_preview() {
-    let server = TestServer::new_with_regtest();
-    server.mine_blocks(1);
+      let json = response.json::<serde_json::Value>().unwrap();
+      assert_eq!(json["code"], 7, "{path}");
+      assert_eq!(
+        json["msg"], "request body exceeds limit of 64 bytes",
+        "{path}"
+      );
+    }
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        inscription("text/plain;charset=utf-8", "hello").to_witness(),
-      )],
-      ..Default::default()
-    });
+    let response = client
+      .post(server.join_url("/api/v1/inscriptions/batch"))
+      .json(&serde_json::json!({ "ids": [] }))
+      .send()
+      .unwrap();
 
-    let inscription_id = InscriptionId { txid, index: 0 };
+    assert_eq!(response.status(), StatusCode::OK);
+  }
 
-    server.mine_blocks(1);
+  fn rpc(server: &TestServer, request: serde_json::Value) -> serde_json::Value {
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/rpc"))
+      .json(&request)
+      .send()
+      .unwrap();
 
-    server.assert_response_csp(
-      format!("/preview/{}", inscription_id),
-      StatusCode::OK,
-      "default-src 'self'",
-      format!(".*<html lang=en data-inscription={}>.*", inscription_id),
-    );
+    assert_eq!(response.status(), StatusCode::OK);
+
+    response.json().unwrap()
   }
 
   #[test]
-  fn audio_preview() {
-    let server = TestServer::new_with_regtest();
+  fn rpc_call_returns_rest_response_data() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
     server.mine_blocks(1);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("audio/flac", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      format!(r".*<audio .*>\s*<source src=/content/{inscription_id}>.*"),
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    pretty_assert_eq!(
+      rpc(
+        &server,
+        serde_json::json!({
+          "jsonrpc": "2.0",
+          "method": "ord_getInscription",
+          "params": [inscription_id.to_string()],
+          "id": 1,
+        })
+      ),
+      serde_json::json!({
+        "jsonrpc": "2.0",
+        "result": server.get_json::<serde_json::Value>(format!(
+          "/api/v1/inscription/{inscription_id}"
+        ))["data"],
+        "id": 1,
+      })
     );
   }
 
   #[test]
-  fn font_preview() {
-    let server = TestServer::new_with_regtest();
+  fn rpc_batch_answers_each_call() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
     server.mine_blocks(1);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("font/ttf", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      format!(r".*src: url\(/content/{inscription_id}\).*"),
+    let outpoint = OutPoint { txid, vout: 0 };
+
+    let responses = rpc(
+      &server,
+      serde_json::json!([
+        {"jsonrpc": "2.0", "method": "ord_getInscription", "params": {"id": "0"}, "id": "a"},
+        {"jsonrpc": "2.0", "method": "ord_getOutpoint", "params": [outpoint.to_string()], "id": "b"},
+        {"jsonrpc": "2.0", "method": "ord_getInscription", "params": ["0"]},
+        {"jsonrpc": "2.0", "method": "ord_getOutpoint", "params": ["foo"], "id": "c"},
+        {"jsonrpc": "2.0", "method": "ord_getInscription", "params": ["1"], "id": "d"},
+        {"method": "ord_getInscription", "params": ["0"], "id": "e"},
+        {"method": "ord_getInscription", "params": ["0"]},
+        1,
+      ]),
     );
-  }
 
-  #[test]
-  fn pdf_preview() {
-    let server = TestServer::new_with_regtest();
-    server.mine_blocks(1);
+    let responses = responses.as_array().unwrap();
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        inscription("application/pdf", "hello").to_witness(),
-      )],
-      ..Default::default()
-    });
-    let inscription_id = InscriptionId { txid, index: 0 };
+    assert_eq!(responses.len(), 7);
 
-    server.mine_blocks(1);
+    assert_eq!(
+      responses[0]["result"]["id"],
+      InscriptionId { txid, index: 0 }.to_string()
+    );
+    assert_eq!(responses[0]["id"], "a");
 
-    server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      format!(r".*<canvas data-inscription={inscription_id}></canvas>.*"),
+    assert_eq!(
+      responses[1]["result"]["result"]["inscriptionDigest"][0]["location"],
+      format!("{outpoint}:0")
     );
-  }
+    assert_eq!(responses[1]["id"], "b");
 
-  #[test]
-  fn markdown_preview() {
-    let server = TestServer::new_with_regtest();
-    server.mine_blocks(1);
+    assert_eq!(
+      responses[2],
+      serde_json::json!({
+        "jsonrpc": "2.0",
+        "error": {"code": -32602, "message": "invalid outpoint"},
+        "id": "c",
+      })
+    );
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/markdown", "hello").to_witness())],
-      ..Default::default()
-    });
-    let inscription_id = InscriptionId { txid, index: 0 };
+    assert_eq!(responses[3]["error"]["code"], 3);
+    assert_eq!(responses[3]["id"], "d");
 
-    server.mine_blocks(1);
+    assert_eq!(responses[4]["error"]["code"], -32600);
+    assert_eq!(responses[4]["id"], "e");
 
-    server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      format!(r".*<html lang=en data-inscription={inscription_id}>.*"),
-    );
+    for response in &responses[5..] {
+      assert_eq!(response["error"]["code"], -32600);
+      assert_eq!(response["id"], serde_json::Value::Null);
+    }
   }
 
   #[test]
-  fn image_preview() {
-    let server = TestServer::new_with_regtest();
-    server.mine_blocks(1);
+  fn rpc_unknown_method_is_not_found() {
+    let server = TestServer::new_with_regtest_with_json_api();
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
-      ..Default::default()
-    });
-    let inscription_id = InscriptionId { txid, index: 0 };
+    pretty_assert_eq!(
+      rpc(
+        &server,
+        serde_json::json!({"jsonrpc": "2.0", "method": "ord_foo", "id": 7})
+      ),
+      serde_json::json!({
+        "jsonrpc": "2.0",
+        "error": {"code": -32601, "message": "method `ord_foo` not found"},
+        "id": 7,
+      })
+    );
 
-    server.mine_blocks(1);
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/rpc"))
+      .body("{")
+      .send()
+      .unwrap();
 
-    server.assert_response_csp(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      "default-src 'self' 'unsafe-inline'",
-      format!(r".*background-image: url\(/content/{inscription_id}\);.*"),
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["error"]["code"],
+      -32700
     );
   }
 
   #[test]
-  fn iframe_preview() {
-    let server = TestServer::new_with_regtest();
-    server.mine_blocks(1);
+  fn rpc_methods_of_disabled_route_groups_are_not_found() {
+    let server = TestServer::new_with_regtest_with_json_api();
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        inscription("text/html;charset=utf-8", "hello").to_witness(),
-      )],
-      ..Default::default()
+    let request = serde_json::json!({
+      "jsonrpc": "2.0",
+      "method": "brc20_getBalance",
+      "params": ["ordi", "foo"],
+      "id": 1,
     });
 
-    server.mine_blocks(1);
+    assert_ne!(rpc(&server, request.clone())["error"]["code"], -32601);
 
-    server.assert_response_csp(
-      format!("/preview/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
-      "hello",
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest"],
+      &["--enable-json-api", "--disable-routes", "brc20"],
+    );
+
+    pretty_assert_eq!(
+      rpc(&server, request),
+      serde_json::json!({
+        "jsonrpc": "2.0",
+        "error": {"code": -32601, "message": "method `brc20_getBalance` not found"},
+        "id": 1,
+      })
     );
   }
 
   #[test]
-  fn unknown_preview() {
-    let server = TestServer::new_with_regtest();
-    server.mine_blocks(1);
+  fn outputs_batch_reports_missing_outpoints() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    server.mine_blocks(2);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
       inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
@@ -3809,883 +6007,5808 @@ mod tests {
 
     server.mine_blocks(1);
 
-    server.assert_response_csp(
-      format!("/preview/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      "default-src 'self'",
-      fs::read_to_string("templates/preview-unknown.html").unwrap(),
-    );
-  }
+    let inscribed = OutPoint { txid, vout: 0 };
+    let coinbase = OutPoint {
+      txid: server.bitcoin_rpc_server.tx(2, 0).txid(),
+      vout: 0,
+    };
+    let missing_vout = OutPoint { txid, vout: 1 };
+    let missing_txid = OutPoint {
+      txid: crate::test::txid(9),
+      vout: 0,
+    };
 
-  #[test]
-  fn video_preview() {
-    let server = TestServer::new_with_regtest();
-    server.mine_blocks(1);
+    let client = reqwest::blocking::Client::new();
+
+    let response = client
+      .post(server.join_url("/api/v1/ord/outputs"))
+      .json(&serde_json::json!({
+        "outpoints": [
+          inscribed.to_string(),
+          "foo",
+          coinbase.to_string(),
+          missing_vout.to_string(),
+          missing_txid.to_string(),
+        ]
+      }))
+      .send()
+      .unwrap();
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("video/webm", "hello").to_witness())],
-      ..Default::default()
-    });
-    let inscription_id = InscriptionId { txid, index: 0 };
+    assert_eq!(response.status(), StatusCode::OK);
 
-    server.mine_blocks(1);
+    let json = response.json::<serde_json::Value>().unwrap();
+    let outputs = json["data"]["outputs"].as_object().unwrap();
 
-    server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      format!(r".*<video .*>\s*<source src=/content/{inscription_id}>.*"),
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(outputs[&inscribed.to_string()]["txid"], txid.to_string());
+    assert_eq!(
+      outputs[&inscribed.to_string()]["inscriptionDigest"],
+      serde_json::json!([{
+        "id": InscriptionId { txid, index: 0 }.to_string(),
+        "number": 0,
+        "location": format!("{inscribed}:0"),
+      }])
     );
+    assert_eq!(
+      outputs[&coinbase.to_string()]["inscriptionDigest"],
+      serde_json::json!([])
+    );
+    assert_eq!(
+      json["data"]["notFound"],
+      serde_json::json!(["foo", missing_vout.to_string(), missing_txid.to_string()])
+    );
+    assert_eq!(json["data"]["latestHeight"], 3);
+
+    let response = client
+      .post(server.join_url("/api/v1/ord/outputs"))
+      .json(&serde_json::json!({
+        "outpoints": vec![inscribed.to_string(); 101]
+      }))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
   }
 
   #[test]
-  fn inscription_page_title() {
-    let server = TestServer::new_with_regtest_with_index_sats();
-    server.mine_blocks(1);
-
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
-      ..Default::default()
-    });
+  fn inscription_metadata_decodes_cbor_on_request() {
+    let server = TestServer::new_with_regtest_with_json_api();
 
-    server.mine_blocks(1);
+    server.mine_blocks(3);
 
-    server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      ".*<title>Inscription 0</title>.*",
-    );
-  }
+    let mut cbor = Vec::new();
+    ciborium::into_writer(
+      &Value::Map(vec![
+        (Value::Text("foo".into()), Value::Integer(1.into())),
+        (
+          Value::Text("bar".into()),
+          Value::Array(vec![Value::Bool(true), Value::Bytes(vec![0xab])]),
+        ),
+        (Value::Integer(2.into()), Value::Null),
+      ]),
+      &mut cbor,
+    )
+    .unwrap();
 
-  #[test]
-  fn inscription_page_has_sat_when_sats_are_tracked() {
-    let server = TestServer::new_with_regtest_with_index_sats();
-    server.mine_blocks(1);
+    let inscribe = |block: usize, metadata: Vec<u8>| InscriptionId {
+      txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          block,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            metadata: Some(metadata),
+            ..Default::default()
+          }
+          .to_witness(),
+        )],
+        ..Default::default()
+      }),
+      index: 0,
+    };
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
-      ..Default::default()
-    });
+    let valid = inscribe(1, cbor.clone());
+    let invalid = inscribe(2, vec![0xff, 0x00]);
+    let deep = inscribe(3, [vec![0x81; 64], vec![0xf6]].concat());
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      r".*<dt>sat</dt>\s*<dd><a href=/sat/5000000000>5000000000</a></dd>\s*<dt>preview</dt>.*",
+    assert_eq!(
+      server.get_json::<serde_json::Value>(format!("/api/v1/inscription/{valid}/metadata"))["data"],
+      serde_json::json!({
+        "decoded": false,
+        "hex": hex::encode(&cbor),
+      })
+    );
+
+    assert_eq!(
+      server
+        .get_json::<serde_json::Value>(format!("/api/v1/inscription/{valid}/metadata?format=json"))
+        ["data"],
+      serde_json::json!({
+        "decoded": true,
+        "json": {
+          "foo": 1,
+          "bar": [true, "ab"],
+          "2": null,
+        },
+      })
+    );
+
+    assert_eq!(
+      server.get_json::<serde_json::Value>(format!(
+        "/api/v1/inscription/{invalid}/metadata?format=json"
+      ))["data"],
+      serde_json::json!({
+        "decoded": false,
+        "hex": "ff00",
+      })
+    );
+
+    assert_eq!(
+      server
+        .get(format!("/api/v1/inscription/{deep}/metadata?format=json"))
+        .status(),
+      StatusCode::BAD_REQUEST
+    );
+
+    assert_eq!(
+      server
+        .get(format!("/api/v1/inscription/{valid}/metadata?format=foo"))
+        .status(),
+      StatusCode::BAD_REQUEST
     );
   }
 
   #[test]
-  fn inscription_page_does_not_have_sat_when_sats_are_not_tracked() {
-    let server = TestServer::new_with_regtest();
+  fn ord_outpoint_data() {
+    let server = TestServer::new_with_regtest_with_index_runes();
+
     server.mine_blocks(1);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: 1000,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
       ..Default::default()
     });
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      r".*<dt>output value</dt>\s*<dd>5000000000</dd>\s*<dt>preview</dt>.*",
+    let outpoint = OutPoint { txid, vout: 0 };
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>(format!("/api/v1/ord/outpoint/{outpoint}"))["data"],
+      serde_json::json!({
+        "outpoint": outpoint.to_string(),
+        "value": 50 * COIN_VALUE,
+        "address": null,
+        "addressType": "nonstandard",
+        "scriptPubkey": "",
+        "spent": false,
+        "confirmations": 1,
+        "inscriptions": [{
+          "id": inscription_id.to_string(),
+          "number": 0,
+          "location": format!("{outpoint}:0"),
+        }],
+        "runes": [{
+          "rune": "AAAAAAAAAAAAA",
+          "id": "2:1",
+          "amount": "1000",
+          "rawAmount": "1000",
+          "divisibility": 0,
+          "symbol": null,
+        }],
+      })
     );
-  }
 
-  #[test]
-  fn strict_transport_security_header_is_set() {
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let data =
+      &server.get_json::<serde_json::Value>(format!("/api/v1/ord/outpoint/{outpoint}"))["data"];
+
+    assert_eq!(data["spent"], true);
+    assert_eq!(data["confirmations"], serde_json::Value::Null);
+    assert_eq!(data["inscriptions"], serde_json::json!([]));
+    assert_eq!(data["runes"], serde_json::json!([]));
+
     assert_eq!(
-      TestServer::new()
-        .get("/status")
-        .headers()
-        .get(header::STRICT_TRANSPORT_SECURITY)
-        .unwrap(),
-      "max-age=31536000; includeSubDomains; preload",
+      server
+        .get(format!("/api/v1/ord/outpoint/{}:0", crate::test::txid(9)))
+        .status(),
+      StatusCode::NOT_FOUND
     );
   }
 
   #[test]
-  fn feed() {
-    let server = TestServer::new_with_regtest_with_index_sats();
-    server.mine_blocks(1);
+  fn ord_outpoint_offset() {
+    let server = TestServer::new_with_regtest_with_json_api();
 
-    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+    server.mine_blocks(2);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, inscription("text/plain", "foo").to_witness()),
+        (2, 0, 0, inscription("text/plain", "bar").to_witness()),
+      ],
       ..Default::default()
     });
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      "/feed.xml",
-      StatusCode::OK,
-      ".*<title>Inscription 0</title>.*",
+    let outpoint = OutPoint { txid, vout: 0 };
+
+    for (offset, index, number) in [(0, 0, 0), (50 * COIN_VALUE, 1, -1)] {
+      pretty_assert_eq!(
+        server.get_json::<serde_json::Value>(format!(
+          "/api/v1/ord/outpoint/{outpoint}/offset/{offset}"
+        ))["data"],
+        serde_json::json!({
+          "satpoint": format!("{outpoint}:{offset}"),
+          "inscriptions": [{
+            "id": InscriptionId { txid, index }.to_string(),
+            "number": number,
+            "location": format!("{outpoint}:{offset}"),
+          }],
+        })
+      );
+    }
+
+    let response = server.get(format!("/api/v1/ord/outpoint/{outpoint}/offset/1"));
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      format!("no inscription at {outpoint}:1")
+    );
+
+    let response = server.get(format!(
+      "/api/v1/ord/outpoint/{outpoint}/offset/{}",
+      100 * COIN_VALUE
+    ));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      format!(
+        "offset {} is beyond output {outpoint} of {} sats",
+        100 * COIN_VALUE,
+        100 * COIN_VALUE
+      )
+    );
+
+    assert_eq!(
+      server
+        .get(format!(
+          "/api/v1/ord/outpoint/{}:0/offset/0",
+          crate::test::txid(9)
+        ))
+        .status(),
+      StatusCode::NOT_FOUND
     );
   }
 
   #[test]
-  fn inscription_with_unknown_type_and_no_body_has_unknown_preview() {
-    let server = TestServer::new_with_regtest_with_index_sats();
-    server.mine_blocks(1);
+  fn ord_outpoint_data_includes_sat_ranges_with_sat_index() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--index-sats"],
+      &["--enable-json-api"],
+    );
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        Inscription::new(Some("foo/bar".as_bytes().to_vec()), None).to_witness(),
-      )],
-      ..Default::default()
-    });
+    let outpoint = OutPoint {
+      txid: server.mine_blocks(1)[0].txdata[0].txid(),
+      vout: 0,
+    };
 
-    let inscription_id = InscriptionId { txid, index: 0 };
+    assert_eq!(
+      server.get_json::<serde_json::Value>(format!("/api/v1/ord/outpoint/{outpoint}"))["data"]
+        ["satRanges"],
+      serde_json::json!([[50 * COIN_VALUE, 100 * COIN_VALUE]])
+    );
+  }
+
+  #[test]
+  fn inscription_by_id_or_number() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    server.mine_blocks(3);
+
+    let blessed = InscriptionId {
+      txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "blessed").to_witness())],
+        ..Default::default()
+      }),
+      index: 0,
+    };
 
     server.mine_blocks(1);
 
-    server.assert_response(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    let cursed = InscriptionId {
+      txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[
+          (2, 0, 0, Witness::default()),
+          (3, 0, 0, inscription("text/plain", "cursed").to_witness()),
+        ],
+        outputs: 2,
+        ..Default::default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    for (id, number) in [(blessed, 0), (cursed, -1)] {
+      let by_id = server.get_json::<serde_json::Value>(format!("/api/v1/inscription/{id}"));
+      let by_number = server.get_json::<serde_json::Value>(format!("/api/v1/inscription/{number}"));
+
+      assert_eq!(by_id["data"]["id"], id.to_string());
+      assert_eq!(by_id["data"]["number"], number);
+      assert_eq!(by_number["data"], by_id["data"]);
+    }
+
+    assert_eq!(
+      server.get("/api/v1/inscription/1").status(),
+      StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+      server.get("/api/v1/inscription/-2").status(),
+      StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+      server.get("/api/v1/inscription/foo").status(),
+      StatusCode::BAD_REQUEST
     );
   }
 
   #[test]
-  fn inscription_with_known_type_and_no_body_has_unknown_preview() {
-    let server = TestServer::new_with_regtest_with_index_sats();
+  fn inscription_confirmations() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest"],
+      &["--enable-json-api", "--scan-mempool"],
+    );
+
     server.mine_blocks(1);
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+    let buried = InscriptionId {
+      txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "buried").to_witness())],
+        ..Default::default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let confirmations = || {
+      server.get_json::<serde_json::Value>(format!("/api/v1/inscription/{buried}"))["data"]
+        ["confirmations"]
+        .clone()
+    };
+
+    assert_eq!(confirmations(), 1);
+
+    server.mine_blocks(3);
+
+    let data =
+      server.get_json::<serde_json::Value>(format!("/api/v1/inscription/{buried}"))["data"].clone();
+
+    assert_eq!(data["genesisHeight"], 2);
+    assert_eq!(data["confirmations"], 5 - 2 + 1);
+
+    let unconfirmed = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
       inputs: &[(
-        1,
+        3,
         0,
         0,
-        Inscription::new(Some("image/png".as_bytes().to_vec()), None).to_witness(),
+        inscription("text/plain", "unconfirmed").to_witness(),
       )],
       ..Default::default()
     });
 
-    let inscription_id = InscriptionId { txid, index: 0 };
+    server.index.update().unwrap();
 
-    server.mine_blocks(1);
+    let inscriptions = &server.get_json::<serde_json::Value>("/api/v1/ord/mempool/inscriptions")
+      ["data"]["inscriptions"];
 
-    server.assert_response(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    assert_eq!(
+      inscriptions[0]["id"],
+      InscriptionId {
+        txid: unconfirmed,
+        index: 0
+      }
+      .to_string()
+    );
+    assert_eq!(inscriptions[0]["confirmations"], 0);
+
+    assert_eq!(
+      server.get_json::<serde_json::Value>(format!("/api/v1/ord/outpoint/{unconfirmed}:0"))["data"]
+        ["confirmations"],
+      serde_json::Value::Null
     );
   }
 
   #[test]
-  fn content_responses_have_cache_control_headers() {
-    let server = TestServer::new_with_regtest();
+  fn outpoint_confirmations_are_counted_from_the_index_without_txindex() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .txindex(false)
+        .build(),
+      None,
+      &["--chain", "regtest"],
+      &["--enable-json-api"],
+    );
+
     server.mine_blocks(1);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      inputs: &[(1, 0, 0, Default::default())],
       ..Default::default()
     });
 
-    server.mine_blocks(1);
-
-    let response = server.get(format!("/content/{}", InscriptionId { txid, index: 0 }));
+    server.mine_blocks(4);
 
-    assert_eq!(response.status(), StatusCode::OK);
     assert_eq!(
-      response.headers().get(header::CACHE_CONTROL).unwrap(),
-      "public, max-age=31536000, immutable"
+      server.get_json::<serde_json::Value>(format!("/api/v1/ord/outpoint/{txid}:0"))["data"]
+        ["confirmations"],
+      5 - 2 + 1
     );
-  }
 
-  #[test]
-  fn error_content_responses_have_max_age_zero_cache_control_headers() {
-    let server = TestServer::new_with_regtest();
-    let response =
-      server.get("/content/6ac5cacb768794f4fd7a78bf00f2074891fce68bd65c4ff36e77177237aacacai0");
+    server.bitcoin_rpc_server.mine_blocks(2);
 
-    assert_eq!(response.status(), 404);
-    assert_eq!(
-      response.headers().get(header::CACHE_CONTROL).unwrap(),
-      "no-store"
-    );
-  }
+    let data = reqwest::blocking::get(server.join_url(&format!("/api/v1/ord/outpoint/{txid}:0")))
+      .unwrap()
+      .json::<serde_json::Value>()
+      .unwrap();
 
-  #[test]
-  fn inscriptions_page_with_no_prev_or_next() {
-    TestServer::new_with_regtest_with_index_sats().assert_response_regex(
-      "/inscriptions",
-      StatusCode::OK,
-      ".*prev\nnext.*",
-    );
+    assert_eq!(data["data"]["confirmations"], 5 - 2 + 1);
   }
 
   #[test]
-  fn inscriptions_page_with_no_next() {
-    let server = TestServer::new_with_regtest_with_index_sats();
+  fn inscription_numbers_are_signed() {
+    let server = TestServer::new_with_regtest_with_json_api();
 
-    for i in 0..101 {
-      server.mine_blocks(1);
-      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
-        ..Default::default()
-      });
-    }
+    server.mine_blocks(6);
 
-    server.mine_blocks(1);
+    let mut inscription_ids = Vec::new();
 
-    server.assert_response_regex(
-      "/inscriptions/1",
-      StatusCode::OK,
-      ".*<a class=prev href=/inscriptions/0>prev</a>\nnext.*",
-    );
-  }
+    for (height, number) in [(1, 0), (3, -1), (4, 1), (6, -2)] {
+      let witness = inscription("text/plain", "foo").to_witness();
 
-  #[test]
-  fn inscriptions_page_with_no_prev() {
-    let server = TestServer::new_with_regtest_with_index_sats();
+      // Inscriptions on inputs other than the first are cursed
+      let inputs = if number < 0 {
+        vec![
+          (height - 1, 0, 0, Witness::default()),
+          (height, 0, 0, witness),
+        ]
+      } else {
+        vec![(height, 0, 0, witness)]
+      };
 
-    for i in 0..101 {
-      server.mine_blocks(1);
-      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &inputs,
+        outputs: 2,
         ..Default::default()
       });
-    }
 
-    server.mine_blocks(1);
+      server.mine_blocks(1);
 
-    server.assert_response_regex(
-      "/inscriptions/0",
-      StatusCode::OK,
-      ".*prev\n<a class=next href=/inscriptions/1>next</a>.*",
-    );
-  }
+      inscription_ids.push((InscriptionId { txid, index: 0 }, number));
+    }
 
-  #[test]
-  #[ignore]
-  fn collections_page_prev_and_next() {
-    let server = TestServer::new_with_regtest_with_index_sats();
+    for (id, number) in &inscription_ids {
+      let data = server
+        .get_json::<serde_json::Value>(format!("/api/v1/ord/number/{number}/inscription"))["data"]
+        .clone();
 
-    let mut parent_ids = Vec::new();
+      assert_eq!(data["id"], id.to_string());
+      assert_eq!(data["number"], *number);
 
-    for i in 0..101 {
-      server.mine_blocks(1);
-
-      parent_ids.push(InscriptionId {
-        txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-          inputs: &[(i + 1, 0, 0, inscription("text/plain", "hello").to_witness())],
-          ..Default::default()
-        }),
-        index: 0,
-      });
-    }
-
-    for (i, parent_id) in parent_ids.iter().enumerate().take(101) {
-      server.mine_blocks(1);
+      assert_eq!(
+        server
+          .get_json::<InscriptionJson>(format!("/inscription/{number}"))
+          .inscription_id,
+        *id
+      );
 
-      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[
-          (i + 2, 1, 0, Default::default()),
-          (
-            i + 102,
-            0,
-            0,
-            Inscription {
-              content_type: Some("text/plain".into()),
-              body: Some("hello".into()),
-              parent: Some(parent_id.value()),
-              ..Default::default()
-            }
-            .to_witness(),
-          ),
-        ],
-        outputs: 2,
-        output_values: &[50 * COIN_VALUE, 50 * COIN_VALUE],
-        ..Default::default()
-      });
+      assert_eq!(
+        server.get_json::<serde_json::Value>(format!("/inscription/{id}"))["inscription_number"],
+        *number
+      );
     }
 
-    server.mine_blocks(1);
-
-    server.assert_response_regex(
-      "/collections",
-      StatusCode::OK,
-      r".*
-<h1>Collections</h1>
-<div class=thumbnails>
-  <a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>
-  (<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>\s*){99}
-</div>
-<div class=center>
-prev
-<a class=next href=/collections/1>next</a>
-</div>.*"
-        .to_string()
-        .unindent(),
+    pretty_assert_eq!(
+      server
+        .get_json::<InscriptionsJson>("/inscriptions")
+        .inscriptions,
+      inscription_ids
+        .iter()
+        .rev()
+        .map(|(id, _number)| *id)
+        .collect::<Vec<InscriptionId>>(),
     );
 
-    server.assert_response_regex(
-      "/collections/1",
-      StatusCode::OK,
-      ".*
-<h1>Collections</h1>
-<div class=thumbnails>
-  <a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>
-</div>
-<div class=center>
-<a class=prev href=/collections/0>prev</a>
-next
-</div>.*"
-        .unindent(),
-    );
+    for number in ["99999999999", "foo"] {
+      assert_eq!(
+        server
+          .get(format!("/api/v1/ord/number/{number}/inscription"))
+          .status(),
+        StatusCode::BAD_REQUEST,
+        "{number}"
+      );
+    }
   }
 
   #[test]
-  fn responses_are_gzipped() {
-    let server = TestServer::new();
+  fn ord_block_mints() {
+    let server = TestServer::new_with_regtest_with_json_api();
 
-    let mut headers = HeaderMap::new();
+    server.mine_blocks(2);
 
-    headers.insert(header::ACCEPT_ENCODING, "gzip".parse().unwrap());
+    let ids = (1..=2)
+      .map(|i| InscriptionId {
+        txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+          inputs: &[(i, 0, 0, inscription("text/foo", "hello").to_witness())],
+          fee: 1000 * i as u64,
+          ..Default::default()
+        }),
+        index: 0,
+      })
+      .collect::<Vec<InscriptionId>>();
 
-    let response = reqwest::blocking::Client::builder()
-      .default_headers(headers)
-      .build()
-      .unwrap()
-      .get(server.join_url("/"))
-      .send()
-      .unwrap();
+    server.mine_blocks(1);
 
-    assert_eq!(
-      response.headers().get(header::CONTENT_ENCODING).unwrap(),
-      "gzip"
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/ord/block/3/inscriptions")["data"],
+      serde_json::json!({
+        "height": 3,
+        "inscriptions": [
+          {"id": ids[0], "number": 0, "fee": 1000},
+          {"id": ids[1], "number": 1, "fee": 2000},
+        ],
+        "pageIndex": 0,
+        "limit": 100,
+        "more": false,
+      })
     );
-  }
 
-  #[test]
-  fn responses_are_brotlied() {
-    let server = TestServer::new();
-
-    let mut headers = HeaderMap::new();
-
-    headers.insert(header::ACCEPT_ENCODING, "br".parse().unwrap());
+    let blockhash = server.index.block_hash(Some(3)).unwrap().unwrap();
 
-    let response = reqwest::blocking::Client::builder()
-      .default_headers(headers)
-      .brotli(false)
-      .build()
-      .unwrap()
-      .get(server.join_url("/"))
-      .send()
-      .unwrap();
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>(format!(
+        "/api/v1/ord/block/{blockhash}/inscriptions?limit=1&page=1"
+      ))["data"],
+      server.get_json::<serde_json::Value>("/api/v1/ord/block/3/inscriptions?limit=1&page=1")
+        ["data"],
+    );
 
     assert_eq!(
-      response.headers().get(header::CONTENT_ENCODING).unwrap(),
-      "br"
+      server
+        .get(format!(
+          "/api/v1/ord/block/{}/inscriptions",
+          BlockHash::all_zeros()
+        ))
+        .status(),
+      StatusCode::NOT_FOUND
     );
-  }
-
-  #[test]
-  fn inscriptions_can_be_hidden_with_config() {
-    let bitcoin_rpc_server = test_bitcoincore_rpc::spawn();
-    bitcoin_rpc_server.mine_blocks(1);
-    let txid = bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        inscription("text/plain;charset=utf-8", "hello").to_witness(),
-      )],
-      ..Default::default()
-    });
-    let inscription = InscriptionId { txid, index: 0 };
-    bitcoin_rpc_server.mine_blocks(1);
 
-    let server = TestServer::new_with_bitcoin_rpc_server_and_config(
-      bitcoin_rpc_server,
-      format!("\"hidden\":\n - {inscription}"),
+    assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/ord/block/2/inscriptions")["data"]
+        ["inscriptions"],
+      serde_json::json!([])
     );
 
-    server.assert_response(
-      format!("/preview/{inscription}"),
-      StatusCode::OK,
-      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    assert_eq!(
+      server.get("/api/v1/ord/block/4/inscriptions").status(),
+      StatusCode::NOT_FOUND
     );
 
-    server.assert_response(
-      format!("/content/{inscription}"),
-      StatusCode::OK,
-      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    assert_eq!(
+      server.get("/api/v1/ord/block/foo/inscriptions").status(),
+      StatusCode::BAD_REQUEST
     );
   }
 
   #[test]
-  fn inscription_links_to_parent() {
-    let server = TestServer::new_with_regtest_with_json_api();
+  fn ord_tx_inscriptions() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-save-ord-receipts"],
+      &["--enable-json-api"],
+    );
+
     server.mine_blocks(1);
 
-    let parent_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+    let reveal = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..Default::default()
     });
 
     server.mine_blocks(1);
 
-    let parent_inscription_id = InscriptionId {
-      txid: parent_txid,
+    let inscription_id = InscriptionId {
+      txid: reveal,
       index: 0,
     };
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[
-        (
-          2,
-          0,
-          0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parent: Some(parent_inscription_id.value()),
-            ..Default::default()
-          }
-          .to_witness(),
-        ),
-        (2, 1, 0, Default::default()),
-      ],
+    let data = &server
+      .get_json::<serde_json::Value>(format!("/api/v1/ord/tx/{reveal}/inscriptions"))["data"];
+
+    assert_eq!(data["inscribed"], serde_json::json!([inscription_id]));
+    assert_eq!(data["transferred"], serde_json::json!([]));
+
+    let transfer = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
       ..Default::default()
     });
 
     server.mine_blocks(1);
 
-    let inscription_id = InscriptionId { txid, index: 0 };
+    let data = &server
+      .get_json::<serde_json::Value>(format!("/api/v1/ord/tx/{transfer}/inscriptions"))["data"];
 
-    server.assert_response_regex(
-      format!("/inscription/{inscription_id}"),
-      StatusCode::OK,
-      format!(".*<title>Inscription 1</title>.*<dt>parent</dt>.*<dd><a class=monospace href=/inscription/{parent_inscription_id}>{parent_inscription_id}</a></dd>.*"),
+    assert_eq!(data["inscribed"], serde_json::json!([]));
+    assert_eq!(
+      data["transferred"],
+      serde_json::json!([{
+        "id": inscription_id,
+        "fromSatpoint": format!("{reveal}:0:0"),
+        "toSatpoint": format!("{transfer}:0:0"),
+      }])
     );
 
-    server.assert_response_regex(
-      format!("/inscription/{parent_inscription_id}"),
-      StatusCode::OK,
-      format!(".*<title>Inscription 0</title>.*<dt>children</dt>.*<a href=/inscription/{inscription_id}>.*</a>.*"),
-    );
+    let coinbase = server.mine_blocks(1)[0].txdata[0].txid();
 
-    assert_eq!(
-      server
-        .get_json::<InscriptionJson>(format!("/inscription/{inscription_id}"))
-        .parent,
-      Some(parent_inscription_id),
-    );
+    let data = &server
+      .get_json::<serde_json::Value>(format!("/api/v1/ord/tx/{coinbase}/inscriptions"))["data"];
+
+    assert_eq!(data["inscribed"], serde_json::json!([]));
+    assert_eq!(data["transferred"], serde_json::json!([]));
 
     assert_eq!(
       server
-        .get_json::<InscriptionJson>(format!("/inscription/{parent_inscription_id}"))
-        .children,
-      [inscription_id],
+        .get(format!(
+          "/api/v1/ord/tx/{}/inscriptions",
+          crate::test::txid(9)
+        ))
+        .status(),
+      StatusCode::NOT_FOUND
     );
   }
 
   #[test]
-  fn inscription_with_and_without_children_page() {
-    let server = TestServer::new_with_regtest();
-    server.mine_blocks(1);
-
-    let parent_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
-      ..Default::default()
-    });
-
-    server.mine_blocks(1);
+  fn sat_rarity_filter() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--index-sats"],
+      &["--enable-json-api"],
+    );
 
-    let parent_inscription_id = InscriptionId {
-      txid: parent_txid,
-      index: 0,
+    let outpoint = OutPoint {
+      txid: server.mine_blocks(1)[0].txdata[0].txid(),
+      vout: 0,
     };
 
-    server.assert_response_regex(
-      format!("/children/{parent_inscription_id}"),
-      StatusCode::OK,
-      ".*<h3>No children</h3>.*",
+    assert_eq!(
+      server.get_json::<serde_json::Value>(format!(
+        "/api/v1/sat/outpoint/{outpoint}/rarity?rarity=uncommon,rare"
+      ))["data"]["result"]["satRanges"],
+      serde_json::json!([{
+        "first": 50 * COIN_VALUE,
+        "last": 100 * COIN_VALUE,
+        "raritySats": [{"sat": 50 * COIN_VALUE, "offset": 0, "rarity": "uncommon"}],
+      }])
     );
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[
-        (
-          2,
-          0,
-          0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parent: Some(parent_inscription_id.value()),
-            ..Default::default()
-          }
-          .to_witness(),
-        ),
-        (2, 1, 0, Default::default()),
-      ],
-      ..Default::default()
-    });
+    assert_eq!(
+      server.get_json::<serde_json::Value>(format!(
+        "/api/v1/sat/outpoint/{outpoint}/rarity?rarity=epic"
+      ))["data"]["result"]["satRanges"],
+      serde_json::json!([])
+    );
 
-    server.mine_blocks(1);
+    let response = server.get(format!(
+      "/api/v1/sat/outpoint/{outpoint}/rarity?rarity=shiny"
+    ));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "invalid rarity: shiny"
+    );
+  }
 
-    let inscription_id = InscriptionId { txid, index: 0 };
+  #[test]
+  fn sat_rarity_filter_requires_sat_index() {
+    let server = TestServer::new_with_regtest_with_json_api();
 
-    server.assert_response_regex(
-      format!("/children/{parent_inscription_id}"),
-      StatusCode::OK,
-      format!(".*<title>Inscription 0 Children</title>.*<h1><a href=/inscription/{parent_inscription_id}>Inscription 0</a> Children</h1>.*<div class=thumbnails>.*<a href=/inscription/{inscription_id}><iframe .* src=/preview/{inscription_id}></iframe></a>.*"),
+    let response = server.get(format!(
+      "/api/v1/sat/outpoint/{}:0/rarity?rarity=uncommon",
+      crate::test::txid(1)
+    ));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "filtering by rarity requires an index built with --index-sats"
     );
   }
 
   #[test]
-  fn inscriptions_page_shows_max_four_children() {
-    let server = TestServer::new_with_regtest();
-    server.mine_blocks(1);
+  fn runes_balances_of_address_without_runes() {
+    let server = TestServer::new_with_regtest_with_index_runes();
 
-    let parent_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
-      ..Default::default()
-    });
+    server.mine_blocks(1);
 
-    server.mine_blocks(6);
+    assert_eq!(
+      server.get_json::<serde_json::Value>(
+        "/api/v1/runes/balances/bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+      )["data"],
+      serde_json::json!({
+        "address": "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw",
+        "addressType": "p2wpkh",
+        "balances": [],
+      })
+    );
 
-    let parent_inscription_id = InscriptionId {
-      txid: parent_txid,
-      index: 0,
-    };
+    let response = server.get("/api/v1/runes/balances/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "invalid address bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+    );
+  }
 
-    let _txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[
-        (
-          2,
-          0,
-          0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parent: Some(parent_inscription_id.value()),
-            ..Default::default()
-          }
-          .to_witness(),
-        ),
-        (
-          3,
-          0,
-          0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parent: Some(parent_inscription_id.value()),
-            ..Default::default()
-          }
-          .to_witness(),
-        ),
-        (
-          4,
-          0,
-          0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parent: Some(parent_inscription_id.value()),
-            ..Default::default()
-          }
-          .to_witness(),
-        ),
-        (
-          5,
-          0,
-          0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parent: Some(parent_inscription_id.value()),
+  #[test]
+  fn runes_transfers() {
+    let server = TestServer::new_with_regtest_with_index_runes();
+
+    server.mine_blocks(1);
+
+    let etching = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: 0,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            limit: Some(1000),
             ..Default::default()
-          }
-          .to_witness(),
-        ),
-        (
-          6,
-          0,
-          0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parent: Some(parent_inscription_id.value()),
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    let mint = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: u128::from(id) | CLAIM_BIT,
+            amount: 1000,
+            output: 0,
+          }],
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let transfer = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let transfers = serde_json::json!([
+      {
+        "height": 2,
+        "txid": etching,
+        "etching": true,
+        "inputs": [],
+        "outputs": [{"outpoint": format!("{etching}:0"), "amount": "1000"}],
+        "minted": "1000",
+        "burned": "0",
+      },
+      {
+        "height": 3,
+        "txid": mint,
+        "etching": false,
+        "inputs": [],
+        "outputs": [{"outpoint": format!("{mint}:0"), "amount": "1000"}],
+        "minted": "1000",
+        "burned": "0",
+      },
+      {
+        "height": 4,
+        "txid": transfer,
+        "etching": false,
+        "inputs": [{"outpoint": format!("{etching}:0"), "amount": "1000"}],
+        "outputs": [{"outpoint": format!("{transfer}:0"), "amount": "1000"}],
+        "minted": "0",
+        "burned": "0",
+      },
+    ]);
+
+    assert_eq!(
+      server.get_json::<serde_json::Value>(
+        "/api/v1/runes/AAAAAAAAAAAAA/transfers?from_height=0&to_height=10"
+      )["data"],
+      serde_json::json!({
+        "rune": "AAAAAAAAAAAAA",
+        "id": "2:1",
+        "divisibility": 0,
+        "transfers": transfers,
+        "pageIndex": 0,
+        "limit": 100,
+        "more": false,
+      })
+    );
+
+    assert_eq!(
+      server.get_json::<serde_json::Value>(
+        "/api/v1/runes/AAAAAAAAAAAAA/transfers?from_height=3&to_height=4"
+      )["data"]["transfers"],
+      serde_json::json!([transfers[1]])
+    );
+
+    for (path, status, message) in [
+      (
+        "/api/v1/runes/AAAAAAAAAAAAA/transfers",
+        StatusCode::BAD_REQUEST,
+        "from_height and to_height are required",
+      ),
+      (
+        "/api/v1/runes/AAAAAAAAAAAAA/transfers?from_height=0&to_height=1001",
+        StatusCode::BAD_REQUEST,
+        "height range may span at most 1000 blocks",
+      ),
+      (
+        "/api/v1/runes/=/transfers?from_height=0&to_height=10",
+        StatusCode::BAD_REQUEST,
+        "invalid rune =",
+      ),
+      (
+        "/api/v1/runes/BBBBBBBBBBBBB/transfers?from_height=0&to_height=10",
+        StatusCode::NOT_FOUND,
+        "rune BBBBBBBBBBBBB not found",
+      ),
+    ] {
+      let response = server.get(path);
+      assert_eq!(response.status(), status);
+      assert_eq!(
+        response.json::<serde_json::Value>().unwrap()["msg"],
+        message
+      );
+    }
+  }
+
+  #[test]
+  fn runes_holders() {
+    let server = TestServer::new_with_regtest_with_index_runes();
+
+    server.mine_blocks(1);
+
+    let scripts =
+      [1, 2, 3].map(|n| ScriptBuf::new_v0_p2wpkh(&bitcoin::WPubkeyHash::from_byte_array([n; 20])));
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: [(0, 200), (1, 500), (2, 100), (3, 200)]
+            .into_iter()
+            .map(|(output, amount)| Edict {
+              id: 0,
+              amount,
+              output,
+            })
+            .collect(),
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
             ..Default::default()
-          }
-          .to_witness(),
-        ),
-        (2, 1, 0, Default::default()),
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      outputs: 4,
+      output_scripts: &[
+        scripts[2].clone(),
+        scripts[0].clone(),
+        scripts[1].clone(),
+        scripts[1].clone(),
       ],
       ..Default::default()
     });
 
-    server.mine_blocks(1);
+    server.mine_blocks(1);
+
+    let holders =
+      server.get_json::<serde_json::Value>("/api/v1/runes/AAAAAAAAAAAAA/holders")["data"].clone();
+
+    assert_eq!(holders["rune"], "AAAAAAAAAAAAA");
+    assert_eq!(holders["holderCount"], 3);
+    assert_eq!(holders["more"], false);
+
+    let holders = holders["holders"].as_array().unwrap();
+
+    assert_eq!(
+      holders
+        .iter()
+        .map(|holder| (
+          holder["address"].as_str().unwrap().to_string(),
+          holder["amount"].as_str().unwrap().to_string()
+        ))
+        .collect::<Vec<(String, String)>>(),
+      [(0, "500"), (1, "300"), (2, "200")]
+        .map(|(i, amount)| (
+          Chain::Regtest
+            .address_from_script(&scripts[i])
+            .unwrap()
+            .to_string(),
+          amount.to_string()
+        ))
+        .to_vec(),
+    );
+
+    let percent = holders
+      .iter()
+      .map(|holder| holder["percent"].as_f64().unwrap())
+      .collect::<Vec<f64>>();
+
+    assert!((percent[0] - 50.0).abs() < 1e-9);
+    assert!((percent[1] - 30.0).abs() < 1e-9);
+    assert!((percent[2] - 20.0).abs() < 1e-9);
+    assert!((percent.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+
+    assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/runes/AAAAAAAAAAAAA/holders?page=1")["data"]
+        ["holders"],
+      serde_json::json!([])
+    );
+
+    let response = server.get("/api/v1/runes/BBBBBBBBBBBBB/holders");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  }
+
+  #[test]
+  fn runes_list() {
+    let server = TestServer::new_with_regtest_with_index_runes();
+
+    server.mine_blocks(1);
+
+    let mut runes = Vec::new();
+
+    for (i, (limit, spacers)) in [(300, 0), (100, 1 << 11), (200, 0)].into_iter().enumerate() {
+      let rune = SpacedRune {
+        rune: Rune(RUNE + u128::try_from(i).unwrap()),
+        spacers,
+      };
+
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, Witness::new())],
+        op_return: Some(
+          Runestone {
+            edicts: vec![Edict {
+              id: 0,
+              amount: 0,
+              output: 0,
+            }],
+            etching: Some(Etching {
+              rune: Some(rune.rune),
+              limit: Some(limit),
+              spacers,
+              ..Default::default()
+            }),
+            ..Default::default()
+          }
+          .encipher(),
+        ),
+        ..Default::default()
+      });
+
+      server.mine_blocks(1);
+
+      runes.push(rune.to_string());
+    }
+
+    let list = |query: &str| -> Vec<(String, String)> {
+      let response = server.get(format!("/api/v1/runes{query}"));
+      assert_eq!(response.status(), StatusCode::OK);
+      response.json::<serde_json::Value>().unwrap()["data"]["runes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|rune| {
+          (
+            rune["rune"].as_str().unwrap().into(),
+            rune["supply"].as_str().unwrap().into(),
+          )
+        })
+        .collect()
+    };
+
+    let names = |query: &str| -> Vec<String> {
+      list(query)
+        .into_iter()
+        .map(|(name, _supply)| name)
+        .collect()
+    };
+
+    assert_eq!(
+      list(""),
+      [
+        (runes[0].clone(), "300".into()),
+        (runes[1].clone(), "100".into()),
+        (runes[2].clone(), "200".into()),
+      ]
+    );
+
+    assert_eq!(
+      names("?sort=timestamp"),
+      [runes[2].clone(), runes[1].clone(), runes[0].clone()]
+    );
+
+    assert_eq!(
+      names("?sort=supply"),
+      [runes[0].clone(), runes[2].clone(), runes[1].clone()]
+    );
+
+    let spaced = &runes[1][runes[1].len() - "A•B".len()..];
+    assert!(spaced.contains('•'), "{spaced}");
+    let plain = spaced.replace('•', "");
+
+    assert_eq!(names(&format!("?q={spaced}")), [runes[1].clone()]);
+    assert_eq!(names(&format!("?q={plain}")), [runes[1].clone()]);
+    assert_eq!(
+      names(&format!("?q={}", plain.to_lowercase())),
+      [runes[1].clone()]
+    );
+    assert!(names("?q=Z").is_empty());
+
+    assert_eq!(names("?page=1"), Vec::<String>::new());
+
+    assert_eq!(
+      server.get("/api/v1/runes?sort=name").status(),
+      StatusCode::BAD_REQUEST
+    );
+  }
+
+  #[test]
+  fn runes_transfers_require_rune_index() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    let response = server.get("/api/v1/runes/AAAAAAAAAAAAA/transfers?from_height=0&to_height=10");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "rune index is not enabled"
+    );
+  }
+
+  #[test]
+  fn runes_list_requires_rune_index() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    let response = server.get("/api/v1/runes");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "rune index is not enabled"
+    );
+  }
+
+  #[test]
+  fn runes_balances_require_rune_index() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    let response =
+      server.get("/api/v1/runes/balances/bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "rune index is not enabled"
+    );
+  }
+
+  #[test]
+  fn sat_ranges_are_coalesced() {
+    let server = TestServer::new_with_args(&["--index-sats"], &["--enable-json-api"]);
+
+    server.mine_blocks(4);
+
+    let coinbase = |height| OutPoint::new(server.bitcoin_rpc_server.tx(height, 0).txid(), 0);
+
+    let missing = OutPoint::new(txid(9), 0);
+
+    let client = reqwest::blocking::Client::new();
+
+    let response = client
+      .post(server.join_url("/api/v1/ord/sat-ranges"))
+      .json(&serde_json::json!({
+        "outpoints": [
+          coinbase(2).to_string(),
+          coinbase(4).to_string(),
+          coinbase(1).to_string(),
+          coinbase(1).to_string(),
+          "foo",
+          missing.to_string(),
+        ]
+      }))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["data"],
+      serde_json::json!({
+        "ranges": [[50 * COIN_VALUE, 150 * COIN_VALUE], [200 * COIN_VALUE, 250 * COIN_VALUE]],
+        "total": 150 * COIN_VALUE,
+        "notFound": ["foo", missing.to_string()],
+      })
+    );
+
+    let response = client
+      .post(server.join_url("/api/v1/ord/sat-ranges"))
+      .json(&serde_json::json!({
+        "outpoints": vec![coinbase(1).to_string(); 101]
+      }))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+  }
+
+  #[test]
+  fn sat_ranges_require_sat_index() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/api/v1/ord/sat-ranges"))
+      .json(&serde_json::json!({ "outpoints": [] }))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "sat index is not enabled"
+    );
+  }
+
+  #[test]
+  fn mempool_inscriptions() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest"],
+      &["--enable-json-api", "--scan-mempool"],
+    );
+
+    server.mine_blocks(1);
+
+    let confirmed = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let unconfirmed = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("image/png", "bar").to_witness())],
+      ..Default::default()
+    });
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..Default::default()
+    });
+
+    server.index.update().unwrap();
+
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/ord/mempool/inscriptions")["data"],
+      serde_json::json!({
+        "mempoolEnabled": true,
+        "inscriptions": [{
+          "id": InscriptionId { txid: unconfirmed, index: 0 },
+          "contentType": "image/png",
+          "contentLength": 3,
+          "confirmed": false,
+          "height": null,
+          "confirmations": 0,
+        }],
+        "more": false,
+      })
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<InscriptionsJson>("/inscriptions")
+        .inscriptions,
+      [InscriptionId {
+        txid: confirmed,
+        index: 0
+      }],
+    );
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/ord/mempool/inscriptions")["data"]
+        ["inscriptions"],
+      serde_json::json!([]),
+    );
+  }
+
+  #[test]
+  fn mempool_inscriptions_are_not_listed_without_scan_mempool() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    server.mine_blocks(1);
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/ord/mempool/inscriptions")["data"],
+      serde_json::json!({
+        "mempoolEnabled": false,
+        "inscriptions": [],
+        "more": false,
+      })
+    );
+  }
+
+  #[test]
+  fn sat_notations() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/ord/sat/0")["data"],
+      serde_json::json!({
+        "sat": 0,
+        "name": "nvtdijuwxlp",
+        "degree": "0°0′0″0‴",
+        "decimal": "0.0",
+        "percentile": "0%",
+        "block": 0,
+        "offset": 0,
+        "cycle": 0,
+        "epoch": 0,
+        "period": 0,
+        "rarity": "mythic",
+      })
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/ord/sat/1050000000000000")["data"],
+      serde_json::json!({
+        "sat": 1050000000000000_u64,
+        "name": "gkjbdrhkfqf",
+        "degree": "0°0′336″0‴",
+        "decimal": "210000.0",
+        "percentile": "50.00000005500003%",
+        "block": 210000,
+        "offset": 0,
+        "cycle": 0,
+        "epoch": 1,
+        "period": 104,
+        "rarity": "epic",
+      })
+    );
+
+    assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/ord/sat/gkjbdrhkfqf")["data"]["sat"],
+      1050000000000000_u64
+    );
+
+    for sat in ["2099999997690000", "-1", "foo1"] {
+      let response = server.get(format!("/api/v1/ord/sat/{sat}"));
+      assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{sat}");
+      assert_eq!(
+        response.json::<serde_json::Value>().unwrap()["msg"],
+        format!("invalid sat {sat}, sats are numbered from 0 to 2099999997689999"),
+      );
+    }
+  }
+
+  #[test]
+  fn sat_satpoint() {
+    let server = TestServer::new_with_args(&["--index-sats"], &["--enable-json-api"]);
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/ord/sat/5000000001/satpoint")["data"],
+      serde_json::json!({
+        "sat": 5000000001u64,
+        "satpoint": format!("{}:0:1", server.bitcoin_rpc_server.tx(1, 0).txid()),
+        "address": null,
+        "unconfirmed": false,
+      })
+    );
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
+    });
+
+    assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/ord/sat/5000000001/satpoint")["data"]
+        ["unconfirmed"],
+      true
+    );
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/ord/sat/5000000001/satpoint")["data"]
+        ["satpoint"],
+      format!("{txid}:0:1")
+    );
+
+    server.mine_blocks_with_subsidy(1, 0);
+
+    let response = server.get("/api/v1/ord/sat/15000000000/satpoint");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "sat 15000000000 was spent to fees and lost"
+    );
+
+    assert_eq!(
+      server.get("/api/v1/ord/sat/20000000000/satpoint").status(),
+      StatusCode::NOT_FOUND
+    );
+
+    assert_eq!(
+      server.get("/api/v1/ord/sat/foo.bar/satpoint").status(),
+      StatusCode::BAD_REQUEST
+    );
+  }
+
+  #[test]
+  fn page_size_defaults_and_is_clamped() {
+    let server = TestServer::new_with_args(
+      &["--index-sats"],
+      &[
+        "--enable-json-api",
+        "--default-page-size",
+        "10",
+        "--max-page-size",
+        "20",
+      ],
+    );
+
+    server.mine_blocks(1);
+
+    let page = |query: &str| {
+      server.get_json::<serde_json::Value>(format!(
+        "/api/v1/ord/sats/special?from_height=0&to_height=100{query}"
+      ))["data"]
+        .clone()
+    };
+
+    let data = page("");
+    assert_eq!(data["sats"].as_array().unwrap().len(), 10);
+    assert_eq!(data["limit"], 10);
+
+    let data = page("&limit=5");
+    assert_eq!(data["sats"].as_array().unwrap().len(), 5);
+    assert_eq!(data["limit"], 5);
+
+    let data = page("&limit=1000000");
+    assert_eq!(data["sats"].as_array().unwrap().len(), 20);
+    assert_eq!(data["limit"], 20);
+    assert_eq!(data["more"], true);
+
+    let data = page("&limit=1000000&page=1");
+    assert_eq!(data["sats"][0]["block"], 20);
+    assert_eq!(data["limit"], 20);
+
+    let response = server.get("/api/v1/ord/sats/special?from_height=0&to_height=100&limit=0");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "limit must be greater than 0"
+    );
+  }
+
+  #[test]
+  fn inscription_listings_use_page_size() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest"],
+      &[
+        "--enable-json-api",
+        "--default-page-size",
+        "2",
+        "--max-page-size",
+        "3",
+      ],
+    );
+
+    server.mine_blocks(4);
+
+    for i in 1..=4 {
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i, 0, 0, inscription("text/plain", "foo").to_witness())],
+        ..Default::default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    for path in [
+      "/inscriptions",
+      "/inscriptions/0",
+      "/inscriptions/block/5",
+      "/inscriptions?since_sequence=0",
+    ] {
+      let separator = if path.contains('?') { '&' } else { '?' };
+
+      let json = server.get_json::<InscriptionsJson>(path);
+      assert_eq!(json.inscriptions.len(), 2, "{path}");
+      assert_eq!(json.limit, 2, "{path}");
+      assert!(json.more, "{path}");
+
+      let json = server.get_json::<InscriptionsJson>(format!("{path}{separator}limit=1000"));
+      assert_eq!(json.inscriptions.len(), 3, "{path}");
+      assert_eq!(json.limit, 3, "{path}");
+
+      server.assert_response(
+        format!("{path}{separator}limit=0"),
+        StatusCode::BAD_REQUEST,
+        "limit must be greater than 0",
+      );
+    }
+  }
+
+  #[test]
+  fn special_sats() {
+    let server = TestServer::new_with_args(&["--index-sats"], &["--enable-json-api"]);
+
+    server.mine_blocks(2);
+
+    let sats = server
+      .get_json::<serde_json::Value>("/api/v1/ord/sats/special?from_height=0&to_height=3")["data"]
+      .clone();
+
+    assert_eq!(sats["sats"].as_array().unwrap().len(), 3);
+    assert_eq!(sats["more"], false);
+    assert_eq!(sats["sats"][0]["rarity"], "mythic");
+
+    for height in 1..=2 {
+      let sat = Height(height).starting_sat();
+      pretty_assert_eq!(
+        sats["sats"][usize::try_from(height).unwrap()],
+        serde_json::json!({
+          "sat": sat.n(),
+          "name": sat.name(),
+          "rarity": "uncommon",
+          "block": height,
+          "satpoint": format!(
+            "{}:0:0",
+            server.bitcoin_rpc_server.tx(height.try_into().unwrap(), 0).txid()
+          ),
+          "address": null,
+        })
+      );
+    }
+
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>(
+        "/api/v1/ord/sats/special?rarity=epic&from_height=209999&to_height=210001"
+      )["data"],
+      serde_json::json!({
+        "sats": [{
+          "sat": 1050000000000000_u64,
+          "name": "gkjbdrhkfqf",
+          "rarity": "epic",
+          "block": 210000,
+          "satpoint": null,
+          "address": null,
+        }],
+        "pageIndex": 0,
+        "limit": 100,
+        "more": false,
+      })
+    );
+
+    let page = server.get_json::<serde_json::Value>(
+      "/api/v1/ord/sats/special?rarity=uncommon&from_height=1&to_height=151",
+    )["data"]
+      .clone();
+    assert_eq!(page["sats"].as_array().unwrap().len(), 100);
+    assert_eq!(page["more"], true);
+
+    let page = server.get_json::<serde_json::Value>(
+      "/api/v1/ord/sats/special?rarity=uncommon&from_height=1&to_height=151&page=1",
+    )["data"]
+      .clone();
+    assert_eq!(page["sats"].as_array().unwrap().len(), 50);
+    assert_eq!(page["sats"][0]["block"], 101);
+    assert_eq!(page["more"], false);
+
+    let page = server.get_json::<serde_json::Value>(
+      "/api/v1/ord/sats/special?rarity=uncommon&from_height=1&to_height=151&page=1&limit=40",
+    )["data"]
+      .clone();
+    assert_eq!(page["sats"].as_array().unwrap().len(), 40);
+    assert_eq!(page["sats"][0]["block"], 41);
+    assert_eq!(page["limit"], 40);
+    assert_eq!(page["more"], true);
+
+    for (query, message) in [
+      (
+        "from_height=2&to_height=2",
+        "from_height must be less than to_height",
+      ),
+      (
+        "from_height=0&to_height=10001",
+        "height range may span at most 10000 blocks",
+      ),
+    ] {
+      let response = server.get(format!("/api/v1/ord/sats/special?{query}"));
+      assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+      assert_eq!(
+        response.json::<serde_json::Value>().unwrap()["msg"],
+        message
+      );
+    }
+
+    assert_eq!(
+      server
+        .get("/api/v1/ord/sats/special?from_height=0")
+        .status(),
+      StatusCode::BAD_REQUEST
+    );
+  }
+
+  #[test]
+  fn special_sats_require_sat_index() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    let response = server.get("/api/v1/ord/sats/special?from_height=0&to_height=1");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "sat index is not enabled"
+    );
+  }
+
+  #[test]
+  fn metrics_count_requests_and_api_errors() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    fn sample(metrics: &str, name: &str) -> u64 {
+      metrics
+        .lines()
+        .find_map(|line| line.strip_prefix(name)?.trim().parse().ok())
+        .unwrap_or_default()
+    }
+
+    let metrics = server.get("/metrics").text().unwrap();
+    let not_found = sample(&metrics, r#"ord_api_errors_total{code="3"}"#);
+
+    server.assert_response(
+      "/blockhash/999",
+      StatusCode::NOT_FOUND,
+      "blockhash not found",
+    );
+
+    assert_eq!(
+      server.get("/api/v1/ord/id/foo/inscription").status(),
+      StatusCode::BAD_REQUEST
+    );
+
+    let metrics = server.get("/metrics").text().unwrap();
+
+    assert!(sample(&metrics, r#"ord_api_errors_total{code="3"}"#) > not_found);
+    assert!(sample(&metrics, r#"ord_api_errors_total{code="2"}"#) > 0);
+    assert!(
+      sample(
+        &metrics,
+        r#"ord_http_requests_total{route="/blockhash/:height",status="404"}"#
+      ) > 0
+    );
+    assert!(
+      sample(
+        &metrics,
+        r#"ord_http_requests_total{route="/api/v1/ord/id/:id/inscription",status="400"}"#
+      ) > 0
+    );
+    assert!(metrics.contains(r#"ord_http_request_duration_seconds_count{route="/metrics"}"#));
+  }
+
+  #[test]
+  fn graceful_shutdown_drains_in_flight_requests() {
+    let port = TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap()
+      .port();
+
+    let (_options, server) = parse_server_args(&format!(
+      "ord server --address 127.0.0.1 --http-port {port} --shutdown-grace-secs 5"
+    ));
+
+    assert_eq!(server.shutdown_grace(), Duration::from_secs(5));
+
+    let runtime = Runtime::new().unwrap();
+    let _guard = runtime.enter();
+
+    let handle = Handle::new();
+
+    let router = Router::new().route(
+      "/slow",
+      get(|| async {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        "done"
+      }),
+    );
+
+    let server_task = server
+      .spawn(router, handle.clone(), port, SpawnConfig::Http)
+      .unwrap();
+
+    runtime.block_on(handle.listening()).unwrap();
+
+    let request = thread::spawn(move || {
+      reqwest::blocking::get(format!("http://127.0.0.1:{port}/slow"))
+        .unwrap()
+        .text()
+        .unwrap()
+    });
+
+    thread::sleep(Duration::from_millis(100));
+
+    handle.graceful_shutdown(Some(server.shutdown_grace()));
+
+    assert_eq!(request.join().unwrap(), "done");
+
+    runtime.block_on(server_task).unwrap().unwrap();
+
+    assert!(reqwest::blocking::get(format!("http://127.0.0.1:{port}/slow")).is_err());
+  }
+
+  #[test]
+  fn bind_listens_on_every_address() {
+    let bitcoin_rpc_server = test_bitcoincore_rpc::spawn();
+
+    let tempdir = TempDir::new().unwrap();
+    let cookiefile = tempdir.path().join("cookie");
+    fs::write(&cookiefile, "username:password").unwrap();
+
+    let ports = [(); 2].map(|()| {
+      TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+    });
+
+    let (options, server) = parse_server_args(&format!(
+      "ord --rpc-url {} --cookie-file {} --data-dir {} server --enable-json-api --bind 127.0.0.1:{} --bind 127.0.0.1:{}",
+      bitcoin_rpc_server.url(),
+      cookiefile.to_str().unwrap(),
+      tempdir.path().to_str().unwrap(),
+      ports[0],
+      ports[1],
+    ));
+
+    let index = Index::open(&options).unwrap();
+    let handle = Handle::new();
+
+    let server_thread = {
+      let reader = index.reader();
+      let writer = index.clone();
+      let handle = handle.clone();
+      thread::spawn(move || server.run(options, reader, Some(writer), handle).unwrap())
+    };
+
+    let client = reqwest::blocking::Client::new();
+
+    let statuses = ports.map(|port| {
+      for i in 0.. {
+        if let Ok(response) = client
+          .get(format!("http://127.0.0.1:{port}/status"))
+          .header(reqwest::header::ACCEPT, "application/json")
+          .send()
+        {
+          assert_eq!(response.status(), StatusCode::OK);
+          let mut status = response.json::<serde_json::Value>().unwrap();
+          let status = status.as_object_mut().unwrap();
+          status.remove("uptime");
+          status.remove("uptime_secs");
+          return status.clone();
+        }
+
+        assert!(i < 400, "server failed to start on port {port}");
+
+        thread::sleep(Duration::from_millis(25));
+      }
+
+      unreachable!()
+    });
+
+    pretty_assert_eq!(statuses[0], statuses[1]);
+
+    handle.shutdown();
+
+    server_thread.join().unwrap();
+  }
+
+  #[test]
+  fn bind_fails_if_any_address_cannot_be_bound() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let taken = listener.local_addr().unwrap();
+
+    let free = TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap();
+
+    let (_options, server) = parse_server_args(&format!("ord server --bind {free} --bind {taken}"));
+
+    assert_eq!(
+      server.listeners().unwrap_err().to_string(),
+      format!("failed to bind to {taken}")
+    );
+
+    assert!(TcpListener::bind(free).is_ok(), "no address is left bound");
+  }
+
+  #[test]
+  fn bind_conflicts_with_address_and_ports() {
+    for args in ["--address 127.0.0.1", "--http-port 8080", "--https"] {
+      assert!(Arguments::try_parse_from(
+        format!("ord server --bind 127.0.0.1:80 {args}").split_whitespace()
+      )
+      .is_err());
+    }
+  }
+
+  #[test]
+  fn errors_are_text_when_request_prefers_html() {
+    let test_server = TestServer::new();
+
+    let response = reqwest::blocking::Client::new()
+      .get(test_server.join_url("/blockhash/999"))
+      .header(header::ACCEPT, "text/html, application/json;q=0.9")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/plain; charset=utf-8"
+    );
+    assert_eq!(response.text().unwrap(), "blockhash not found");
+  }
+
+  #[test]
+  fn range() {
+    TestServer::new().assert_response_regex(
+      "/range/0/1",
+      StatusCode::OK,
+      r".*<title>Sat range 0–1</title>.*<h1>Sat range 0–1</h1>
+<dl>
+  <dt>value</dt><dd>1</dd>
+  <dt>first</dt><dd><a href=/sat/0 class=mythic>0</a></dd>
+</dl>.*",
+    );
+  }
+  #[test]
+  fn sat_number() {
+    TestServer::new().assert_response_regex("/sat/0", StatusCode::OK, ".*<h1>Sat 0</h1>.*");
+  }
+
+  #[test]
+  fn sat_decimal() {
+    TestServer::new().assert_response_regex("/sat/0.0", StatusCode::OK, ".*<h1>Sat 0</h1>.*");
+  }
+
+  #[test]
+  fn sat_degree() {
+    TestServer::new().assert_response_regex("/sat/0°0′0″0‴", StatusCode::OK, ".*<h1>Sat 0</h1>.*");
+  }
+
+  #[test]
+  fn sat_name() {
+    TestServer::new().assert_response_regex(
+      "/sat/nvtdijuwxlp",
+      StatusCode::OK,
+      ".*<h1>Sat 0</h1>.*",
+    );
+  }
+
+  #[test]
+  fn sat() {
+    TestServer::new().assert_response_regex(
+      "/sat/0",
+      StatusCode::OK,
+      ".*<title>Sat 0</title>.*<h1>Sat 0</h1>.*",
+    );
+  }
+
+  #[test]
+  fn block() {
+    TestServer::new().assert_response_regex(
+      "/block/0",
+      StatusCode::OK,
+      ".*<title>Block 0</title>.*<h1>Block 0</h1>.*",
+    );
+  }
+
+  #[test]
+  fn sat_out_of_range() {
+    TestServer::new().assert_response(
+      "/sat/2099999997690000",
+      StatusCode::BAD_REQUEST,
+      "invalid sat",
+    );
+  }
+
+  #[test]
+  fn invalid_outpoint_hash_returns_400() {
+    TestServer::new().assert_response("/output/foo:0", StatusCode::BAD_REQUEST, "invalid outpoint");
+  }
+
+  #[test]
+  fn malformed_path_params_return_400() {
+    let server = TestServer::new_with_sat_index();
+
+    server.assert_response(
+      "/content/foo",
+      StatusCode::BAD_REQUEST,
+      "invalid inscription id",
+    );
+    server.assert_response(
+      "/children/foo/0",
+      StatusCode::BAD_REQUEST,
+      "invalid inscription id",
+    );
+    server.assert_response("/r/sat/=", StatusCode::BAD_REQUEST, "invalid sat");
+
+    for (path, message) in [
+      ("/api/v1/ord/id/foo/inscription", "invalid inscription id"),
+      ("/api/v1/ord/outpoint/foo/info", "invalid outpoint"),
+    ] {
+      let response = server.get(path);
+      assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+      assert_eq!(
+        response.json::<serde_json::Value>().unwrap()["msg"],
+        message
+      );
+    }
+  }
+
+  #[test]
+  fn output_with_sat_index() {
+    let txid = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+    TestServer::new_with_sat_index().assert_response_regex(
+      format!("/output/{txid}:0"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Output {txid}:0</title>.*<h1>Output <span class=monospace>{txid}:0</span></h1>
+<dl>
+  <dt>value</dt><dd>5000000000</dd>
+  <dt>script pubkey</dt><dd class=monospace>OP_PUSHBYTES_65 [[:xdigit:]]{{130}} OP_CHECKSIG</dd>
+  <dt>transaction</dt><dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
+</dl>
+<h2>1 Sat Range</h2>
+<ul class=monospace>
+  <li><a href=/range/0/5000000000 class=mythic>0–5000000000</a></li>
+</ul>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn output_without_sat_index() {
+    let txid = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+    TestServer::new().assert_response_regex(
+      format!("/output/{txid}:0"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Output {txid}:0</title>.*<h1>Output <span class=monospace>{txid}:0</span></h1>
+<dl>
+  <dt>value</dt><dd>5000000000</dd>
+  <dt>script pubkey</dt><dd class=monospace>OP_PUSHBYTES_65 [[:xdigit:]]{{130}} OP_CHECKSIG</dd>
+  <dt>transaction</dt><dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
+</dl>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn null_output_is_initially_empty() {
+    let txid = "0000000000000000000000000000000000000000000000000000000000000000";
+    TestServer::new_with_sat_index().assert_response_regex(
+      format!("/output/{txid}:4294967295"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Output {txid}:4294967295</title>.*<h1>Output <span class=monospace>{txid}:4294967295</span></h1>
+<dl>
+  <dt>value</dt><dd>0</dd>
+  <dt>script pubkey</dt><dd class=monospace></dd>
+  <dt>transaction</dt><dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
+</dl>
+<h2>0 Sat Ranges</h2>
+<ul class=monospace>
+</ul>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn null_output_receives_lost_sats() {
+    let server = TestServer::new_with_sat_index();
+
+    server.mine_blocks_with_subsidy(1, 0);
+
+    let txid = "0000000000000000000000000000000000000000000000000000000000000000";
+
+    server.assert_response_regex(
+      format!("/output/{txid}:4294967295"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Output {txid}:4294967295</title>.*<h1>Output <span class=monospace>{txid}:4294967295</span></h1>
+<dl>
+  <dt>value</dt><dd>5000000000</dd>
+  <dt>script pubkey</dt><dd class=monospace></dd>
+  <dt>transaction</dt><dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
+</dl>
+<h2>1 Sat Range</h2>
+<ul class=monospace>
+  <li><a href=/range/5000000000/10000000000 class=uncommon>5000000000–10000000000</a></li>
+</ul>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn unbound_output_receives_unbound_inscriptions() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+
+    server.mine_blocks(1);
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      fee: 50 * 100_000_000,
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        1,
+        0,
+        inscription("text/plain;charset=utf-8", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.assert_response_regex(
+      format!("/inscription/{}", inscription_id),
+      StatusCode::OK,
+      format!(
+        ".*<dl>
+  <dt>id</dt>
+  <dd class=monospace>{inscription_id}</dd>.*<dt>output</dt>
+  <dd><a class=monospace href=/output/0000000000000000000000000000000000000000000000000000000000000000:0>0000000000000000000000000000000000000000000000000000000000000000:0</a></dd>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      "/output/0000000000000000000000000000000000000000000000000000000000000000:0",
+      StatusCode::OK,
+      ".*<h1>Output <span class=monospace>0000000000000000000000000000000000000000000000000000000000000000:0</span></h1>
+<dl>
+  <dt>inscriptions</dt>
+  <dd class=thumbnails>
+    <a href=/inscription/.*><iframe sandbox=allow-scripts scrolling=no loading=lazy src=/preview/.*></iframe></a>
+  </dd>.*",
+    );
+  }
+
+  #[test]
+  fn unbound_output_returns_200() {
+    TestServer::new().assert_response_regex(
+      "/output/0000000000000000000000000000000000000000000000000000000000000000:0",
+      StatusCode::OK,
+      ".*",
+    );
+  }
+
+  #[test]
+  fn invalid_output_returns_400() {
+    TestServer::new().assert_response("/output/foo:0", StatusCode::BAD_REQUEST, "invalid outpoint");
+  }
+
+  #[test]
+  fn home() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    let mut ids = Vec::new();
+
+    for i in 0..101 {
+      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("image/png", "hello").to_witness())],
+        ..Default::default()
+      });
+      ids.push(InscriptionId { txid, index: 0 });
+      server.mine_blocks(1);
+    }
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "{}").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/",
+      StatusCode::OK,
+      format!(
+        r".*<title>Ordinals</title>.*
+<h1>Latest Inscriptions</h1>
+<div class=thumbnails>
+  <a href=/inscription/{}>.*</a>
+  (<a href=/inscription/[[:xdigit:]]{{64}}i0>.*</a>\s*){{99}}
+</div>
+.*
+",
+        ids[100]
+      ),
+    );
+  }
+
+  #[test]
+  fn blocks() {
+    let test_server = TestServer::new();
+
+    test_server.mine_blocks(1);
+
+    test_server.assert_response_regex(
+      "/blocks",
+      StatusCode::OK,
+      ".*<title>Blocks</title>.*
+<h1>Blocks</h1>
+<div class=block>
+  <h2><a href=/block/1>Block 1</a></h2>
+  <div class=thumbnails>
+  </div>
+</div>
+<div class=block>
+  <h2><a href=/block/0>Block 0</a></h2>
+  <div class=thumbnails>
+  </div>
+</div>
+</ol>.*",
+    );
+  }
+
+  #[test]
+  fn nav_displays_chain() {
+    TestServer::new_with_regtest().assert_response_regex(
+      "/",
+      StatusCode::OK,
+      ".*<a href=/ title=home>Ordinals<sup>regtest</sup></a>.*",
+    );
+  }
+
+  #[test]
+  fn blocks_block_limit() {
+    let test_server = TestServer::new();
+
+    test_server.mine_blocks(101);
+
+    test_server.assert_response_regex(
+      "/blocks",
+      StatusCode::OK,
+      ".*<ol start=96 reversed class=block-list>\n(  <li><a href=/block/[[:xdigit:]]{64}>[[:xdigit:]]{64}</a></li>\n){95}</ol>.*"
+    );
+  }
+
+  #[test]
+  fn block_not_found() {
+    TestServer::new().assert_response(
+      "/block/467a86f0642b1d284376d13a98ef58310caa49502b0f9a560ee222e0a122fe16",
+      StatusCode::NOT_FOUND,
+      "block 467a86f0642b1d284376d13a98ef58310caa49502b0f9a560ee222e0a122fe16 not found",
+    );
+  }
+
+  #[test]
+  fn unmined_sat() {
+    TestServer::new().assert_response_regex(
+      "/sat/0",
+      StatusCode::OK,
+      ".*<dt>timestamp</dt><dd><time>2009-01-03 18:15:05 UTC</time></dd>.*",
+    );
+  }
+
+  #[test]
+  fn mined_sat() {
+    TestServer::new().assert_response_regex(
+      "/sat/5000000000",
+      StatusCode::OK,
+      ".*<dt>timestamp</dt><dd><time>.*</time> \\(expected\\)</dd>.*",
+    );
+  }
+
+  #[test]
+  fn static_asset() {
+    TestServer::new().assert_response_regex(
+      "/static/index.css",
+      StatusCode::OK,
+      r".*\.rare \{
+  background-color: var\(--rare\);
+}.*",
+    );
+  }
+
+  #[test]
+  fn favicon() {
+    TestServer::new().assert_response_regex("/favicon.ico", StatusCode::OK, r".*");
+  }
+
+  #[test]
+  fn clock_updates() {
+    let test_server = TestServer::new();
+    test_server.assert_response_regex("/clock", StatusCode::OK, ".*<text.*>0</text>.*");
+    test_server.mine_blocks(1);
+    test_server.assert_response_regex("/clock", StatusCode::OK, ".*<text.*>1</text>.*");
+  }
+
+  #[test]
+  fn block_by_hash() {
+    let test_server = TestServer::new();
+
+    test_server.mine_blocks(1);
+    let transaction = TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      fee: 0,
+      ..Default::default()
+    };
+    test_server.bitcoin_rpc_server.broadcast_tx(transaction);
+    let block_hash = test_server.mine_blocks(1)[0].block_hash();
+
+    test_server.assert_response_regex(
+      format!("/block/{block_hash}"),
+      StatusCode::OK,
+      ".*<h1>Block 2</h1>.*",
+    );
+  }
+
+  #[test]
+  fn block_by_height() {
+    let test_server = TestServer::new();
+
+    test_server.assert_response_regex("/block/0", StatusCode::OK, ".*<h1>Block 0</h1>.*");
+  }
+
+  #[test]
+  fn transaction() {
+    let test_server = TestServer::new();
+
+    let block = test_server.mine_blocks(1)[0].clone();
+    let block_hash = block.block_hash();
+    let txid = block.txdata[0].txid();
+
+    test_server.assert_response_regex(
+      format!("/tx/{txid}"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Transaction {txid}</title>.*<h1>Transaction <span class=monospace>{txid}</span></h1>
+<dl>
+  <dt>block</dt>
+  <dd><a href=/block/{block_hash} class=monospace>{block_hash}</a></dd>
+</dl>
+<h2>1 Input</h2>
+<ul>
+  <li><a class=monospace href=/output/0000000000000000000000000000000000000000000000000000000000000000:4294967295>0000000000000000000000000000000000000000000000000000000000000000:4294967295</a></li>
+</ul>
+<h2>1 Output</h2>
+<ul class=monospace>
+  <li>
+    <a href=/output/84aca0d43f45ac753d4744f40b2f54edec3a496b298951735d450e601386089d:0 class=monospace>
+      84aca0d43f45ac753d4744f40b2f54edec3a496b298951735d450e601386089d:0
+    </a>
+    <dl>
+      <dt>value</dt><dd>5000000000</dd>
+      <dt>script pubkey</dt><dd class=monospace></dd>
+    </dl>
+  </li>
+</ul>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn detect_unrecoverable_reorg() {
+    let test_server = TestServer::new();
+
+    test_server.mine_blocks(21);
+
+    test_server.assert_response_regex(
+      "/status",
+      StatusCode::OK,
+      ".*<dt>unrecoverably reorged</dt>\n  <dd>false</dd>.*",
+    );
+
+    for _ in 0..15 {
+      test_server.bitcoin_rpc_server.invalidate_tip();
+    }
+
+    test_server.bitcoin_rpc_server.mine_blocks(21);
+
+    test_server.assert_response_regex(
+      "/status",
+      StatusCode::OK,
+      ".*<dt>unrecoverably reorged</dt>\n  <dd>true</dd>.*",
+    );
+  }
+
+  #[test]
+  fn rare_with_sat_index() {
+    TestServer::new_with_sat_index().assert_response(
+      "/rare.txt",
+      StatusCode::OK,
+      "sat\tsatpoint
+0\t4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0
+",
+    );
+  }
+
+  #[test]
+  fn rare_without_sat_index() {
+    TestServer::new().assert_response(
+      "/rare.txt",
+      StatusCode::OK,
+      "sat\tsatpoint
+",
+    );
+  }
+
+  #[test]
+  fn show_rare_txt_in_header_with_sat_index() {
+    TestServer::new_with_sat_index().assert_response_regex(
+      "/",
+      StatusCode::OK,
+      ".*
+      <a href=/clock title=clock>.*</a>
+      <a href=/rare.txt title=rare>.*</a>.*",
+    );
+  }
+
+  #[test]
+  fn rare_sat_location() {
+    TestServer::new_with_sat_index().assert_response_regex(
+      "/sat/0",
+      StatusCode::OK,
+      ".*>4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0<.*",
+    );
+  }
+
+  #[test]
+  fn dont_show_rare_txt_in_header_without_sat_index() {
+    TestServer::new().assert_response_regex(
+      "/",
+      StatusCode::OK,
+      ".*
+      <a href=/clock title=clock>.*</a>
+      <a href=https://docs.ordinals.com/.*",
+    );
+  }
+
+  #[test]
+  fn input() {
+    TestServer::new().assert_response_regex(
+      "/input/0/0/0",
+      StatusCode::OK,
+      ".*<title>Input /0/0/0</title>.*<h1>Input /0/0/0</h1>.*<dt>text</dt><dd>.*The Times 03/Jan/2009 Chancellor on brink of second bailout for banks</dd>.*",
+    );
+  }
+
+  #[test]
+  fn input_missing() {
+    TestServer::new().assert_response(
+      "/input/1/1/1",
+      StatusCode::NOT_FOUND,
+      "input /1/1/1 not found",
+    );
+  }
+
+  #[test]
+  fn commits_are_tracked() {
+    let server = TestServer::new();
+
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(server.index.statistic(crate::index::Statistic::Commits), 1);
+
+    let info = server.index.info().unwrap();
+    assert_eq!(info.transactions.len(), 1);
+    assert_eq!(info.transactions[0].starting_block_count, 0);
+
+    server.index.update().unwrap();
+
+    assert_eq!(server.index.statistic(crate::index::Statistic::Commits), 1);
+
+    let info = server.index.info().unwrap();
+    assert_eq!(info.transactions.len(), 1);
+    assert_eq!(info.transactions[0].starting_block_count, 0);
+
+    server.mine_blocks(1);
+
+    thread::sleep(Duration::from_millis(10));
+    server.index.update().unwrap();
+
+    assert_eq!(server.index.statistic(crate::index::Statistic::Commits), 2);
+
+    let info = server.index.info().unwrap();
+    assert_eq!(info.transactions.len(), 2);
+    assert_eq!(info.transactions[0].starting_block_count, 0);
+    assert_eq!(info.transactions[1].starting_block_count, 1);
+    assert!(
+      info.transactions[1].starting_timestamp - info.transactions[0].starting_timestamp >= 10
+    );
+  }
+
+  #[test]
+  fn outputs_traversed_are_tracked() {
+    let server = TestServer::new_with_sat_index();
+
+    assert_eq!(
+      server
+        .index
+        .statistic(crate::index::Statistic::OutputsTraversed),
+      1
+    );
+
+    server.index.update().unwrap();
+
+    assert_eq!(
+      server
+        .index
+        .statistic(crate::index::Statistic::OutputsTraversed),
+      1
+    );
+
+    server.mine_blocks(2);
+
+    server.index.update().unwrap();
+
+    assert_eq!(
+      server
+        .index
+        .statistic(crate::index::Statistic::OutputsTraversed),
+      3
+    );
+  }
+
+  #[test]
+  fn coinbase_sat_ranges_are_tracked() {
+    let server = TestServer::new_with_sat_index();
+
+    assert_eq!(
+      server.index.statistic(crate::index::Statistic::SatRanges),
+      1
+    );
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.index.statistic(crate::index::Statistic::SatRanges),
+      2
+    );
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.index.statistic(crate::index::Statistic::SatRanges),
+      3
+    );
+  }
+
+  #[test]
+  fn split_sat_ranges_are_tracked() {
+    let server = TestServer::new_with_sat_index();
+
+    assert_eq!(
+      server.index.statistic(crate::index::Statistic::SatRanges),
+      1
+    );
+
+    server.mine_blocks(1);
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      outputs: 2,
+      fee: 0,
+      ..Default::default()
+    });
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.index.statistic(crate::index::Statistic::SatRanges),
+      4,
+    );
+  }
+
+  #[test]
+  fn fee_sat_ranges_are_tracked() {
+    let server = TestServer::new_with_sat_index();
+
+    assert_eq!(
+      server.index.statistic(crate::index::Statistic::SatRanges),
+      1
+    );
+
+    server.mine_blocks(1);
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      outputs: 2,
+      fee: 2,
+      ..Default::default()
+    });
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.index.statistic(crate::index::Statistic::SatRanges),
+      5,
+    );
+  }
+
+  #[tokio::test]
+  async fn content_response_no_content() {
+    assert_eq!(
+      content_response(
+        Inscription::new(Some("text/plain".as_bytes().to_vec()), None),
+        AcceptEncoding::default(),
+        &ServerConfig::default(),
+      )
+      .await
+      .unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn content_response_declares_utf8_charset_of_text() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "héllo").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = server.get(format!("/content/{}", InscriptionId { txid, index: 0 }));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers()[header::CONTENT_TYPE],
+      "text/plain; charset=utf-8"
+    );
+    assert!(!response.headers().contains_key(charset::X_INVALID_UTF8));
+    assert_eq!(response.text().unwrap(), "héllo");
+  }
+
+  #[test]
+  fn content_response_flags_invalid_utf8_text() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription::new(Some("text/plain".into()), Some(vec![0xff, 0xfe])).to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = server.get(format!("/content/{}", InscriptionId { txid, index: 0 }));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+    assert_eq!(response.headers()[charset::X_INVALID_UTF8], "true");
+    assert_eq!(response.bytes().unwrap().as_ref(), [0xff, 0xfe]);
+  }
+
+  #[tokio::test]
+  async fn content_response_with_content() {
+    let (headers, body) = content_response(
+      Inscription::new(Some("text/plain".as_bytes().to_vec()), Some(vec![1, 2, 3])),
+      AcceptEncoding::default(),
+      &ServerConfig::default(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["content-type"], "text/plain; charset=utf-8");
+    assert_eq!(headers.get(header::VARY), None);
+    assert_eq!(body, vec![1, 2, 3]);
+  }
+
+  #[tokio::test]
+  async fn content_response_with_zstd_encoding() {
+    let inscription = Inscription {
+      content_encoding: Some("zstd".as_bytes().to_vec()),
+      ..Inscription::new(Some("text/plain".as_bytes().to_vec()), Some(vec![1, 2, 3]))
+    };
+
+    let (headers, body) = content_response(
+      inscription.clone(),
+      AcceptEncoding(Some("gzip;q=0.5, zstd".into())),
+      &ServerConfig::default(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["content-encoding"], "zstd");
+    assert_eq!(headers["vary"], "Accept-Encoding");
+    assert_eq!(body, vec![1, 2, 3]);
+
+    let Err(ServerError::NotAcceptable {
+      accept_encoding,
+      content_encoding,
+    }) = content_response(
+      inscription,
+      AcceptEncoding(Some("br, zstd;q=0".into())),
+      &ServerConfig::default(),
+    )
+    .await
+    else {
+      panic!("zstd should not be acceptable");
+    };
+
+    assert_eq!(accept_encoding.0.unwrap(), "br, zstd;q=0");
+    assert_eq!(content_encoding, "zstd");
+  }
+
+  #[tokio::test]
+  async fn content_response_decompression_is_bounded() {
+    let mut bomb = Vec::new();
+
+    brotli::CompressorWriter::new(&mut bomb, 4096, 11, 22)
+      .write_all(&vec![0; 1 << 20])
+      .unwrap();
+
+    assert!(bomb.len() < 1024);
+
+    let inscription = Inscription {
+      content_encoding: Some("br".as_bytes().to_vec()),
+      ..Inscription::new(Some("text/plain".as_bytes().to_vec()), Some(bomb))
+    };
+
+    let (headers, body) = content_response(
+      inscription.clone(),
+      AcceptEncoding::default(),
+      &ServerConfig {
+        decompress: true,
+        decompress_brotli_limit: 1 << 20,
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert!(headers.get(header::CONTENT_ENCODING).is_none());
+    assert_eq!(body, vec![0; 1 << 20]);
+
+    let Err(ServerError::PayloadTooLarge(message)) = content_response(
+      inscription,
+      AcceptEncoding::default(),
+      &ServerConfig {
+        decompress: true,
+        decompress_brotli_limit: (1 << 20) - 1,
+        ..Default::default()
+      },
+    )
+    .await
+    else {
+      panic!("decompressed content should exceed limit");
+    };
+
+    assert_eq!(
+      message,
+      "decompressed content exceeds limit of 1048575 bytes"
+    );
+  }
+
+  #[test]
+  fn content_security_policy_is_set_on_content_but_not_json_api() {
+    for (server_args, csp) in [
+      (
+        &["--enable-json-api"][..],
+        "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:; sandbox allow-scripts",
+      ),
+      (
+        &["--enable-json-api", "--content-csp", "sandbox"][..],
+        "sandbox",
+      ),
+    ] {
+      let server = TestServer::new_server(
+        test_bitcoincore_rpc::builder()
+          .network(bitcoin::Network::Regtest)
+          .build(),
+        None,
+        &["--chain", "regtest"],
+        server_args,
+      );
+
+      server.mine_blocks(1);
+
+      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          1,
+          0,
+          0,
+          inscription("text/html", "<p>hello</p>").to_witness(),
+        )],
+        ..Default::default()
+      });
+
+      server.mine_blocks(1);
+
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      for path in [
+        format!("/content/{inscription_id}"),
+        format!("/preview/{inscription_id}"),
+      ] {
+        let response = server.get(&path);
+        assert_eq!(response.status(), StatusCode::OK, "{path}");
+        assert_eq!(
+          response.headers()[header::CONTENT_SECURITY_POLICY],
+          csp,
+          "{path}"
+        );
+      }
+
+      assert_eq!(
+        server.get("/").headers()[header::CONTENT_SECURITY_POLICY],
+        "default-src 'self'"
+      );
+
+      let response = server.get(format!("/api/v1/ord/id/{inscription_id}/inscription"));
+      assert_eq!(response.status(), StatusCode::OK);
+      assert!(response
+        .headers()
+        .get(header::CONTENT_SECURITY_POLICY)
+        .is_none());
+    }
+  }
+
+  #[test]
+  fn content_is_redirected_to_content_origin() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest"],
+      &["--content-origin", "https://content.example.com"],
+    );
+
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let client = reqwest::blocking::Client::builder()
+      .redirect(reqwest::redirect::Policy::none())
+      .build()
+      .unwrap();
+
+    for route in ["content", "preview"] {
+      let path = format!("/{route}/{inscription_id}");
+
+      let response = client.get(server.join_url(&path)).send().unwrap();
+      assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+      assert_eq!(
+        response.headers()[header::LOCATION],
+        format!("https://content.example.com{path}")
+      );
+
+      let response = client
+        .get(server.join_url(&path))
+        .header(header::HOST, "content.example.com")
+        .send()
+        .unwrap();
+      assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let path = format!("/content/{inscription_id}?download=1");
+
+    let response = client.get(server.join_url(&path)).send().unwrap();
+    assert_eq!(
+      response.headers()[header::LOCATION],
+      format!("https://content.example.com{path}")
+    );
+  }
+
+  #[test]
+  fn content_download() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    let png = InscriptionId {
+      txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
+        ..Default::default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let unknown = InscriptionId {
+      txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 0, 0, inscription("foo/bar", "hello").to_witness())],
+        ..Default::default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    for download in ["1", "true"] {
+      let response = server.get(format!("/content/{png}?download={download}"));
+      assert_eq!(response.status(), StatusCode::OK);
+      assert_eq!(
+        response.headers()[header::CONTENT_DISPOSITION],
+        format!("attachment; filename=\"{png}.png\"")
+      );
+      assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+      assert_eq!(response.text().unwrap(), "hello");
+    }
+
+    let response = server.get(format!("/content/{unknown}?download=1"));
+    assert_eq!(
+      response.headers()[header::CONTENT_DISPOSITION],
+      format!("attachment; filename=\"{unknown}.bin\"")
+    );
+
+    let response = server.get(format!("/content/{png}?download=1&hex=true"));
+    assert_eq!(
+      response.headers()[header::CONTENT_DISPOSITION],
+      format!("attachment; filename=\"{png}.txt\"")
+    );
+
+    for path in [
+      format!("/content/{png}"),
+      format!("/content/{png}?download=0"),
+    ] {
+      let response = server.get(path);
+      assert_eq!(response.status(), StatusCode::OK);
+      assert!(response
+        .headers()
+        .get(header::CONTENT_DISPOSITION)
+        .is_none());
+    }
+
+    assert_eq!(
+      server.get(format!("/content/{png}?download=yes")).status(),
+      StatusCode::BAD_REQUEST
+    );
+  }
+
+  #[tokio::test]
+  async fn content_security_policy_no_origin() {
+    let (headers, _) = content_response(
+      Inscription::new(Some("text/plain".as_bytes().to_vec()), Some(vec![1, 2, 3])),
+      AcceptEncoding::default(),
+      &ServerConfig::default(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(
+      headers["content-security-policy"],
+      HeaderValue::from_static(
+        "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:; sandbox allow-scripts"
+      )
+    );
+  }
+
+  #[tokio::test]
+  async fn content_security_policy_with_origin() {
+    let (headers, _) = content_response(
+      Inscription::new(Some("text/plain".as_bytes().to_vec()), Some(vec![1, 2, 3])),
+      AcceptEncoding::default(),
+      &ServerConfig {
+        csp_origin: Some("https://ordinals.com".into()),
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["content-security-policy"], HeaderValue::from_static("default-src https://ordinals.com/content/ https://ordinals.com/blockheight https://ordinals.com/blockhash https://ordinals.com/blockhash/ https://ordinals.com/blocktime https://ordinals.com/r/ 'unsafe-eval' 'unsafe-inline' data: blob:; sandbox allow-scripts"));
+  }
+
+  #[test]
+  fn code_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/javascript", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<html lang=en data-inscription={inscription_id} data-language=javascript>.*"),
+    );
+  }
+
+  #[tokio::test]
+  async fn content_response_no_content_type() {
+    let (headers, body) = content_response(
+      Inscription::new(None, Some(Vec::new())),
+      AcceptEncoding::default(),
+      &ServerConfig::default(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["content-type"], "application/octet-stream");
+    assert!(body.is_empty());
+  }
+
+  #[tokio::test]
+  async fn content_response_bad_content_type() {
+    let (headers, body) = content_response(
+      Inscription::new(Some("\n".as_bytes().to_vec()), Some(Vec::new())),
+      AcceptEncoding::default(),
+      &ServerConfig::default(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["content-type"], "application/octet-stream");
+    assert!(body.is_empty());
+  }
+
+  const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+  #[tokio::test]
+  async fn content_response_sniffs_missing_content_type() {
+    let (headers, body) = content_response(
+      Inscription::new(None, Some(PNG.into())),
+      AcceptEncoding::default(),
+      &ServerConfig::default(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["content-type"], "image/png");
+    assert!(!headers.contains_key(sniff::X_ORIGINAL_CONTENT_TYPE));
+    assert_eq!(body, PNG);
+  }
+
+  #[tokio::test]
+  async fn content_response_sniffs_invalid_content_type() {
+    let (headers, _body) = content_response(
+      Inscription::new(Some("foo".into()), Some(PNG.into())),
+      AcceptEncoding::default(),
+      &ServerConfig::default(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["content-type"], "image/png");
+    assert_eq!(headers[sniff::X_ORIGINAL_CONTENT_TYPE], "foo");
+  }
+
+  #[tokio::test]
+  async fn content_response_does_not_override_valid_content_type() {
+    let (headers, _body) = content_response(
+      Inscription::new(Some("text/plain;charset=utf-8".into()), Some(PNG.into())),
+      AcceptEncoding::default(),
+      &ServerConfig::default(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["content-type"], "text/plain;charset=utf-8");
+    assert!(!headers.contains_key(sniff::X_ORIGINAL_CONTENT_TYPE));
+  }
+
+  #[tokio::test]
+  async fn content_response_does_not_sniff_encoded_content() {
+    let (headers, _body) = content_response(
+      Inscription {
+        content_encoding: Some("br".into()),
+        ..Inscription::new(None, Some(PNG.into()))
+      },
+      AcceptEncoding(Some("br".into())),
+      &ServerConfig::default(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["content-type"], "application/octet-stream");
+  }
+
+  #[test]
+  fn content_with_missing_content_type_is_sniffed() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription::new(None, Some(PNG.into())).to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = server.get(format!("/content/{}", InscriptionId { txid, index: 0 }));
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+    assert!(!response
+      .headers()
+      .contains_key(sniff::X_ORIGINAL_CONTENT_TYPE));
+    assert_eq!(response.bytes().unwrap(), PNG);
+  }
+
+  #[test]
+  fn text_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{}", inscription_id),
+      StatusCode::OK,
+      "default-src 'self'",
+      format!(".*<html lang=en data-inscription={}>.*", inscription_id),
+    );
+  }
+
+  #[test]
+  fn audio_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("audio/flac", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<audio .*>\s*<source src=/content/{inscription_id}>.*"),
+    );
+  }
+
+  #[test]
+  fn font_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("font/ttf", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*src: url\(/content/{inscription_id}\).*"),
+    );
+  }
+
+  #[test]
+  fn pdf_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("application/pdf", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<canvas data-inscription={inscription_id}></canvas>.*"),
+    );
+  }
+
+  #[test]
+  fn markdown_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/markdown", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<html lang=en data-inscription={inscription_id}>.*"),
+    );
+  }
+
+  #[test]
+  fn image_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      "default-src 'self' 'unsafe-inline'",
+      format!(r".*background-image: url\(/content/{inscription_id}\);.*"),
+    );
+  }
+
+  #[test]
+  fn iframe_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/html;charset=utf-8", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:; sandbox allow-scripts",
+      "hello",
+    );
+  }
+
+  #[test]
+  fn unknown_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      "default-src 'self'",
+      fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    );
+  }
+
+  #[test]
+  fn video_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("video/webm", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<video .*>\s*<source src=/content/{inscription_id}>.*"),
+    );
+  }
+
+  #[test]
+  fn inscription_page_title() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      ".*<title>Inscription 0</title>.*",
+    );
+  }
+
+  #[test]
+  fn inscription_page_has_sat_when_sats_are_tracked() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      r".*<dt>sat</dt>\s*<dd><a href=/sat/5000000000>5000000000</a></dd>\s*<dt>preview</dt>.*",
+    );
+  }
+
+  #[test]
+  fn inscription_page_does_not_have_sat_when_sats_are_not_tracked() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      r".*<dt>output value</dt>\s*<dd>5000000000</dd>\s*<dt>preview</dt>.*",
+    );
+  }
+
+  #[test]
+  fn strict_transport_security_header_is_set() {
+    assert_eq!(
+      TestServer::new()
+        .get("/status")
+        .headers()
+        .get(header::STRICT_TRANSPORT_SECURITY)
+        .unwrap(),
+      "max-age=31536000; includeSubDomains; preload",
+    );
+  }
+
+  #[test]
+  fn responses_carry_the_ord_version() {
+    let server = TestServer::new();
+
+    for path in ["/status", "/api/v1/node/info", "/nonexistent"] {
+      let version = server.get(path).headers()[info::X_ORD_VERSION]
+        .to_str()
+        .unwrap()
+        .to_owned();
+
+      assert!(
+        version.starts_with(env!("CARGO_PKG_VERSION")),
+        "{path}: {version}"
+      );
+    }
+
+    let header = server.get("/version").headers()[info::X_ORD_VERSION]
+      .to_str()
+      .unwrap()
+      .to_owned();
+
+    let version = server.get_json::<info::VersionJson>("/version");
+
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(version.schema_version, crate::index::SCHEMA_VERSION);
+    assert_eq!(
+      header,
+      match version.commit {
+        Some(commit) => format!("{}+{commit}", version.version),
+        None => version.version,
+      }
+    );
+  }
+
+  #[test]
+  fn feed() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+    server.mine_blocks(1);
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/feed.xml",
+      StatusCode::OK,
+      ".*<title>Inscription 0</title>.*",
+    );
+  }
+
+  #[test]
+  fn inscription_with_unknown_type_and_no_body_has_unknown_preview() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription::new(Some("foo/bar".as_bytes().to_vec()), None).to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    );
+  }
+
+  #[test]
+  fn inscription_with_known_type_and_no_body_has_unknown_preview() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription::new(Some("image/png".as_bytes().to_vec()), None).to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    );
+  }
+
+  #[test]
+  fn content_responses_have_cache_control_headers() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = server.get(format!("/content/{}", InscriptionId { txid, index: 0 }));
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "public, max-age=31536000, immutable"
+    );
+  }
+
+  #[test]
+  fn sequential_requests_are_within_concurrency_limit() {
+    let server = TestServer::new_with_args(&[], &["--max-concurrent-per-ip", "1"]);
+
+    for _ in 0..3 {
+      server.assert_response("/blockcount", StatusCode::OK, "1");
+    }
+
+    server.assert_response(
+      "/inscription/0",
+      StatusCode::NOT_FOUND,
+      "inscription 0 not found",
+    );
+    server.assert_response("/blockcount", StatusCode::OK, "1");
+  }
+
+  #[test]
+  fn responses_vary_on_negotiated_headers() {
+    let server = TestServer::new_with_regtest_with_json_api();
+    server.mine_blocks(1);
+
+    let plain = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let encoded = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        Inscription {
+          content_encoding: Some("br".into()),
+          ..inscription("text/plain", "hello")
+        }
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let vary = |path: String| {
+      server
+        .get(path)
+        .headers()
+        .get_all(header::VARY)
+        .iter()
+        .map(|value| value.to_str().unwrap().to_string())
+        .filter(|value| value.starts_with("Accept"))
+        .collect::<Vec<String>>()
+    };
+
+    let plain = InscriptionId {
+      txid: plain,
+      index: 0,
+    };
+
+    let encoded = InscriptionId {
+      txid: encoded,
+      index: 0,
+    };
+
+    assert_eq!(vary(format!("/content/{plain}")), Vec::<String>::new());
+    assert_eq!(vary(format!("/content/{encoded}")), ["Accept-Encoding"]);
+    assert_eq!(
+      vary(format!("/content/{encoded}?hex=true")),
+      Vec::<String>::new()
+    );
+    assert_eq!(vary(format!("/inscription/{plain}")), ["Accept"]);
+    assert_eq!(vary("/sat/0".into()), ["Accept"]);
+    assert_eq!(vary("/blockheight".into()), Vec::<String>::new());
+    assert_eq!(vary(format!("/content/{}", inscription_id(1))), ["Accept"]);
+    assert_eq!(vary("/sat/=".into()), ["Accept"]);
+    assert_eq!(
+      vary("/api/v1/ord/id/foo/inscription".into()),
+      Vec::<String>::new()
+    );
+
+    assert_eq!(
+      server
+        .get(format!("/content/{encoded}"))
+        .headers()
+        .get_all(header::VARY)
+        .iter()
+        .collect::<Vec<&HeaderValue>>(),
+      [
+        "Accept-Encoding",
+        "origin, access-control-request-method, access-control-request-headers"
+      ]
+    );
+  }
+
+  #[test]
+  fn content_responses_have_etag() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let response = server.get(format!("/content/{inscription_id}"));
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::ETAG).unwrap(),
+      &format!("\"{inscription_id}\"")
+    );
+
+    let client = reqwest::blocking::Client::new();
+
+    let response = client
+      .get(server.join_url(&format!("/content/{inscription_id}")))
+      .header(header::IF_NONE_MATCH, format!("\"{inscription_id}\""))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "public, max-age=31536000, immutable"
+    );
+    assert_eq!(response.text().unwrap(), "");
+
+    let response = client
+      .get(server.join_url(&format!("/content/{inscription_id}")))
+      .header(header::IF_NONE_MATCH, "\"foo\"")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), "hello");
+  }
+
+  #[test]
+  fn content_responses_have_content_length_and_last_modified() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let response = server.get(format!("/content/{inscription_id}"));
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get(header::CONTENT_LENGTH).unwrap(), "5");
+    assert_eq!(
+      response.headers().get(header::LAST_MODIFIED).unwrap(),
+      "Thu, 01 Jan 1970 00:00:02 GMT"
+    );
+  }
+
+  #[test]
+  fn content_is_served_from_cache() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest"],
+      &["--content-cache-bytes", "1000"],
+    );
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    fn lookups(server: &TestServer, result: &str) -> u64 {
+      let name = format!("ord_content_cache_lookups_total{{result=\"{result}\"}}");
+      server
+        .get("/metrics")
+        .text()
+        .unwrap()
+        .lines()
+        .find_map(|line| line.strip_prefix(&name)?.trim().parse().ok())
+        .unwrap_or_default()
+    }
+
+    server.assert_response(
+      format!("/content/{inscription_id}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {inscription_id} not found"),
+    );
+
+    server.mine_blocks(1);
+
+    let hits = lookups(&server, "hit");
+    let misses = lookups(&server, "miss");
+
+    for _ in 0..2 {
+      let response = server.get(format!("/content/{inscription_id}"));
+      assert_eq!(response.status(), StatusCode::OK);
+      assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/foo; charset=utf-8"
+      );
+      assert_eq!(
+        response.headers()[header::LAST_MODIFIED],
+        "Thu, 01 Jan 1970 00:00:02 GMT"
+      );
+      assert_eq!(response.text().unwrap(), "hello");
+    }
+
+    assert_eq!(lookups(&server, "miss"), misses + 1);
+    assert_eq!(lookups(&server, "hit"), hits + 1);
+  }
+
+  #[test]
+  fn compressed_content_is_served_from_cache() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest"],
+      &["--content-cache-bytes", "100000"],
+    );
+    server.mine_blocks(1);
+
+    let body = "console.log('hello');\n".repeat(100);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/javascript", &body).to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let client = reqwest::blocking::Client::builder()
+      .brotli(false)
+      .build()
+      .unwrap();
+
+    for _ in 0..2 {
+      let response = client
+        .get(server.join_url(&format!("/content/{}", InscriptionId { txid, index: 0 })))
+        .header(header::ACCEPT_ENCODING, "br")
+        .send()
+        .unwrap();
+
+      assert_eq!(response.status(), StatusCode::OK);
+      assert_eq!(response.headers()[header::CONTENT_ENCODING], "br");
+      assert_eq!(response.headers()[header::VARY], "Accept-Encoding");
+
+      let compressed = response.bytes().unwrap();
+      assert!(compressed.len() < body.len());
+
+      let mut decompressed = String::new();
+      Decompressor::new(&compressed[..], 4096)
+        .read_to_string(&mut decompressed)
+        .unwrap();
+      assert_eq!(decompressed, body);
+    }
+
+    let response = client
+      .get(server.join_url(&format!("/content/{}", InscriptionId { txid, index: 0 })))
+      .header(header::ACCEPT_ENCODING, "br")
+      .header(header::RANGE, "bytes=0-6")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers().get(header::CONTENT_ENCODING), None);
+    assert_eq!(response.text().unwrap(), "console");
+  }
+
+  #[test]
+  fn head_requests_have_get_headers_and_no_body() {
+    let server = TestServer::new_with_regtest_with_json_api();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let client = reqwest::blocking::Client::new();
+
+    for path in [
+      format!("/content/{inscription_id}"),
+      "/r/blockheight".into(),
+      format!("/api/v1/ord/id/{inscription_id}/inscription"),
+    ] {
+      let get = client.get(server.join_url(&path)).send().unwrap();
+      let head = client.head(server.join_url(&path)).send().unwrap();
+
+      assert_eq!(get.status(), StatusCode::OK, "{path}");
+      assert_eq!(head.status(), StatusCode::OK, "{path}");
+
+      for name in [
+        header::CONTENT_LENGTH,
+        header::CONTENT_TYPE,
+        header::ETAG,
+        header::CACHE_CONTROL,
+      ] {
+        assert_eq!(
+          head.headers().get(&name),
+          get.headers().get(&name),
+          "{path} {name}"
+        );
+      }
+
+      assert_eq!(head.bytes().unwrap().len(), 0, "{path}");
+      assert!(!get.bytes().unwrap().is_empty(), "{path}");
+    }
+  }
+
+  #[test]
+  fn unconfirmed_content_is_not_cached() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url(&format!("/content/{inscription_id}")))
+      .header(header::IF_NONE_MATCH, format!("\"{inscription_id}\""))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers().get(header::ETAG), None);
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "no-store"
+    );
+  }
+
+  #[test]
+  fn content_etag_depends_on_content_encoding() {
+    let inscription_id = inscription_id(1);
+
+    let mut headers = HeaderMap::new();
+
+    assert_eq!(
+      Server::content_etag(inscription_id, &headers, false),
+      format!("\"{inscription_id}\"").parse::<ETag>().unwrap()
+    );
+
+    headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static("br"));
+
+    assert_eq!(
+      Server::content_etag(inscription_id, &headers, false),
+      format!("\"{inscription_id}-br\"").parse::<ETag>().unwrap()
+    );
+
+    assert_eq!(
+      Server::content_etag(inscription_id, &headers, true),
+      format!("\"{inscription_id}-hex\"").parse::<ETag>().unwrap()
+    );
+  }
+
+  #[test]
+  fn large_content_is_streamed_intact() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    let body = (0..1 << 20)
+      .map(|i: u32| (i % 256).try_into().unwrap())
+      .collect::<Vec<u8>>();
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("application/octet-stream", &body).to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url(&format!("/content/{}", InscriptionId { txid, index: 0 })))
+      .header(header::ACCEPT_ENCODING, "identity")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_LENGTH).unwrap(),
+      &body.len().to_string()
+    );
+    assert_eq!(response.headers().get(header::TRANSFER_ENCODING), None);
+    assert_eq!(response.bytes().unwrap(), body);
+  }
+
+  #[test]
+  fn content_range_requests() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("audio/mpeg", "0123456789").to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let get = |range: &str| {
+      reqwest::blocking::Client::new()
+        .get(server.join_url(&format!("/content/{inscription_id}")))
+        .header(header::RANGE, range)
+        .send()
+        .unwrap()
+    };
+
+    let response = get("bytes=2-5");
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 2-5/10");
+    assert_eq!(response.headers()[header::CONTENT_LENGTH], "4");
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "audio/mpeg");
+    assert_eq!(response.text().unwrap(), "2345");
+
+    let response = get("bytes=7-");
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 7-9/10");
+    assert_eq!(response.text().unwrap(), "789");
+
+    let response = get("bytes=10-20");
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes */10");
+    assert_eq!(response.text().unwrap(), "");
+
+    let response = get("bytes=0-1,4-5");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+    assert_eq!(response.headers().get(header::CONTENT_RANGE), None);
+    assert_eq!(response.text().unwrap(), "0123456789");
+  }
+
+  #[test]
+  fn content_ranges_are_of_the_stored_representation() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    let body = (0..2048)
+      .map(|i: u32| (i % 256).try_into().unwrap())
+      .collect::<Vec<u8>>();
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_encoding: Some("br".into()),
+          ..inscription("text/plain", &body)
+        }
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = reqwest::blocking::Client::builder()
+      .brotli(false)
+      .build()
+      .unwrap()
+      .get(server.join_url(&format!("/content/{}", InscriptionId { txid, index: 0 })))
+      .header(header::ACCEPT_ENCODING, "br, gzip")
+      .header(header::RANGE, "bytes=1024-")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()[header::CONTENT_ENCODING], "br");
+    assert_eq!(
+      response.headers()[header::CONTENT_RANGE],
+      "bytes 1024-2047/2048"
+    );
+    assert_eq!(response.bytes().unwrap(), body[1024..]);
+  }
+
+  #[test]
+  fn content_and_metadata_as_hex() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    let body = vec![0xff, 0x00, 0xc3, 0x28];
+    let metadata = vec![0xa1, 0x01, 0x02];
+
+    let inscription = Inscription {
+      content_encoding: Some("br".into()),
+      metadata: Some(metadata.clone()),
+      ..Inscription::new(Some("text/plain".into()), Some(body.clone()))
+    };
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription.to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let response = server.get(format!("/content/{inscription_id}?hex=true"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/plain; charset=utf-8"
+    );
+    assert_eq!(response.headers().get(header::CONTENT_ENCODING), None);
+    assert_eq!(
+      response.headers().get(header::ETAG).unwrap(),
+      &format!("\"{inscription_id}-hex\"")
+    );
+    assert_eq!(hex::decode(response.text().unwrap()).unwrap(), body);
+
+    let response = server.get(format!("/r/metadata/{inscription_id}?hex=true"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(hex::decode(response.text().unwrap()).unwrap(), metadata);
+
+    assert_eq!(
+      server.get_json::<String>(format!("/r/metadata/{inscription_id}")),
+      hex::encode(&metadata)
+    );
+  }
+
+  #[test]
+  fn hex_output_is_bounded() {
+    assert_eq!(HexQuery::encode(&[0xab; 2]).unwrap(), "abab");
+
+    let Err(ServerError::PayloadTooLarge(message)) =
+      HexQuery::encode(&vec![0; HexQuery::MAX_BYTES + 1])
+    else {
+      panic!("hex output should be bounded");
+    };
+
+    assert_eq!(message, "hex output is limited to 2097152 stored bytes");
+  }
+
+  #[test]
+  fn error_content_responses_have_max_age_zero_cache_control_headers() {
+    let server = TestServer::new_with_regtest();
+    let response =
+      server.get("/content/6ac5cacb768794f4fd7a78bf00f2074891fce68bd65c4ff36e77177237aacacai0");
+
+    assert_eq!(response.status(), 404);
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "no-store"
+    );
+  }
+
+  #[test]
+  fn inscriptions_page_with_no_prev_or_next() {
+    TestServer::new_with_regtest_with_index_sats().assert_response_regex(
+      "/inscriptions",
+      StatusCode::OK,
+      ".*prev\nnext.*",
+    );
+  }
+
+  #[test]
+  fn inscriptions_page_with_no_next() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+
+    for i in 0..101 {
+      server.mine_blocks(1);
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+        ..Default::default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/inscriptions/1",
+      StatusCode::OK,
+      ".*<a class=prev href=/inscriptions/0>prev</a>\nnext.*",
+    );
+  }
+
+  #[test]
+  fn inscriptions_page_with_no_prev() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+
+    for i in 0..101 {
+      server.mine_blocks(1);
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+        ..Default::default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/inscriptions/0",
+      StatusCode::OK,
+      ".*prev\n<a class=next href=/inscriptions/1>next</a>.*",
+    );
+  }
+
+  #[test]
+  fn inscriptions_cursor_pagination_matches_offset_pagination() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    server.mine_blocks(210);
+
+    for i in 0..210 {
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+        ..Default::default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    for (order, query) in [
+      ("asc", ""),
+      ("desc", ""),
+      ("asc", "&from_height=211&to_height=212"),
+    ] {
+      let mut offset = Vec::new();
+
+      for page_index in 0.. {
+        let page = server
+          .get_json::<InscriptionsJson>(format!("/inscriptions/{page_index}?order={order}{query}"));
+
+        offset.extend(page.inscriptions);
+
+        if !page.more {
+          assert_eq!(page.next_cursor, None);
+          break;
+        }
+      }
+
+      let mut page =
+        server.get_json::<InscriptionsJson>(format!("/inscriptions?order={order}{query}"));
+
+      let mut cursor = Vec::new();
+
+      cursor.extend(page.inscriptions);
+
+      while let Some(next_cursor) = page.next_cursor {
+        page = server.get_json::<InscriptionsJson>(format!(
+          "/inscriptions?order={order}{query}&cursor={next_cursor}"
+        ));
+
+        cursor.extend(page.inscriptions);
+      }
+
+      assert_eq!(offset.len(), 210);
+      pretty_assert_eq!(cursor, offset);
+    }
+
+    server.assert_response(
+      "/inscriptions?cursor=!!",
+      StatusCode::BAD_REQUEST,
+      "malformed cursor `!!`",
+    );
+
+    server.assert_response(
+      format!("/inscriptions?cursor={}", CursorQuery::encode(1_000)),
+      StatusCode::BAD_REQUEST,
+      "cursor out of range",
+    );
+
+    server.assert_response(
+      format!("/inscriptions/1?cursor={}", CursorQuery::encode(0)),
+      StatusCode::BAD_REQUEST,
+      "cursor and page cannot be combined",
+    );
+  }
+
+  #[test]
+  fn inscriptions_since_sequence_waits_for_new_inscriptions() {
+    let server = Arc::new(TestServer::new_with_regtest_with_json_api());
+
+    server.mine_blocks(2);
+
+    let first = InscriptionId {
+      txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "first").to_witness())],
+        ..Default::default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let listed = server.get_json::<InscriptionsJson>("/inscriptions?since_sequence=0");
+    assert_eq!(listed.inscriptions, Vec::new());
+    assert_eq!(listed.last_sequence, None);
+
+    assert_eq!(
+      server
+        .get_json::<InscriptionsJson>("/inscriptions")
+        .inscriptions,
+      [first]
+    );
+
+    let start = Instant::now();
+    let listed = server.get_json::<InscriptionsJson>("/inscriptions?since_sequence=0&wait=1");
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert_eq!(listed.inscriptions, Vec::new());
+
+    let poll = {
+      let server = server.clone();
+      thread::spawn(move || {
+        let start = Instant::now();
+
+        let response = reqwest::blocking::Client::new()
+          .get(server.join_url("/inscriptions?since_sequence=0&wait=8"))
+          .header(header::ACCEPT, "application/json")
+          .send()
+          .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        (
+          start.elapsed(),
+          response.json::<InscriptionsJson>().unwrap(),
+        )
+      })
+    };
+
+    thread::sleep(Duration::from_millis(500));
+
+    let second = InscriptionId {
+      txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 0, 0, inscription("text/plain", "second").to_witness())],
+        ..Default::default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+    server.index.update().unwrap();
+
+    let (elapsed, listed) = poll.join().unwrap();
+
+    assert!(elapsed < Duration::from_secs(8), "{elapsed:?}");
+    assert_eq!(listed.inscriptions, [second]);
+    assert_eq!(listed.last_sequence, Some(1));
+    assert!(!listed.more);
+  }
+
+  #[test]
+  fn inscriptions_wait_requires_since_sequence() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    server.assert_response(
+      "/inscriptions?wait=1",
+      StatusCode::BAD_REQUEST,
+      "wait requires since_sequence",
+    );
+
+    server.assert_response(
+      "/inscriptions?since_sequence=0&order=asc",
+      StatusCode::BAD_REQUEST,
+      "since_sequence cannot be combined with other listing parameters",
+    );
+  }
+
+  #[test]
+  fn inscriptions_content_type_filter() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    server.mine_blocks(4);
+
+    let mut ids = Vec::new();
+
+    let content_types = [
+      "text/plain;charset=utf-8",
+      "image/png",
+      "text/html",
+      "IMAGE/jpeg",
+    ];
+
+    for (i, content_type) in content_types.into_iter().enumerate() {
+      ids.push(InscriptionId {
+        txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+          inputs: &[(i + 1, 0, 0, inscription(content_type, "hello").to_witness())],
+          ..Default::default()
+        }),
+        index: 0,
+      });
+
+      server.mine_blocks(1);
+    }
+
+    for (filter, expected) in [
+      ("image", vec![3, 1]),
+      ("text/plain", vec![0]),
+      ("text/html", vec![2]),
+      ("video", Vec::new()),
+    ] {
+      pretty_assert_eq!(
+        server.get_json::<InscriptionsJson>(format!("/inscriptions?content_type={filter}")),
+        InscriptionsJson {
+          inscriptions: expected.iter().map(|i| ids[*i]).collect(),
+          page_index: 0,
+          limit: 100,
+          more: false,
+          next_cursor: None,
+          truncated: false,
+          items: expected
+            .iter()
+            .map(|i| InscriptionItemJson {
+              id: ids[*i],
+              content_type: Some(content_types[*i].into()),
+              content_length: Some(5),
+            })
+            .collect(),
+          last_sequence: None,
+        }
+      );
+    }
+
+    assert_eq!(
+      server
+        .get_json::<InscriptionsJson>("/inscriptions?content_type=image&order=asc")
+        .inscriptions,
+      [ids[1], ids[3]],
+    );
+
+    for filter in ["", "image/", "/png", "text/plain;charset=utf-8", "a/b/c"] {
+      server.assert_response(
+        format!("/inscriptions?content_type={}", filter.replace(';', "%3B")),
+        StatusCode::BAD_REQUEST,
+        &format!("invalid content type filter `{filter}`"),
+      );
+    }
+
+    server.assert_response(
+      "/inscriptions/1?content_type=image",
+      StatusCode::BAD_REQUEST,
+      "content_type and page cannot be combined",
+    );
+  }
+
+  #[test]
+  fn inscriptions_order() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    server.mine_blocks(110);
+
+    let mut ids = Vec::new();
+
+    for i in 0..110 {
+      ids.push(InscriptionId {
+        txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+          inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+          ..Default::default()
+        }),
+        index: 0,
+      });
+    }
+
+    server.mine_blocks(1);
+
+    let pages = |order: &str| {
+      let mut inscriptions = Vec::new();
+
+      for page_index in 0.. {
+        let page =
+          server.get_json::<InscriptionsJson>(format!("/inscriptions/{page_index}?order={order}"));
+
+        assert_eq!(page.page_index, page_index);
+        assert_eq!(page.inscriptions.len(), if page.more { 100 } else { 10 });
+
+        inscriptions.extend(page.inscriptions);
+
+        if !page.more {
+          break;
+        }
+      }
+
+      inscriptions
+    };
+
+    pretty_assert_eq!(pages("asc"), ids);
+
+    ids.reverse();
+
+    pretty_assert_eq!(pages("desc"), ids);
+
+    pretty_assert_eq!(
+      server
+        .get_json::<InscriptionsJson>("/inscriptions")
+        .inscriptions,
+      ids[..100]
+    );
+
+    server.assert_response_regex(
+      "/inscriptions/0?order=asc",
+      StatusCode::OK,
+      ".*prev\n<a class=next href=/inscriptions/1\\?order=asc>next</a>.*",
+    );
+
+    server.assert_response(
+      "/inscriptions?order=foo",
+      StatusCode::BAD_REQUEST,
+      "unknown order `foo`, expected `asc` or `desc`",
+    );
+  }
+
+  #[test]
+  #[ignore]
+  fn collections_page_prev_and_next() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+
+    let mut parent_ids = Vec::new();
+
+    for i in 0..101 {
+      server.mine_blocks(1);
+
+      parent_ids.push(InscriptionId {
+        txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+          inputs: &[(i + 1, 0, 0, inscription("text/plain", "hello").to_witness())],
+          ..Default::default()
+        }),
+        index: 0,
+      });
+    }
+
+    for (i, parent_id) in parent_ids.iter().enumerate().take(101) {
+      server.mine_blocks(1);
+
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[
+          (i + 2, 1, 0, Default::default()),
+          (
+            i + 102,
+            0,
+            0,
+            Inscription {
+              content_type: Some("text/plain".into()),
+              body: Some("hello".into()),
+              parents: vec![parent_id.value()],
+              ..Default::default()
+            }
+            .to_witness(),
+          ),
+        ],
+        outputs: 2,
+        output_values: &[50 * COIN_VALUE, 50 * COIN_VALUE],
+        ..Default::default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/collections",
+      StatusCode::OK,
+      r".*
+<h1>Collections</h1>
+<div class=thumbnails>
+  <a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>
+  (<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>\s*){99}
+</div>
+<div class=center>
+prev
+<a class=next href=/collections/1>next</a>
+</div>.*"
+        .to_string()
+        .unindent(),
+    );
+
+    server.assert_response_regex(
+      "/collections/1",
+      StatusCode::OK,
+      ".*
+<h1>Collections</h1>
+<div class=thumbnails>
+  <a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>
+</div>
+<div class=center>
+<a class=prev href=/collections/0>prev</a>
+next
+</div>.*"
+        .unindent(),
+    );
+  }
+
+  #[test]
+  fn responses_are_gzipped() {
+    let server = TestServer::new();
+
+    let mut headers = HeaderMap::new();
+
+    headers.insert(header::ACCEPT_ENCODING, "gzip".parse().unwrap());
+
+    let response = reqwest::blocking::Client::builder()
+      .default_headers(headers)
+      .build()
+      .unwrap()
+      .get(server.join_url("/"))
+      .send()
+      .unwrap();
+
+    assert_eq!(
+      response.headers().get(header::CONTENT_ENCODING).unwrap(),
+      "gzip"
+    );
+  }
+
+  #[test]
+  fn responses_are_brotlied() {
+    let server = TestServer::new();
+
+    let mut headers = HeaderMap::new();
+
+    headers.insert(header::ACCEPT_ENCODING, "br".parse().unwrap());
+
+    let response = reqwest::blocking::Client::builder()
+      .default_headers(headers)
+      .brotli(false)
+      .build()
+      .unwrap()
+      .get(server.join_url("/"))
+      .send()
+      .unwrap();
+
+    assert_eq!(
+      response.headers().get(header::CONTENT_ENCODING).unwrap(),
+      "br"
+    );
+  }
+
+  #[test]
+  fn inscriptions_can_be_hidden_with_config() {
+    let bitcoin_rpc_server = test_bitcoincore_rpc::spawn();
+    bitcoin_rpc_server.mine_blocks(1);
+    let txid = bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+    let inscription = InscriptionId { txid, index: 0 };
+    bitcoin_rpc_server.mine_blocks(1);
+
+    let server = TestServer::new_with_bitcoin_rpc_server_and_config(
+      bitcoin_rpc_server,
+      format!("\"hidden\":\n - {inscription}"),
+    );
+
+    server.assert_response(
+      format!("/preview/{inscription}"),
+      StatusCode::OK,
+      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    );
+
+    server.assert_response(
+      format!("/content/{inscription}"),
+      StatusCode::OK,
+      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    );
+  }
+
+  #[test]
+  fn inscription_links_to_parent() {
+    let server = TestServer::new_with_regtest_with_json_api();
+    server.mine_blocks(1);
+
+    let parent_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let parent_inscription_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          2,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value()],
+            ..Default::default()
+          }
+          .to_witness(),
+        ),
+        (2, 1, 0, Default::default()),
+      ],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.assert_response_regex(
+      format!("/inscription/{inscription_id}"),
+      StatusCode::OK,
+      format!(".*<title>Inscription 1</title>.*<dt>parent</dt>.*<dd><a class=monospace href=/inscription/{parent_inscription_id}>{parent_inscription_id}</a></dd>.*"),
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{parent_inscription_id}"),
+      StatusCode::OK,
+      format!(".*<title>Inscription 0</title>.*<dt>children</dt>.*<a href=/inscription/{inscription_id}>.*</a>.*"),
+    );
+
+    assert_eq!(
+      server
+        .get_json::<InscriptionJson>(format!("/inscription/{inscription_id}"))
+        .parent,
+      Some(parent_inscription_id),
+    );
+
+    assert_eq!(
+      server
+        .get_json::<InscriptionJson>(format!("/inscription/{parent_inscription_id}"))
+        .children,
+      [inscription_id],
+    );
+  }
+
+  #[test]
+  fn inscription_with_and_without_children_page() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let parent_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let parent_inscription_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    server.assert_response_regex(
+      format!("/children/{parent_inscription_id}"),
+      StatusCode::OK,
+      ".*<h3>No children</h3>.*",
+    );
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          2,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value()],
+            ..Default::default()
+          }
+          .to_witness(),
+        ),
+        (2, 1, 0, Default::default()),
+      ],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.assert_response_regex(
+      format!("/children/{parent_inscription_id}"),
+      StatusCode::OK,
+      format!(".*<title>Inscription 0 Children</title>.*<h1><a href=/inscription/{parent_inscription_id}>Inscription 0</a> Children</h1>.*<div class=thumbnails>.*<a href=/inscription/{inscription_id}><iframe .* src=/preview/{inscription_id}></iframe></a>.*"),
+    );
+  }
+
+  #[test]
+  fn inscriptions_page_shows_max_four_children() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let parent_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(6);
+
+    let parent_inscription_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let _txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          2,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value()],
+            ..Default::default()
+          }
+          .to_witness(),
+        ),
+        (
+          3,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value()],
+            ..Default::default()
+          }
+          .to_witness(),
+        ),
+        (
+          4,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value()],
+            ..Default::default()
+          }
+          .to_witness(),
+        ),
+        (
+          5,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value()],
+            ..Default::default()
+          }
+          .to_witness(),
+        ),
+        (
+          6,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value()],
+            ..Default::default()
+          }
+          .to_witness(),
+        ),
+        (2, 1, 0, Default::default()),
+      ],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{parent_inscription_id}"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscription 0</title>.*
+.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
+.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
+.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
+.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
+    <div class=center>
+      <a href=/children/{parent_inscription_id}>all</a>
+    </div>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn inscription_number_endpoint() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(2);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, inscription("text/plain", "hello").to_witness()),
+        (2, 0, 0, inscription("text/plain", "cursed").to_witness()),
+      ],
+      outputs: 2,
+      ..Default::default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+    let cursed_inscription_id = InscriptionId { txid, index: 1 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{inscription_id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription 0</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{inscription_id}</dd>.*"
+      ),
+    );
+    server.assert_response_regex(
+      "/inscription/0",
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription 0</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{inscription_id}</dd>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      "/inscription/-1",
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription -1</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{cursed_inscription_id}</dd>.*"
+      ),
+    )
+  }
+
+  #[test]
+  fn charm_cursed() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(2);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, Witness::default()),
+        (2, 0, 0, inscription("text/plain", "cursed").to_witness()),
+      ],
+      outputs: 2,
+      ..Default::default()
+    });
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription -1</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{id}</dd>
+  <dt>charms</dt>
+  <dd>
+    <span title=cursed>👹</span>
+  </dd>
+  .*
+</dl>
+.*
+"
+      ),
+    );
+  }
+
+  #[test]
+  fn charm_vindicated() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(110);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, Witness::default()),
+        (2, 0, 0, inscription("text/plain", "cursed").to_witness()),
+      ],
+      outputs: 2,
+      ..Default::default()
+    });
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription 0</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{id}</dd>
+  <dt>charms</dt>
+  <dd>
+    <span title=vindicated>❤️‍🔥</span>
+  </dd>
+  .*
+</dl>
+.*
+"
+      ),
+    );
+  }
+
+  #[test]
+  fn charm_coin() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+
+    server.mine_blocks(2);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription 0</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{id}</dd>
+  <dt>charms</dt>
+  <dd>.*<span title=coin>🪙</span>.*</dd>
+  .*
+</dl>
+.*
+"
+      ),
+    );
+  }
+
+  #[test]
+  fn charm_uncommon() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+
+    server.mine_blocks(2);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription 0</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{id}</dd>
+  <dt>charms</dt>
+  <dd>.*<span title=uncommon>🌱</span>.*</dd>
+  .*
+</dl>
+.*
+"
+      ),
+    );
+  }
+
+  #[test]
+  fn charm_nineball() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+
+    server.mine_blocks(9);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(9, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription 0</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{id}</dd>
+  <dt>charms</dt>
+  <dd>.*<span title=nineball>9️⃣</span>.*</dd>
+  .*
+</dl>
+.*
+"
+      ),
+    );
+  }
+
+  #[test]
+  fn charm_reinscription() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, inscription("text/plain", "bar").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription -1</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{id}</dd>
+  <dt>charms</dt>
+  <dd>
+    <span title=reinscription>♻️</span>
+    <span title=cursed>👹</span>
+  </dd>
+  .*
+</dl>
+.*
+"
+      ),
+    );
+  }
+
+  #[test]
+  fn charm_reinscription_in_same_tx_input() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    let script = script::Builder::new()
+      .push_opcode(opcodes::OP_FALSE)
+      .push_opcode(opcodes::all::OP_IF)
+      .push_slice(b"ord")
+      .push_slice([1])
+      .push_slice(b"text/plain;charset=utf-8")
+      .push_slice([])
+      .push_slice(b"foo")
+      .push_opcode(opcodes::all::OP_ENDIF)
+      .push_opcode(opcodes::OP_FALSE)
+      .push_opcode(opcodes::all::OP_IF)
+      .push_slice(b"ord")
+      .push_slice([1])
+      .push_slice(b"text/plain;charset=utf-8")
+      .push_slice([])
+      .push_slice(b"bar")
+      .push_opcode(opcodes::all::OP_ENDIF)
+      .push_opcode(opcodes::OP_FALSE)
+      .push_opcode(opcodes::all::OP_IF)
+      .push_slice(b"ord")
+      .push_slice([1])
+      .push_slice(b"text/plain;charset=utf-8")
+      .push_slice([])
+      .push_slice(b"qix")
+      .push_opcode(opcodes::all::OP_ENDIF)
+      .into_script();
+
+    let witness = Witness::from_slice(&[script.into_bytes(), Vec::new()]);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, witness)],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription 0</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{id}</dd>
+  <dt>output value</dt>
+  .*
+</dl>
+.*
+"
+      ),
+    );
+
+    let id = InscriptionId { txid, index: 1 };
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      ".*
+    <span title=reinscription>♻️</span>
+    <span title=cursed>👹</span>.*",
+    );
+
+    let id = InscriptionId { txid, index: 2 };
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      ".*
+    <span title=reinscription>♻️</span>
+    <span title=cursed>👹</span>.*",
+    );
+  }
+
+  #[test]
+  fn charm_reinscription_in_same_tx_with_pointer() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(3);
+
+    let cursed_inscription = inscription("text/plain", "bar");
+    let reinscription: Inscription = InscriptionTemplate {
+      pointer: Some(0),
+      ..Default::default()
+    }
+    .into();
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, inscription("text/plain", "foo").to_witness()),
+        (2, 0, 0, cursed_inscription.to_witness()),
+        (3, 0, 0, reinscription.to_witness()),
+      ],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription 0</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{id}</dd>
+  <dt>output value</dt>
+  .*
+</dl>
+.*
+"
+      ),
+    );
+
+    let id = InscriptionId { txid, index: 1 };
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      ".*
+    <span title=cursed>👹</span>.*",
+    );
+
+    let id = InscriptionId { txid, index: 2 };
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      ".*
+    <span title=reinscription>♻️</span>
+    <span title=cursed>👹</span>.*",
+    );
+  }
+
+  #[test]
+  fn charm_unbound() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, envelope(&[b"ord", &[128], &[0]]))],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription -1</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{id}</dd>
+  <dt>charms</dt>
+  <dd>
+    <span title=cursed>👹</span>
+    <span title=unbound>🔓</span>
+  </dd>
+  .*
+</dl>
+.*
+"
+      ),
+    );
+  }
+
+  #[test]
+  fn charm_lost() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription 0</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{id}</dd>
+  <dt>output value</dt>
+  <dd>5000000000</dd>
+  .*
+</dl>
+.*
+"
+      ),
+    );
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      fee: 50 * COIN_VALUE,
+      ..Default::default()
+    });
+
+    server.mine_blocks_with_subsidy(1, 0);
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription 0</h1>.*
+<dl>
+  <dt>id</dt>
+  <dd class=monospace>{id}</dd>
+  <dt>charms</dt>
+  <dd>
+    <span title=lost>🤔</span>
+  </dd>
+  .*
+</dl>
+.*
+"
+      ),
+    );
+  }
+
+  #[test]
+  fn sat_recursive_endpoints() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+
+    server.assert_response(
+      "/r/sat/5000000000",
+      StatusCode::NOT_FOUND,
+      "inscriptions on sat 5000000000 not found",
+    );
+
+    let paths = ApiDoc::openapi().paths.paths;
+    assert!(paths.contains_key("/r/sat/{sat_number}"));
+    assert!(paths.contains_key("/r/sat/{sat_number}/{page}"));
+
+    server.assert_response(
+      "/r/sat/5000000000/at/0",
+      StatusCode::NOT_FOUND,
+      "inscription at index 0 on sat 5000000000 not found",
+    );
+
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let mut ids = Vec::new();
+    ids.push(InscriptionId { txid, index: 0 });
+
+    for i in 1..111 {
+      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 1, 0, inscription("text/plain", "foo").to_witness())],
+        ..Default::default()
+      });
+
+      server.mine_blocks(1);
+
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    let paginated_response = server.get_json::<SatInscriptionsJson>("/r/sat/5000000000");
+
+    let equivalent_paginated_response =
+      server.get_json::<SatInscriptionsJson>("/r/sat/5000000000/0");
+
+    assert_eq!(paginated_response.ids.len(), 100);
+    assert!(paginated_response.more);
+    assert_eq!(paginated_response.page, 0);
+
+    assert_eq!(
+      paginated_response.ids.len(),
+      equivalent_paginated_response.ids.len()
+    );
+    assert_eq!(paginated_response.more, equivalent_paginated_response.more);
+    assert_eq!(paginated_response.page, equivalent_paginated_response.page);
+
+    let paginated_response = server.get_json::<SatInscriptionsJson>("/r/sat/5000000000/1");
+
+    assert_eq!(paginated_response.ids.len(), 11);
+    assert!(!paginated_response.more);
+    assert_eq!(paginated_response.page, 1);
+
+    assert_eq!(
+      server.get_json::<SatInscriptionsJson>("/r/sat/5000000000?page=1"),
+      paginated_response
+    );
+
+    assert_eq!(
+      server
+        .get_json::<SatInscriptionJson>("/r/sat/5000000000/at/0")
+        .id,
+      Some(ids[0])
+    );
+
+    assert_eq!(
+      server
+        .get_json::<SatInscriptionJson>("/r/sat/5000000000/at/-111")
+        .id,
+      Some(ids[0])
+    );
+
+    assert_eq!(
+      server
+        .get_json::<SatInscriptionJson>("/r/sat/5000000000/at/110")
+        .id,
+      Some(ids[110])
+    );
+
+    assert_eq!(
+      server
+        .get_json::<SatInscriptionJson>("/r/sat/5000000000/at/55")
+        .id,
+      Some(ids[55])
+    );
+
+    assert_eq!(
+      server
+        .get_json::<SatInscriptionJson>("/r/sat/5000000000/at/-56")
+        .id,
+      Some(ids[55])
+    );
+
+    assert_eq!(
+      server
+        .get_json::<SatInscriptionJson>("/r/sat/5000000000/at/-1")
+        .id,
+      Some(ids[110])
+    );
+
+    server.assert_response(
+      "/r/sat/5000000000/at/111",
+      StatusCode::NOT_FOUND,
+      "inscription at index 111 on sat 5000000000 not found",
+    );
 
-    server.assert_response_regex(
-      format!("/inscription/{parent_inscription_id}"),
-      StatusCode::OK,
-      format!(
-        ".*<title>Inscription 0</title>.*
-.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
-.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
-.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
-.*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
-    <div class=center>
-      <a href=/children/{parent_inscription_id}>all</a>
-    </div>.*"
-      ),
+    server.assert_response(
+      "/r/sat/5000000000/at/-112",
+      StatusCode::NOT_FOUND,
+      "inscription at index -112 on sat 5000000000 not found",
     );
   }
 
   #[test]
-  fn inscription_number_endpoint() {
-    let server = TestServer::new_with_regtest();
-    server.mine_blocks(2);
+  fn children_recursive_endpoint() {
+    let server = TestServer::new_with_regtest_with_json_api();
+    server.mine_blocks(1);
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[
-        (1, 0, 0, inscription("text/plain", "hello").to_witness()),
-        (2, 0, 0, inscription("text/plain", "cursed").to_witness()),
-      ],
-      outputs: 2,
+    let parent_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
       ..Default::default()
     });
 
-    let inscription_id = InscriptionId { txid, index: 0 };
-    let cursed_inscription_id = InscriptionId { txid, index: 1 };
+    let parent_inscription_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    server.assert_response(
+      format!("/r/children/{parent_inscription_id}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {parent_inscription_id} not found"),
+    );
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{inscription_id}"),
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription 0</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{inscription_id}</dd>.*"
-      ),
-    );
-    server.assert_response_regex(
-      "/inscription/0",
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription 0</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{inscription_id}</dd>.*"
-      ),
-    );
+    let children_json =
+      server.get_json::<ChildrenJson>(format!("/r/children/{parent_inscription_id}"));
+    assert_eq!(children_json.ids.len(), 0);
 
-    server.assert_response_regex(
-      "/inscription/-1",
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription -1</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{cursed_inscription_id}</dd>.*"
-      ),
-    )
+    let mut builder = script::Builder::new();
+    for _ in 0..111 {
+      builder = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        parents: vec![parent_inscription_id.value()],
+        unrecognized_even_field: false,
+        ..Default::default()
+      }
+      .append_reveal_script_to_builder(builder);
+    }
+
+    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let first_child_inscription_id = InscriptionId { txid, index: 0 };
+    let hundredth_child_inscription_id = InscriptionId { txid, index: 99 };
+    let hundred_first_child_inscription_id = InscriptionId { txid, index: 100 };
+    let hundred_eleventh_child_inscription_id = InscriptionId { txid, index: 110 };
+
+    let children_json =
+      server.get_json::<ChildrenJson>(format!("/r/children/{parent_inscription_id}"));
+
+    assert_eq!(children_json.ids.len(), 100);
+    assert_eq!(children_json.ids[0], first_child_inscription_id);
+    assert_eq!(children_json.ids[99], hundredth_child_inscription_id);
+    assert!(children_json.more);
+    assert_eq!(children_json.page, 0);
+
+    let children_json =
+      server.get_json::<ChildrenJson>(format!("/r/children/{parent_inscription_id}/1"));
+
+    assert_eq!(children_json.ids.len(), 11);
+    assert_eq!(children_json.ids[0], hundred_first_child_inscription_id);
+    assert_eq!(children_json.ids[10], hundred_eleventh_child_inscription_id);
+    assert!(!children_json.more);
+    assert_eq!(children_json.page, 1);
   }
 
   #[test]
-  fn charm_cursed() {
-    let server = TestServer::new_with_regtest();
+  fn listing_items_include_content_type_and_length() {
+    let server = TestServer::new_with_regtest_with_json_api();
+    server.mine_blocks(1);
 
-    server.mine_blocks(2);
+    let parent = InscriptionId {
+      txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..Default::default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let mut builder = script::Builder::new();
+    for inscription in [
+      Inscription {
+        content_type: Some("image/png".into()),
+        body: Some(vec![0; 1000]),
+        parents: vec![parent.value()],
+        ..Default::default()
+      },
+      Inscription {
+        parents: vec![parent.value()],
+        ..Default::default()
+      },
+    ] {
+      builder = inscription.append_reveal_script_to_builder(builder);
+    }
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
       inputs: &[
-        (1, 0, 0, Witness::default()),
-        (2, 0, 0, inscription("text/plain", "cursed").to_witness()),
+        (
+          2,
+          0,
+          0,
+          Witness::from_slice(&[builder.into_bytes(), Vec::new()]),
+        ),
+        (2, 1, 0, Default::default()),
       ],
-      outputs: 2,
       ..Default::default()
     });
 
-    let id = InscriptionId { txid, index: 0 };
-
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription -1</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{id}</dd>
-  <dt>charms</dt>
-  <dd>
-    <span title=cursed>👹</span>
-  </dd>
-  .*
-</dl>
-.*
-"
-      ),
+    let item = |id, content_type: Option<&str>, content_length| InscriptionItemJson {
+      id,
+      content_type: content_type.map(str::to_string),
+      content_length,
+    };
+
+    let image = InscriptionId { txid, index: 0 };
+    let empty = InscriptionId { txid, index: 1 };
+
+    pretty_assert_eq!(
+      server
+        .get_json::<ChildrenJson>(format!("/r/children/{parent}"))
+        .items,
+      [
+        item(image, Some("image/png"), Some(1000)),
+        item(empty, None, None),
+      ]
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<InscriptionsJson>("/inscriptions").items,
+      [
+        item(empty, None, None),
+        item(image, Some("image/png"), Some(1000)),
+        item(parent, Some("text/plain"), Some(5)),
+      ]
+    );
+
+    let children = server
+      .get_json::<ChildInscriptionsJson>(format!("/r/children/{parent}/inscriptions"))
+      .children;
+
+    assert_eq!(
+      children
+        .iter()
+        .map(|child| (child.content_type.as_deref(), child.content_length))
+        .collect::<Vec<(Option<&str>, Option<usize>)>>(),
+      [(Some("image/png"), Some(1000)), (None, None)]
     );
   }
 
   #[test]
-  fn charm_vindicated() {
-    let server = TestServer::new_with_regtest();
-
-    server.mine_blocks(110);
+  fn child_inscriptions_recursive_endpoint() {
+    let server = TestServer::new_with_regtest_with_json_api();
+    server.mine_blocks(1);
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[
-        (1, 0, 0, Witness::default()),
-        (2, 0, 0, inscription("text/plain", "cursed").to_witness()),
-      ],
-      outputs: 2,
+    let parent_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
       ..Default::default()
     });
 
-    let id = InscriptionId { txid, index: 0 };
+    let parent_inscription_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription 0</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{id}</dd>
-  <dt>charms</dt>
-  <dd>
-    <span title=vindicated>❤️‍🔥</span>
-  </dd>
-  .*
-</dl>
-.*
-"
-      ),
+    pretty_assert_eq!(
+      server.get_json::<ChildInscriptionsJson>(format!(
+        "/r/children/{parent_inscription_id}/inscriptions"
+      )),
+      ChildInscriptionsJson {
+        children: Vec::new(),
+        more: false,
+        page: 0,
+      }
     );
-  }
 
-  #[test]
-  fn charm_coin() {
-    let server = TestServer::new_with_regtest_with_index_sats();
+    let mut builder = script::Builder::new();
+    for i in 0..101 {
+      builder = Inscription {
+        content_type: Some(if i == 0 { "image/png" } else { "text/plain" }.into()),
+        body: Some("hello".into()),
+        parents: vec![parent_inscription_id.value()],
+        unrecognized_even_field: false,
+        ..Default::default()
+      }
+      .append_reveal_script_to_builder(builder);
+    }
 
-    server.mine_blocks(2);
+    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
       ..Default::default()
     });
 
-    let id = InscriptionId { txid, index: 0 };
-
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription 0</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{id}</dd>
-  <dt>charms</dt>
-  <dd>.*<span title=coin>🪙</span>.*</dd>
-  .*
-</dl>
-.*
-"
-      ),
+    let first_child = InscriptionId { txid, index: 0 };
+    let last_child = InscriptionId { txid, index: 100 };
+
+    let child_inscriptions_json = server.get_json::<ChildInscriptionsJson>(format!(
+      "/r/children/{parent_inscription_id}/inscriptions"
+    ));
+
+    assert_eq!(child_inscriptions_json.children.len(), 100);
+    assert!(child_inscriptions_json.more);
+    assert_eq!(child_inscriptions_json.page, 0);
+
+    pretty_assert_eq!(
+      child_inscriptions_json.children[0],
+      ChildInscriptionJson {
+        id: first_child,
+        number: 1,
+        content_type: Some("image/png".into()),
+        content_length: Some(5),
+        satpoint: server
+          .index
+          .get_inscription_satpoint_by_id(first_child)
+          .unwrap()
+          .unwrap(),
+      }
+    );
+
+    assert_eq!(
+      child_inscriptions_json.children[1].content_type,
+      Some("text/plain".into())
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<ChildInscriptionsJson>(format!(
+        "/r/children/{parent_inscription_id}/inscriptions/1"
+      )),
+      ChildInscriptionsJson {
+        children: vec![ChildInscriptionJson {
+          id: last_child,
+          number: -100,
+          content_type: Some("text/plain".into()),
+          content_length: Some(5),
+          satpoint: server
+            .index
+            .get_inscription_satpoint_by_id(last_child)
+            .unwrap()
+            .unwrap(),
+        }],
+        more: false,
+        page: 1,
+      }
     );
   }
 
   #[test]
-  fn charm_uncommon() {
+  fn inscriptions_in_block_page() {
     let server = TestServer::new_with_regtest_with_index_sats();
 
-    server.mine_blocks(2);
-
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
-      ..Default::default()
-    });
+    for _ in 0..101 {
+      server.mine_blocks(1);
+    }
 
-    let id = InscriptionId { txid, index: 0 };
+    for i in 0..101 {
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+        ..Default::default()
+      });
+    }
 
     server.mine_blocks(1);
 
     server.assert_response_regex(
-      format!("/inscription/{id}"),
+      "/inscriptions/block/102",
       StatusCode::OK,
-      format!(
-        ".*<h1>Inscription 0</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{id}</dd>
-  <dt>charms</dt>
-  <dd>.*<span title=uncommon>🌱</span>.*</dd>
-  .*
-</dl>
-.*
-"
-      ),
+      r".*(<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*){100}.*",
+    );
+
+    server.assert_response_regex(
+      "/inscriptions/block/102/1",
+      StatusCode::OK,
+      r".*<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*",
     );
   }
 
   #[test]
-  fn charm_nineball() {
-    let server = TestServer::new_with_regtest_with_index_sats();
+  fn inscription_query_display() {
+    assert_eq!(
+      InscriptionQuery::Id(inscription_id(1)).to_string(),
+      "1111111111111111111111111111111111111111111111111111111111111111i1"
+    );
+    assert_eq!(InscriptionQuery::Number(1).to_string(), "1")
+  }
 
-    server.mine_blocks(9);
+  #[test]
+  fn inscription_not_found() {
+    TestServer::new_with_regtest_with_json_api().assert_response(
+      "/inscription/0",
+      StatusCode::NOT_FOUND,
+      "inscription 0 not found",
+    );
+  }
+
+  #[test]
+  fn delegate() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    let delegate = Inscription {
+      content_type: Some("text/html".into()),
+      body: Some("foo".into()),
+      ..Default::default()
+    };
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(9, 0, 0, inscription("text/plain", "foo").to_witness())],
+      inputs: &[(1, 0, 0, delegate.to_witness())],
       ..Default::default()
     });
 
-    let id = InscriptionId { txid, index: 0 };
+    let delegate = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    let inscription = Inscription {
+      delegate: Some(delegate.value()),
+      ..Default::default()
+    };
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription.to_witness())],
+      ..Default::default()
+    });
 
     server.mine_blocks(1);
 
+    let id = InscriptionId { txid, index: 0 };
+
     server.assert_response_regex(
       format!("/inscription/{id}"),
       StatusCode::OK,
       format!(
-        ".*<h1>Inscription 0</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{id}</dd>
-  <dt>charms</dt>
-  <dd>.*<span title=nineball>9️⃣</span>.*</dd>
-  .*
-</dl>
-.*
-"
-      ),
+        ".*<h1>Inscription 1</h1>.*
+        <dl>
+          <dt>id</dt>
+          <dd class=monospace>{id}</dd>
+          .*
+          <dt>delegate</dt>
+          <dd><a href=/inscription/{delegate}>{delegate}</a></dd>
+          .*
+        </dl>.*"
+      )
+      .unindent(),
+    );
+
+    server.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
+
+    assert_eq!(
+      server
+        .get(format!("/content/{id}"))
+        .headers()
+        .get(header::ETAG)
+        .unwrap(),
+      &format!("\"{delegate}\""),
+    );
+
+    server.assert_response(format!("/preview/{id}"), StatusCode::OK, "foo");
+
+    server.assert_response(
+      format!("/r/undelegated-content/{id}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {id} content not found"),
     );
   }
 
   #[test]
-  fn charm_reinscription() {
+  fn undelegated_content() {
     let server = TestServer::new_with_regtest();
 
     server.mine_blocks(1);
 
-    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/html", "foo").to_witness())],
       ..Default::default()
     });
 
+    let delegate = InscriptionId { txid, index: 0 };
+
     server.mine_blocks(1);
 
+    let inscription = Inscription {
+      delegate: Some(delegate.value()),
+      ..inscription("text/plain;charset=utf-8", "bar")
+    };
+
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(2, 1, 0, inscription("text/plain", "bar").to_witness())],
+      inputs: &[(2, 0, 0, inscription.to_witness())],
       ..Default::default()
     });
 
@@ -4693,531 +11816,754 @@ next
 
     let id = InscriptionId { txid, index: 0 };
 
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription -1</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{id}</dd>
-  <dt>charms</dt>
-  <dd>
-    <span title=reinscription>♻️</span>
-    <span title=cursed>👹</span>
-  </dd>
-  .*
-</dl>
-.*
-"
-      ),
+    let response = server.get(format!("/content/{id}"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers()[header::CONTENT_TYPE],
+      "text/html; charset=utf-8"
+    );
+    assert_eq!(response.text().unwrap(), "foo");
+
+    let response = server.get(format!("/r/undelegated-content/{id}"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers()[header::CONTENT_TYPE],
+      "text/plain;charset=utf-8"
+    );
+    assert!(response
+      .headers()
+      .contains_key(header::CONTENT_SECURITY_POLICY));
+    assert_eq!(response.text().unwrap(), "bar");
+
+    let response = server.get(format!("/r/undelegated-content/{delegate}"));
+    assert_eq!(
+      response.headers()[header::CONTENT_TYPE],
+      "text/html; charset=utf-8"
+    );
+    assert_eq!(response.text().unwrap(), "foo");
+
+    server.assert_response(
+      format!("/r/undelegated-content/{}", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {} not found", inscription_id(1)),
     );
   }
 
   #[test]
-  fn charm_reinscription_in_same_tx_input() {
+  fn undelegated_content_is_sniffed_and_declares_charset() {
     let server = TestServer::new_with_regtest();
 
     server.mine_blocks(1);
 
-    let script = script::Builder::new()
-      .push_opcode(opcodes::OP_FALSE)
-      .push_opcode(opcodes::all::OP_IF)
-      .push_slice(b"ord")
-      .push_slice([1])
-      .push_slice(b"text/plain;charset=utf-8")
-      .push_slice([])
-      .push_slice(b"foo")
-      .push_opcode(opcodes::all::OP_ENDIF)
-      .push_opcode(opcodes::OP_FALSE)
-      .push_opcode(opcodes::all::OP_IF)
-      .push_slice(b"ord")
-      .push_slice([1])
-      .push_slice(b"text/plain;charset=utf-8")
-      .push_slice([])
-      .push_slice(b"bar")
-      .push_opcode(opcodes::all::OP_ENDIF)
-      .push_opcode(opcodes::OP_FALSE)
-      .push_opcode(opcodes::all::OP_IF)
-      .push_slice(b"ord")
-      .push_slice([1])
-      .push_slice(b"text/plain;charset=utf-8")
-      .push_slice([])
-      .push_slice(b"qix")
-      .push_opcode(opcodes::all::OP_ENDIF)
-      .into_script();
-
-    let witness = Witness::from_slice(&[script.into_bytes(), Vec::new()]);
-
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, witness)],
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
       ..Default::default()
     });
 
+    let delegate = InscriptionId { txid, index: 0 };
+
     server.mine_blocks(1);
 
-    let id = InscriptionId { txid, index: 0 };
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription 0</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{id}</dd>
-  <dt>output value</dt>
-  .*
-</dl>
-.*
-"
-      ),
-    );
+    let mut ids = Vec::new();
 
-    let id = InscriptionId { txid, index: 1 };
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      ".*
-    <span title=reinscription>♻️</span>
-    <span title=cursed>👹</span>.*",
-    );
+    for (i, inscription) in [
+      Inscription::new(Some("foo".into()), Some(PNG.into())),
+      Inscription::new(Some("text/html".into()), Some("bar".into())),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          i + 2,
+          0,
+          0,
+          Inscription {
+            delegate: Some(delegate.value()),
+            ..inscription
+          }
+          .to_witness(),
+        )],
+        ..Default::default()
+      });
 
-    let id = InscriptionId { txid, index: 2 };
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      ".*
-    <span title=reinscription>♻️</span>
-    <span title=cursed>👹</span>.*",
+      ids.push(InscriptionId { txid, index: 0 });
+
+      server.mine_blocks(1);
+    }
+
+    let response = server.get(format!("/r/undelegated-content/{}", ids[0]));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+    assert_eq!(response.headers()[sniff::X_ORIGINAL_CONTENT_TYPE], "foo");
+    assert_eq!(response.bytes().unwrap(), PNG);
+
+    let response = server.get(format!("/r/undelegated-content/{}", ids[1]));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers()[header::CONTENT_TYPE],
+      "text/html; charset=utf-8"
     );
+    assert_eq!(response.text().unwrap(), "bar");
   }
 
   #[test]
-  fn charm_reinscription_in_same_tx_with_pointer() {
+  fn dangling_delegate() {
     let server = TestServer::new_with_regtest();
 
-    server.mine_blocks(3);
+    server.mine_blocks(1);
 
-    let cursed_inscription = inscription("text/plain", "bar");
-    let reinscription: Inscription = InscriptionTemplate {
-      pointer: Some(0),
+    let delegate = inscription_id(1);
+
+    let inscription = Inscription {
+      delegate: Some(delegate.value()),
       ..Default::default()
-    }
-    .into();
+    };
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[
-        (1, 0, 0, inscription("text/plain", "foo").to_witness()),
-        (2, 0, 0, cursed_inscription.to_witness()),
-        (3, 0, 0, reinscription.to_witness()),
-      ],
+      inputs: &[(1, 0, 0, inscription.to_witness())],
       ..Default::default()
     });
 
     server.mine_blocks(1);
 
     let id = InscriptionId { txid, index: 0 };
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription 0</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{id}</dd>
-  <dt>output value</dt>
-  .*
-</dl>
-.*
-"
-      ),
-    );
 
-    let id = InscriptionId { txid, index: 1 };
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      ".*
-    <span title=cursed>👹</span>.*",
+    server.assert_response(
+      format!("/content/{id}"),
+      StatusCode::NOT_FOUND,
+      &format!("delegate {delegate} not found"),
     );
 
-    let id = InscriptionId { txid, index: 2 };
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      ".*
-    <span title=reinscription>♻️</span>
-    <span title=cursed>👹</span>.*",
+    server.assert_response(
+      format!("/preview/{id}"),
+      StatusCode::NOT_FOUND,
+      &format!("delegate {delegate} not found"),
     );
   }
 
   #[test]
-  fn charm_unbound() {
+  fn inscriptions_websocket_streams_new_inscriptions() {
     let server = TestServer::new_with_regtest();
+    server.mine_blocks(2);
 
-    server.mine_blocks(1);
+    let mut url = server.join_url("/ws/inscriptions?content_type=text/*");
+    url.set_scheme("ws").unwrap();
+
+    let (mut socket, _response) = tungstenite::connect(url).unwrap();
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, envelope(&[b"ord", &[128], &[0]]))],
+      inputs: &[
+        (1, 0, 0, inscription("text/plain", "hello").to_witness()),
+        (2, 0, 0, inscription("image/png", "hello").to_witness()),
+      ],
       ..Default::default()
     });
 
-    server.mine_blocks(1);
+    let block = server.mine_blocks(1).remove(0);
 
-    let id = InscriptionId { txid, index: 0 };
+    let mut read_event = || {
+      serde_json::from_str::<crate::index::Event>(socket.read().unwrap().to_text().unwrap())
+        .unwrap()
+    };
 
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription -1</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{id}</dd>
-  <dt>charms</dt>
-  <dd>
-    <span title=cursed>👹</span>
-    <span title=unbound>🔓</span>
-  </dd>
-  .*
-</dl>
-.*
-"
-      ),
+    assert_eq!(
+      read_event(),
+      crate::index::Event::InscriptionCreated {
+        id: InscriptionId { txid, index: 0 },
+        number: 0,
+        height: 3,
+        content_type: Some("text/plain".into()),
+      }
+    );
+
+    assert_eq!(
+      read_event(),
+      crate::index::Event::BlockCommitted {
+        height: 3,
+        hash: block.block_hash(),
+      }
     );
   }
 
   #[test]
-  fn charm_lost() {
-    let server = TestServer::new_with_regtest();
-
+  fn inscription_json_includes_parent_on_request() {
+    let server = TestServer::new_with_regtest_with_json_api();
     server.mine_blocks(1);
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+    let parent_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
       ..Default::default()
     });
 
-    let id = InscriptionId { txid, index: 0 };
+    let parent_inscription_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription 0</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{id}</dd>
-  <dt>output value</dt>
-  <dd>5000000000</dd>
-  .*
-</dl>
-.*
-"
-      ),
-    );
-
-    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(2, 1, 0, Default::default())],
-      fee: 50 * COIN_VALUE,
+    let child_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          2,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value()],
+            ..Default::default()
+          }
+          .to_witness(),
+        ),
+        (2, 1, 0, Default::default()),
+      ],
       ..Default::default()
     });
 
-    server.mine_blocks_with_subsidy(1, 0);
+    let child_inscription_id = InscriptionId {
+      txid: child_txid,
+      index: 0,
+    };
 
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription 0</h1>.*
-<dl>
-  <dt>id</dt>
-  <dd class=monospace>{id}</dd>
-  <dt>charms</dt>
-  <dd>
-    <span title=lost>🤔</span>
-  </dd>
-  .*
-</dl>
-.*
-"
-      ),
+    server.mine_blocks(1);
+
+    let inscription_json =
+      server.get_json::<InscriptionJson>(format!("/inscription/{child_inscription_id}"));
+
+    assert_eq!(inscription_json.parent, Some(parent_inscription_id));
+    assert_eq!(inscription_json.parent_details, None);
+
+    pretty_assert_eq!(
+      server
+        .get_json::<InscriptionJson>(format!(
+          "/inscription/{child_inscription_id}?include=parent"
+        ))
+        .parent_details,
+      Some(vec![InscriptionParentJson {
+        id: parent_inscription_id,
+        number: 0,
+        content_type: Some("image/png".into()),
+      }])
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<InscriptionJson>(format!(
+          "/inscription/{parent_inscription_id}?include=parent"
+        ))
+        .parent_details,
+      Some(Vec::new())
+    );
+
+    server.assert_response(
+      format!("/inscription/{child_inscription_id}?include=foo"),
+      StatusCode::BAD_REQUEST,
+      "unknown include `foo`",
     );
   }
 
   #[test]
-  fn sat_recursive_endpoints() {
-    let server = TestServer::new_with_regtest_with_index_sats();
+  fn inscription_with_two_parents() {
+    let server = TestServer::new_with_regtest_with_json_api();
+    server.mine_blocks(2);
 
-    assert_eq!(
-      server.get_json::<SatInscriptionsJson>("/r/sat/5000000000"),
-      SatInscriptionsJson {
-        ids: vec![],
-        page: 0,
-        more: false
-      }
-    );
+    let mut parents = Vec::new();
 
-    assert_eq!(
-      server.get_json::<SatInscriptionJson>("/r/sat/5000000000/at/0"),
-      SatInscriptionJson { id: None }
-    );
+    for i in 1..=2 {
+      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i, 0, 0, inscription("text/plain", "parent").to_witness())],
+        ..Default::default()
+      });
 
-    server.mine_blocks(1);
+      parents.push(InscriptionId { txid, index: 0 });
+
+      server.mine_blocks(1);
+    }
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      inputs: &[
+        (
+          3,
+          1,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("child".into()),
+            parents: parents.iter().map(|parent| parent.value()).collect(),
+            ..Default::default()
+          }
+          .to_witness(),
+        ),
+        (4, 1, 0, Default::default()),
+      ],
       ..Default::default()
     });
 
-    server.mine_blocks(1);
+    let child = InscriptionId { txid, index: 0 };
 
-    let mut ids = Vec::new();
-    ids.push(InscriptionId { txid, index: 0 });
+    server.mine_blocks(1);
 
-    for i in 1..111 {
-      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(i + 1, 1, 0, inscription("text/plain", "foo").to_witness())],
-        ..Default::default()
-      });
+    let inscription_json = server.get_json::<InscriptionJson>(format!("/inscription/{child}"));
 
-      server.mine_blocks(1);
+    assert_eq!(inscription_json.parents, parents);
+    assert_eq!(inscription_json.parent, Some(parents[0]));
 
-      ids.push(InscriptionId { txid, index: 0 });
-    }
+    let data =
+      &server.get_json::<serde_json::Value>(format!("/api/v1/inscription/{child}"))["data"];
 
-    let paginated_response = server.get_json::<SatInscriptionsJson>("/r/sat/5000000000");
+    assert_eq!(
+      data["parents"],
+      serde_json::json!([parents[0].to_string(), parents[1].to_string()])
+    );
+    assert_eq!(data["parent"], parents[0].to_string());
 
-    let equivalent_paginated_response =
-      server.get_json::<SatInscriptionsJson>("/r/sat/5000000000/0");
+    let parents_json = server.get_json::<ParentsJson>(format!("/r/parents/{child}"));
 
-    assert_eq!(paginated_response.ids.len(), 100);
-    assert!(paginated_response.more);
-    assert_eq!(paginated_response.page, 0);
+    assert_eq!(parents_json.ids, parents);
+    assert!(!parents_json.more);
+    assert_eq!(parents_json.items.len(), 2);
 
     assert_eq!(
-      paginated_response.ids.len(),
-      equivalent_paginated_response.ids.len()
+      server
+        .get_json::<ParentsJson>(format!("/r/parents/{child}/1"))
+        .ids,
+      Vec::new()
     );
-    assert_eq!(paginated_response.more, equivalent_paginated_response.more);
-    assert_eq!(paginated_response.page, equivalent_paginated_response.page);
 
-    let paginated_response = server.get_json::<SatInscriptionsJson>("/r/sat/5000000000/1");
+    for parent in &parents {
+      assert_eq!(
+        server
+          .get_json::<ChildrenJson>(format!("/r/children/{parent}"))
+          .ids,
+        [child]
+      );
+    }
+
+    server.assert_response_regex(
+      format!("/inscription/{child}"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>parents</dt>\\s*<dd><a class=monospace href=/inscription/{}>.*</a></dd>\\s*<dd><a class=monospace href=/inscription/{}>.*",
+        parents[0], parents[1],
+      ),
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<InscriptionJson>(format!("/inscription/{child}?include=parent"))
+        .parent_details,
+      Some(vec![
+        InscriptionParentJson {
+          id: parents[0],
+          number: 0,
+          content_type: Some("text/plain".into()),
+        },
+        InscriptionParentJson {
+          id: parents[1],
+          number: 1,
+          content_type: Some("text/plain".into()),
+        },
+      ])
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{child}?include=parent"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>parents</dt>\\s*<dd><a class=monospace href=/inscription/{}>.*</a> inscription 0, text/plain</dd>\\s*<dd><a class=monospace href=/inscription/{}>.*</a> inscription 1, text/plain</dd>.*",
+        parents[0], parents[1],
+      ),
+    );
+  }
+
+  #[test]
+  fn detail_fields_can_be_selected() {
+    let server = TestServer::new_with_regtest_with_json_api();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>(format!(
+        "/inscription/{id}?fields=inscription_id,inscription_number,content_type"
+      )),
+      serde_json::json!({
+        "inscription_id": id,
+        "inscription_number": 0,
+        "content_type": "text/plain",
+      })
+    );
 
-    assert_eq!(paginated_response.ids.len(), 11);
-    assert!(!paginated_response.more);
-    assert_eq!(paginated_response.page, 1);
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>(format!(
+        "/api/v1/inscription/{id}?fields=id,number,contentType"
+      ))["data"],
+      serde_json::json!({
+        "id": id,
+        "number": 0,
+        "contentType": "text/plain",
+      })
+    );
 
-    assert_eq!(
+    pretty_assert_eq!(
       server
-        .get_json::<SatInscriptionJson>("/r/sat/5000000000/at/0")
-        .id,
-      Some(ids[0])
+        .get_json::<serde_json::Value>(format!("/api/v1/inscription/{id}?fields=id,contentHash"))
+        ["data"],
+      serde_json::json!({
+        "id": id,
+        "contentHash": null,
+      })
     );
 
-    assert_eq!(
-      server
-        .get_json::<SatInscriptionJson>("/r/sat/5000000000/at/-111")
-        .id,
-      Some(ids[0])
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>(format!("/inscription/{id}?fields=parent_details")),
+      serde_json::json!({
+        "parent_details": null,
+      })
     );
 
-    assert_eq!(
-      server
-        .get_json::<SatInscriptionJson>("/r/sat/5000000000/at/110")
-        .id,
-      Some(ids[110])
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>("/sat/0?fields=name,rarity"),
+      serde_json::json!({
+        "name": "nvtdijuwxlp",
+        "rarity": "mythic",
+      })
     );
 
-    assert_eq!(
-      server
-        .get_json::<SatInscriptionJson>("/r/sat/5000000000/at/-1")
-        .id,
-      Some(ids[110])
+    assert!(
+      server.get_json::<serde_json::Value>(format!("/api/v1/inscription/{id}"))["data"]
+        .get("genesisHeight")
+        .is_some()
     );
 
-    assert!(server
-      .get_json::<SatInscriptionJson>("/r/sat/5000000000/at/111")
-      .id
-      .is_none());
+    let response = server.get(format!("/api/v1/ord/id/{id}/inscription?fields=id,foo"));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "unknown field `foo`"
+    );
   }
 
   #[test]
-  fn children_recursive_endpoint() {
+  fn inscription_content_hash() {
     let server = TestServer::new_with_regtest_with_json_api();
     server.mine_blocks(1);
 
-    let parent_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
       inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
       ..Default::default()
     });
 
-    let parent_inscription_id = InscriptionId {
-      txid: parent_txid,
-      index: 0,
-    };
-
-    server.assert_response(
-      format!("/r/children/{parent_inscription_id}"),
-      StatusCode::NOT_FOUND,
-      &format!("inscription {parent_inscription_id} not found"),
-    );
+    let id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    let children_json =
-      server.get_json::<ChildrenJson>(format!("/r/children/{parent_inscription_id}"));
-    assert_eq!(children_json.ids.len(), 0);
-
-    let mut builder = script::Builder::new();
-    for _ in 0..111 {
-      builder = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello".into()),
-        parent: Some(parent_inscription_id.value()),
-        unrecognized_even_field: false,
-        ..Default::default()
-      }
-      .append_reveal_script_to_builder(builder);
-    }
-
-    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
-
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
+      inputs: &[(
+        2,
+        0,
+        0,
+        Inscription {
+          delegate: Some(id.value()),
+          ..Default::default()
+        }
+        .to_witness(),
+      )],
       ..Default::default()
     });
 
-    server.mine_blocks(1);
-
-    let first_child_inscription_id = InscriptionId { txid, index: 0 };
-    let hundredth_child_inscription_id = InscriptionId { txid, index: 99 };
-    let hundred_first_child_inscription_id = InscriptionId { txid, index: 100 };
-    let hundred_eleventh_child_inscription_id = InscriptionId { txid, index: 110 };
+    let delegating = InscriptionId { txid, index: 0 };
 
-    let children_json =
-      server.get_json::<ChildrenJson>(format!("/r/children/{parent_inscription_id}"));
+    server.mine_blocks(1);
 
-    assert_eq!(children_json.ids.len(), 100);
-    assert_eq!(children_json.ids[0], first_child_inscription_id);
-    assert_eq!(children_json.ids[99], hundredth_child_inscription_id);
-    assert!(children_json.more);
-    assert_eq!(children_json.page, 0);
+    assert!(
+      server.get_json::<serde_json::Value>(format!("/api/v1/inscription/{id}"))["data"]
+        .get("contentHash")
+        .is_none()
+    );
+
+    for path in [
+      format!("/api/v1/inscription/{id}?include=content_hash"),
+      format!("/api/v1/ord/id/{id}/inscription?include=content_hash"),
+      "/api/v1/ord/number/0/inscription?include=content_hash".into(),
+    ] {
+      pretty_assert_eq!(
+        server.get_json::<serde_json::Value>(&path)["data"]["contentHash"],
+        serde_json::json!({
+          "sha256": "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+          "delegated": false,
+        })
+      );
+    }
 
-    let children_json =
-      server.get_json::<ChildrenJson>(format!("/r/children/{parent_inscription_id}/1"));
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>(format!(
+        "/api/v1/inscription/{delegating}?include=content_hash"
+      ))["data"]["contentHash"],
+      serde_json::json!({
+        "sha256": "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        "delegated": true,
+      })
+    );
 
-    assert_eq!(children_json.ids.len(), 11);
-    assert_eq!(children_json.ids[0], hundred_first_child_inscription_id);
-    assert_eq!(children_json.ids[10], hundred_eleventh_child_inscription_id);
-    assert!(!children_json.more);
-    assert_eq!(children_json.page, 1);
+    let response = server.get(format!("/api/v1/inscription/{id}?include=foo"));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "unknown include `foo`"
+    );
   }
 
   #[test]
-  fn inscriptions_in_block_page() {
-    let server = TestServer::new_with_regtest_with_index_sats();
+  fn inscriptions_search() {
+    let server = TestServer::new_with_regtest_with_json_api();
+    server.mine_blocks(1);
 
-    for _ in 0..101 {
-      server.mine_blocks(1);
-    }
+    let mut ids = Vec::new();
 
-    for i in 0..101 {
-      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+    for (i, (content_type, body)) in [
+      ("text/plain;charset=utf-8", "gm, Hello World!"),
+      ("image/png", "hello world"),
+      ("text/html;charset=utf-8", "<p>hello world</p>"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription(content_type, body).to_witness())],
         ..Default::default()
       });
+
+      ids.push(InscriptionId { txid, index: 0 });
+
+      server.mine_blocks(1);
     }
 
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        4,
+        0,
+        0,
+        Inscription {
+          content_encoding: Some("br".into()),
+          ..inscription("text/plain;charset=utf-8", "hello world")
+        }
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      "/inscriptions/block/102",
-      StatusCode::OK,
-      r".*(<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*){100}.*",
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/inscriptions/search?q=hello%20world")["data"],
+      serde_json::json!({
+        "matches": [
+          {
+            "id": ids[2].to_string(),
+            "snippet": "<p>hello world</p>",
+          },
+          {
+            "id": ids[0].to_string(),
+            "snippet": "gm, Hello World!",
+          },
+        ],
+        "truncated": false,
+      })
     );
 
-    server.assert_response_regex(
-      "/inscriptions/block/102/1",
-      StatusCode::OK,
-      r".*<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*",
+    assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/inscriptions/search?q=hello&limit=1")["data"]
+        ["matches"]
+        .as_array()
+        .unwrap()
+        .len(),
+      1
     );
-  }
 
-  #[test]
-  fn inscription_query_display() {
     assert_eq!(
-      InscriptionQuery::Id(inscription_id(1)).to_string(),
-      "1111111111111111111111111111111111111111111111111111111111111111i1"
+      server.get_json::<serde_json::Value>("/api/v1/inscriptions/search?q=goodbye")["data"],
+      serde_json::json!({
+        "matches": [],
+        "truncated": false,
+      })
     );
-    assert_eq!(InscriptionQuery::Number(1).to_string(), "1")
+
+    for q in ["", "gm", &"a".repeat(65)] {
+      let response = server.get(format!("/api/v1/inscriptions/search?q={q}"));
+      assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+      assert_eq!(
+        response.json::<serde_json::Value>().unwrap()["msg"],
+        "q must be between 3 and 64 characters long"
+      );
+    }
   }
 
   #[test]
-  fn inscription_not_found() {
-    TestServer::new_with_regtest_with_json_api().assert_response(
-      "/inscription/0",
-      StatusCode::NOT_FOUND,
-      "inscription 0 not found",
-    );
+  fn inscription_media_type_and_category() {
+    let server = TestServer::new_with_regtest_with_json_api();
+    server.mine_blocks(1);
+
+    let mut ids = Vec::new();
+
+    for (i, content_type) in [
+      "text/html;charset=utf-8",
+      "image/svg+xml",
+      "model/gltf+json",
+      "application/x-foo",
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription(content_type, "hello").to_witness())],
+        ..Default::default()
+      });
+
+      ids.push(InscriptionId { txid, index: 0 });
+
+      server.mine_blocks(1);
+    }
+
+    for (id, content_type, media_type, media_category) in [
+      (ids[0], "text/html;charset=utf-8", "text/html", "text"),
+      (ids[1], "image/svg+xml", "image/svg+xml", "image"),
+      (ids[2], "model/gltf+json", "model/gltf+json", "model"),
+      (ids[3], "application/x-foo", "application/x-foo", "other"),
+    ] {
+      let data = &server.get_json::<serde_json::Value>(format!("/api/v1/inscription/{id}"))["data"];
+
+      assert_eq!(data["contentType"], content_type);
+      assert_eq!(data["mediaType"], media_type);
+      assert_eq!(data["mediaCategory"], media_category);
+    }
   }
 
   #[test]
-  fn delegate() {
-    let server = TestServer::new_with_regtest();
+  fn protobuf_responses() {
+    use prost::Message;
 
+    let server = TestServer::new_with_regtest_with_json_api();
     server.mine_blocks(1);
 
-    let delegate = Inscription {
-      content_type: Some("text/html".into()),
-      body: Some("foo".into()),
-      ..Default::default()
-    };
-
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, delegate.to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
       ..Default::default()
     });
 
-    let delegate = InscriptionId { txid, index: 0 };
+    let id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    let inscription = Inscription {
-      delegate: Some(delegate.value()),
-      ..Default::default()
+    let protobuf = |path: &str| {
+      let response = reqwest::blocking::Client::new()
+        .get(server.join_url(path))
+        .header(header::ACCEPT, protobuf::CONTENT_TYPE)
+        .send()
+        .unwrap();
+      assert_eq!(response.status(), StatusCode::OK);
+      assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        protobuf::CONTENT_TYPE
+      );
+      assert_eq!(response.headers()[header::VARY], "Accept");
+      response.bytes().unwrap()
     };
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(2, 0, 0, inscription.to_witness())],
-      ..Default::default()
-    });
+    let inscription =
+      server.get_json::<ApiResponse<ord::ApiInscription>>(format!("/api/v1/inscription/{id}"));
 
-    server.mine_blocks(1);
+    pretty_assert_eq!(
+      InscriptionResponse::decode(protobuf(&format!("/api/v1/inscription/{id}"))).unwrap(),
+      inscription.into()
+    );
 
-    let id = InscriptionId { txid, index: 0 };
+    let outpoint = server.get_json::<ApiResponse<ord::ApiOutPointResult>>(format!(
+      "/api/v1/ord/outpoint/{txid}:0/info"
+    ));
 
-    server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
-      format!(
-        ".*<h1>Inscription 1</h1>.*
-        <dl>
-          <dt>id</dt>
-          <dd class=monospace>{id}</dd>
-          .*
-          <dt>delegate</dt>
-          <dd><a href=/inscription/{delegate}>{delegate}</a></dd>
-          .*
-        </dl>.*"
-      )
-      .unindent(),
+    assert!(outpoint.data.result.is_some());
+
+    pretty_assert_eq!(
+      OutPointResultResponse::decode(protobuf(&format!("/api/v1/ord/outpoint/{txid}:0/info")))
+        .unwrap(),
+      outpoint.into()
     );
 
-    server.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
+    let content_type = |accept: &str| {
+      reqwest::blocking::Client::new()
+        .get(server.join_url(&format!("/api/v1/inscription/{id}")))
+        .header(header::ACCEPT, accept)
+        .send()
+        .unwrap()
+        .headers()[header::CONTENT_TYPE]
+        .clone()
+    };
 
-    server.assert_response(format!("/preview/{id}"), StatusCode::OK, "foo");
+    assert_eq!(
+      content_type("application/json;q=0.5, application/x-protobuf"),
+      protobuf::CONTENT_TYPE
+    );
+    assert_eq!(
+      content_type("application/x-protobuf;q=0.5, application/json"),
+      "application/json"
+    );
+    assert_eq!(
+      content_type("application/x-protobuf;q=0"),
+      "application/json"
+    );
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url(&format!("/api/v1/inscription/{id}?fields=id")))
+      .header(header::ACCEPT, protobuf::CONTENT_TYPE)
+      .send()
+      .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<serde_json::Value>().unwrap()["msg"],
+      "fields cannot be selected from protobuf responses"
+    );
+  }
+
+  #[test]
+  fn index_at_capacity() {
+    let server = TestServer::new_with_args(&["--max-index-size-gb", "0.000001"], &[]);
+
+    server.assert_response(
+      "/blockheight",
+      StatusCode::INSUFFICIENT_STORAGE,
+      "index at capacity",
+    );
+
+    server.assert_response_regex("/block/0", StatusCode::OK, ".*<h1>Block 0</h1>.*");
+  }
+
+  #[test]
+  fn recursive_reorgs_endpoint() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(server.get_json::<Vec<ReorgEvent>>("/r/reorgs"), Vec::new());
+
+    pretty_assert_eq!(
+      server.get_json::<Vec<ReorgEvent>>("/r/reorgs?since=1"),
+      Vec::new()
+    );
+
+    server.assert_response(
+      "/r/reorgs?since=foo",
+      StatusCode::BAD_REQUEST,
+      "Failed to deserialize query string: invalid digit found in string",
+    );
   }
 }
@@ -1,6 +1,6 @@
 use {super::*, axum::extract::FromRef};
 
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 pub(crate) struct AcceptEncoding(pub(crate) Option<String>);
 
 #[async_trait::async_trait]
@@ -25,17 +25,43 @@ where
 }
 
 impl AcceptEncoding {
+  /// The quality value the client assigned to `encoding`, falling back to the
+  /// `*` wildcard, or 0 if the encoding is not listed.
+  pub(crate) fn quality(&self, encoding: &str) -> f32 {
+    let mut wildcard = None;
+
+    for value in self.0.as_deref().unwrap_or_default().split(',') {
+      let mut params = value.split(';');
+
+      let coding = params.next().unwrap_or_default().trim();
+
+      if coding.is_empty() {
+        continue;
+      }
+
+      let quality = params
+        .find_map(|param| param.trim().strip_prefix("q="))
+        .and_then(|quality| quality.trim().parse::<f32>().ok())
+        .unwrap_or(1.0);
+
+      if coding.eq_ignore_ascii_case(encoding) {
+        return quality;
+      }
+
+      if coding == "*" {
+        wildcard = Some(quality);
+      }
+    }
+
+    wildcard.unwrap_or_default()
+  }
+
   pub(crate) fn is_acceptable(&self, encoding: &HeaderValue) -> bool {
     let Ok(encoding) = encoding.to_str() else {
       return false;
     };
 
-    self
-      .0
-      .clone()
-      .unwrap_or_default()
-      .split(',')
-      .any(|value| value.split(';').next().unwrap_or_default().trim() == encoding)
+    self.quality(encoding) > 0.0
   }
 }
 
@@ -122,4 +148,40 @@ mod tests {
     assert!(encodings.is_acceptable(&HeaderValue::from_static("br")));
     assert!(!encodings.is_acceptable(&HeaderValue::from_static("bzip2")));
   }
+
+  #[test]
+  fn accepts_zstd_and_br() {
+    let encodings = AcceptEncoding(Some("zstd, br".into()));
+
+    assert!(encodings.is_acceptable(&HeaderValue::from_static("zstd")));
+    assert!(encodings.is_acceptable(&HeaderValue::from_static("br")));
+    assert!(!encodings.is_acceptable(&HeaderValue::from_static("gzip")));
+  }
+
+  #[test]
+  fn quality_values_set_precedence() {
+    let encodings = AcceptEncoding(Some("gzip;q=0.5, zstd;q=1.0, br;q=0.8, deflate;q=0".into()));
+
+    assert_eq!(encodings.quality("zstd"), 1.0);
+    assert_eq!(encodings.quality("br"), 0.8);
+    assert_eq!(encodings.quality("gzip"), 0.5);
+    assert_eq!(encodings.quality("deflate"), 0.0);
+    assert_eq!(encodings.quality("bzip2"), 0.0);
+
+    assert!(encodings.quality("zstd") > encodings.quality("br"));
+    assert!(encodings.quality("br") > encodings.quality("gzip"));
+
+    assert!(!encodings.is_acceptable(&HeaderValue::from_static("deflate")));
+  }
+
+  #[test]
+  fn wildcard_and_case_insensitive_encodings() {
+    let encodings = AcceptEncoding(Some("ZSTD, *;q=0.1, gzip;q=0".into()));
+
+    assert_eq!(encodings.quality("zstd"), 1.0);
+    assert_eq!(encodings.quality("br"), 0.1);
+    assert!(!encodings.is_acceptable(&HeaderValue::from_static("gzip")));
+
+    assert!(!AcceptEncoding(None).is_acceptable(&HeaderValue::from_static("br")));
+  }
 }
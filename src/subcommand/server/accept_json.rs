@@ -1,7 +1,42 @@
-use {super::*, axum::extract::FromRef};
+use {
+  super::{error::ApiError, *},
+  axum::{extract::FromRef, http::Request, middleware::Next},
+  std::cell::Cell,
+};
+
+tokio::task_local! {
+  static NEGOTIATED: Cell<bool>;
+}
 
 pub(crate) struct AcceptJson(pub(crate) bool);
 
+/// Add `Vary: Accept` to the responses of handlers that extract `AcceptJson`
+/// or `AcceptProtobuf`, since they serve different representations depending
+/// on the `Accept` header.
+pub(super) async fn vary_accept<B>(request: Request<B>, next: Next<B>) -> Response {
+  let (negotiated, mut response) = NEGOTIATED
+    .scope(Cell::new(false), async {
+      let response = next.run(request).await;
+      (NEGOTIATED.with(Cell::get), response)
+    })
+    .await;
+
+  // `json_server_errors` marks error responses itself
+  if negotiated && response.extensions().get::<ApiError>().is_none() {
+    response
+      .headers_mut()
+      .append(header::VARY, HeaderValue::from_static("Accept"));
+  }
+
+  response
+}
+
+/// Record that the response to the current request depends on its `Accept`
+/// header.
+pub(super) fn negotiated() {
+  NEGOTIATED.try_with(|negotiated| negotiated.set(true)).ok();
+}
+
 #[async_trait::async_trait]
 impl<S> axum::extract::FromRequestParts<S> for AcceptJson
 where
@@ -14,6 +49,8 @@ where
     parts: &mut http::request::Parts,
     state: &S,
   ) -> Result<Self, Self::Rejection> {
+    negotiated();
+
     let state = Arc::from_ref(state);
     let json_api_enabled = state.is_json_api_enabled;
     let json_header = parts
@@ -8,6 +8,91 @@ pub struct Pagination {
   pub limit: Option<usize>,
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct ListQuery {
+  /// Response version. Set to 2 to wrap the list in a page envelope.
+  pub v: Option<u8>,
+  /// Page index, only used when `v=2`.
+  pub page: Option<usize>,
+  /// Page size, only used when `v=2`. Defaults to `--default-page-size`, and
+  /// larger sizes are reduced to `--max-page-size`.
+  pub limit: Option<usize>,
+}
+
+impl ListQuery {
+  /// Returns the requested page index and size if the page envelope was requested.
+  pub(crate) fn page(
+    &self,
+    server_config: &ServerConfig,
+  ) -> Result<Option<(usize, usize)>, ApiError> {
+    match self.v {
+      None | Some(1) => Ok(None),
+      Some(2) => Ok(Some((
+        self.page.unwrap_or_default(),
+        page_size(server_config, self.limit)?,
+      ))),
+      Some(v) => Err(ApiError::bad_request(format!("unsupported version {v}"))),
+    }
+  }
+}
+
+/// The size of a page of `limit` items, or of `--default-page-size` items if
+/// no limit was requested. Limits above `--max-page-size` are clamped to it,
+/// so paginated responses echo the size actually used.
+pub(crate) fn page_size(
+  server_config: &ServerConfig,
+  limit: Option<usize>,
+) -> Result<usize, ApiError> {
+  match limit {
+    Some(0) => Err(ApiError::bad_request("limit must be greater than 0")),
+    Some(limit) => Ok(limit.min(server_config.max_page_size)),
+    None => Ok(server_config.default_page_size),
+  }
+}
+
+/// A single page of a list response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiPage<T> {
+  pub data: Vec<T>,
+  pub page_index: usize,
+  pub limit: usize,
+  pub more: bool,
+}
+
+impl<T> ApiPage<T> {
+  /// Collects page `page_index` of `items`, fetching one extra item to tell whether more follow.
+  pub(crate) fn paginate(
+    items: impl IntoIterator<Item = T>,
+    page_index: usize,
+    page_size: usize,
+  ) -> Self {
+    let mut data = items
+      .into_iter()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .collect::<Vec<T>>();
+
+    let more = data.len() > page_size;
+
+    data.truncate(page_size);
+
+    Self {
+      data,
+      page_index,
+      limit: page_size,
+      more,
+    }
+  }
+}
+
+/// A list response, either in its original shape or wrapped in a page envelope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ApiList<L, T> {
+  V1(L),
+  V2(ApiPage<T>),
+}
+
 pub(crate) type ApiResult<T> = Result<axum::Json<ApiResponse<T>>, ApiError>;
 
 pub(super) trait ApiOptionExt<T> {
@@ -29,3 +114,90 @@ impl<T> ApiOptionExt<T> for Option<T> {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn paginate_sets_more_at_page_boundaries() {
+    assert_eq!(
+      ApiPage::paginate(0..4, 0, 2),
+      ApiPage {
+        data: vec![0, 1],
+        page_index: 0,
+        limit: 2,
+        more: true,
+      }
+    );
+    assert_eq!(
+      ApiPage::paginate(0..4, 1, 2),
+      ApiPage {
+        data: vec![2, 3],
+        page_index: 1,
+        limit: 2,
+        more: false,
+      }
+    );
+    assert_eq!(
+      ApiPage::paginate(0..5, 1, 2),
+      ApiPage {
+        data: vec![2, 3],
+        page_index: 1,
+        limit: 2,
+        more: true,
+      }
+    );
+    assert_eq!(
+      ApiPage::paginate(0..4, 2, 2),
+      ApiPage {
+        data: Vec::new(),
+        page_index: 2,
+        limit: 2,
+        more: false,
+      }
+    );
+  }
+
+  #[test]
+  fn list_query_versions() {
+    let query = |v, page, limit| ListQuery { v, page, limit };
+
+    let server_config = ServerConfig {
+      default_page_size: 100,
+      max_page_size: 1000,
+      ..Default::default()
+    };
+
+    let page = |query: ListQuery| query.page(&server_config);
+
+    assert_eq!(page(query(None, Some(3), None)).unwrap(), None);
+    assert_eq!(page(query(Some(1), None, None)).unwrap(), None);
+    assert_eq!(page(query(Some(2), None, None)).unwrap(), Some((0, 100)));
+    assert_eq!(
+      page(query(Some(2), Some(3), Some(10))).unwrap(),
+      Some((3, 10))
+    );
+    assert_eq!(
+      page(query(Some(2), None, Some(1_000_000))).unwrap(),
+      Some((0, 1000))
+    );
+    assert!(page(query(Some(2), None, Some(0))).is_err());
+    assert!(page(query(Some(3), None, None)).is_err());
+  }
+
+  #[test]
+  fn page_size_is_clamped_to_max() {
+    let server_config = ServerConfig {
+      default_page_size: 20,
+      max_page_size: 50,
+      ..Default::default()
+    };
+
+    assert_eq!(page_size(&server_config, None).unwrap(), 20);
+    assert_eq!(page_size(&server_config, Some(1)).unwrap(), 1);
+    assert_eq!(page_size(&server_config, Some(50)).unwrap(), 50);
+    assert_eq!(page_size(&server_config, Some(1_000_000)).unwrap(), 50);
+    assert!(page_size(&server_config, Some(0)).is_err());
+  }
+}
@@ -0,0 +1,28 @@
+use {
+  super::{error::ApiError, *},
+  axum::{http::Request, middleware::Next},
+  subtle::ConstantTimeEq,
+};
+
+/// Reject requests that don't carry `Authorization: Bearer <api_key>`. The
+/// key is compared in constant time so response timing doesn't leak it.
+pub(super) async fn require_api_key<B>(
+  api_key: Arc<str>,
+  request: Request<B>,
+  next: Next<B>,
+) -> Response {
+  let authorized = request
+    .headers()
+    .get(header::AUTHORIZATION)
+    .and_then(|value| value.to_str().ok())
+    .and_then(|value| value.strip_prefix("Bearer "))
+    .map_or(false, |token| {
+      bool::from(token.as_bytes().ct_eq(api_key.as_bytes()))
+    });
+
+  if !authorized {
+    return ApiError::unauthorized("missing or invalid API key").into_response();
+  }
+
+  next.run(request).await
+}
@@ -1,4 +1,9 @@
-use {super::*, crate::okx::datastore::brc20::Tick, axum::Json, utoipa::ToSchema};
+use {
+  super::*,
+  crate::okx::datastore::brc20::Tick,
+  axum::Json,
+  utoipa::{IntoParams, ToSchema},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -62,6 +67,81 @@ pub(crate) async fn brc20_balance(
   })))
 }
 
+const BALANCE_HISTORY_MAX_DEPTH: u32 = 1_000;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct BalanceAtHeightQuery {
+  /// Height of the block after which to take the balance, at most 1000 blocks
+  /// below the indexed height. Defaults to the indexed height.
+  height: Option<u32>,
+}
+
+/// Get the ticker balance of the address at a block height.
+///
+/// Retrieve the asset balance of the 'ticker' for the address as it was after the block at `height`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/tick/{ticker}/balance/{address}",
+    params(
+        ("ticker" = String, Path, description = "Token ticker", min_length = 4, max_length = 5),
+        ("address" = String, Path, description = "Address"),
+        BalanceAtHeightQuery,
+  ),
+    responses(
+      (status = 200, description = "Obtain account balance by query ticker and height.", body = BRC20Balance),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_balance_at_height(
+  Extension(index): Extension<Arc<Index>>,
+  Path((tick, address)): Path<(String, String)>,
+  Query(query): Query<BalanceAtHeightQuery>,
+) -> ApiResult<ApiBalance> {
+  log::debug!(
+    "rpc: get brc20_balance_at_height: {} {} {:?}",
+    tick,
+    address,
+    query
+  );
+
+  let rtx = index.begin_read()?;
+  let chain = index.get_chain();
+
+  let ticker = Tick::from_str(&tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))?;
+  let script_key = utils::parse_and_validate_script_key_with_chain(&address, chain)
+    .map_err(ApiError::bad_request)?;
+
+  let Some(indexed) = rtx.block_height()?.map(|height| height.n()) else {
+    return Err(ApiError::not_found("no blocks indexed"));
+  };
+
+  let height = query.height.unwrap_or(indexed);
+
+  if height > indexed {
+    return Err(ApiError::bad_request(format!(
+      "height {height} is above the indexed height {indexed}"
+    )));
+  }
+
+  if indexed - height > BALANCE_HISTORY_MAX_DEPTH {
+    return Err(ApiError::bad_request(format!(
+      "height may be at most {BALANCE_HISTORY_MAX_DEPTH} blocks below the indexed height {indexed}"
+    )));
+  }
+
+  let balance = Index::get_brc20_balance_at_height(ticker, script_key, height, &rtx)?
+    .ok_or(BRC20ApiError::UnknownTicker(tick.clone()))?;
+
+  Ok(Json(ApiResponse::ok(ApiBalance {
+    tick: balance.tick.to_string(),
+    available_balance: (balance.overall_balance - balance.transferable_balance).to_string(),
+    transferable_balance: balance.transferable_balance.to_string(),
+    overall_balance: balance.overall_balance.to_string(),
+  })))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[schema(as = brc20::AllBalance)]
@@ -72,12 +152,14 @@ pub struct ApiBalances {
 
 /// Get all ticker balances of the address.
 ///
-/// Retrieve all BRC20 protocol asset balances associated with a address.
+/// Retrieve all BRC20 protocol asset balances associated with a address.<br>
+/// With `v=2` the balances are returned as `{ "data": [...], "page_index": N, "limit": N, "more": bool }`.
 #[utoipa::path(
     get,
     path = "/api/v1/brc20/address/{address}/balance",
     params(
-        ("address" = String, Path, description = "Address")
+        ("address" = String, Path, description = "Address"),
+        ListQuery
   ),
     responses(
       (status = 200, description = "Obtain account balances by query address.", body = BRC20AllBalance),
@@ -88,10 +170,14 @@ pub struct ApiBalances {
   )]
 pub(crate) async fn brc20_all_balance(
   Extension(index): Extension<Arc<Index>>,
+  Extension(server_config): Extension<Arc<ServerConfig>>,
   Path(account): Path<String>,
-) -> ApiResult<ApiBalances> {
+  Query(query): Query<ListQuery>,
+) -> ApiResult<ApiList<ApiBalances, ApiBalance>> {
   log::debug!("rpc: get brc20_all_balance: {}", account);
 
+  let page = query.page(&server_config)?;
+
   let rtx = index.begin_read()?;
   let chain = index.get_chain();
 
@@ -101,15 +187,17 @@ pub(crate) async fn brc20_all_balance(
   let all_balance = rtx.brc20_get_all_balance_by_address(script_key)?;
   log::debug!("rpc: get brc20_all_balance: {} {:?}", account, all_balance);
 
-  Ok(Json(ApiResponse::ok(ApiBalances {
-    balance: all_balance
-      .into_iter()
-      .map(|bal| ApiBalance {
-        tick: bal.tick.to_string(),
-        available_balance: (bal.overall_balance - bal.transferable_balance).to_string(),
-        transferable_balance: bal.transferable_balance.to_string(),
-        overall_balance: bal.overall_balance.to_string(),
-      })
-      .collect(),
+  let balance = all_balance.into_iter().map(|bal| ApiBalance {
+    tick: bal.tick.to_string(),
+    available_balance: (bal.overall_balance - bal.transferable_balance).to_string(),
+    transferable_balance: bal.transferable_balance.to_string(),
+    overall_balance: bal.overall_balance.to_string(),
+  });
+
+  Ok(Json(ApiResponse::ok(match page {
+    Some((page_index, page_size)) => ApiList::V2(ApiPage::paginate(balance, page_index, page_size)),
+    None => ApiList::V1(ApiBalances {
+      balance: balance.collect(),
+    }),
   })))
 }
@@ -1,7 +1,7 @@
 use {super::*, utoipa::ToSchema};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
-#[schema(as = ord::ApiOutPointResult)]
+#[schema(as = brc20::ApiOutPointResult)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiOutPointResult {
   #[schema(value_type = Option<brc20::ApiTransferableAssets>)]
@@ -20,7 +20,7 @@ pub struct ApiOutPointResult {
       ("outpoint" = String, Path, description = "Outpoint")
 ),
   responses(
-    (status = 200, description = "Obtain outpoint infomation", body = OrdOutPointData),
+    (status = 200, description = "Obtain outpoint infomation", body = BRC20OutPointResult),
     (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
     (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
     (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
@@ -28,7 +28,7 @@ pub struct ApiOutPointResult {
 )]
 pub(crate) async fn brc20_outpoint(
   Extension(index): Extension<Arc<Index>>,
-  Path(outpoint): Path<OutPoint>,
+  PathParams(Param(outpoint)): PathParams<Param<OutPoint>>,
 ) -> ApiResult<ApiOutPointResult> {
   log::debug!("rpc: get brc20_outpoint: {outpoint}");
 
@@ -51,6 +51,8 @@ pub(crate) async fn brc20_outpoint(
     })));
   }
 
+  let spendable = spendable(&index.bitcoin_rpc_client()?, outpoint)?;
+
   Ok(Json(ApiResponse::ok(ApiOutPointResult {
     result: Some(
       transferable_assets_with_satpoints
@@ -62,6 +64,7 @@ pub(crate) async fn brc20_outpoint(
           tick: asset.tick.as_str().to_string(),
           owner: asset.owner.to_string(),
           location: satpoint,
+          spendable,
         })
         .collect(),
     ),
@@ -1,6 +1,9 @@
 use {
-  self::okx::datastore::brc20::OperationType, super::*,
-  crate::okx::datastore::brc20 as brc20_store, axum::Json, utoipa::ToSchema,
+  self::okx::datastore::brc20::OperationType,
+  super::*,
+  crate::okx::datastore::brc20::{self as brc20_store, Tick},
+  axum::Json,
+  utoipa::{IntoParams, ToSchema},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -386,3 +389,123 @@ pub(crate) async fn brc20_block_events(
       .collect(),
   })))
 }
+
+const TICK_EVENTS_MAX_SPAN: u32 = 1_000;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct TickEventsQuery {
+  /// First block height to include.
+  from_height: Option<u32>,
+  /// Block height to stop before, at most 1000 blocks after `from_height`.
+  to_height: Option<u32>,
+  /// Page index, starting at 0.
+  page: Option<usize>,
+  /// Page size. Defaults to `--default-page-size`, and larger sizes are
+  /// reduced to `--max-page-size`.
+  limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::TickEvent)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTickEvent {
+  /// The height of the block the event occurred in.
+  #[schema(format = "uint32")]
+  pub height: u32,
+  /// The transaction the event occurred in.
+  pub txid: String,
+  #[serde(flatten)]
+  #[schema(value_type = brc20::TxEvent)]
+  pub event: ApiTxEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::TickEvents)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTickEvents {
+  #[schema(value_type = Vec<brc20::TickEvent>)]
+  pub events: Vec<ApiTickEvent>,
+  pub page_index: usize,
+  pub limit: usize,
+  pub more: bool,
+}
+
+/// Get ticker events by height range.
+///
+/// Retrieve the successful deploy, mint, inscribeTransfer and transfer events of a ticker, in chain order.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/tick/{ticker}/events",
+    params(
+        ("ticker" = String, Path, description = "Token ticker", min_length = 4, max_length = 5),
+        TickEventsQuery,
+  ),
+    responses(
+      (status = 200, description = "Obtain ticker events by height range", body = BRC20TickEvents),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Ticker not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_tick_events(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Path(tick): Path<String>,
+  Query(query): Query<TickEventsQuery>,
+) -> ApiResult<ApiTickEvents> {
+  log::debug!("rpc: get brc20_tick_events: {} {:?}", tick, query);
+
+  let (Some(from_height), Some(to_height)) = (query.from_height, query.to_height) else {
+    return Err(ApiError::bad_request(
+      "from_height and to_height are required",
+    ));
+  };
+
+  if from_height >= to_height {
+    return Err(ApiError::bad_request(
+      "from_height must be less than to_height",
+    ));
+  }
+
+  if to_height - from_height > TICK_EVENTS_MAX_SPAN {
+    return Err(ApiError::bad_request(format!(
+      "height range may span at most {TICK_EVENTS_MAX_SPAN} blocks"
+    )));
+  }
+
+  let ticker = Tick::from_str(&tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))?;
+
+  let rtx = index.begin_read()?;
+
+  if rtx.brc20_get_tick_info(&ticker)?.is_none() {
+    return Err(BRC20ApiError::UnknownTicker(tick).into());
+  }
+
+  let page_index = query.page.unwrap_or_default();
+
+  let limit = page_size(&server_config, query.limit)?;
+
+  let mut events = Index::get_brc20_tick_receipts(&ticker, from_height, to_height, &rtx)?
+    .into_iter()
+    .skip(page_index.saturating_mul(limit))
+    .take(limit.saturating_add(1))
+    .map(|(height, txid, receipt)| ApiTickEvent {
+      height,
+      txid: txid.to_string(),
+      event: receipt.into(),
+    })
+    .collect::<Vec<ApiTickEvent>>();
+
+  let more = events.len() > limit;
+
+  if more {
+    events.pop();
+  }
+
+  Ok(Json(ApiResponse::ok(ApiTickEvents {
+    events,
+    page_index,
+    limit,
+    more,
+  })))
+}
@@ -34,6 +34,12 @@ pub struct ApiTickInfo {
   /// The amount of the ticker that has been minted.
   #[schema(format = "uint64")]
   pub minted: String,
+  /// The amount of the ticker that can still be minted.
+  #[schema(format = "uint64")]
+  pub remaining_mintable: String,
+  /// The share of the supply that has been minted, in percent, rounded to two decimals.
+  #[schema(example = 37.5)]
+  pub minted_percent: f64,
   /// The decimal of the ticker.<br>
   /// Number of decimals cannot exceed 18 (default).
   #[schema(
@@ -67,6 +73,11 @@ impl From<TokenInfo> for ApiTickInfo {
       burned_supply: tick_info.burned_supply.to_string(),
       limit_per_mint: tick_info.limit_per_mint.to_string(),
       minted: tick_info.minted.to_string(),
+      remaining_mintable: tick_info
+        .supply
+        .saturating_sub(tick_info.minted)
+        .to_string(),
+      minted_percent: minted_percent(tick_info.minted, tick_info.supply),
       decimal: tick_info.decimal,
       self_mint: tick_info.is_self_mint,
       deploy_by: tick_info.deploy_by.clone().into(),
@@ -77,6 +88,15 @@ impl From<TokenInfo> for ApiTickInfo {
   }
 }
 
+#[allow(clippy::cast_precision_loss)]
+fn minted_percent(minted: u128, supply: u128) -> f64 {
+  if supply == 0 {
+    return 0.0;
+  }
+
+  (minted as f64 / supply as f64 * 10_000.0).round() / 100.0
+}
+
 /// Get the ticker info.
 ///
 /// Retrieve detailed information about the ticker.
@@ -121,10 +141,12 @@ pub struct ApiTickInfos {
 
 /// Get all tickers info.
 ///
-/// Retrieve detailed information about all tickers.
+/// Retrieve detailed information about all tickers.<br>
+/// With `v=2` the tickers are returned as `{ "data": [...], "page_index": N, "limit": N, "more": bool }`.
 #[utoipa::path(
     get,
     path = "/api/v1/brc20/tick",
+    params(ListQuery),
     responses(
       (status = 200, description = "Obtain matching all BRC20 tickers.", body = BRC20AllTick),
       (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
@@ -134,14 +156,23 @@ pub struct ApiTickInfos {
   )]
 pub(crate) async fn brc20_all_tick_info(
   Extension(index): Extension<Arc<Index>>,
-) -> ApiResult<ApiTickInfos> {
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Query(query): Query<ListQuery>,
+) -> ApiResult<ApiList<ApiTickInfos, ApiTickInfo>> {
   log::debug!("rpc: get brc20_all_tick_info");
 
+  let page = query.page(&server_config)?;
+
   let rtx = index.begin_read()?;
   let all_tick_info = rtx.brc20_get_all_tick_info()?;
   log::debug!("rpc: get brc20_all_tick_info: {:?}", all_tick_info);
 
-  Ok(Json(ApiResponse::ok(ApiTickInfos {
-    tokens: all_tick_info.into_iter().map(|t| t.into()).collect(),
+  let tokens = all_tick_info.into_iter().map(ApiTickInfo::from);
+
+  Ok(Json(ApiResponse::ok(match page {
+    Some((page_index, page_size)) => ApiList::V2(ApiPage::paginate(tokens, page_index, page_size)),
+    None => ApiList::V1(ApiTickInfos {
+      tokens: tokens.collect(),
+    }),
   })))
 }
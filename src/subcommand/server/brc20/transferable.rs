@@ -17,6 +17,19 @@ pub struct ApiTransferableAsset {
   pub owner: String,
   /// The inscription location.
   pub location: SatPoint,
+  /// Whether the output holding the inscription is unspent, counting spends
+  /// in the mempool, so that sending it still transfers the amount.
+  pub spendable: bool,
+}
+
+/// Whether `outpoint` is unspent, according to the node, including its
+/// mempool.
+pub(super) fn spendable(client: &Client, outpoint: OutPoint) -> Result<bool> {
+  Ok(
+    client
+      .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
+      .is_some(),
+  )
 }
 
 /// Get the transferable inscriptions of the address.
@@ -44,6 +57,7 @@ pub(crate) async fn brc20_transferable(
 
   let rtx = index.begin_read()?;
   let chain = index.get_chain();
+  let client = index.bitcoin_rpc_client()?;
 
   let ticker = Tick::from_str(&tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))?;
   let script_key = utils::parse_and_validate_script_key_with_chain(&address, chain)
@@ -67,10 +81,11 @@ pub(crate) async fn brc20_transferable(
       tick: transferable_asset.tick.as_str().to_string(),
       owner: transferable_asset.owner.to_string(),
       location: satpoint,
+      spendable: spendable(&client, satpoint.outpoint)?,
     });
   }
 
-  api_transferable_assets.sort_by(|a, b| a.inscription_number.cmp(&b.inscription_number));
+  api_transferable_assets.sort_by_key(|asset| asset.inscription_number);
 
   Ok(Json(ApiResponse::ok(ApiTransferableAssets {
     inscriptions: api_transferable_assets,
@@ -109,6 +124,7 @@ pub(crate) async fn brc20_all_transferable(
 
   let rtx = index.begin_read()?;
   let chain = index.get_chain();
+  let client = index.bitcoin_rpc_client()?;
 
   let script_key = utils::parse_and_validate_script_key_with_chain(&account, chain)
     .map_err(ApiError::bad_request)?;
@@ -128,10 +144,11 @@ pub(crate) async fn brc20_all_transferable(
       tick: transferable_asset.tick.as_str().to_string(),
       owner: transferable_asset.owner.to_string(),
       location: satpoint,
+      spendable: spendable(&client, satpoint.outpoint)?,
     });
   }
 
-  api_transferable_assets.sort_by(|a, b| a.inscription_number.cmp(&b.inscription_number));
+  api_transferable_assets.sort_by_key(|asset| asset.inscription_number);
 
   Ok(Json(ApiResponse::ok(ApiTransferableAssets {
     inscriptions: api_transferable_assets,
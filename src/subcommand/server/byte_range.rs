@@ -0,0 +1,139 @@
+use {super::*, std::ops::Range};
+
+/// The `Range` header of a request, if any. Only single ranges in bytes are
+/// supported. Requests for several ranges, ranges in other units, and
+/// malformed ranges are answered with the whole content, as if no range had
+/// been requested.
+#[derive(Default, Debug)]
+pub(crate) struct RangeHeader(pub(crate) Option<String>);
+
+#[async_trait::async_trait]
+impl<S> axum::extract::FromRequestParts<S> for RangeHeader
+where
+  S: Send + Sync,
+{
+  type Rejection = (StatusCode, &'static str);
+
+  async fn from_request_parts(
+    parts: &mut http::request::Parts,
+    _state: &S,
+  ) -> Result<Self, Self::Rejection> {
+    Ok(Self(
+      parts
+        .headers
+        .get(header::RANGE)
+        .map(|value| value.to_str().unwrap_or_default().to_owned()),
+    ))
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ByteRange {
+  Whole,
+  Slice(Range<usize>),
+  Unsatisfiable,
+}
+
+impl RangeHeader {
+  /// The part of content `len` bytes long that was requested.
+  pub(crate) fn resolve(&self, len: usize) -> ByteRange {
+    let Some(spec) = self
+      .0
+      .as_deref()
+      .and_then(|value| value.trim().strip_prefix("bytes="))
+    else {
+      return ByteRange::Whole;
+    };
+
+    if spec.contains(',') {
+      return ByteRange::Whole;
+    }
+
+    let Some((first, last)) = spec.split_once('-') else {
+      return ByteRange::Whole;
+    };
+
+    let (first, last) = (first.trim(), last.trim());
+
+    if first.is_empty() {
+      let Ok(suffix) = last.parse::<usize>() else {
+        return ByteRange::Whole;
+      };
+
+      if suffix == 0 || len == 0 {
+        return ByteRange::Unsatisfiable;
+      }
+
+      return ByteRange::Slice(len.saturating_sub(suffix)..len);
+    }
+
+    let Ok(start) = first.parse::<usize>() else {
+      return ByteRange::Whole;
+    };
+
+    let end = if last.is_empty() {
+      len
+    } else {
+      match last.parse::<usize>() {
+        Ok(last) if last >= start => last.saturating_add(1).min(len),
+        _ => return ByteRange::Whole,
+      }
+    };
+
+    if start >= len {
+      return ByteRange::Unsatisfiable;
+    }
+
+    ByteRange::Slice(start..end)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn resolve(range: &str, len: usize) -> ByteRange {
+    RangeHeader(Some(range.into())).resolve(len)
+  }
+
+  #[test]
+  fn missing_header_requests_whole_content() {
+    assert_eq!(RangeHeader(None).resolve(10), ByteRange::Whole);
+  }
+
+  #[test]
+  fn closed_ranges_are_inclusive_and_clamped() {
+    assert_eq!(resolve("bytes=0-0", 10), ByteRange::Slice(0..1));
+    assert_eq!(resolve("bytes=2-5", 10), ByteRange::Slice(2..6));
+    assert_eq!(resolve("bytes=2-100", 10), ByteRange::Slice(2..10));
+  }
+
+  #[test]
+  fn open_ended_ranges_run_to_the_end() {
+    assert_eq!(resolve("bytes=4-", 10), ByteRange::Slice(4..10));
+  }
+
+  #[test]
+  fn suffix_ranges_count_from_the_end() {
+    assert_eq!(resolve("bytes=-3", 10), ByteRange::Slice(7..10));
+    assert_eq!(resolve("bytes=-30", 10), ByteRange::Slice(0..10));
+  }
+
+  #[test]
+  fn out_of_bounds_ranges_are_unsatisfiable() {
+    assert_eq!(resolve("bytes=10-", 10), ByteRange::Unsatisfiable);
+    assert_eq!(resolve("bytes=20-30", 10), ByteRange::Unsatisfiable);
+    assert_eq!(resolve("bytes=-0", 10), ByteRange::Unsatisfiable);
+    assert_eq!(resolve("bytes=0-", 0), ByteRange::Unsatisfiable);
+    assert_eq!(resolve("bytes=-1", 0), ByteRange::Unsatisfiable);
+  }
+
+  #[test]
+  fn unsupported_and_malformed_ranges_request_whole_content() {
+    assert_eq!(resolve("bytes=0-1,4-5", 10), ByteRange::Whole);
+    assert_eq!(resolve("items=0-1", 10), ByteRange::Whole);
+    assert_eq!(resolve("bytes=5-2", 10), ByteRange::Whole);
+    assert_eq!(resolve("bytes=a-b", 10), ByteRange::Whole);
+    assert_eq!(resolve("bytes=5", 10), ByteRange::Whole);
+  }
+}
@@ -0,0 +1,84 @@
+/// Text content that is not valid UTF-8 is served with this header.
+pub(super) const X_INVALID_UTF8: &str = "x-invalid-utf8";
+
+#[derive(Debug, PartialEq)]
+pub(super) enum Charset {
+  /// Serve the content type as stored.
+  Unchanged,
+  /// Serve this content type, which declares the UTF-8 charset.
+  Utf8(String),
+  /// The content is text, but not valid UTF-8.
+  InvalidUtf8,
+}
+
+/// Whether to declare the UTF-8 charset of `body`, served as `content_type`.
+/// Only text and JSON content types that do not declare a charset already
+/// are changed.
+pub(super) fn charset(content_type: &str, body: &[u8]) -> Charset {
+  let Ok(mime) = content_type.parse::<mime::Mime>() else {
+    return Charset::Unchanged;
+  };
+
+  if mime.get_param(mime::CHARSET).is_some() {
+    return Charset::Unchanged;
+  }
+
+  let text = mime.type_() == mime::TEXT;
+
+  if !text && mime.essence_str() != mime::APPLICATION_JSON.essence_str() {
+    return Charset::Unchanged;
+  }
+
+  if std::str::from_utf8(body).is_ok() {
+    Charset::Utf8(format!(
+      "{}; charset=utf-8",
+      content_type.trim_end_matches(|c: char| c == ';' || c.is_ascii_whitespace())
+    ))
+  } else if text {
+    Charset::InvalidUtf8
+  } else {
+    Charset::Unchanged
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn utf8_charset_is_added_to_text_and_json() {
+    for (content_type, expected) in [
+      ("text/plain", "text/plain; charset=utf-8"),
+      ("text/html;", "text/html; charset=utf-8"),
+      ("application/json", "application/json; charset=utf-8"),
+      (
+        "text/plain; format=flowed",
+        "text/plain; format=flowed; charset=utf-8",
+      ),
+    ] {
+      assert_eq!(
+        charset(content_type, "héllo".as_bytes()),
+        Charset::Utf8(expected.into())
+      );
+    }
+  }
+
+  #[test]
+  fn declared_charsets_and_other_types_are_unchanged() {
+    for content_type in [
+      "text/plain;charset=utf-8",
+      "text/plain; charset=ISO-8859-1",
+      "image/png",
+      "application/octet-stream",
+      "not a content type",
+    ] {
+      assert_eq!(charset(content_type, b"hello"), Charset::Unchanged);
+    }
+  }
+
+  #[test]
+  fn invalid_utf8_text_is_flagged() {
+    assert_eq!(charset("text/plain", b"\xff\xfe"), Charset::InvalidUtf8);
+    assert_eq!(charset("application/json", b"\xff\xfe"), Charset::Unchanged);
+  }
+}
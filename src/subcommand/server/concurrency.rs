@@ -0,0 +1,244 @@
+use {
+  super::{error::ApiError, *},
+  axum::{extract::ConnectInfo, http::Request, middleware::Next},
+  std::net::IpAddr,
+};
+
+/// Clients told to back off are asked to retry after this many seconds.
+const RETRY_AFTER_SECS: u64 = 1;
+
+/// The number of requests in flight from each client IP.
+pub(super) struct ConcurrencyLimit {
+  in_flight: Mutex<HashMap<IpAddr, usize>>,
+  max: usize,
+  trust_proxy: bool,
+}
+
+/// A request slot of a client, released when dropped, whether the request
+/// was served, failed, or was aborted.
+struct Slot {
+  ip: IpAddr,
+  limit: Arc<ConcurrencyLimit>,
+}
+
+impl Drop for Slot {
+  fn drop(&mut self) {
+    let mut in_flight = self.limit.in_flight.lock().unwrap();
+
+    if let Some(count) = in_flight.get_mut(&self.ip) {
+      *count -= 1;
+
+      if *count == 0 {
+        in_flight.remove(&self.ip);
+      }
+    }
+  }
+}
+
+impl ConcurrencyLimit {
+  pub(super) fn new(max: usize, trust_proxy: bool) -> Self {
+    Self {
+      in_flight: Mutex::new(HashMap::new()),
+      max,
+      trust_proxy,
+    }
+  }
+
+  /// The first address in `X-Forwarded-For` if the proxy is trusted,
+  /// otherwise the address of the peer.
+  fn client_ip<B>(&self, request: &Request<B>) -> Option<IpAddr> {
+    let forwarded = self
+      .trust_proxy
+      .then(|| request.headers().get("x-forwarded-for"))
+      .flatten()
+      .and_then(|value| value.to_str().ok())
+      .and_then(|value| value.split(',').next())
+      .and_then(|ip| ip.trim().parse().ok());
+
+    forwarded.or_else(|| {
+      request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(address)| address.ip())
+    })
+  }
+
+  fn acquire(self: &Arc<Self>, ip: IpAddr) -> Option<Slot> {
+    let mut in_flight = self.in_flight.lock().unwrap();
+
+    let count = in_flight.entry(ip).or_default();
+
+    if *count >= self.max {
+      return None;
+    }
+
+    *count += 1;
+
+    Some(Slot {
+      ip,
+      limit: self.clone(),
+    })
+  }
+}
+
+/// Reject requests from clients that already have `--max-concurrent-per-ip`
+/// requests in flight with a 429. Requests whose client IP is unknown are not
+/// limited.
+pub(super) async fn limit_concurrency<B>(
+  limit: Option<Arc<ConcurrencyLimit>>,
+  request: Request<B>,
+  next: Next<B>,
+) -> Response {
+  let Some(limit) = limit else {
+    return next.run(request).await;
+  };
+
+  let Some(ip) = limit.client_ip(&request) else {
+    return next.run(request).await;
+  };
+
+  let Some(_slot) = limit.acquire(ip) else {
+    return ApiError::rate_limited(RETRY_AFTER_SECS).into_response();
+  };
+
+  next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    axum::{body::Body, routing::get},
+    hyper::service::Service,
+    tokio::sync::oneshot,
+  };
+
+  fn request(peer: &str, forwarded_for: Option<&str>, path: &str) -> Request<Body> {
+    let mut request = Request::get(path);
+
+    if let Some(forwarded_for) = forwarded_for {
+      request = request.header("x-forwarded-for", forwarded_for);
+    }
+
+    let mut request = request.body(Body::empty()).unwrap();
+
+    request
+      .extensions_mut()
+      .insert(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 1234)));
+
+    request
+  }
+
+  /// A router whose `/wait` requests are held in flight until `release` is
+  /// sent to, and whose `/fail` requests fail.
+  fn router(trust_proxy: bool) -> (Router, oneshot::Sender<()>) {
+    let (release, released) = oneshot::channel::<()>();
+    let released = Arc::new(tokio::sync::Mutex::new(Some(released)));
+
+    let limit = Some(Arc::new(ConcurrencyLimit::new(1, trust_proxy)));
+
+    let router = Router::new()
+      .route("/", get(|| async { "ok" }))
+      .route(
+        "/fail",
+        get(|| async { ServerError::NotFound("nothing".into()) }),
+      )
+      .route(
+        "/wait",
+        get(move || async move {
+          if let Some(released) = released.lock().await.take() {
+            released.await.ok();
+          }
+          "done"
+        }),
+      )
+      .layer(axum::middleware::from_fn(move |request, next| {
+        limit_concurrency(limit.clone(), request, next)
+      }));
+
+    (router, release)
+  }
+
+  #[tokio::test]
+  async fn concurrent_requests_from_the_same_ip_are_limited() {
+    let (mut router, release) = router(false);
+
+    let held = tokio::spawn(router.call(request("10.0.0.1", None, "/wait")));
+
+    tokio::task::yield_now().await;
+
+    let response = router.call(request("10.0.0.1", None, "/")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+
+    let response = router.call(request("10.0.0.2", None, "/")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    release.send(()).unwrap();
+    assert_eq!(held.await.unwrap().unwrap().status(), StatusCode::OK);
+
+    let response = router.call(request("10.0.0.1", None, "/")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+  }
+
+  #[tokio::test]
+  async fn slots_are_released_after_errors() {
+    let (mut router, _release) = router(false);
+
+    for _ in 0..2 {
+      let response = router
+        .call(request("10.0.0.1", None, "/fail"))
+        .await
+        .unwrap();
+      assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    let response = router.call(request("10.0.0.1", None, "/")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+  }
+
+  #[tokio::test]
+  async fn forwarded_for_is_only_honored_behind_a_trusted_proxy() {
+    for trust_proxy in [false, true] {
+      let (mut router, release) = router(trust_proxy);
+
+      let held = tokio::spawn(router.call(request("10.0.0.1", Some("192.0.2.1"), "/wait")));
+
+      tokio::task::yield_now().await;
+
+      let response = router
+        .call(request("10.0.0.1", Some("192.0.2.2, 10.0.0.1"), "/"))
+        .await
+        .unwrap();
+
+      assert_eq!(
+        response.status(),
+        if trust_proxy {
+          StatusCode::OK
+        } else {
+          StatusCode::TOO_MANY_REQUESTS
+        },
+        "{trust_proxy}"
+      );
+
+      release.send(()).unwrap();
+      held.await.unwrap().unwrap();
+    }
+  }
+
+  #[test]
+  fn ips_are_forgotten_once_idle() {
+    let limit = Arc::new(ConcurrencyLimit::new(2, false));
+    let ip = "10.0.0.1".parse().unwrap();
+
+    let first = limit.acquire(ip).unwrap();
+    let second = limit.acquire(ip).unwrap();
+    assert!(limit.acquire(ip).is_none());
+
+    drop(first);
+    assert_eq!(limit.in_flight.lock().unwrap()[&ip], 1);
+
+    drop(second);
+    assert!(limit.in_flight.lock().unwrap().is_empty());
+  }
+}
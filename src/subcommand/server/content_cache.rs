@@ -0,0 +1,500 @@
+use {
+  super::*,
+  axum::body::{BoxBody, Bytes, StreamBody},
+  linked_hash_map::LinkedHashMap,
+  once_cell::sync::OnceCell,
+  std::{
+    convert::Infallible,
+    io::Write,
+    ops::Range,
+    sync::atomic::{self, AtomicU64},
+  },
+};
+
+/// Stored content longer than this is written to responses in chunks of this
+/// size, rather than copied into the response all at once.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Brotli quality and window size of compressed content, a middle ground that
+/// compresses far faster than the maximum quality of 11.
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_LGWIN: u32 = 22;
+
+/// Least recently used cache of `/content` responses, bounded by the total
+/// size of the cached content and its compressed representations. Only
+/// inscriptions found in the index are ever inserted, and those never change,
+/// so entries are not invalidated.
+pub(super) struct ContentCache {
+  capacity: usize,
+  compression: Vec<Compression>,
+  compression_min_size: usize,
+  compressions: AtomicU64,
+  state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+  entries: LinkedHashMap<InscriptionId, Entry>,
+  size: usize,
+}
+
+struct Entry {
+  content: Arc<CachedContent>,
+  /// The size the entry was accounted at, which grows as compressed
+  /// representations of the content are added.
+  size: usize,
+}
+
+pub(super) struct CachedContent {
+  /// The inscription the content belongs to, which is the delegate when the
+  /// requested inscription delegates its content.
+  pub(super) id: InscriptionId,
+  pub(super) inscription: Inscription,
+  pub(super) timestamp: u32,
+  br: OnceCell<Bytes>,
+  gzip: OnceCell<Bytes>,
+}
+
+impl CachedContent {
+  pub(super) fn new(id: InscriptionId, inscription: Inscription, timestamp: u32) -> Self {
+    Self {
+      id,
+      inscription: Inscription {
+        body: inscription.body,
+        content_encoding: inscription.content_encoding,
+        content_type: inscription.content_type,
+        ..Default::default()
+      },
+      timestamp,
+      br: OnceCell::new(),
+      gzip: OnceCell::new(),
+    }
+  }
+
+  /// A response body with `range` of the stored content, which must be in
+  /// bounds.
+  pub(super) fn stream(self: Arc<Self>, range: Range<usize>) -> BoxBody {
+    if range.len() <= STREAM_CHUNK_SIZE {
+      return body::boxed(Full::from(Bytes::copy_from_slice(
+        &self.inscription.body().unwrap_or_default()[range],
+      )));
+    }
+
+    let end = range.end;
+
+    body::boxed(StreamBody::new(futures::stream::iter(
+      range.step_by(STREAM_CHUNK_SIZE).map(move |start| {
+        Ok::<_, Infallible>(Bytes::copy_from_slice(
+          &self.inscription.body().unwrap_or_default()[start..end.min(start + STREAM_CHUNK_SIZE)],
+        ))
+      }),
+    )))
+  }
+
+  fn compressed(&self, compression: Compression) -> &OnceCell<Bytes> {
+    match compression {
+      Compression::Br => &self.br,
+      Compression::Gzip => &self.gzip,
+    }
+  }
+
+  fn compress(&self, compression: Compression) -> io::Result<Bytes> {
+    let body = self.inscription.body().unwrap_or_default();
+
+    let compressed = match compression {
+      Compression::Br => {
+        let mut writer =
+          brotli::CompressorWriter::new(Vec::new(), 4096, BROTLI_QUALITY, BROTLI_LGWIN);
+        writer.write_all(body)?;
+        writer.into_inner()
+      }
+      Compression::Gzip => {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(body)?;
+        encoder.finish()?
+      }
+    };
+
+    Ok(compressed.into())
+  }
+
+  /// The size of the stored content, without any compressed representations.
+  fn size(&self) -> usize {
+    [
+      &self.inscription.body,
+      &self.inscription.content_encoding,
+      &self.inscription.content_type,
+    ]
+    .iter()
+    .map(|field| field.as_ref().map(Vec::len).unwrap_or_default())
+    .sum()
+  }
+}
+
+impl ContentCache {
+  pub(super) fn new(
+    capacity: usize,
+    compression: Vec<Compression>,
+    compression_min_size: usize,
+  ) -> Self {
+    Self {
+      capacity,
+      compression,
+      compression_min_size,
+      compressions: AtomicU64::new(0),
+      state: Mutex::new(State::default()),
+    }
+  }
+
+  fn enabled(&self) -> bool {
+    self.capacity > 0
+  }
+
+  pub(super) fn get(&self, inscription_id: InscriptionId) -> Option<Arc<CachedContent>> {
+    if !self.enabled() {
+      return None;
+    }
+
+    let content = self
+      .state
+      .lock()
+      .unwrap()
+      .entries
+      .get_refresh(&inscription_id)
+      .map(|entry| entry.content.clone());
+
+    metrics::record_content_cache_lookup(content.is_some());
+
+    content
+  }
+
+  pub(super) fn insert(&self, inscription_id: InscriptionId, content: Arc<CachedContent>) {
+    let size = content.size();
+
+    if !self.enabled() || size > self.capacity {
+      return;
+    }
+
+    let mut state = self.state.lock().unwrap();
+
+    if let Some(previous) = state
+      .entries
+      .insert(inscription_id, Entry { content, size })
+    {
+      state.size -= previous.size;
+    }
+
+    state.size += size;
+
+    Self::evict(&mut state, self.capacity);
+  }
+
+  /// The stored content of `inscription_id`, compressed with the algorithm
+  /// the client prefers among those enabled with `--compression`, or `None`
+  /// if it should be served as stored. The compressed representation is
+  /// computed on `transforms` on first demand and kept with the cached
+  /// content, so that popular content is not compressed again for every
+  /// request. Without a cache, content is left to the compression layer.
+  pub(super) async fn compressed(
+    &self,
+    inscription_id: InscriptionId,
+    content: &Arc<CachedContent>,
+    accept_encoding: &AcceptEncoding,
+    transforms: &TransformPool,
+  ) -> ServerResult<Option<(Compression, Bytes)>> {
+    let content_type = content.inscription.content_type().unwrap_or_default();
+
+    // like the compression layer, leave images other than SVG uncompressed
+    if !self.enabled()
+      || content.inscription.content_encoding.is_some()
+      || content.inscription.body().unwrap_or_default().len() < self.compression_min_size
+      || (content_type.starts_with("image/") && !content_type.starts_with("image/svg+xml"))
+    {
+      return Ok(None);
+    }
+
+    let Some(compression) = self
+      .compression
+      .iter()
+      .copied()
+      .filter(|compression| accept_encoding.quality(compression.encoding()) > 0.0)
+      .max_by(|a, b| {
+        accept_encoding
+          .quality(a.encoding())
+          .total_cmp(&accept_encoding.quality(b.encoding()))
+          // prefer the algorithm listed first in `--compression` on ties
+          .then(Ordering::Greater)
+      })
+    else {
+      return Ok(None);
+    };
+
+    if let Some(compressed) = content.compressed(compression).get() {
+      return Ok(Some((compression, compressed.clone())));
+    }
+
+    self.compressions.fetch_add(1, atomic::Ordering::Relaxed);
+
+    let compressed = transforms
+      .run({
+        let content = content.clone();
+        move || content.compress(compression)
+      })
+      .await?
+      .map_err(|err| ServerError::Internal(err.into()))?;
+
+    // only account for the compressed content once, should a concurrent
+    // request have compressed it too
+    if content
+      .compressed(compression)
+      .set(compressed.clone())
+      .is_ok()
+    {
+      let mut guard = self.state.lock().unwrap();
+      let state = &mut *guard;
+
+      let entry = state
+        .entries
+        .get_mut(&inscription_id)
+        .filter(|entry| Arc::ptr_eq(&entry.content, content));
+
+      if let Some(entry) = entry {
+        entry.size += compressed.len();
+        state.size += compressed.len();
+        Self::evict(state, self.capacity);
+      }
+    }
+
+    Ok(Some((compression, compressed)))
+  }
+
+  fn evict(state: &mut State, capacity: usize) {
+    while state.size > capacity {
+      let Some((_, evicted)) = state.entries.pop_front() else {
+        break;
+      };
+      state.size -= evicted.size;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, axum::body::HttpBody};
+
+  fn content(len: usize) -> Arc<CachedContent> {
+    Arc::new(CachedContent::new(
+      inscription_id(0),
+      Inscription::new(None, Some(vec![0; len])),
+      0,
+    ))
+  }
+
+  #[test]
+  fn disabled_cache_stores_nothing() {
+    let cache = ContentCache::new(0, Vec::new(), 0);
+    cache.insert(inscription_id(1), content(0));
+    assert!(cache.get(inscription_id(1)).is_none());
+  }
+
+  #[test]
+  fn oversized_content_is_not_cached() {
+    let cache = ContentCache::new(10, Vec::new(), 0);
+    cache.insert(inscription_id(1), content(11));
+    assert!(cache.get(inscription_id(1)).is_none());
+  }
+
+  #[test]
+  fn least_recently_used_content_is_evicted() {
+    let cache = ContentCache::new(10, Vec::new(), 0);
+
+    cache.insert(inscription_id(1), content(4));
+    cache.insert(inscription_id(2), content(4));
+
+    assert!(cache.get(inscription_id(1)).is_some());
+
+    cache.insert(inscription_id(3), content(4));
+
+    assert!(cache.get(inscription_id(1)).is_some());
+    assert!(cache.get(inscription_id(2)).is_none());
+    assert!(cache.get(inscription_id(3)).is_some());
+    assert_eq!(cache.state.lock().unwrap().size, 8);
+  }
+
+  #[tokio::test]
+  async fn large_content_is_streamed_in_chunks() {
+    async fn chunks(mut body: BoxBody) -> Vec<Bytes> {
+      let mut chunks = Vec::new();
+      while let Some(chunk) = body.data().await {
+        chunks.push(chunk.unwrap());
+      }
+      chunks
+    }
+
+    let body = (0..STREAM_CHUNK_SIZE * 2 + 1)
+      .map(|i| (i % 256).try_into().unwrap())
+      .collect::<Vec<u8>>();
+
+    let content = Arc::new(CachedContent::new(
+      inscription_id(0),
+      Inscription::new(None, Some(body.clone())),
+      0,
+    ));
+
+    let streamed = chunks(content.clone().stream(0..body.len())).await;
+    assert_eq!(
+      streamed.iter().map(Bytes::len).collect::<Vec<usize>>(),
+      [STREAM_CHUNK_SIZE, STREAM_CHUNK_SIZE, 1]
+    );
+    assert_eq!(streamed.concat(), body);
+
+    let streamed = chunks(content.stream(1..STREAM_CHUNK_SIZE + 1)).await;
+    assert_eq!(streamed.len(), 1);
+    assert_eq!(streamed.concat(), body[1..STREAM_CHUNK_SIZE + 1]);
+  }
+
+  #[test]
+  fn cached_content_drops_other_fields() {
+    let content = CachedContent::new(
+      inscription_id(0),
+      Inscription {
+        metadata: Some(vec![1; 100]),
+        ..Inscription::new(Some("text/plain".into()), Some(vec![0; 4]))
+      },
+      7,
+    );
+
+    assert_eq!(content.inscription.metadata, None);
+    assert_eq!(content.size(), 14);
+  }
+
+  #[tokio::test]
+  async fn compressed_content_is_computed_once_and_reused() {
+    let cache = ContentCache::new(10_000, vec![Compression::Br, Compression::Gzip], 0);
+    let transforms = TransformPool::new(1).unwrap();
+
+    let body = "hello ".repeat(100).into_bytes();
+
+    let content = Arc::new(CachedContent::new(
+      inscription_id(0),
+      Inscription::new(Some("text/javascript".into()), Some(body.clone())),
+      0,
+    ));
+
+    cache.insert(inscription_id(1), content.clone());
+
+    let accept_encoding = AcceptEncoding(Some("gzip, br".into()));
+
+    let (compression, first) = cache
+      .compressed(inscription_id(1), &content, &accept_encoding, &transforms)
+      .await
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(compression, Compression::Br);
+    assert_eq!(cache.compressions.load(atomic::Ordering::Relaxed), 1);
+
+    let (_, second) = cache
+      .compressed(inscription_id(1), &content, &accept_encoding, &transforms)
+      .await
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(cache.compressions.load(atomic::Ordering::Relaxed), 1);
+    assert_eq!(first, second);
+
+    let mut decompressed = Vec::new();
+    brotli::Decompressor::new(&first[..], 4096)
+      .read_to_end(&mut decompressed)
+      .unwrap();
+    assert_eq!(decompressed, body);
+
+    assert_eq!(
+      cache.state.lock().unwrap().size,
+      content.size() + first.len()
+    );
+  }
+
+  #[tokio::test]
+  async fn compression_follows_client_preference() {
+    let cache = ContentCache::new(10_000, vec![Compression::Br, Compression::Gzip], 0);
+
+    let content = Arc::new(CachedContent::new(
+      inscription_id(0),
+      Inscription::new(Some("text/plain".into()), Some(b"hello".to_vec())),
+      0,
+    ));
+
+    let transforms = TransformPool::new(1).unwrap();
+
+    let compressed = |accept_encoding: &'static str| {
+      let (cache, content, transforms) = (&cache, &content, &transforms);
+      async move {
+        cache
+          .compressed(
+            inscription_id(1),
+            content,
+            &AcceptEncoding(Some(accept_encoding.into())),
+            transforms,
+          )
+          .await
+          .unwrap()
+          .map(|(compression, _)| compression)
+      }
+    };
+
+    assert_eq!(compressed("gzip").await, Some(Compression::Gzip));
+    assert_eq!(compressed("gzip, br;q=0.5").await, Some(Compression::Gzip));
+    assert_eq!(compressed("br, gzip").await, Some(Compression::Br));
+    assert_eq!(compressed("identity").await, None);
+    assert_eq!(cache.compressions.load(atomic::Ordering::Relaxed), 2);
+  }
+
+  #[tokio::test]
+  async fn small_images_and_encoded_content_are_not_compressed() {
+    let cache = ContentCache::new(10_000, vec![Compression::Br], 4);
+    let transforms = TransformPool::new(1).unwrap();
+
+    let accept_encoding = AcceptEncoding(Some("br".into()));
+
+    for inscription in [
+      Inscription::new(Some("text/plain".into()), Some(b"hi".to_vec())),
+      Inscription::new(Some("image/png".into()), Some(b"hello".to_vec())),
+      Inscription {
+        content_encoding: Some("br".into()),
+        ..Inscription::new(Some("text/plain".into()), Some(b"hello".to_vec()))
+      },
+    ] {
+      let content = Arc::new(CachedContent::new(inscription_id(0), inscription, 0));
+      assert!(cache
+        .compressed(inscription_id(1), &content, &accept_encoding, &transforms)
+        .await
+        .unwrap()
+        .is_none());
+    }
+  }
+
+  #[tokio::test]
+  async fn content_is_not_compressed_without_cache() {
+    let cache = ContentCache::new(0, vec![Compression::Br], 0);
+
+    let content = Arc::new(CachedContent::new(
+      inscription_id(0),
+      Inscription::new(Some("text/plain".into()), Some(b"hello".to_vec())),
+      0,
+    ));
+
+    assert!(cache
+      .compressed(
+        inscription_id(1),
+        &content,
+        &AcceptEncoding(Some("br".into())),
+        &TransformPool::new(1).unwrap(),
+      )
+      .await
+      .unwrap()
+      .is_none());
+
+    assert_eq!(cache.compressions.load(atomic::Ordering::Relaxed), 0);
+  }
+}
@@ -1,26 +1,120 @@
 use serde::ser::SerializeStruct;
 use utoipa::ToSchema;
-use {super::*, std::fmt::Write};
+use {
+  super::{request_id::RequestId, *},
+  axum::{http::Request, middleware::Next},
+  std::{fmt::Write, future::Future},
+};
+
+const PROBLEM_JSON: &str = "application/problem+json";
+
+tokio::task_local! {
+  static ERROR_FORMAT: ErrorFormat;
+}
+
+/// How `ApiError` response bodies are rendered, chosen with `--error-format`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub(super) enum ErrorFormat {
+  /// `{ "code", "msg" }` JSON
+  #[default]
+  Code,
+  /// RFC 9457 `application/problem+json` problem details
+  Problem,
+}
+
+impl ErrorFormat {
+  /// The format of the request being served, or the default outside of one.
+  pub(super) fn current() -> Self {
+    ERROR_FORMAT.try_with(|format| *format).unwrap_or_default()
+  }
+
+  /// Run `future`, rendering errors in this format. Errors are only rendered
+  /// so if they are turned into responses in the task running `future`, so
+  /// handlers must not be moved to other tasks.
+  pub(super) async fn scope<F: Future>(self, future: F) -> F::Output {
+    ERROR_FORMAT.scope(self, future).await
+  }
+}
 
 #[derive(Debug)]
 pub(super) enum ServerError {
   BadRequest(String),
+  InsufficientStorage(String),
   Internal(Error),
   NotAcceptable {
     accept_encoding: AcceptEncoding,
     content_encoding: HeaderValue,
   },
   NotFound(String),
+  PayloadTooLarge(String),
+  RateLimited {
+    retry_after_secs: u64,
+  },
+  ServiceUnavailable(String),
 }
 
 pub(super) type ServerResult<T> = Result<T, ServerError>;
 
+impl ServerError {
+  fn api_error(&self) -> ApiError {
+    match self {
+      Self::BadRequest(message) => ApiError::bad_request(message),
+      Self::InsufficientStorage(message) => ApiError::internal(message),
+      Self::Internal(_) => ApiError::internal(
+        StatusCode::INTERNAL_SERVER_ERROR
+          .canonical_reason()
+          .unwrap_or_default(),
+      ),
+      Self::NotAcceptable {
+        accept_encoding,
+        content_encoding,
+      } => ApiError::bad_request(Self::not_acceptable_message(
+        accept_encoding,
+        content_encoding,
+      )),
+      Self::NotFound(message) => ApiError::not_found(message),
+      Self::PayloadTooLarge(message) => ApiError::payload_too_large(message),
+      Self::RateLimited { retry_after_secs } => ApiError::rate_limited(*retry_after_secs),
+      Self::ServiceUnavailable(message) => ApiError::internal(message),
+    }
+  }
+
+  fn not_acceptable_message(
+    accept_encoding: &AcceptEncoding,
+    content_encoding: &HeaderValue,
+  ) -> String {
+    let mut message = format!(
+      "inscription content encoding `{}` is not acceptable.",
+      String::from_utf8_lossy(content_encoding.as_bytes())
+    );
+
+    if let Some(accept_encoding) = &accept_encoding.0 {
+      write!(message, " `Accept-Encoding` header: `{accept_encoding}`").unwrap();
+    } else {
+      write!(message, " `Accept-Encoding` header not present").unwrap();
+    };
+
+    message
+  }
+}
+
 impl IntoResponse for ServerError {
   fn into_response(self) -> Response {
-    match self {
+    let api_error = self.api_error();
+
+    metrics::record_api_error(api_error.code());
+
+    let mut response = match self {
       Self::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+      Self::InsufficientStorage(message) => {
+        (StatusCode::INSUFFICIENT_STORAGE, message).into_response()
+      }
       Self::Internal(error) => {
-        eprintln!("error serving request: {error}");
+        tracing::error!(
+          error = %error,
+          request_id = RequestId::current().as_deref().unwrap_or_default(),
+          "error serving request"
+        );
         (
           StatusCode::INTERNAL_SERVER_ERROR,
           StatusCode::INTERNAL_SERVER_ERROR
@@ -32,28 +126,137 @@ impl IntoResponse for ServerError {
       Self::NotAcceptable {
         accept_encoding,
         content_encoding,
-      } => {
-        let mut message = format!(
-          "inscription content encoding `{}` is not acceptable.",
-          String::from_utf8_lossy(content_encoding.as_bytes())
-        );
-
-        if let Some(accept_encoding) = accept_encoding.0 {
-          write!(message, " `Accept-Encoding` header: `{accept_encoding}`").unwrap();
-        } else {
-          write!(message, " `Accept-Encoding` header not present").unwrap();
-        };
-
-        (StatusCode::NOT_ACCEPTABLE, message).into_response()
-      }
+      } => (
+        StatusCode::NOT_ACCEPTABLE,
+        [(header::VARY, HeaderValue::from_static("Accept-Encoding"))],
+        Self::not_acceptable_message(&accept_encoding, &content_encoding),
+      )
+        .into_response(),
       Self::NotFound(message) => (
         StatusCode::NOT_FOUND,
         [(header::CACHE_CONTROL, HeaderValue::from_static("no-store"))],
         message,
       )
         .into_response(),
+      Self::PayloadTooLarge(message) => (StatusCode::PAYLOAD_TOO_LARGE, message).into_response(),
+      Self::RateLimited { retry_after_secs } => (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, HeaderValue::from(retry_after_secs))],
+        StatusCode::TOO_MANY_REQUESTS
+          .canonical_reason()
+          .unwrap_or_default(),
+      )
+        .into_response(),
+      Self::ServiceUnavailable(message) => (
+        StatusCode::SERVICE_UNAVAILABLE,
+        [(header::RETRY_AFTER, HeaderValue::from_static("5"))],
+        message,
+      )
+        .into_response(),
+    };
+
+    response.extensions_mut().insert(api_error);
+
+    response
+  }
+}
+
+/// Replace the plain text body of a `ServerError` response with the `ApiError`
+/// JSON envelope when the request prefers `application/json`, or is for an
+/// `/api` route, whose clients always expect JSON. Elsewhere the body depends
+/// on `Accept`, so such responses carry `Vary: Accept`.
+pub(super) async fn json_server_errors<B>(request: Request<B>, next: Next<B>) -> Response {
+  let api = request.uri().path().starts_with("/api/");
+  let prefers_json = api || prefers_json(request.headers());
+
+  let mut response = next.run(request).await;
+
+  if !api && response.extensions().get::<ApiError>().is_some() {
+    response
+      .headers_mut()
+      .append(header::VARY, HeaderValue::from_static("Accept"));
+  }
+
+  if !prefers_json {
+    return response;
+  }
+
+  let Some(api_error) = response.extensions_mut().remove::<ApiError>() else {
+    return response;
+  };
+
+  let (mut parts, _body) = response.into_parts();
+
+  let (content_type, body) = api_error.body(parts.status);
+
+  parts.headers.remove(header::CONTENT_LENGTH);
+  parts
+    .headers
+    .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+
+  Response::from_parts(parts, body::boxed(body::Full::from(body)))
+}
+
+/// Replace axum's plain text rejection of request bodies larger than
+/// `--max-request-body-bytes` with the `ApiError` JSON envelope.
+pub(super) async fn json_body_limit_rejections<B>(
+  limit: usize,
+  request: Request<B>,
+  next: Next<B>,
+) -> Response {
+  let response = next.run(request).await;
+
+  // errors of handlers, such as oversized decompressed content, are already
+  // answered in full
+  if response.status() != StatusCode::PAYLOAD_TOO_LARGE
+    || response.extensions().get::<ApiError>().is_some()
+    || [
+      Some(&HeaderValue::from_static("application/json")),
+      Some(&HeaderValue::from_static(PROBLEM_JSON)),
+    ]
+    .contains(&response.headers().get(header::CONTENT_TYPE))
+  {
+    return response;
+  }
+
+  ApiError::payload_too_large(format!("request body exceeds limit of {limit} bytes"))
+    .into_response()
+}
+
+fn prefers_json(headers: &HeaderMap) -> bool {
+  prefers_media_type(headers, "application/json")
+}
+
+/// Whether the media range of the `Accept` header with the highest quality
+/// value is `media_type`, and acceptable.
+pub(super) fn prefers_media_type(headers: &HeaderMap, media_type: &str) -> bool {
+  let Some(accept) = headers
+    .get(header::ACCEPT)
+    .and_then(|value| value.to_str().ok())
+  else {
+    return false;
+  };
+
+  let mut preferred: Option<(&str, f32)> = None;
+
+  for media_range in accept.split(',') {
+    let mut params = media_range.split(';');
+
+    let media_type = params.next().unwrap_or_default().trim();
+
+    let quality = params
+      .find_map(|param| param.trim().strip_prefix("q="))
+      .and_then(|quality| quality.parse::<f32>().ok())
+      .unwrap_or(1.0);
+
+    if preferred.map_or(true, |(_, best)| quality > best) {
+      preferred = Some((media_type, quality));
     }
   }
+
+  preferred.map_or(false, |(preferred, quality)| {
+    preferred == media_type && quality > 0.0
+  })
 }
 
 pub(super) trait OptionExt<T> {
@@ -76,7 +279,7 @@ impl From<Error> for ServerError {
 }
 
 #[repr(i32)]
-#[derive(ToSchema)]
+#[derive(Debug, ToSchema)]
 pub(crate) enum ApiError {
   /// Internal server error.
   #[schema(example = json!(&ApiError::internal("internal error")))]
@@ -89,6 +292,22 @@ pub(crate) enum ApiError {
   /// Resource not found.
   #[schema(example = json!(&ApiError::internal("not found")))]
   NotFound(String) = 3,
+
+  /// Too many requests.
+  #[schema(example = json!(&ApiError::rate_limited(5)))]
+  RateLimited { retry_after_secs: u64 } = 4,
+
+  /// Missing or invalid credentials.
+  #[schema(example = json!(&ApiError::unauthorized("missing or invalid API key")))]
+  Unauthorized(String) = 5,
+
+  /// The request took too long to serve.
+  #[schema(example = json!(&ApiError::gateway_timeout("request timed out after 10 seconds")))]
+  GatewayTimeout(String) = 6,
+
+  /// The request body is too large.
+  #[schema(example = json!(&ApiError::payload_too_large("request body exceeds limit of 1048576 bytes")))]
+  PayloadTooLarge(String) = 7,
 }
 
 impl ApiError {
@@ -97,6 +316,22 @@ impl ApiError {
       Self::Internal(_) => 1,
       Self::BadRequest(_) => 2,
       Self::NotFound(_) => 3,
+      Self::RateLimited { .. } => 4,
+      Self::Unauthorized(_) => 5,
+      Self::GatewayTimeout(_) => 6,
+      Self::PayloadTooLarge(_) => 7,
+    }
+  }
+
+  pub(crate) fn message(&self) -> &str {
+    match self {
+      Self::Internal(msg)
+      | Self::BadRequest(msg)
+      | Self::NotFound(msg)
+      | Self::Unauthorized(msg)
+      | Self::GatewayTimeout(msg)
+      | Self::PayloadTooLarge(msg) => msg,
+      Self::RateLimited { .. } => "rate limited",
     }
   }
 
@@ -111,14 +346,92 @@ impl ApiError {
   pub(crate) fn bad_request<S: ToString>(message: S) -> Self {
     Self::BadRequest(message.to_string())
   }
+
+  pub(crate) fn unauthorized<S: ToString>(message: S) -> Self {
+    Self::Unauthorized(message.to_string())
+  }
+
+  pub(crate) fn gateway_timeout<S: ToString>(message: S) -> Self {
+    Self::GatewayTimeout(message.to_string())
+  }
+
+  pub(crate) fn payload_too_large<S: ToString>(message: S) -> Self {
+    Self::PayloadTooLarge(message.to_string())
+  }
+
+  pub(crate) fn rate_limited(retry_after_secs: u64) -> Self {
+    Self::RateLimited { retry_after_secs }
+  }
+
+  /// The JSON body of the error, tagged with the id of the current request.
+  pub(crate) fn to_json(&self) -> serde_json::Value {
+    let mut json = serde_json::to_value(self).unwrap();
+
+    if let (Some(request_id), Some(object)) = (RequestId::current(), json.as_object_mut()) {
+      object.insert("request_id".into(), request_id.into());
+    }
+
+    json
+  }
+
+  /// The error as RFC 9457 problem details, for a response with `status`.
+  pub(crate) fn to_problem_json(&self, status: StatusCode) -> serde_json::Value {
+    let mut json = serde_json::json!({
+      "type": "about:blank",
+      "title": status.canonical_reason().unwrap_or_default(),
+      "status": status.as_u16(),
+      "detail": self.message(),
+    });
+
+    let object = json.as_object_mut().unwrap();
+
+    if let Self::RateLimited { retry_after_secs } = self {
+      object.insert("retry_after".into(), (*retry_after_secs).into());
+    }
+
+    if let Some(request_id) = RequestId::current() {
+      object.insert("request_id".into(), request_id.into());
+    }
+
+    json
+  }
+
+  /// The content type and body of a response with `status` for the error, in
+  /// the current `ErrorFormat`.
+  fn body(&self, status: StatusCode) -> (&'static str, String) {
+    match ErrorFormat::current() {
+      ErrorFormat::Code => ("application/json", self.to_json().to_string()),
+      ErrorFormat::Problem => (PROBLEM_JSON, self.to_problem_json(status).to_string()),
+    }
+  }
+
+  fn status(&self) -> StatusCode {
+    match self {
+      Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+      Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+      Self::NotFound(_) => StatusCode::NOT_FOUND,
+      Self::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+      Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+      Self::GatewayTimeout(_) => StatusCode::GATEWAY_TIMEOUT,
+      Self::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+    }
+  }
 }
+
 impl Serialize for ApiError {
   fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-    let mut state = serializer.serialize_struct("ApiError", 2)?;
     match self {
-      ApiError::Internal(msg) | ApiError::BadRequest(msg) | ApiError::NotFound(msg) => {
+      ApiError::RateLimited { retry_after_secs } => {
+        let mut state = serializer.serialize_struct("ApiError", 3)?;
+        state.serialize_field("code", &self.code())?;
+        state.serialize_field("msg", self.message())?;
+        state.serialize_field("retry_after", retry_after_secs)?;
+        state.end()
+      }
+      _ => {
+        let mut state = serializer.serialize_struct("ApiError", 2)?;
         state.serialize_field("code", &self.code())?;
-        state.serialize_field("msg", &msg)?;
+        state.serialize_field("msg", self.message())?;
         state.end()
       }
     }
@@ -127,13 +440,36 @@ impl Serialize for ApiError {
 
 impl IntoResponse for ApiError {
   fn into_response(self) -> Response {
-    let status_code = match &self {
-      Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
-      Self::BadRequest(_) => StatusCode::BAD_REQUEST,
-      Self::NotFound(_) => StatusCode::NOT_FOUND,
-    };
+    metrics::record_api_error(self.code());
+
+    let status = self.status();
 
-    (status_code, axum::Json(self)).into_response()
+    let (content_type, body) = self.body(status);
+
+    let mut response = (status, [(header::CONTENT_TYPE, content_type)], body).into_response();
+
+    match self {
+      Self::RateLimited { retry_after_secs } => {
+        response.headers_mut().insert(
+          header::RETRY_AFTER,
+          HeaderValue::from_str(&retry_after_secs.to_string()).unwrap(),
+        );
+      }
+      Self::Unauthorized(_) => {
+        response
+          .headers_mut()
+          .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+      }
+      _ => {}
+    }
+
+    response
+  }
+}
+
+impl From<ServerError> for ApiError {
+  fn from(error: ServerError) -> Self {
+    error.api_error()
   }
 }
 
@@ -160,5 +496,191 @@ mod tests {
     let api_error = ApiError::not_found("not found");
     let json = serde_json::to_string(&api_error).unwrap();
     assert_eq!(json, r#"{"code":3,"msg":"not found"}"#);
+
+    let api_error = ApiError::unauthorized("unauthorized");
+    let json = serde_json::to_string(&api_error).unwrap();
+    assert_eq!(json, r#"{"code":5,"msg":"unauthorized"}"#);
+
+    let api_error = ApiError::gateway_timeout("timed out");
+    let json = serde_json::to_string(&api_error).unwrap();
+    assert_eq!(json, r#"{"code":6,"msg":"timed out"}"#);
+
+    let api_error = ApiError::payload_too_large("too large");
+    let json = serde_json::to_string(&api_error).unwrap();
+    assert_eq!(json, r#"{"code":7,"msg":"too large"}"#);
+
+    let api_error = ApiError::rate_limited(30);
+    let json = serde_json::to_string(&api_error).unwrap();
+    assert_eq!(json, r#"{"code":4,"msg":"rate limited","retry_after":30}"#);
+  }
+
+  #[tokio::test]
+  async fn error_formats() {
+    let body = |format: ErrorFormat| async move {
+      let response = format
+        .scope(async { ApiError::not_found("inscription foo not found").into_response() })
+        .await;
+
+      assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+      let content_type = response.headers()[header::CONTENT_TYPE].clone();
+
+      let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+      (
+        content_type,
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+      )
+    };
+
+    assert_eq!(
+      body(ErrorFormat::Code).await,
+      (
+        HeaderValue::from_static("application/json"),
+        serde_json::json!({
+          "code": 3,
+          "msg": "inscription foo not found",
+        })
+      )
+    );
+
+    assert_eq!(
+      body(ErrorFormat::Problem).await,
+      (
+        HeaderValue::from_static("application/problem+json"),
+        serde_json::json!({
+          "type": "about:blank",
+          "title": "Not Found",
+          "status": 404,
+          "detail": "inscription foo not found",
+        })
+      )
+    );
+  }
+
+  #[test]
+  fn problem_details_match_status() {
+    for (error, status, title) in [
+      (ApiError::internal("foo"), 500, "Internal Server Error"),
+      (ApiError::bad_request("foo"), 400, "Bad Request"),
+      (ApiError::not_found("foo"), 404, "Not Found"),
+    ] {
+      let json = error.to_problem_json(error.status());
+      assert_eq!(json["status"], status);
+      assert_eq!(json["title"], title);
+      assert_eq!(json["detail"], "foo");
+    }
+
+    assert_eq!(
+      ApiError::rate_limited(30).to_problem_json(StatusCode::TOO_MANY_REQUESTS)["retry_after"],
+      30
+    );
+  }
+
+  #[test]
+  fn rate_limited_response_has_retry_after_header() {
+    let response = ApiError::rate_limited(30).into_response();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "30");
+  }
+
+  #[test]
+  fn server_error_api_error_codes() {
+    assert_eq!(ServerError::BadRequest("foo".into()).api_error().code(), 2);
+    assert_eq!(ServerError::NotFound("foo".into()).api_error().code(), 3);
+    assert_eq!(ServerError::Internal(anyhow!("foo")).api_error().code(), 1);
+    assert_eq!(
+      ServerError::PayloadTooLarge("foo".into())
+        .api_error()
+        .code(),
+      7
+    );
+    assert_eq!(
+      ServerError::RateLimited {
+        retry_after_secs: 1
+      }
+      .api_error()
+      .code(),
+      4
+    );
+  }
+
+  #[test]
+  fn payload_too_large_status() {
+    assert_eq!(
+      ServerError::PayloadTooLarge("foo".into())
+        .into_response()
+        .status(),
+      StatusCode::PAYLOAD_TOO_LARGE
+    );
+  }
+
+  #[test]
+  fn internal_error_emits_error_event() {
+    use {
+      std::sync::{Arc, Mutex},
+      tracing::{span, Event, Level, Metadata, Subscriber},
+    };
+
+    #[derive(Clone, Default)]
+    struct Events(Arc<Mutex<Vec<(Level, Vec<String>)>>>);
+
+    impl Subscriber for Events {
+      fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+      }
+
+      fn new_span(&self, _span: &span::Attributes) -> span::Id {
+        span::Id::from_u64(1)
+      }
+
+      fn record(&self, _span: &span::Id, _values: &span::Record) {}
+
+      fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+      fn event(&self, event: &Event) {
+        self.0.lock().unwrap().push((
+          *event.metadata().level(),
+          event
+            .fields()
+            .map(|field| field.name().to_string())
+            .collect(),
+        ));
+      }
+
+      fn enter(&self, _span: &span::Id) {}
+
+      fn exit(&self, _span: &span::Id) {}
+    }
+
+    let events = Events::default();
+
+    let response = tracing::subscriber::with_default(events.clone(), || {
+      ServerError::Internal(anyhow!("foo")).into_response()
+    });
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    let events = events.0.lock().unwrap();
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].0, Level::ERROR);
+    assert!(events[0].1.contains(&"error".to_string()));
+  }
+
+  #[test]
+  fn prefers_json_honors_quality_values() {
+    fn case(accept: &str, expected: bool) {
+      let mut headers = HeaderMap::new();
+      headers.insert(header::ACCEPT, accept.parse().unwrap());
+      assert_eq!(prefers_json(&headers), expected, "{accept}");
+    }
+
+    case("application/json", true);
+    case("text/html", false);
+    case("text/html, application/json", false);
+    case("text/html;q=0.5, application/json", true);
+    case("application/json;q=0", false);
+    assert!(!prefers_json(&HeaderMap::new()));
   }
 }
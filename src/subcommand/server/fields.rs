@@ -0,0 +1,178 @@
+use {
+  super::*,
+  serde::de::{self, DeserializeOwned, Visitor},
+  serde_json::Value,
+  utoipa::IntoParams,
+};
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub(crate) struct FieldsQuery {
+  /// Comma-separated names of the fields to return, as named in the response.
+  /// All fields are returned by default. Fields omitted from the full response
+  /// when empty are returned as `null`.
+  fields: Option<String>,
+}
+
+/// A response with either all of its fields, or only those selected.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub(crate) enum Selected<T> {
+  All(T),
+  Fields(serde_json::Map<String, Value>),
+}
+
+impl FieldsQuery {
+  /// Whether only some fields were requested.
+  pub(crate) fn is_selective(&self) -> bool {
+    self.fields.is_some()
+  }
+
+  /// `value` with only the requested fields, in the requested order. Fields
+  /// that `T` does not have are rejected as a bad request, while fields it
+  /// has but leaves out of `value` because they are empty are `null`.
+  pub(crate) fn select<T: Serialize + DeserializeOwned>(
+    &self,
+    value: T,
+  ) -> ServerResult<Selected<T>> {
+    let Some(fields) = &self.fields else {
+      return Ok(Selected::All(value));
+    };
+
+    let names = field_names::<T>();
+
+    let value = serde_json::to_value(value).map_err(|err| ServerError::Internal(err.into()))?;
+
+    let Value::Object(object) = value else {
+      return Err(ServerError::BadRequest(
+        "fields cannot be selected from this response".into(),
+      ));
+    };
+
+    let mut selected = serde_json::Map::new();
+
+    for field in fields.split(',') {
+      if !names.contains(&field) {
+        return Err(ServerError::BadRequest(format!("unknown field `{field}`")));
+      }
+
+      selected.insert(
+        field.into(),
+        object.get(field).cloned().unwrap_or(Value::Null),
+      );
+    }
+
+    Ok(Selected::Fields(selected))
+  }
+}
+
+/// The names of the fields of struct `T` as serialized, including those
+/// skipped when empty, which are only known to its `Deserialize`
+/// implementation, which passes them to `deserialize_struct`.
+fn field_names<T: DeserializeOwned>() -> &'static [&'static str] {
+  struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+  impl<'de, 'a> de::Deserializer<'de> for FieldNames<'a> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+      Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+      self,
+      _name: &'static str,
+      fields: &'static [&'static str],
+      _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+      *self.0 = fields;
+      Err(de::Error::custom("only field names are read"))
+    }
+
+    serde::forward_to_deserialize_any! {
+      bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes
+      byte_buf option unit unit_struct newtype_struct seq tuple tuple_struct map
+      enum identifier ignored_any
+    }
+  }
+
+  let mut names: &'static [&'static str] = &[];
+  T::deserialize(FieldNames(&mut names)).ok();
+  names
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Debug, Serialize, Deserialize)]
+  #[serde(rename_all = "camelCase")]
+  struct Foo {
+    a: u32,
+    b: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    c_d: Option<u32>,
+  }
+
+  const FOO: Foo = Foo {
+    a: 1,
+    b: 2,
+    c_d: None,
+  };
+
+  fn fields(fields: &str) -> FieldsQuery {
+    FieldsQuery {
+      fields: Some(fields.into()),
+    }
+  }
+
+  fn select(fields: FieldsQuery) -> ServerResult<Value> {
+    fields
+      .select(FOO)
+      .map(|selected| serde_json::to_value(selected).unwrap())
+  }
+
+  #[test]
+  fn everything_is_returned_by_default() {
+    assert_eq!(
+      select(FieldsQuery::default()).unwrap(),
+      serde_json::json!({"a": 1, "b": 2})
+    );
+  }
+
+  #[test]
+  fn only_requested_fields_are_returned() {
+    assert_eq!(
+      select(fields("b,a")).unwrap(),
+      serde_json::json!({"b": 2, "a": 1})
+    );
+  }
+
+  #[test]
+  fn skipped_fields_are_returned_as_null() {
+    assert_eq!(
+      select(fields("cD,a")).unwrap(),
+      serde_json::json!({"cD": null, "a": 1})
+    );
+  }
+
+  #[test]
+  fn unknown_fields_are_rejected() {
+    for (fields, message) in [
+      ("a,d", "unknown field `d`"),
+      ("c_d", "unknown field `c_d`"),
+      ("", "unknown field ``"),
+    ] {
+      let Err(ServerError::BadRequest(error)) = select(self::fields(fields)) else {
+        panic!("{fields} should be rejected");
+      };
+
+      assert_eq!(error, message);
+    }
+  }
+
+  #[test]
+  fn field_names_are_those_of_structs() {
+    assert_eq!(field_names::<Foo>(), ["a", "b", "cD"]);
+    assert!(field_names::<Value>().is_empty());
+  }
+}
@@ -4,6 +4,37 @@ use shadow_rs::shadow;
 use utoipa::{IntoParams, ToSchema};
 shadow!(build);
 
+pub(crate) const X_ORD_VERSION: &str = "x-ord-version";
+
+/// The crate version and the commit it was built from, if known, as sent in
+/// the `X-Ord-Version` header of every response.
+pub(super) fn ord_version() -> String {
+  if build::COMMIT_HASH.is_empty() {
+    build::PKG_VERSION.into()
+  } else {
+    format!("{}+{}", build::PKG_VERSION, build::COMMIT_HASH)
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct VersionJson {
+  pub version: String,
+  pub commit: Option<String>,
+  pub schema_version: u64,
+}
+
+/// The version and commit of this build, and the index schema version it
+/// reads and writes.
+pub(super) async fn version() -> Json<VersionJson> {
+  Json(VersionJson {
+    version: build::PKG_VERSION.into(),
+    commit: Some(build::COMMIT_HASH)
+      .filter(|commit| !commit.is_empty())
+      .map(str::to_string),
+    schema_version: crate::index::SCHEMA_VERSION,
+  })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NodeInfo {
@@ -0,0 +1,315 @@
+use {
+  super::*,
+  axum::{extract::MatchedPath, http::Request, middleware::Next},
+  once_cell::sync::Lazy,
+  prometheus::{
+    core::Collector, Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry,
+    TextEncoder,
+  },
+  std::path::Path,
+};
+
+static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+struct Metrics {
+  api_errors: IntCounterVec,
+  content_cache_lookups: IntCounterVec,
+  registry: Registry,
+  request_duration: HistogramVec,
+  requests: IntCounterVec,
+}
+
+impl Metrics {
+  fn new() -> Self {
+    let registry = Registry::new();
+
+    let api_errors = IntCounterVec::new(
+      Opts::new(
+        "ord_api_errors_total",
+        "API errors returned, by error code.",
+      ),
+      &["code"],
+    )
+    .unwrap();
+
+    let content_cache_lookups = IntCounterVec::new(
+      Opts::new(
+        "ord_content_cache_lookups_total",
+        "Content cache lookups, by result.",
+      ),
+      &["result"],
+    )
+    .unwrap();
+
+    let request_duration = HistogramVec::new(
+      HistogramOpts::new(
+        "ord_http_request_duration_seconds",
+        "HTTP request latency, by route.",
+      ),
+      &["route"],
+    )
+    .unwrap();
+
+    let requests = IntCounterVec::new(
+      Opts::new(
+        "ord_http_requests_total",
+        "HTTP requests, by route and status.",
+      ),
+      &["route", "status"],
+    )
+    .unwrap();
+
+    registry.register(Box::new(api_errors.clone())).unwrap();
+    registry
+      .register(Box::new(content_cache_lookups.clone()))
+      .unwrap();
+    registry
+      .register(Box::new(request_duration.clone()))
+      .unwrap();
+    registry.register(Box::new(requests.clone())).unwrap();
+
+    Self {
+      api_errors,
+      content_cache_lookups,
+      registry,
+      request_duration,
+      requests,
+    }
+  }
+}
+
+/// A series of a counter and its count.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Series {
+  labels: BTreeMap<String, String>,
+  count: u64,
+}
+
+/// The cumulative counters persisted to `--metrics-file` across restarts.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Counters {
+  api_errors: Vec<Series>,
+  requests: Vec<Series>,
+}
+
+impl Metrics {
+  // integer counters are exported as whole, non-negative floats
+  #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+  fn series(counter: &IntCounterVec) -> Vec<Series> {
+    counter
+      .collect()
+      .iter()
+      .flat_map(|family| family.get_metric())
+      .map(|metric| Series {
+        labels: metric
+          .get_label()
+          .iter()
+          .map(|label| (label.get_name().into(), label.get_value().into()))
+          .collect(),
+        count: metric.get_counter().get_value() as u64,
+      })
+      .collect()
+  }
+
+  fn restore(counter: &IntCounterVec, series: &[Series]) -> Result {
+    for series in series {
+      counter
+        .get_metric_with(
+          &series
+            .labels
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect(),
+        )?
+        .inc_by(series.count);
+    }
+
+    Ok(())
+  }
+
+  fn counters(&self) -> Counters {
+    Counters {
+      api_errors: Self::series(&self.api_errors),
+      requests: Self::series(&self.requests),
+    }
+  }
+
+  fn save(&self, path: &Path) -> Result {
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, serde_json::to_vec(&self.counters())?)?;
+    fs::rename(&temp, path)?;
+    Ok(())
+  }
+
+  fn load(&self, path: &Path) -> Result {
+    let counters = match fs::read(path) {
+      Ok(counters) => serde_json::from_slice::<Counters>(&counters)?,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+      Err(err) => return Err(err.into()),
+    };
+
+    Self::restore(&self.api_errors, &counters.api_errors)?;
+    Self::restore(&self.requests, &counters.requests)?;
+
+    Ok(())
+  }
+}
+
+/// Resume the request and API error counters from `path`, if it exists. The
+/// counters start from zero if they cannot be loaded.
+pub(super) fn load(path: &Path) {
+  if let Err(err) = METRICS.load(path) {
+    log::warn!("Failed to load metrics from `{}`: {err}", path.display());
+  }
+}
+
+/// Save the request and API error counters to `path`. Failures are only
+/// logged, so that they never prevent shutting down.
+pub(super) fn save(path: &Path) {
+  if let Err(err) = METRICS.save(path) {
+    log::warn!("Failed to save metrics to `{}`: {err}", path.display());
+  }
+}
+
+pub(super) fn record_api_error(code: i32) {
+  METRICS
+    .api_errors
+    .with_label_values(&[&code.to_string()])
+    .inc();
+}
+
+pub(super) fn record_content_cache_lookup(hit: bool) {
+  METRICS
+    .content_cache_lookups
+    .with_label_values(&[if hit { "hit" } else { "miss" }])
+    .inc();
+}
+
+/// Count and time requests, labeled by the matched route template rather than
+/// the raw path to keep label cardinality bounded.
+pub(super) async fn track_requests<B>(request: Request<B>, next: Next<B>) -> Response {
+  let route = request
+    .extensions()
+    .get::<MatchedPath>()
+    .map(|path| path.as_str().to_string())
+    .unwrap_or_else(|| "unmatched".into());
+
+  let start = Instant::now();
+
+  let response = next.run(request).await;
+
+  METRICS
+    .request_duration
+    .with_label_values(&[&route])
+    .observe(start.elapsed().as_secs_f64());
+
+  METRICS
+    .requests
+    .with_label_values(&[&route, response.status().as_str()])
+    .inc();
+
+  response
+}
+
+pub(super) async fn metrics() -> ServerResult<Response> {
+  let encoder = TextEncoder::new();
+
+  let mut buffer = Vec::new();
+
+  encoder
+    .encode(&METRICS.registry.gather(), &mut buffer)
+    .map_err(|err| ServerError::Internal(err.into()))?;
+
+  Ok(
+    (
+      [(
+        header::CONTENT_TYPE,
+        HeaderValue::from_str(encoder.format_type())
+          .map_err(|err| ServerError::Internal(err.into()))?,
+      )],
+      buffer,
+    )
+      .into_response(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, tempfile::TempDir};
+
+  #[test]
+  fn counters_are_reloaded_after_restart() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("metrics.json");
+
+    let metrics = Metrics::new();
+    metrics
+      .requests
+      .with_label_values(&["/status", "200"])
+      .inc_by(3);
+    metrics.api_errors.with_label_values(&["3"]).inc();
+    metrics
+      .content_cache_lookups
+      .with_label_values(&["hit"])
+      .inc();
+    metrics.save(&path).unwrap();
+
+    let restarted = Metrics::new();
+    restarted.load(&path).unwrap();
+
+    assert_eq!(restarted.counters(), metrics.counters());
+    assert_eq!(
+      restarted
+        .requests
+        .with_label_values(&["/status", "200"])
+        .get(),
+      3
+    );
+    assert_eq!(restarted.api_errors.with_label_values(&["3"]).get(), 1);
+    assert_eq!(
+      restarted
+        .content_cache_lookups
+        .with_label_values(&["hit"])
+        .get(),
+      0
+    );
+
+    restarted
+      .requests
+      .with_label_values(&["/status", "200"])
+      .inc();
+    restarted.save(&path).unwrap();
+
+    let restarted = Metrics::new();
+    restarted.load(&path).unwrap();
+
+    assert_eq!(
+      restarted
+        .requests
+        .with_label_values(&["/status", "200"])
+        .get(),
+      4
+    );
+  }
+
+  #[test]
+  fn missing_metrics_file_is_not_an_error() {
+    let tempdir = TempDir::new().unwrap();
+
+    let metrics = Metrics::new();
+    metrics.load(&tempdir.path().join("metrics.json")).unwrap();
+
+    assert_eq!(metrics.counters(), Counters::default());
+  }
+
+  #[test]
+  fn failing_to_save_is_not_an_error() {
+    let tempdir = TempDir::new().unwrap();
+
+    assert!(Metrics::new()
+      .save(&tempdir.path().join("missing/metrics.json"))
+      .is_err());
+
+    save(&tempdir.path().join("missing/metrics.json"));
+  }
+}
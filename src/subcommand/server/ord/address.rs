@@ -0,0 +1,180 @@
+use {
+  super::{error::ApiError, types::AddressType, *},
+  axum::Json,
+  utoipa::{IntoParams, ToSchema},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct AddressInscriptionsQuery {
+  /// Page index, starting at 0.
+  page: Option<usize>,
+  /// Page size. Defaults to `--default-page-size`, and larger sizes are
+  /// reduced to `--max-page-size`.
+  limit: Option<usize>,
+  /// Only list inscriptions on outputs holding at least this many sats, to
+  /// leave out dust. Defaults to 0, which lists inscriptions on outputs of any
+  /// value.
+  #[param(minimum = 0)]
+  min_value: Option<i64>,
+}
+
+impl AddressInscriptionsQuery {
+  fn min_value(&self) -> Result<u64, ApiError> {
+    let min_value = self.min_value.unwrap_or_default();
+
+    u64::try_from(min_value).map_err(|_| {
+      ApiError::bad_request(format!(
+        "min_value must not be negative, but was {min_value}"
+      ))
+    })
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiAddressInscription)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAddressInscription {
+  /// The inscription id.
+  pub id: String,
+  /// The inscription number.
+  pub number: i32,
+  /// The inscription content type.
+  pub content_type: Option<String>,
+  /// The inscription satpoint.
+  pub satpoint: String,
+  /// The value of the transaction output holding the inscription.
+  #[schema(format = "uint64")]
+  pub value: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiAddressInscriptions)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAddressInscriptions {
+  /// The type of the address.
+  pub address_type: AddressType,
+  #[schema(value_type = Vec<ord::ApiAddressInscription>)]
+  pub inscriptions: Vec<ApiAddressInscription>,
+  pub page_index: usize,
+  pub limit: usize,
+  pub more: bool,
+}
+
+// /ord/address/:address/inscriptions
+/// Get the inscriptions held by an address.
+///
+/// Retrieve the inscriptions on all unspent outputs controlled by the address, a page at a time,
+/// optionally leaving out those on outputs worth less than `min_value` sats.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/address/{address}/inscriptions",
+  params(
+      ("address" = String, Path, description = "Address"),
+      AddressInscriptionsQuery,
+),
+  responses(
+    (status = 200, description = "Obtain inscriptions by query address.", body = OrdAddressInscriptions),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_address_inscriptions(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Path(address): Path<String>,
+  Query(query): Query<AddressInscriptionsQuery>,
+) -> ApiResult<ApiAddressInscriptions> {
+  log::debug!("rpc: get ord_address_inscriptions: {address} {query:?}");
+
+  let min_value = query.min_value()?;
+
+  let limit = page_size(&server_config, query.limit)?;
+
+  let chain = index.get_chain();
+
+  let script_pubkey = Address::from_str(&address)
+    .ok()
+    .and_then(|address| address.require_network(chain.network()).ok())
+    .ok_or_else(|| OrdApiError::InvalidAddress(address.clone()))?
+    .script_pubkey();
+
+  let rtx = index.begin_read()?;
+  let client = index.bitcoin_rpc_client()?;
+  let index_transactions = index.has_transactions_index();
+
+  let mut values = HashMap::new();
+
+  let mut inscriptions = Vec::new();
+  for outpoint in rtx.script_pubkey_to_inscription_outpoints(&script_pubkey)? {
+    cancel::check()?;
+
+    // output values are only needed up front to filter, otherwise they are
+    // fetched for the requested page alone
+    if min_value > 0 {
+      let value = Index::fetch_vout(&rtx, &client, outpoint, chain, index_transactions)?
+        .ok_or(OrdApiError::TransactionNotFound(outpoint.txid))?
+        .value;
+
+      values.insert(outpoint, value);
+
+      if value < min_value {
+        continue;
+      }
+    }
+
+    inscriptions.extend(rtx.inscriptions_on_output_with_satpoints(outpoint)?);
+  }
+
+  let page = ApiPage::paginate(inscriptions, query.page.unwrap_or_default(), limit);
+
+  let mut summaries = Vec::with_capacity(page.data.len());
+  for (satpoint, inscription_id) in page.data {
+    let entry = rtx
+      .get_inscription_entry(inscription_id)?
+      .ok_or(OrdApiError::UnknownInscriptionId(inscription_id))?;
+
+    let tx = Index::get_transaction_with_rtx(
+      inscription_id.txid,
+      &rtx,
+      &client,
+      chain,
+      index_transactions,
+    )?
+    .ok_or(OrdApiError::TransactionNotFound(inscription_id.txid))?;
+
+    let content_type = ParsedEnvelope::from_transaction(&tx)
+      .into_iter()
+      .nth(usize::try_from(inscription_id.index).unwrap())
+      .ok_or(OrdApiError::InvalidInscription(inscription_id))?
+      .payload
+      .content_type()
+      .map(str::to_string);
+
+    let value = match values.get(&satpoint.outpoint) {
+      Some(value) => *value,
+      None => {
+        let value = Index::fetch_vout(&rtx, &client, satpoint.outpoint, chain, index_transactions)?
+          .ok_or(OrdApiError::TransactionNotFound(satpoint.outpoint.txid))?
+          .value;
+        values.insert(satpoint.outpoint, value);
+        value
+      }
+    };
+
+    summaries.push(ApiAddressInscription {
+      id: inscription_id.to_string(),
+      number: entry.inscription_number,
+      content_type,
+      satpoint: satpoint.to_string(),
+      value,
+    });
+  }
+
+  Ok(Json(ApiResponse::ok(ApiAddressInscriptions {
+    address_type: AddressType::from_script(&script_pubkey),
+    inscriptions: summaries,
+    page_index: page.page_index,
+    limit: page.limit,
+    more: page.more,
+  })))
+}
@@ -0,0 +1,91 @@
+use {
+  super::*,
+  bitcoin::hashes::{sha256, Hash},
+  linked_hash_map::LinkedHashMap,
+};
+
+/// Hashes of at most this many inscriptions are kept.
+const CONTENT_HASH_CACHE_CAPACITY: usize = 100_000;
+
+/// Least recently used cache of the sha256 hashes of inscription bodies. The
+/// body of an inscription never changes, so entries are not invalidated.
+pub(crate) struct ContentHashCache {
+  capacity: usize,
+  entries: Mutex<LinkedHashMap<InscriptionId, sha256::Hash>>,
+}
+
+impl Default for ContentHashCache {
+  fn default() -> Self {
+    Self::new(CONTENT_HASH_CACHE_CAPACITY)
+  }
+}
+
+impl ContentHashCache {
+  fn new(capacity: usize) -> Self {
+    Self {
+      capacity,
+      entries: Mutex::new(LinkedHashMap::new()),
+    }
+  }
+
+  /// The hash of the body of `inscription_id`, hashing `body` on the
+  /// transform pool only if it is not already cached.
+  pub(crate) async fn hash(
+    &self,
+    inscription_id: InscriptionId,
+    body: &[u8],
+    transforms: &TransformPool,
+  ) -> ServerResult<sha256::Hash> {
+    if let Some(hash) = self.entries.lock().unwrap().get_refresh(&inscription_id) {
+      return Ok(*hash);
+    }
+
+    let body = body.to_vec();
+
+    let hash = transforms.run(move || sha256::Hash::hash(&body)).await?;
+
+    let mut entries = self.entries.lock().unwrap();
+
+    entries.insert(inscription_id, hash);
+
+    while entries.len() > self.capacity {
+      entries.pop_front();
+    }
+
+    Ok(hash)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  async fn hash(cache: &ContentHashCache, n: u32, body: &[u8]) -> sha256::Hash {
+    cache
+      .hash(inscription_id(n), body, &TransformPool::new(1).unwrap())
+      .await
+      .unwrap()
+  }
+
+  #[tokio::test]
+  async fn hashes_are_cached() {
+    let cache = ContentHashCache::new(2);
+
+    let foo = hash(&cache, 1, b"foo").await;
+    assert_eq!(foo, sha256::Hash::hash(b"foo"));
+    assert_eq!(hash(&cache, 1, b"bar").await, foo);
+  }
+
+  #[tokio::test]
+  async fn least_recently_used_hashes_are_evicted() {
+    let cache = ContentHashCache::new(2);
+
+    hash(&cache, 1, b"foo").await;
+    hash(&cache, 2, b"bar").await;
+    hash(&cache, 1, b"foo").await;
+    hash(&cache, 3, b"baz").await;
+
+    assert_eq!(hash(&cache, 1, b"qux").await, sha256::Hash::hash(b"foo"));
+    assert_eq!(hash(&cache, 2, b"qux").await, sha256::Hash::hash(b"qux"));
+  }
+}
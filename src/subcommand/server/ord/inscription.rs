@@ -1,7 +1,7 @@
 use {
   super::{error::ApiError, types::ScriptPubkey, *},
-  crate::{index::rtx::Rtx, okx::datastore::ScriptKey},
-  axum::Json,
+  crate::{index::rtx::Rtx, media::MediaCategory, okx::datastore::ScriptKey},
+  axum::{extract::rejection::QueryRejection, Json},
   utoipa::ToSchema,
 };
 
@@ -22,8 +22,13 @@ pub struct ApiInscription {
   pub id: String,
   /// The inscription number.
   pub number: i32,
-  /// The inscription content type.
+  /// The inscription content type, as inscribed.
   pub content_type: Option<String>,
+  /// The media type of the content type: its type and subtype in lowercase,
+  /// without parameters.
+  pub media_type: Option<String>,
+  /// The kind of content, by media type.
+  pub media_category: MediaCategory,
   /// The inscription content body.
   pub content: Option<String>,
   /// The inscription content body length.
@@ -34,8 +39,10 @@ pub struct ApiInscription {
   pub metadata: Option<String>,
   /// The inscription metaprotocol.
   pub metaprotocol: Option<String>,
-  /// The inscription parent inscription id.
+  /// The first parent inscription id. Deprecated, use `parents`.
   pub parent: Option<InscriptionId>,
+  /// The parent inscription ids, in the order they were declared.
+  pub parents: Vec<InscriptionId>,
   /// The delegate inscription id of the inscription.
   pub delegate: Option<InscriptionId>,
   /// The inscription pointer.
@@ -48,6 +55,9 @@ pub struct ApiInscription {
   /// The inscription genesis timestamp.
   #[schema(format = "uint32")]
   pub genesis_timestamp: u32,
+  /// The number of blocks from the genesis block to the chain tip, inclusive.
+  #[schema(format = "uint32")]
+  pub confirmations: u32,
   /// The inscription location.
   pub location: String,
   /// Collections of Inscriptions.
@@ -56,15 +66,34 @@ pub struct ApiInscription {
   pub charms: Vec<String>,
   /// The inscription sat index.  
   pub sat: Option<u64>,
+  /// The hash of the inscription content, if requested with `include=content_hash`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub content_hash: Option<ApiContentHash>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = ord::ApiContentHash)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiContentHash {
+  /// The hex-encoded sha256 hash of the inscription body, empty if it has none.
+  pub sha256: String,
+  /// Whether the inscription delegates its content, in which case the hash is
+  /// of its own body, not of the content served for it.
+  pub delegated: bool,
 }
 
 // /ord/id/:id/inscription
 /// Retrieve the inscription infomation with the specified inscription id.
+///
+/// Responds with an `ord.InscriptionResponse` protobuf message instead of JSON if
+/// requested with `Accept: application/x-protobuf`.
 #[utoipa::path(
   get,
   path = "/api/v1/ord/id/{id}/inscription",
   params(
-      ("id" = String, Path, description = "inscription ID")
+      ("id" = String, Path, description = "inscription ID"),
+      FieldsQuery,
+      IncludeQuery,
 ),
   responses(
     (status = 200, description = "Obtain inscription infomation.", body = OrdOrdInscription),
@@ -75,27 +104,52 @@ pub struct ApiInscription {
 )]
 pub(crate) async fn ord_inscription_id(
   Extension(index): Extension<Arc<Index>>,
-  Path(id): Path<String>,
-) -> ApiResult<ApiInscription> {
+  Extension(content_hashes): Extension<Arc<ContentHashCache>>,
+  Extension(transforms): Extension<Arc<TransformPool>>,
+  PathParams(Param(id)): PathParams<Param<InscriptionId>>,
+  Query(fields): Query<FieldsQuery>,
+  Query(include): Query<IncludeQuery>,
+  accept_protobuf: AcceptProtobuf,
+) -> ApiEncodedResult<Selected<ApiInscription>, InscriptionResponse> {
   log::debug!("rpc: get ord_inscription_id: {id}");
 
+  let content_hashes = include
+    .includes("content_hash", &["content_hash"])?
+    .then_some(&*content_hashes);
+
   let rtx = index.begin_read()?;
   let chain = index.get_chain();
   let client = index.bitcoin_rpc_client()?;
   let index_transactions = index.has_transactions_index();
 
-  let id = InscriptionId::from_str(&id).map_err(ApiError::bad_request)?;
-
-  ord_get_inscription_by_id(id, &rtx, client, chain, index_transactions)
+  respond_with_inscription(
+    ord_get_inscription_by_id(
+      id,
+      &rtx,
+      &client,
+      chain,
+      index_transactions,
+      content_hashes,
+      &transforms,
+    )
+    .await?,
+    &fields,
+    &accept_protobuf,
+  )
 }
 
 // /ord/number/:number/inscription
 /// Retrieve the inscription infomation with the specified inscription number.
+///
+/// Responds with an `ord.InscriptionResponse` protobuf message instead of JSON if
+/// requested with `Accept: application/x-protobuf`.
 #[utoipa::path(
   get,
   path = "/api/v1/ord/number/{number}/inscription",
   params(
-      ("number" = i64, Path, description = "inscription number")
+      ("number" = i32, Path, description = "inscription number, negative if cursed"),
+      FieldsQuery,
+      IncludeQuery,
 ),
   responses(
     (status = 200, description = "Obtain inscription infomation.", body = OrdOrdInscription),
@@ -106,10 +160,19 @@ pub(crate) async fn ord_inscription_id(
 )]
 pub(crate) async fn ord_inscription_number(
   Extension(index): Extension<Arc<Index>>,
-  Path(number): Path<i32>,
-) -> ApiResult<ApiInscription> {
+  Extension(content_hashes): Extension<Arc<ContentHashCache>>,
+  Extension(transforms): Extension<Arc<TransformPool>>,
+  PathParams(number): PathParams<i32>,
+  Query(fields): Query<FieldsQuery>,
+  Query(include): Query<IncludeQuery>,
+  accept_protobuf: AcceptProtobuf,
+) -> ApiEncodedResult<Selected<ApiInscription>, InscriptionResponse> {
   log::debug!("rpc: get ord_inscription_number: {number}");
 
+  let content_hashes = include
+    .includes("content_hash", &["content_hash"])?
+    .then_some(&*content_hashes);
+
   let rtx = index.begin_read()?;
   let chain = index.get_chain();
   let client = index.bitcoin_rpc_client()?;
@@ -118,21 +181,355 @@ pub(crate) async fn ord_inscription_number(
   let inscription_id = Index::get_inscription_id_by_inscription_number_with_rtx(number, &rtx)?
     .ok_or(OrdApiError::UnknownInscriptionNumber(number))?;
 
-  ord_get_inscription_by_id(inscription_id, &rtx, client, chain, index_transactions)
+  respond_with_inscription(
+    ord_get_inscription_by_id(
+      inscription_id,
+      &rtx,
+      &client,
+      chain,
+      index_transactions,
+      content_hashes,
+      &transforms,
+    )
+    .await?,
+    &fields,
+    &accept_protobuf,
+  )
+}
+
+/// The inscription as protobuf, if the client asked for it, or as JSON with
+/// only the selected fields. Protobuf responses always have every field.
+fn respond_with_inscription(
+  inscription: ApiInscription,
+  fields: &FieldsQuery,
+  accept_protobuf: &AcceptProtobuf,
+) -> ApiEncodedResult<Selected<ApiInscription>, InscriptionResponse> {
+  if accept_protobuf.0 {
+    if fields.is_selective() {
+      return Err(ApiError::bad_request(
+        "fields cannot be selected from protobuf responses",
+      ));
+    }
+
+    return Ok(ApiEncoded::Protobuf(ApiResponse::ok(inscription).into()));
+  }
+
+  Ok(ApiEncoded::Json(ApiResponse::ok(
+    fields.select(inscription)?,
+  )))
+}
+
+// /inscription/:id
+/// Retrieve the inscription infomation with the specified inscription ID or number.
+///
+/// Responds with an `ord.InscriptionResponse` protobuf message instead of JSON if
+/// requested with `Accept: application/x-protobuf`.
+#[utoipa::path(
+  get,
+  path = "/api/v1/inscription/{id}",
+  params(
+      ("id" = String, Path, description = "inscription ID, or inscription number, negative if cursed"),
+      FieldsQuery,
+      IncludeQuery,
+),
+  responses(
+    (status = 200, description = "Obtain inscription infomation.", body = OrdOrdInscription),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_inscription(
+  Extension(index): Extension<Arc<Index>>,
+  content_hashes: Extension<Arc<ContentHashCache>>,
+  transforms: Extension<Arc<TransformPool>>,
+  Path(id): Path<String>,
+  fields: Query<FieldsQuery>,
+  include: Query<IncludeQuery>,
+  accept_protobuf: AcceptProtobuf,
+) -> ApiEncodedResult<Selected<ApiInscription>, InscriptionResponse> {
+  if let Ok(id) = InscriptionId::from_str(&id) {
+    return ord_inscription_id(
+      Extension(index),
+      content_hashes,
+      transforms,
+      PathParams(Param(id)),
+      fields,
+      include,
+      accept_protobuf,
+    )
+    .await;
+  }
+
+  let number = id
+    .parse::<i32>()
+    .map_err(|_| ApiError::bad_request(format!("invalid inscription id or number `{id}`")))?;
+
+  ord_inscription_number(
+    Extension(index),
+    content_hashes,
+    transforms,
+    PathParams(number),
+    fields,
+    include,
+    accept_protobuf,
+  )
+  .await
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiInscriptionsBatchRequest)]
+pub struct ApiInscriptionsBatchRequest {
+  /// The inscription ids to look up, at most 100.
+  pub ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiInscriptionsBatch)]
+pub struct ApiInscriptionsBatch {
+  /// The inscriptions found, keyed by inscription id.
+  #[schema(value_type = HashMap<String, ord::ApiInscription>)]
+  pub inscriptions: BTreeMap<String, ApiInscription>,
+  /// The requested ids that are invalid or do not match any inscription.
+  pub not_found: Vec<String>,
+}
+
+// /inscriptions/batch
+/// Retrieve the inscription infomation of up to 100 inscription ids at once.
+#[utoipa::path(
+  post,
+  path = "/api/v1/inscriptions/batch",
+  request_body = ord::ApiInscriptionsBatchRequest,
+  responses(
+    (status = 200, description = "Obtain inscriptions infomation.", body = OrdInscriptionsBatch),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_inscriptions_batch(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(transforms): Extension<Arc<TransformPool>>,
+  Json(request): Json<ApiInscriptionsBatchRequest>,
+) -> ApiResult<ApiInscriptionsBatch> {
+  log::debug!("rpc: get ord_inscriptions_batch: {:?}", request.ids);
+
+  const MAX_IDS: usize = 100;
+
+  if request.ids.len() > MAX_IDS {
+    return Err(ApiError::bad_request(format!(
+      "at most {MAX_IDS} inscription ids may be requested at once"
+    )));
+  }
+
+  let rtx = index.begin_read()?;
+  let chain = index.get_chain();
+  let client = index.bitcoin_rpc_client()?;
+  let index_transactions = index.has_transactions_index();
+
+  let mut inscriptions = BTreeMap::new();
+  let mut not_found = Vec::new();
+
+  for id in request.ids {
+    cancel::check()?;
+
+    let Ok(inscription_id) = InscriptionId::from_str(&id) else {
+      not_found.push(id);
+      continue;
+    };
+
+    if Index::get_inscription_entry_with_rtx(inscription_id, &rtx)?.is_none() {
+      not_found.push(id);
+      continue;
+    }
+
+    let inscription = ord_get_inscription_by_id(
+      inscription_id,
+      &rtx,
+      &client,
+      chain,
+      index_transactions,
+      None,
+      &transforms,
+    )
+    .await?;
+
+    inscriptions.insert(id, inscription);
+  }
+
+  Ok(Json(ApiResponse::ok(ApiInscriptionsBatch {
+    inscriptions,
+    not_found,
+  })))
 }
 
-fn ord_get_inscription_by_id(
+/// Metadata larger than this is not decoded.
+const MAX_METADATA_SIZE: usize = 1 << 20;
+/// Metadata nested deeper than this is not decoded.
+const MAX_METADATA_DEPTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum MetadataFormat {
+  #[default]
+  Hex,
+  Json,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct MetadataQuery {
+  #[serde(default)]
+  format: MetadataFormat,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = ord::ApiInscriptionMetadata)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiInscriptionMetadata {
+  /// Whether the metadata was decoded from CBOR into `json`.
+  pub decoded: bool,
+  /// The decoded metadata, present when `decoded` is true.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[schema(value_type = Option<Object>)]
+  pub json: Option<serde_json::Value>,
+  /// The raw metadata as hex, present when `decoded` is false.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub hex: Option<String>,
+}
+
+// /inscription/:id/metadata
+/// Retrieve the metadata of the specified inscription, as hex or decoded from CBOR to JSON.
+#[utoipa::path(
+  get,
+  path = "/api/v1/inscription/{id}/metadata",
+  params(
+      ("id" = String, Path, description = "inscription ID"),
+      ("format" = Option<String>, Query, description = "`hex` (default) or `json`"),
+),
+  responses(
+    (status = 200, description = "Obtain inscription metadata.", body = OrdInscriptionMetadata),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_inscription_metadata(
+  Extension(index): Extension<Arc<Index>>,
+  PathParams(Param(id)): PathParams<Param<InscriptionId>>,
+  query: Result<Query<MetadataQuery>, QueryRejection>,
+) -> ApiResult<ApiInscriptionMetadata> {
+  log::debug!("rpc: get ord_inscription_metadata: {id}");
+
+  let Query(query) = query.map_err(|_| ApiError::bad_request("format must be `hex` or `json`"))?;
+
+  let metadata = index
+    .get_inscription_by_id(id)?
+    .ok_or(OrdApiError::UnknownInscriptionId(id))?
+    .metadata
+    .ok_or_api_not_found(format!("inscription {id} has no metadata"))?;
+
+  Ok(Json(ApiResponse::ok(match query.format {
+    MetadataFormat::Hex => ApiInscriptionMetadata {
+      decoded: false,
+      json: None,
+      hex: Some(hex::encode(metadata)),
+    },
+    MetadataFormat::Json => match decode_metadata(&metadata)? {
+      Some(json) => ApiInscriptionMetadata {
+        decoded: true,
+        json: Some(json),
+        hex: None,
+      },
+      None => ApiInscriptionMetadata {
+        decoded: false,
+        json: None,
+        hex: Some(hex::encode(metadata)),
+      },
+    },
+  })))
+}
+
+/// Decode CBOR metadata to JSON, returning `None` if it isn't valid CBOR.
+fn decode_metadata(metadata: &[u8]) -> Result<Option<serde_json::Value>, ApiError> {
+  if metadata.len() > MAX_METADATA_SIZE {
+    return Err(ApiError::bad_request(format!(
+      "metadata exceeds {MAX_METADATA_SIZE} bytes"
+    )));
+  }
+
+  let value = match ciborium::from_reader::<Value, _>(metadata) {
+    Ok(value) => value,
+    Err(ciborium::de::Error::RecursionLimitExceeded) => {
+      return Err(ApiError::bad_request(format!(
+        "metadata nested deeper than {MAX_METADATA_DEPTH} levels"
+      )))
+    }
+    Err(_) => return Ok(None),
+  };
+
+  cbor_to_json(value, 0).map(Some)
+}
+
+fn cbor_to_json(value: Value, depth: usize) -> Result<serde_json::Value, ApiError> {
+  use serde_json::Value as Json;
+
+  if depth > MAX_METADATA_DEPTH {
+    return Err(ApiError::bad_request(format!(
+      "metadata nested deeper than {MAX_METADATA_DEPTH} levels"
+    )));
+  }
+
+  Ok(match value {
+    Value::Integer(integer) => {
+      let integer = i128::from(integer);
+      i64::try_from(integer)
+        .map(Json::from)
+        .or_else(|_| u64::try_from(integer).map(Json::from))
+        .unwrap_or_else(|_| Json::String(integer.to_string()))
+    }
+    Value::Bytes(bytes) => Json::String(hex::encode(bytes)),
+    Value::Float(float) => serde_json::Number::from_f64(float)
+      .map(Json::Number)
+      .unwrap_or(Json::Null),
+    Value::Text(text) => Json::String(text),
+    Value::Bool(bool) => Json::Bool(bool),
+    Value::Null => Json::Null,
+    Value::Tag(_tag, value) => cbor_to_json(*value, depth + 1)?,
+    Value::Array(array) => Json::Array(
+      array
+        .into_iter()
+        .map(|value| cbor_to_json(value, depth + 1))
+        .collect::<Result<_, _>>()?,
+    ),
+    Value::Map(map) => Json::Object(
+      map
+        .into_iter()
+        .map(|(key, value)| {
+          let key = match key {
+            Value::Text(text) => text,
+            key => cbor_to_json(key, depth + 1)?.to_string(),
+          };
+          Ok((key, cbor_to_json(value, depth + 1)?))
+        })
+        .collect::<Result<_, ApiError>>()?,
+    ),
+    _ => Json::Null,
+  })
+}
+
+async fn ord_get_inscription_by_id(
   inscription_id: InscriptionId,
-  rtx: &Rtx,
-  client: Client,
+  rtx: &Rtx<'_>,
+  client: &Client,
   chain: Chain,
   index_transactions: bool,
-) -> ApiResult<ApiInscription> {
+  content_hashes: Option<&ContentHashCache>,
+  transforms: &TransformPool,
+) -> Result<ApiInscription, ApiError> {
   let inscription_entry = Index::get_inscription_entry_with_rtx(inscription_id, rtx)?
     .ok_or(OrdApiError::UnknownInscriptionId(inscription_id))?;
 
   let tx =
-    Index::get_transaction_with_rtx(inscription_id.txid, rtx, &client, chain, index_transactions)?
+    Index::get_transaction_with_rtx(inscription_id.txid, rtx, client, chain, index_transactions)?
       .ok_or(OrdApiError::TransactionNotFound(inscription_id.txid))?;
 
   let inscription = ParsedEnvelope::from_transaction(&tx)
@@ -147,12 +544,13 @@ fn ord_get_inscription_by_id(
     .ord_inscription_id_to_collections(inscription_id)?
     .unwrap_or_default();
 
-  let parent_inscription_id = match inscription_entry.parent {
-    Some(parent) => rtx
-      .sequence_number_to_inscription_entry(parent)?
-      .map(|entry| entry.id),
-    None => None,
-  };
+  let mut parents = Vec::new();
+
+  for parent in &inscription_entry.parents {
+    if let Some(entry) = rtx.sequence_number_to_inscription_entry(*parent)? {
+      parents.push(entry.id);
+    }
+  }
 
   let charms: Vec<Charm> = Charm::ALL
     .iter()
@@ -169,7 +567,7 @@ fn ord_get_inscription_by_id(
       Index::get_transaction_with_rtx(
         location_outpoint.txid,
         rtx,
-        &client,
+        client,
         chain,
         index_transactions,
       )?
@@ -183,48 +581,87 @@ fn ord_get_inscription_by_id(
       .nth(location_outpoint.vout.try_into().unwrap())
   };
 
-  Ok(Json(ApiResponse::ok(ApiInscription {
+  let media_type = inscription.content_type().and_then(media::media_type);
+
+  let confirmations = rtx
+    .block_height()?
+    .map(|height| height.n().saturating_sub(inscription_entry.height) + 1)
+    .unwrap_or_default();
+
+  let content_encoding = decompress_encoding_body(&inscription, transforms).await?;
+
+  let content_hash = match content_hashes {
+    Some(content_hashes) => Some(ApiContentHash {
+      sha256: content_hashes
+        .hash(
+          inscription_id,
+          inscription.body().unwrap_or_default(),
+          transforms,
+        )
+        .await?
+        .to_string(),
+      delegated: inscription.delegate().is_some(),
+    }),
+    None => None,
+  };
+
+  Ok(ApiInscription {
     id: inscription_id.to_string(),
     number: inscription_entry.inscription_number,
     content_type: inscription.content_type().map(str::to_string),
+    media_type: media_type.clone(),
+    media_category: media_type
+      .as_deref()
+      .map(MediaCategory::for_media_type)
+      .unwrap_or(MediaCategory::Other),
     content: inscription.body().map(hex::encode),
     content_length: inscription.content_length(),
-    content_encoding: decompress_encoding_body(&inscription),
+    content_encoding,
     metaprotocol: inscription.metaprotocol().map(str::to_string),
     metadata: inscription
       .metadata()
       .and_then(|_| inscription.metadata.as_deref().map(hex::encode)),
-    parent: parent_inscription_id,
+    parent: parents.first().copied(),
+    parents,
     pointer: inscription.pointer(),
     delegate: inscription.delegate(),
     owner: output.map(|vout| ScriptKey::from_script(&vout.script_pubkey, chain).into()),
     genesis_height: inscription_entry.height,
     genesis_timestamp: inscription_entry.timestamp,
+    confirmations,
     location: sat_point.to_string(),
     collections: collections.iter().map(|c| c.to_string()).collect(),
     charms: charms.iter().map(|c| c.title().into()).collect(),
     sat: inscription_entry.sat.map(|s| s.0),
-  })))
+    content_hash,
+  })
 }
 
-fn decompress_encoding_body(inscription: &Inscription) -> Option<ApiContentEncoding> {
+async fn decompress_encoding_body(
+  inscription: &Inscription,
+  transforms: &TransformPool,
+) -> ServerResult<Option<ApiContentEncoding>> {
   if let Some(header_value) = inscription.content_encoding() {
     if header_value == "br" {
       if let Some(body) = inscription.body() {
-        let mut decompressed = Vec::new();
-        if Decompressor::new(body, 4096)
-          .read_to_end(&mut decompressed)
-          .is_ok()
-        {
-          return Some(ApiContentEncoding::Br {
-            decode: hex::encode(decompressed),
-          });
+        let body = body.to_vec();
+        let decode = transforms
+          .run(move || {
+            let mut decompressed = Vec::new();
+            Decompressor::new(body.as_slice(), 4096)
+              .read_to_end(&mut decompressed)
+              .ok()
+              .map(|_| hex::encode(decompressed))
+          })
+          .await?;
+        if let Some(decode) = decode {
+          return Ok(Some(ApiContentEncoding::Br { decode }));
         }
       }
     }
-    return Some(ApiContentEncoding::Unknown);
+    return Ok(Some(ApiContentEncoding::Unknown));
   }
-  None
+  Ok(None)
 }
 
 // ord/debug/bitmap/district/:number
@@ -267,6 +704,8 @@ mod tests {
       .to_string(),
       number: -100,
       content_type: Some("content_type".to_string()),
+      media_type: None,
+      media_category: MediaCategory::Other,
       content: Some("content".to_string()),
       content_length: Some("content".to_string().len()),
       content_encoding: Some(ApiContentEncoding::Br {
@@ -278,6 +717,10 @@ mod tests {
         txid: txid(1),
         index: 0xFFFFFFFE,
       }),
+      parents: vec![InscriptionId {
+        txid: txid(1),
+        index: 0xFFFFFFFE,
+      }],
       delegate: Some(InscriptionId {
         txid: txid(1),
         index: 0xFFFFFFFD,
@@ -295,6 +738,7 @@ mod tests {
       ),
       genesis_height: 1,
       genesis_timestamp: 100,
+      confirmations: 3,
       location: SatPoint::from_str(
         "5660d06bd69326c18ec63127b37fb3b32ea763c3846b3334c51beb6a800c57d3:1:3000",
       )
@@ -306,6 +750,7 @@ mod tests {
         .map(|c| c.title().into())
         .collect(),
       sat: None,
+      content_hash: None,
     };
     assert_eq!(
       serde_json::to_string_pretty(&ord_inscription).unwrap(),
@@ -313,6 +758,8 @@ mod tests {
   "id": "1111111111111111111111111111111111111111111111111111111111111111i4294967295",
   "number": -100,
   "contentType": "content_type",
+  "mediaType": null,
+  "mediaCategory": "other",
   "content": "content",
   "contentLength": 7,
   "contentEncoding": {
@@ -322,6 +769,9 @@ mod tests {
   "metadata": "0123456789abcdef",
   "metaprotocol": "mata_protocol",
   "parent": "1111111111111111111111111111111111111111111111111111111111111111i4294967294",
+  "parents": [
+    "1111111111111111111111111111111111111111111111111111111111111111i4294967294"
+  ],
   "delegate": "1111111111111111111111111111111111111111111111111111111111111111i4294967293",
   "pointer": 0,
   "owner": {
@@ -329,6 +779,7 @@ mod tests {
   },
   "genesisHeight": 1,
   "genesisTimestamp": 100,
+  "confirmations": 3,
   "location": "5660d06bd69326c18ec63127b37fb3b32ea763c3846b3334c51beb6a800c57d3:1:3000",
   "collections": [],
   "charms": [
@@ -344,6 +795,8 @@ mod tests {
   "id": "1111111111111111111111111111111111111111111111111111111111111111i4294967295",
   "number": -100,
   "contentType": "content_type",
+  "mediaType": null,
+  "mediaCategory": "other",
   "content": "content",
   "contentLength": 7,
   "contentEncoding": {
@@ -353,11 +806,15 @@ mod tests {
   "metadata": "0123456789abcdef",
   "metaprotocol": "mata_protocol",
   "parent": "1111111111111111111111111111111111111111111111111111111111111111i4294967294",
+  "parents": [
+    "1111111111111111111111111111111111111111111111111111111111111111i4294967294"
+  ],
   "delegate": "1111111111111111111111111111111111111111111111111111111111111111i4294967293",
   "pointer": 0,
   "owner": null,
   "genesisHeight": 1,
   "genesisTimestamp": 100,
+  "confirmations": 3,
   "location": "5660d06bd69326c18ec63127b37fb3b32ea763c3846b3334c51beb6a800c57d3:1:3000",
   "collections": [],
   "charms": [
@@ -368,8 +825,8 @@ mod tests {
     );
   }
 
-  #[test]
-  fn test_decompress_encoding_body() {
+  #[tokio::test]
+  async fn test_decompress_encoding_body() {
     let mut compressed = Vec::new();
     let body = "ord".as_bytes();
 
@@ -393,15 +850,17 @@ mod tests {
       ..inscription("text/plain;charset=utf-8", compressed)
     };
     assert_eq!(
-      decompress_encoding_body(&inscription),
+      decompress_encoding_body(&inscription, &TransformPool::new(1).unwrap())
+        .await
+        .unwrap(),
       Some(ApiContentEncoding::Br {
         decode: hex::encode(body)
       })
     );
   }
 
-  #[test]
-  fn test_except_decompress_encoding_body() {
+  #[tokio::test]
+  async fn test_except_decompress_encoding_body() {
     let body = "ord".as_bytes();
 
     let inscription1 = Inscription {
@@ -409,7 +868,9 @@ mod tests {
       ..inscription("text/plain;charset=utf-8", body)
     };
     assert_eq!(
-      decompress_encoding_body(&inscription1),
+      decompress_encoding_body(&inscription1, &TransformPool::new(1).unwrap())
+        .await
+        .unwrap(),
       Some(ApiContentEncoding::Unknown)
     );
     let body = Vec::new();
@@ -419,7 +880,9 @@ mod tests {
       ..inscription("text/plain;charset=utf-8", body)
     };
     assert_eq!(
-      decompress_encoding_body(&inscription2),
+      decompress_encoding_body(&inscription2, &TransformPool::new(1).unwrap())
+        .await
+        .unwrap(),
       Some(ApiContentEncoding::Unknown)
     );
   }
@@ -0,0 +1,234 @@
+use {
+  super::{error::ApiError, *},
+  axum::Json,
+  utoipa::ToSchema,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiMempoolInscription)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiMempoolInscription {
+  /// The inscription id.
+  pub id: String,
+  /// The inscription content type.
+  pub content_type: Option<String>,
+  /// The length of the inscription content in bytes.
+  pub content_length: Option<usize>,
+  /// Whether the inscription has been confirmed, always false.
+  pub confirmed: bool,
+  /// The height of the block the inscription was revealed in, always null.
+  #[schema(format = "uint32")]
+  pub height: Option<u32>,
+  /// The number of blocks confirming the inscription, always 0.
+  #[schema(format = "uint32")]
+  pub confirmations: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiMempoolInscriptions)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiMempoolInscriptions {
+  /// Whether the server scans the mempool. If not, no inscriptions are listed.
+  pub mempool_enabled: bool,
+  #[schema(value_type = Vec<ord::ApiMempoolInscription>)]
+  pub inscriptions: Vec<ApiMempoolInscription>,
+  /// Whether more inscriptions are in the mempool than are listed.
+  pub more: bool,
+}
+
+/// Inscriptions listed per request at most.
+const MAX_MEMPOOL_INSCRIPTIONS: usize = 1_000;
+
+/// Transactions fetched per refresh at most, so that a refresh after a burst
+/// of new transactions does not take too long. The rest are fetched by later
+/// refreshes.
+const MAX_MEMPOOL_FETCHES: usize = 1_000;
+
+/// How long the mempool inscriptions are served before being refreshed.
+const MEMPOOL_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Default)]
+struct MempoolState {
+  refreshed: Option<Instant>,
+  txids: Vec<Txid>,
+  inscriptions: HashMap<Txid, Vec<(InscriptionId, ApiMempoolInscription)>>,
+}
+
+/// The inscriptions revealed by the transactions in the mempool, by
+/// transaction. A refresh lists the mempool and fetches only transactions that
+/// entered it since the last refresh, forgetting those that left it.
+#[derive(Default)]
+pub(crate) struct MempoolInscriptionsCache {
+  state: Mutex<MempoolState>,
+}
+
+impl MempoolInscriptionsCache {
+  /// Up to `limit` inscriptions in the mempool that are not yet indexed, in
+  /// mempool order, and whether there are more.
+  fn get(&self, index: &Index, limit: usize) -> Result<(Vec<ApiMempoolInscription>, bool)> {
+    let mut state = self.state.lock().unwrap();
+
+    if state
+      .refreshed
+      .map(|refreshed| refreshed.elapsed() >= MEMPOOL_REFRESH_INTERVAL)
+      .unwrap_or(true)
+    {
+      Self::refresh(&mut state, index)?;
+    }
+
+    let mut inscriptions = Vec::new();
+
+    for (id, inscription) in state
+      .txids
+      .iter()
+      .filter_map(|txid| state.inscriptions.get(txid))
+      .flatten()
+    {
+      // transactions mined since the last refresh may already be indexed
+      if index.inscription_exists(*id)? {
+        continue;
+      }
+
+      if inscriptions.len() == limit {
+        return Ok((inscriptions, true));
+      }
+
+      inscriptions.push(inscription.clone());
+    }
+
+    Ok((inscriptions, false))
+  }
+
+  fn refresh(state: &mut MempoolState, index: &Index) -> Result {
+    let txids = index.get_raw_mempool()?;
+
+    let mempool = txids.iter().collect::<HashSet<&Txid>>();
+
+    state.inscriptions.retain(|txid, _| mempool.contains(txid));
+
+    let new = txids
+      .iter()
+      .filter(|txid| !state.inscriptions.contains_key(*txid))
+      .take(MAX_MEMPOOL_FETCHES)
+      .copied()
+      .collect::<Vec<Txid>>();
+
+    for txid in new {
+      let Some(inscriptions) = index.get_mempool_transaction_inscriptions(txid)? else {
+        continue;
+      };
+
+      state.inscriptions.insert(
+        txid,
+        inscriptions
+          .into_iter()
+          .map(|(id, inscription)| {
+            (
+              id,
+              ApiMempoolInscription {
+                id: id.to_string(),
+                content_type: inscription.content_type().map(str::to_string),
+                content_length: inscription.content_length(),
+                confirmed: false,
+                height: None,
+                confirmations: 0,
+              },
+            )
+          })
+          .collect(),
+      );
+    }
+
+    state.txids = txids;
+    state.refreshed = Some(Instant::now());
+
+    Ok(())
+  }
+}
+
+// /ord/mempool/inscriptions
+/// Get the inscriptions in the mempool.
+///
+/// Retrieve the inscriptions revealed by unconfirmed transactions in the node's mempool, at most
+/// 1000. These are not indexed, and do not appear in the confirmed inscription listings. The
+/// mempool is scanned every few seconds, so new transactions may take a moment to be listed.
+/// Requires `--scan-mempool`.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/mempool/inscriptions",
+  responses(
+    (status = 200, description = "Obtain the inscriptions in the mempool.", body = OrdMempoolInscriptions),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_mempool_inscriptions(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(cache): Extension<Arc<MempoolInscriptionsCache>>,
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+) -> ApiResult<ApiMempoolInscriptions> {
+  log::debug!("rpc: get ord_mempool_inscriptions");
+
+  if !server_config.scan_mempool {
+    return Ok(Json(ApiResponse::ok(ApiMempoolInscriptions {
+      mempool_enabled: false,
+      inscriptions: Vec::new(),
+      more: false,
+    })));
+  }
+
+  let (inscriptions, more) = cache.get(&index, MAX_MEMPOOL_INSCRIPTIONS)?;
+
+  Ok(Json(ApiResponse::ok(ApiMempoolInscriptions {
+    mempool_enabled: true,
+    inscriptions,
+    more,
+  })))
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  #[test]
+  fn mempool_inscriptions_are_capped_and_refreshed() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(3);
+
+    let broadcast = |height| {
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(height, 0, 0, inscription("text/plain", "foo").to_witness())],
+        ..Default::default()
+      })
+    };
+
+    broadcast(1);
+    broadcast(2);
+
+    let cache = MempoolInscriptionsCache::default();
+
+    let (inscriptions, more) = cache.get(&context.index, 1).unwrap();
+    assert_eq!(inscriptions.len(), 1);
+    assert!(more);
+
+    broadcast(3);
+
+    let (inscriptions, more) = cache.get(&context.index, 10).unwrap();
+    assert_eq!(inscriptions.len(), 2);
+    assert!(!more);
+
+    cache.state.lock().unwrap().refreshed = None;
+
+    assert_eq!(cache.get(&context.index, 10).unwrap().0.len(), 3);
+
+    context.mine_blocks(1);
+
+    assert_eq!(cache.get(&context.index, 10).unwrap().0.len(), 0);
+
+    cache.state.lock().unwrap().refreshed = None;
+
+    cache.get(&context.index, 10).unwrap();
+
+    assert!(cache.state.lock().unwrap().inscriptions.is_empty());
+  }
+}
@@ -1,13 +1,24 @@
 use super::*;
 
+mod address;
+mod content_hash;
 mod inscription;
+mod mempool;
 mod outpoint;
+mod sat;
+mod search;
 mod transaction;
 
-pub(super) use {inscription::*, outpoint::*, transaction::*};
+pub(super) use {
+  address::*, content_hash::*, inscription::*, mempool::*, outpoint::*, sat::*, search::*,
+  transaction::*,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum OrdApiError {
+  /// Thrown when the address is malformed or belongs to another network
+  #[error("invalid address {0}")]
+  InvalidAddress(String),
   /// Thrown when a inscription id was requested but not matching inscription exists
   #[error("unknown inscription id {0}")]
   UnknownInscriptionId(InscriptionId),
@@ -26,6 +37,15 @@ pub enum OrdApiError {
   /// Thrown when the satpoint for the inscription cannot be found
   #[error("satpoint not found for inscription {0}")]
   SatPointNotFound(InscriptionId),
+  /// Thrown when a sat was requested but it has not been mined or could not be located
+  #[error("sat {0} not found")]
+  UnknownSat(Sat),
+  /// Thrown when a sat was requested but it was spent to fees and not claimed by a coinbase
+  #[error("sat {0} was spent to fees and lost")]
+  LostSat(Sat),
+  /// Thrown when a sat was requested but the server does not index sats
+  #[error("sat index is not enabled")]
+  SatIndexNotEnabled,
   /// Thrown when an internal error occurs
   #[error("internal error: {0}")]
   Internal(String),
@@ -34,12 +54,16 @@ pub enum OrdApiError {
 impl From<OrdApiError> for ApiError {
   fn from(error: OrdApiError) -> Self {
     match error {
+      OrdApiError::InvalidAddress(_) => Self::bad_request(error.to_string()),
       OrdApiError::UnknownInscriptionId(_) => Self::not_found(error.to_string()),
       OrdApiError::UnknownInscriptionNumber(_) => Self::not_found(error.to_string()),
       OrdApiError::TransactionReceiptNotFound(_) => Self::not_found(error.to_string()),
       OrdApiError::TransactionNotFound(_) => Self::not_found(error.to_string()),
       OrdApiError::InvalidInscription(_) => Self::internal(error.to_string()),
       OrdApiError::SatPointNotFound(_) => Self::internal(error.to_string()),
+      OrdApiError::UnknownSat(_) => Self::not_found(error.to_string()),
+      OrdApiError::LostSat(_) => Self::not_found(error.to_string()),
+      OrdApiError::SatIndexNotEnabled => Self::bad_request(error.to_string()),
       OrdApiError::Internal(_) => Self::internal(error.to_string()),
     }
   }
@@ -1,5 +1,10 @@
 use {
-  super::{error::ApiError, types::ScriptPubkey, *},
+  super::{
+    error::ApiError,
+    runes::ApiRuneBalance,
+    types::{AddressType, ScriptPubkey},
+    *,
+  },
   crate::okx::datastore::ScriptKey,
   axum::Json,
   utoipa::ToSchema,
@@ -38,6 +43,8 @@ pub struct ApiOutpointInscriptions {
   pub script_pub_key: String,
   /// The owner of the script pubkey.
   pub owner: ScriptPubkey,
+  /// The type of the script pubkey.
+  pub address_type: AddressType,
   /// The value of the transaction output.
   #[schema(format = "uint64")]
   pub value: u64,
@@ -48,6 +55,9 @@ pub struct ApiOutpointInscriptions {
 
 // /ord/outpoint/:outpoint/info
 /// Retrieve the outpoint infomation with the specified outpoint.
+///
+/// Responds with an `ord.OutPointResultResponse` protobuf message instead of JSON if
+/// requested with `Accept: application/x-protobuf`.
 #[utoipa::path(
   get,
   path = "/api/v1/ord/outpoint/{outpoint}/info",
@@ -63,8 +73,9 @@ pub struct ApiOutpointInscriptions {
 )]
 pub(crate) async fn ord_outpoint(
   Extension(index): Extension<Arc<Index>>,
-  Path(outpoint): Path<OutPoint>,
-) -> ApiResult<ApiOutPointResult> {
+  PathParams(Param(outpoint)): PathParams<Param<OutPoint>>,
+  accept_protobuf: AcceptProtobuf,
+) -> ApiEncodedResult<ApiOutPointResult, OutPointResultResponse> {
   log::debug!("rpc: get ord_outpoint: {outpoint}");
 
   let rtx = index.begin_read()?;
@@ -79,7 +90,7 @@ pub(crate) async fn ord_outpoint(
 
   // If there are no inscriptions on the output, return None and parsed block states.
   if inscriptions_with_satpoints.is_empty() {
-    return Ok(Json(ApiResponse::ok(ApiOutPointResult {
+    return Ok(accept_protobuf.respond(ApiResponse::ok(ApiOutPointResult {
       result: None,
       latest_height: latest_height.n(),
       latest_blockhash: latest_blockhash.to_string(),
@@ -108,11 +119,12 @@ pub(crate) async fn ord_outpoint(
   )?
   .ok_or(OrdApiError::TransactionNotFound(outpoint.txid))?;
 
-  Ok(Json(ApiResponse::ok(ApiOutPointResult {
+  Ok(accept_protobuf.respond(ApiResponse::ok(ApiOutPointResult {
     result: Some(ApiOutpointInscriptions {
       txid: outpoint.txid.to_string(),
       script_pub_key: vout.script_pubkey.to_asm_string(),
       owner: ScriptKey::from_script(&vout.script_pubkey, chain).into(),
+      address_type: AddressType::from_script(&vout.script_pubkey),
       value: vout.value,
       inscription_digest: inscription_digests,
     }),
@@ -120,3 +132,310 @@ pub(crate) async fn ord_outpoint(
     latest_blockhash: latest_blockhash.to_string(),
   })))
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiOutputsBatchRequest)]
+pub struct ApiOutputsBatchRequest {
+  /// The outpoints to look up, as `txid:vout`, at most 100.
+  pub outpoints: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiOutputsBatch)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiOutputsBatch {
+  /// The outputs found, keyed by outpoint.
+  #[schema(value_type = HashMap<String, ord::ApiOutpointInscriptions>)]
+  pub outputs: BTreeMap<String, ApiOutpointInscriptions>,
+  /// The requested outpoints that are invalid or do not match any output.
+  pub not_found: Vec<String>,
+  pub latest_blockhash: String,
+  #[schema(format = "uint64")]
+  pub latest_height: u32,
+}
+
+// /ord/outputs
+/// Retrieve the outpoint infomation of up to 100 outpoints at once.
+#[utoipa::path(
+  post,
+  path = "/api/v1/ord/outputs",
+  request_body = ord::ApiOutputsBatchRequest,
+  responses(
+    (status = 200, description = "Obtain outpoints infomation.", body = OrdOutputsBatch),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_outputs_batch(
+  Extension(index): Extension<Arc<Index>>,
+  Json(request): Json<ApiOutputsBatchRequest>,
+) -> ApiResult<ApiOutputsBatch> {
+  log::debug!("rpc: get ord_outputs_batch: {:?}", request.outpoints);
+
+  const MAX_OUTPOINTS: usize = 100;
+
+  if request.outpoints.len() > MAX_OUTPOINTS {
+    return Err(ApiError::bad_request(format!(
+      "at most {MAX_OUTPOINTS} outpoints may be requested at once"
+    )));
+  }
+
+  let rtx = index.begin_read()?;
+  let chain = index.get_chain();
+  let client = index.bitcoin_rpc_client()?;
+  let index_transactions = index.has_transactions_index();
+
+  let (latest_height, latest_blockhash) = rtx.latest_block()?.ok_or_api_err(|| {
+    OrdApiError::Internal("Failed to retrieve the latest block from the database.".to_string())
+      .into()
+  })?;
+
+  let mut outputs = BTreeMap::new();
+  let mut not_found = Vec::new();
+
+  for key in request.outpoints {
+    cancel::check()?;
+
+    let Ok(outpoint) = OutPoint::from_str(&key) else {
+      not_found.push(key);
+      continue;
+    };
+
+    let Some(vout) = Index::fetch_vout(&rtx, &client, outpoint, chain, index_transactions)? else {
+      not_found.push(key);
+      continue;
+    };
+
+    let mut inscription_digest = Vec::new();
+    for (satpoint, inscription_id) in rtx.inscriptions_on_output_with_satpoints(outpoint)? {
+      inscription_digest.push(ApiInscriptionDigest {
+        id: inscription_id.to_string(),
+        number: rtx
+          .get_inscription_entry(inscription_id)?
+          .map(|inscription_entry| inscription_entry.inscription_number)
+          .ok_or(OrdApiError::UnknownInscriptionId(inscription_id))?,
+        location: satpoint.to_string(),
+      });
+    }
+
+    outputs.insert(
+      key,
+      ApiOutpointInscriptions {
+        txid: outpoint.txid.to_string(),
+        script_pub_key: vout.script_pubkey.to_asm_string(),
+        owner: ScriptKey::from_script(&vout.script_pubkey, chain).into(),
+        address_type: AddressType::from_script(&vout.script_pubkey),
+        value: vout.value,
+        inscription_digest,
+      },
+    );
+  }
+
+  Ok(Json(ApiResponse::ok(ApiOutputsBatch {
+    outputs,
+    not_found,
+    latest_blockhash: latest_blockhash.to_string(),
+    latest_height: latest_height.n(),
+  })))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiOutpoint)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiOutpoint {
+  /// The outpoint, as `txid:vout`.
+  pub outpoint: String,
+  /// The value of the transaction output.
+  #[schema(format = "uint64")]
+  pub value: u64,
+  /// The address of the transaction output, if it has one.
+  pub address: Option<String>,
+  /// The type of the script pubkey of the transaction output.
+  pub address_type: AddressType,
+  /// The script pubkey of the transaction output, hex-encoded.
+  pub script_pubkey: String,
+  /// Whether the output has been spent.
+  pub spent: bool,
+  /// The number of indexed blocks confirming the transaction of the output.
+  /// Absent if the output is spent or unconfirmed, since the node only tracks
+  /// confirmed unspent outputs.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[schema(format = "uint32")]
+  pub confirmations: Option<u32>,
+  /// The inscriptions on the transaction output.
+  #[schema(value_type = Vec<ord::ApiInscriptionDigest>)]
+  pub inscriptions: Vec<ApiInscriptionDigest>,
+  /// The rune balances of the transaction output.
+  #[schema(value_type = Vec<runes::Balance>)]
+  pub runes: Vec<ApiRuneBalance>,
+  /// The sat ranges of the transaction output, as `[start, end)` pairs. Only
+  /// present if the index tracks sats, and empty once the output is spent.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[schema(value_type = Option<Vec<Vec<u64>>>)]
+  pub sat_ranges: Option<Vec<(u64, u64)>>,
+}
+
+// /ord/outpoint/:outpoint
+/// Retrieve the inscriptions, rune balances and sat ranges of the specified outpoint.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/outpoint/{outpoint}",
+  params(
+      ("outpoint" = String, Path, description = "Outpoint")
+),
+  responses(
+    (status = 200, description = "Obtain outpoint data", body = OrdOutpoint),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_outpoint_data(
+  Extension(index): Extension<Arc<Index>>,
+  PathParams(Param(outpoint)): PathParams<Param<OutPoint>>,
+) -> ApiResult<ApiOutpoint> {
+  log::debug!("rpc: get ord_outpoint_data: {outpoint}");
+
+  let rtx = index.begin_read()?;
+  let chain = index.get_chain();
+  let client = index.bitcoin_rpc_client()?;
+
+  let vout = Index::fetch_vout(
+    &rtx,
+    &client,
+    outpoint,
+    chain,
+    index.has_transactions_index(),
+  )?
+  .ok_or_api_not_found(format!("output {outpoint} not found"))?;
+
+  let tx_out = client
+    .get_tx_out(&outpoint.txid, outpoint.vout, Some(false))
+    .map_err(anyhow::Error::from)?;
+
+  let spent = tx_out.is_none();
+
+  let confirmations = tx_out
+    .map(|tx_out| Index::get_output_confirmations(&rtx, &client, &tx_out))
+    .transpose()?;
+
+  let mut inscriptions = Vec::new();
+  for (satpoint, inscription_id) in rtx.inscriptions_on_output_with_satpoints(outpoint)? {
+    inscriptions.push(ApiInscriptionDigest {
+      id: inscription_id.to_string(),
+      number: rtx
+        .get_inscription_entry(inscription_id)?
+        .map(|inscription_entry| inscription_entry.inscription_number)
+        .ok_or(OrdApiError::UnknownInscriptionId(inscription_id))?,
+      location: satpoint.to_string(),
+    });
+  }
+
+  let mut runes = Vec::new();
+  for (spaced_rune, pile) in index.get_rune_balances_for_outpoint(outpoint)? {
+    let (id, ..) = index
+      .rune(spaced_rune.rune)?
+      .ok_or_api_err(|| OrdApiError::Internal(format!("unknown rune {spaced_rune}")).into())?;
+
+    runes.push(ApiRuneBalance::new(id, spaced_rune, pile));
+  }
+
+  let sat_ranges = match index.list(outpoint)? {
+    Some(List::Unspent(ranges)) => Some(ranges),
+    Some(List::Spent) => Some(Vec::new()),
+    None if index.has_sat_index() => Some(Vec::new()),
+    None => None,
+  };
+
+  Ok(Json(ApiResponse::ok(ApiOutpoint {
+    outpoint: outpoint.to_string(),
+    value: vout.value,
+    address: chain
+      .address_from_script(&vout.script_pubkey)
+      .ok()
+      .map(|address| address.to_string()),
+    address_type: AddressType::from_script(&vout.script_pubkey),
+    script_pubkey: vout.script_pubkey.to_hex_string(),
+    spent,
+    confirmations,
+    inscriptions,
+    runes,
+    sat_ranges,
+  })))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiSatPointInscriptions)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSatPointInscriptions {
+  /// The satpoint, as `txid:vout:offset`.
+  pub satpoint: String,
+  /// The inscriptions on the sat at the satpoint, oldest first. There is more
+  /// than one if the sat was reinscribed.
+  #[schema(value_type = Vec<ord::ApiInscriptionDigest>)]
+  pub inscriptions: Vec<ApiInscriptionDigest>,
+}
+
+// /ord/outpoint/:outpoint/offset/:offset
+/// Retrieve the inscriptions on the sat at the specified offset of an outpoint.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/outpoint/{outpoint}/offset/{offset}",
+  params(
+      ("outpoint" = String, Path, description = "Outpoint"),
+      ("offset" = u64, Path, description = "Sat offset within the output"),
+),
+  responses(
+    (status = 200, description = "Obtain the inscriptions at the offset.", body = OrdSatPointInscriptions),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_outpoint_offset(
+  Extension(index): Extension<Arc<Index>>,
+  PathParams((Param(outpoint), offset)): PathParams<(Param<OutPoint>, u64)>,
+) -> ApiResult<ApiSatPointInscriptions> {
+  log::debug!("rpc: get ord_outpoint_offset: {outpoint} {offset}");
+
+  let rtx = index.begin_read()?;
+
+  let vout = Index::fetch_vout(
+    &rtx,
+    &index.bitcoin_rpc_client()?,
+    outpoint,
+    index.get_chain(),
+    index.has_transactions_index(),
+  )?
+  .ok_or_api_not_found(format!("output {outpoint} not found"))?;
+
+  if offset >= vout.value {
+    return Err(ApiError::bad_request(format!(
+      "offset {offset} is beyond output {outpoint} of {} sats",
+      vout.value
+    )));
+  }
+
+  let satpoint = SatPoint { outpoint, offset };
+
+  let mut inscriptions = Vec::new();
+  for inscription_id in rtx.inscriptions_at_satpoint(satpoint)? {
+    inscriptions.push(ApiInscriptionDigest {
+      id: inscription_id.to_string(),
+      number: rtx
+        .get_inscription_entry(inscription_id)?
+        .map(|inscription_entry| inscription_entry.inscription_number)
+        .ok_or(OrdApiError::UnknownInscriptionId(inscription_id))?,
+      location: satpoint.to_string(),
+    });
+  }
+
+  if inscriptions.is_empty() {
+    return Err(ApiError::not_found(format!("no inscription at {satpoint}")));
+  }
+
+  Ok(Json(ApiResponse::ok(ApiSatPointInscriptions {
+    satpoint: satpoint.to_string(),
+    inscriptions,
+  })))
+}
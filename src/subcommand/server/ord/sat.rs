@@ -0,0 +1,440 @@
+use {
+  super::{error::ApiError, *},
+  axum::{extract::rejection::QueryRejection, Json},
+  utoipa::{IntoParams, ToSchema},
+};
+
+/// Special sats may be listed from blocks at most this many heights apart.
+const MAX_SPECIAL_SATS_HEIGHT_SPAN: u32 = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiSat)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSat {
+  /// The sat number.
+  #[schema(format = "uint64")]
+  pub sat: u64,
+  /// The sat name, its number in base 26, counting down from the last sat.
+  pub name: String,
+  /// The sat in degree notation, `A°B′C″D‴`.
+  pub degree: String,
+  /// The sat in decimal notation, `block.offset`.
+  pub decimal: String,
+  /// The sat's position among all sats, as a percentage.
+  pub percentile: String,
+  /// The height of the block the sat was mined in.
+  #[schema(format = "uint32")]
+  pub block: u32,
+  /// The offset of the sat in the subsidy of its block.
+  #[schema(format = "uint64")]
+  pub offset: u64,
+  /// The conjunction cycle the sat was mined in.
+  #[schema(format = "uint32")]
+  pub cycle: u32,
+  /// The halving epoch the sat was mined in.
+  #[schema(format = "uint32")]
+  pub epoch: u32,
+  /// The difficulty adjustment period the sat was mined in.
+  #[schema(format = "uint32")]
+  pub period: u32,
+  /// The rarity of the sat.
+  #[schema(value_type = String)]
+  pub rarity: Rarity,
+}
+
+// /ord/sat/:sat
+/// Retrieve the notations of the specified sat.
+///
+/// All fields are computed from the sat number, so no sat index is needed.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/sat/{sat}",
+  params(
+      ("sat" = String, Path, description = "Sat number, name, degree, decimal or percentile")
+),
+  responses(
+    (status = 200, description = "Obtain the notations of the sat.", body = OrdSat),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_sat(Path(sat): Path<String>) -> ApiResult<ApiSat> {
+  log::debug!("rpc: get ord_sat: {sat}");
+
+  let sat = sat.parse::<Sat>().map_err(|_| {
+    ApiError::bad_request(format!(
+      "invalid sat {sat}, sats are numbered from 0 to {}",
+      Sat::LAST.n()
+    ))
+  })?;
+
+  Ok(Json(ApiResponse::ok(ApiSat {
+    sat: sat.n(),
+    name: sat.name(),
+    degree: sat.degree().to_string(),
+    decimal: sat.decimal().to_string(),
+    percentile: sat.percentile(),
+    block: sat.height().n(),
+    offset: sat.third(),
+    cycle: sat.cycle(),
+    epoch: sat.epoch().0,
+    period: sat.period(),
+    rarity: sat.rarity(),
+  })))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiSatPoint)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSatPoint {
+  /// The sat number.
+  #[schema(format = "uint64")]
+  pub sat: u64,
+  /// The satpoint currently holding the sat.
+  #[schema(value_type = String)]
+  pub satpoint: SatPoint,
+  /// The address of the output holding the sat, if its script is an address.
+  pub address: Option<String>,
+  /// Whether the output holding the sat has been spent by a mempool transaction.
+  pub unconfirmed: bool,
+}
+
+// /ord/sat/:sat/satpoint
+/// Retrieve the current satpoint of the specified sat.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/sat/{sat}/satpoint",
+  params(
+      ("sat" = String, Path, description = "Sat number, name, degree, decimal or percentile")
+),
+  responses(
+    (status = 200, description = "Obtain the satpoint of the sat.", body = OrdSatPoint),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_sat_satpoint(
+  Extension(index): Extension<Arc<Index>>,
+  PathParams(Param(sat)): PathParams<Param<Sat>>,
+) -> ApiResult<ApiSatPoint> {
+  log::debug!("rpc: get ord_sat_satpoint: {sat}");
+
+  if !index.has_sat_index() {
+    return Err(OrdApiError::SatIndexNotEnabled.into());
+  }
+
+  let satpoint = match index.rare_sat_satpoint(sat)? {
+    Some(satpoint) => satpoint,
+    None => index.find(sat)?.ok_or(OrdApiError::UnknownSat(sat))?,
+  };
+
+  if satpoint.outpoint == OutPoint::null() {
+    return Err(OrdApiError::LostSat(sat).into());
+  }
+
+  if satpoint.outpoint == unbound_outpoint() {
+    return Err(OrdApiError::UnknownSat(sat).into());
+  }
+
+  let rtx = index.begin_read()?;
+  let chain = index.get_chain();
+  let client = index.bitcoin_rpc_client()?;
+
+  let unconfirmed = client
+    .get_tx_out(&satpoint.outpoint.txid, satpoint.outpoint.vout, Some(true))
+    .map_err(anyhow::Error::from)?
+    .is_none();
+
+  let vout = Index::fetch_vout(
+    &rtx,
+    &client,
+    satpoint.outpoint,
+    chain,
+    index.has_transactions_index(),
+  )?
+  .ok_or(OrdApiError::TransactionNotFound(satpoint.outpoint.txid))?;
+
+  Ok(Json(ApiResponse::ok(ApiSatPoint {
+    sat: sat.n(),
+    satpoint,
+    address: chain
+      .address_from_script(&vout.script_pubkey)
+      .ok()
+      .map(|address| address.to_string()),
+    unconfirmed,
+  })))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiSatRangesRequest)]
+pub struct ApiSatRangesRequest {
+  /// The outpoints whose sat ranges to coalesce, as `txid:vout`, at most 100.
+  pub outpoints: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiCoalescedSatRanges)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiCoalescedSatRanges {
+  /// The sorted `[start, end)` ranges of the sats in the outputs, with
+  /// adjacent ranges merged.
+  #[schema(value_type = Vec<[u64; 2]>)]
+  pub ranges: Vec<(u64, u64)>,
+  /// The number of sats in the ranges.
+  #[schema(format = "uint64")]
+  pub total: u64,
+  /// The requested outpoints that are invalid or do not match an unspent output.
+  pub not_found: Vec<String>,
+}
+
+/// Merge sat ranges into sorted ranges with no two adjacent or overlapping.
+fn coalesce(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+  ranges.sort_unstable();
+
+  let mut coalesced: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+
+  for (start, end) in ranges {
+    match coalesced.last_mut() {
+      Some((_, last)) if start <= *last => *last = (*last).max(end),
+      _ => coalesced.push((start, end)),
+    }
+  }
+
+  coalesced
+}
+
+// /ord/sat-ranges
+/// Retrieve the coalesced sat ranges of up to 100 outpoints at once.
+#[utoipa::path(
+  post,
+  path = "/api/v1/ord/sat-ranges",
+  request_body = ord::ApiSatRangesRequest,
+  responses(
+    (status = 200, description = "Obtain the coalesced sat ranges of the outpoints.", body = OrdCoalescedSatRanges),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_sat_ranges(
+  Extension(index): Extension<Arc<Index>>,
+  Json(request): Json<ApiSatRangesRequest>,
+) -> ApiResult<ApiCoalescedSatRanges> {
+  log::debug!("rpc: get ord_sat_ranges: {:?}", request.outpoints);
+
+  const MAX_OUTPOINTS: usize = 100;
+
+  if !index.has_sat_index() {
+    return Err(OrdApiError::SatIndexNotEnabled.into());
+  }
+
+  if request.outpoints.len() > MAX_OUTPOINTS {
+    return Err(ApiError::bad_request(format!(
+      "at most {MAX_OUTPOINTS} outpoints may be requested at once"
+    )));
+  }
+
+  let rtx = index.begin_read()?;
+
+  let mut outpoints = HashSet::new();
+  let mut ranges = Vec::new();
+  let mut not_found = Vec::new();
+
+  for key in request.outpoints {
+    let Ok(outpoint) = OutPoint::from_str(&key) else {
+      not_found.push(key);
+      continue;
+    };
+
+    if !outpoints.insert(outpoint) {
+      continue;
+    }
+
+    match Index::list_sat_range(&rtx, outpoint, true)? {
+      Some(sat_ranges) => ranges.extend(sat_ranges),
+      None => not_found.push(key),
+    }
+  }
+
+  let ranges = coalesce(ranges);
+
+  Ok(Json(ApiResponse::ok(ApiCoalescedSatRanges {
+    total: ranges.iter().map(|(start, end)| end - start).sum(),
+    ranges,
+    not_found,
+  })))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn adjacent_ranges_are_merged() {
+    assert_eq!(coalesce(vec![(10, 20), (0, 10), (20, 25)]), [(0, 25)]);
+  }
+
+  #[test]
+  fn non_adjacent_ranges_stay_separate() {
+    assert_eq!(
+      coalesce(vec![(30, 40), (0, 10), (11, 20)]),
+      [(0, 10), (11, 20), (30, 40)]
+    );
+  }
+
+  #[test]
+  fn overlapping_ranges_are_merged() {
+    assert_eq!(coalesce(vec![(0, 10), (5, 8), (8, 12)]), [(0, 12)]);
+  }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct SpecialSatsQuery {
+  /// Only list sats of this rarity. All special sats are listed by default.
+  #[param(value_type = Option<String>)]
+  rarity: Option<Rarity>,
+  /// First block height to include.
+  from_height: u32,
+  /// Block height to stop before, at most 10000 blocks after `from_height`.
+  to_height: u32,
+  /// Page index, starting at 0.
+  page: Option<usize>,
+  /// Page size. Defaults to `--default-page-size`, and larger sizes are
+  /// reduced to `--max-page-size`.
+  limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiSpecialSat)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSpecialSat {
+  /// The sat number.
+  #[schema(format = "uint64")]
+  pub sat: u64,
+  /// The sat name.
+  pub name: String,
+  /// The rarity of the sat.
+  #[schema(value_type = String)]
+  pub rarity: Rarity,
+  /// The height of the block the sat was mined in.
+  #[schema(format = "uint32")]
+  pub block: u32,
+  /// The satpoint currently holding the sat, if it has been mined and not lost.
+  #[schema(value_type = Option<String>)]
+  pub satpoint: Option<SatPoint>,
+  /// The address of the output holding the sat, if its script is an address.
+  pub address: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiSpecialSats)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSpecialSats {
+  #[schema(value_type = Vec<ord::ApiSpecialSat>)]
+  pub sats: Vec<ApiSpecialSat>,
+  pub page_index: usize,
+  pub limit: usize,
+  pub more: bool,
+}
+
+// /ord/sats/special
+/// List the special sats mined in a range of blocks.
+///
+/// Every block's first sat is uncommon or rarer, so this lists the first sat of each block from
+/// `from_height` up to but not including `to_height`, like the other height range queries, a
+/// page at a time, with its current location. Requires `--index-sats`.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/sats/special",
+  params(
+      SpecialSatsQuery,
+),
+  responses(
+    (status = 200, description = "Obtain the special sats in the range.", body = OrdSpecialSats),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_special_sats(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  query: Result<Query<SpecialSatsQuery>, QueryRejection>,
+) -> ApiResult<ApiSpecialSats> {
+  let Query(query) = query.map_err(|err| ApiError::bad_request(err.body_text()))?;
+
+  log::debug!("rpc: get ord_special_sats: {query:?}");
+
+  if !index.has_sat_index() {
+    return Err(OrdApiError::SatIndexNotEnabled.into());
+  }
+
+  if query.from_height >= query.to_height {
+    return Err(ApiError::bad_request(
+      "from_height must be less than to_height",
+    ));
+  }
+
+  if query.to_height - query.from_height > MAX_SPECIAL_SATS_HEIGHT_SPAN {
+    return Err(ApiError::bad_request(format!(
+      "height range may span at most {MAX_SPECIAL_SATS_HEIGHT_SPAN} blocks"
+    )));
+  }
+
+  let page_index = query.page.unwrap_or_default();
+
+  let limit = page_size(&server_config, query.limit)?;
+
+  let mut sats = (query.from_height..query.to_height.min(Sat::LAST.height().n() + 1))
+    .map(|height| Height(height).starting_sat())
+    .filter(|sat| query.rarity.map_or(true, |rarity| sat.rarity() == rarity))
+    .skip(page_index.saturating_mul(limit))
+    .take(limit.saturating_add(1))
+    .collect::<Vec<Sat>>();
+
+  let more = sats.len() > limit;
+
+  if more {
+    sats.pop();
+  }
+
+  let rtx = index.begin_read()?;
+  let chain = index.get_chain();
+  let client = index.bitcoin_rpc_client()?;
+
+  let mut special_sats = Vec::with_capacity(sats.len());
+
+  for sat in sats {
+    let satpoint = index
+      .rare_sat_satpoint(sat)?
+      .filter(|satpoint| ![OutPoint::null(), unbound_outpoint()].contains(&satpoint.outpoint));
+
+    let address = match satpoint {
+      Some(satpoint) => Index::fetch_vout(
+        &rtx,
+        &client,
+        satpoint.outpoint,
+        chain,
+        index.has_transactions_index(),
+      )?
+      .and_then(|vout| chain.address_from_script(&vout.script_pubkey).ok())
+      .map(|address| address.to_string()),
+      None => None,
+    };
+
+    special_sats.push(ApiSpecialSat {
+      sat: sat.n(),
+      name: sat.name(),
+      rarity: sat.rarity(),
+      block: sat.height().n(),
+      satpoint,
+      address,
+    });
+  }
+
+  Ok(Json(ApiResponse::ok(ApiSpecialSats {
+    sats: special_sats,
+    page_index,
+    limit,
+    more,
+  })))
+}
@@ -0,0 +1,178 @@
+use {
+  super::{error::ApiError, *},
+  axum::Json,
+  std::ops::RangeInclusive,
+  utoipa::{IntoParams, ToSchema},
+};
+
+/// The most inscriptions a single search visits, newest first, reading the
+/// content of those with a `text/*` content type.
+const MAX_SCANNED: usize = 250;
+
+/// The shortest and longest queries, in characters.
+const QUERY_LENGTH: RangeInclusive<usize> = 3..=64;
+
+/// Characters of content shown on either side of a match in snippets.
+const SNIPPET_CONTEXT: usize = 32;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct SearchQuery {
+  /// Text to search for, matched case-insensitively for ASCII letters.
+  q: String,
+  /// Maximum number of matches. Defaults to `--default-page-size`, and larger
+  /// limits are reduced to `--max-page-size`.
+  limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiSearchMatch)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSearchMatch {
+  /// The inscription id.
+  pub id: String,
+  /// The content around the first match.
+  pub snippet: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiSearchMatches)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSearchMatches {
+  #[schema(value_type = Vec<ord::ApiSearchMatch>)]
+  pub matches: Vec<ApiSearchMatch>,
+  /// Whether the search stopped after visiting the most inscriptions a search
+  /// may visit, so older inscriptions were not searched.
+  pub truncated: bool,
+}
+
+/// The text of `content` around the match of `len` bytes at `start`.
+fn snippet(content: &str, start: usize, len: usize) -> String {
+  let end = start + len;
+
+  let snippet_start = content[..start]
+    .char_indices()
+    .rev()
+    .nth(SNIPPET_CONTEXT - 1)
+    .map(|(i, _)| i)
+    .unwrap_or_default();
+
+  let snippet_end = content[end..]
+    .char_indices()
+    .nth(SNIPPET_CONTEXT)
+    .map(|(i, _)| end + i)
+    .unwrap_or(content.len());
+
+  content[snippet_start..snippet_end].into()
+}
+
+// /inscriptions/search
+/// Search the text content of inscriptions.
+///
+/// Retrieve the newest `text/*` inscriptions whose content contains `q`. This is a best-effort
+/// scan, not an index: only the newest 250 inscriptions are visited, and `truncated` is set if
+/// there were older ones. Content with a content encoding is not searched.
+#[utoipa::path(
+  get,
+  path = "/api/v1/inscriptions/search",
+  params(
+      SearchQuery,
+),
+  responses(
+    (status = 200, description = "Obtain inscriptions matching the query.", body = OrdSearchMatches),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 429, description = "Too many content transforms in progress.", body = ApiError, example = json!(&ApiError::rate_limited(1))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_inscriptions_search(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(transforms): Extension<Arc<TransformPool>>,
+  Query(query): Query<SearchQuery>,
+) -> ApiResult<ApiSearchMatches> {
+  log::debug!("rpc: get ord_inscriptions_search: {query:?}");
+
+  if !QUERY_LENGTH.contains(&query.q.chars().count()) {
+    return Err(ApiError::bad_request(format!(
+      "q must be between {} and {} characters long",
+      QUERY_LENGTH.start(),
+      QUERY_LENGTH.end(),
+    )));
+  }
+
+  let limit = page_size(&server_config, query.limit)?;
+
+  let needle = query.q.to_ascii_lowercase();
+
+  // reading and searching content is CPU-bound, so it is left to the
+  // transform pool, which turns away searches while it is saturated
+  let (matches, truncated) = transforms
+    .run(move || -> Result<_, ApiError> {
+      let (inscription_ids, mut truncated) = index.get_text_inscriptions(MAX_SCANNED)?;
+
+      let mut matches = Vec::new();
+
+      for inscription_id in inscription_ids {
+        let Some(inscription) = index.get_inscription_by_id(inscription_id)? else {
+          continue;
+        };
+
+        // encoded content would have to be decoded to be searched
+        if inscription.content_encoding.is_some() {
+          continue;
+        }
+
+        let Some(content) = inscription
+          .body()
+          .and_then(|body| str::from_utf8(body).ok())
+        else {
+          continue;
+        };
+
+        // ASCII lowercasing keeps byte offsets, so matches index into `content`
+        if let Some(start) = content.to_ascii_lowercase().find(&needle) {
+          matches.push(ApiSearchMatch {
+            id: inscription_id.to_string(),
+            snippet: snippet(content, start, needle.len()),
+          });
+
+          if matches.len() == limit {
+            truncated = false;
+            break;
+          }
+        }
+      }
+
+      Ok((matches, truncated))
+    })
+    .await??;
+
+  Ok(Json(ApiResponse::ok(ApiSearchMatches {
+    matches,
+    truncated,
+  })))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn snippets_are_bounded_by_context() {
+    assert_eq!(snippet("foo bar baz", 4, 3), "foo bar baz");
+
+    let content = format!("{}needle{}", "a".repeat(100), "b".repeat(100));
+
+    assert_eq!(
+      snippet(&content, 100, 6),
+      format!("{}needle{}", "a".repeat(32), "b".repeat(32))
+    );
+
+    let content = format!("{}needle{}", "é".repeat(40), "ü".repeat(40));
+
+    assert_eq!(
+      snippet(&content, 80, 6),
+      format!("{}needle{}", "é".repeat(32), "ü".repeat(32))
+    );
+  }
+}
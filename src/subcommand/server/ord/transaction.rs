@@ -1,7 +1,7 @@
 use {
   super::{error::ApiError, types::ScriptPubkey, *},
   crate::{
-    index::rtx::Rtx,
+    index::{rtx::Rtx, Order},
     okx::datastore::{
       ord::{Action, InscriptionOp},
       ScriptKey,
@@ -96,6 +96,18 @@ impl ApiTxInscription {
   }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiTxTransfer)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTxTransfer {
+  /// The inscription id.
+  pub id: String,
+  /// The inscription satpoint of the transaction input.
+  pub from_satpoint: String,
+  /// The inscription satpoint of the transaction output, if it was not lost.
+  pub to_satpoint: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 #[schema(as = ord::ApiTxInscriptions)]
 #[serde(rename_all = "camelCase")]
@@ -103,18 +115,43 @@ pub struct ApiTxInscriptions {
   #[schema(value_type = Vec<ord::ApiTxInscription>)]
   pub inscriptions: Vec<ApiTxInscription>,
   pub txid: String,
+  /// The ids of the inscriptions inscribed in the transaction.
+  pub inscribed: Vec<String>,
+  /// The inscriptions moved by the transaction.
+  #[schema(value_type = Vec<ord::ApiTxTransfer>)]
+  pub transferred: Vec<ApiTxTransfer>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
-#[schema(as = ord::ApiBlockInscriptions)]
-#[serde(rename_all = "camelCase")]
-pub struct ApiBlockInscriptions {
-  #[schema(value_type = Vec<ord::ApiTxInscriptions>)]
-  pub block: Vec<ApiTxInscriptions>,
+impl ApiTxInscriptions {
+  fn new(txid: Txid, inscriptions: Vec<ApiTxInscription>) -> Self {
+    let mut inscribed = Vec::new();
+    let mut transferred = Vec::new();
+
+    for inscription in &inscriptions {
+      match inscription.action {
+        ApiInscriptionAction::New { .. } => inscribed.push(inscription.inscription_id.clone()),
+        ApiInscriptionAction::Transfer => transferred.push(ApiTxTransfer {
+          id: inscription.inscription_id.clone(),
+          from_satpoint: inscription.old_satpoint.clone(),
+          to_satpoint: inscription.new_satpoint.clone(),
+        }),
+      }
+    }
+
+    Self {
+      inscriptions,
+      txid: txid.to_string(),
+      inscribed,
+      transferred,
+    }
+  }
 }
 
 // ord/tx/:txid/inscriptions
 /// Retrieve the inscription actions from the given transaction.
+///
+/// `inscribed` lists the inscriptions inscribed in the transaction and `transferred` the
+/// inscriptions it moved. Both are empty for a transaction without inscription activity.
 #[utoipa::path(
   get,
   path = "/api/v1/ord/tx/{txid}/inscriptions",
@@ -154,65 +191,141 @@ pub(crate) async fn ord_txid_inscriptions(
     api_tx_inscriptions.push(tx_inscription);
   }
 
-  Ok(Json(ApiResponse::ok(ApiTxInscriptions {
-    inscriptions: api_tx_inscriptions,
-    txid: txid.to_string(),
-  })))
+  Ok(Json(ApiResponse::ok(ApiTxInscriptions::new(
+    txid,
+    api_tx_inscriptions,
+  ))))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiBlockMint)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiBlockMint {
+  /// The inscription id.
+  #[schema(value_type = String)]
+  pub id: InscriptionId,
+  /// The inscription number.
+  pub number: i32,
+  /// The fee paid by the revealing transaction, in sats.
+  #[schema(format = "uint64")]
+  pub fee: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiBlockMints)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiBlockMints {
+  #[schema(format = "uint64")]
+  pub height: u32,
+  #[schema(value_type = Vec<ord::ApiBlockMint>)]
+  pub inscriptions: Vec<ApiBlockMint>,
+  #[schema(format = "uint64")]
+  pub page_index: u32,
+  pub limit: usize,
+  pub more: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct BlockMintsQuery {
+  page: Option<u32>,
+  limit: Option<usize>,
 }
 
-// ord/block/:blockhash/inscriptions
-/// Retrieve the inscription actions from the given block.
+// ord/block/:block/inscriptions
+/// Retrieve a page of the inscriptions created in the given block, addressed
+/// by hash or by height.
 #[utoipa::path(
   get,
-  path = "/api/v1/ord/block/{blockhash}/inscriptions",
+  path = "/api/v1/ord/block/{block}/inscriptions",
   params(
-      ("blockhash" = String, Path, description = "block hash")
+      ("block" = String, Path, description = "block hash, or block height"),
+      ("page" = Option<u32>, Query, description = "page index, starting at 0"),
+      ("limit" = Option<usize>, Query, description = "page size, defaults to `--default-page-size` and reduced to `--max-page-size`"),
 ),
   responses(
-    (status = 200, description = "Obtain inscription actions by blockhash", body = OrdBlockInscriptions),
+    (status = 200, description = "Obtain the inscriptions created in a block.", body = OrdBlock),
     (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
     (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
     (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
   )
 )]
-pub(crate) async fn ord_block_inscriptions(
+pub(crate) async fn ord_block(
   Extension(index): Extension<Arc<Index>>,
-  Path(blockhash): Path<String>,
-) -> ApiResult<ApiBlockInscriptions> {
-  log::debug!("rpc: get ord_block_inscriptions: {}", blockhash);
-
-  let blockhash = bitcoin::BlockHash::from_str(&blockhash).map_err(ApiError::bad_request)?;
-  let rtx = index.begin_read()?;
-  let client = index.bitcoin_rpc_client()?;
-  let index_transactions = index.has_transactions_index();
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Path(block): Path<String>,
+  Query(query): Query<BlockMintsQuery>,
+) -> ApiResult<ApiBlockMints> {
+  let height = if is_block_hash(&block) {
+    let blockhash = bitcoin::BlockHash::from_str(&block).map_err(ApiError::bad_request)?;
 
-  let block_operations = Index::get_ord_block_inscription_operations(blockhash, &rtx, &client)?;
-  log::debug!("rpc: get ord_block_inscriptions: {:?}", block_operations);
-
-  let mut api_block_operations = Vec::new();
-  for (txid, tx_operations) in block_operations.into_iter() {
-    let mut api_tx_operations = Vec::new();
-    for operation in tx_operations.into_iter() {
-      let tx_inscription = ApiTxInscription::parse_from_operation(
-        operation,
-        &rtx,
-        &client,
-        index.get_chain(),
-        index_transactions,
-      )?;
-      api_tx_operations.push(tx_inscription);
-    }
-    if !api_tx_operations.is_empty() {
-      api_block_operations.push(ApiTxInscriptions {
-        inscriptions: api_tx_operations,
-        txid: txid.to_string(),
-      });
+    // The node tells the height of the block, and the index whether the
+    // block at that height is the one it indexed.
+    match index.block_header_info(blockhash)? {
+      Some(info) if index.block_hash(Some(info.height.try_into().unwrap()))? == Some(blockhash) => {
+        info.height.try_into().unwrap()
+      }
+      _ => return Err(ApiError::not_found(format!("block {blockhash} not found"))),
     }
+  } else {
+    block
+      .parse::<u32>()
+      .map_err(|_| ApiError::bad_request(format!("invalid block height or hash `{block}`")))?
+  };
+
+  Ok(Json(ApiResponse::ok(
+    ord_block_mints(index, server_config, height, query).await?,
+  )))
+}
+
+fn is_block_hash(block: &str) -> bool {
+  block.len() == 64 && block.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Retrieve the inscriptions created in the block at the given height.
+async fn ord_block_mints(
+  index: Arc<Index>,
+  server_config: Arc<ServerConfig>,
+  height: u32,
+  query: BlockMintsQuery,
+) -> Result<ApiBlockMints, ApiError> {
+  log::debug!("rpc: get ord_block_mints: {height}");
+
+  if index.block_height()?.map_or(true, |tip| height > tip.n()) {
+    return Err(ApiError::not_found(format!("block {height} not found")));
+  }
+
+  let page_index = query.page.unwrap_or_default();
+
+  let limit = page_size(&server_config, query.limit)?;
+
+  let (ids, more) = index.get_inscriptions_in_height_range_paginated(
+    height,
+    height.saturating_add(1),
+    limit,
+    page_index.try_into().unwrap(),
+    Order::Asc,
+  )?;
+
+  let mut inscriptions = Vec::new();
+  for id in ids {
+    let entry = index
+      .get_inscription_entry(id)?
+      .ok_or(OrdApiError::UnknownInscriptionId(id))?;
+
+    inscriptions.push(ApiBlockMint {
+      id,
+      number: entry.inscription_number,
+      fee: entry.fee,
+    });
   }
 
-  Ok(Json(ApiResponse::ok(ApiBlockInscriptions {
-    block: api_block_operations,
-  })))
+  Ok(ApiBlockMints {
+    height,
+    inscriptions,
+    page_index,
+    limit,
+    more,
+  })
 }
 
 #[cfg(test)]
@@ -305,4 +418,13 @@ mod tests {
 }"#,
     );
   }
+
+  #[test]
+  fn block_hashes_are_told_apart_from_heights() {
+    assert!(is_block_hash(
+      "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"
+    ));
+    assert!(!is_block_hash("840000"));
+    assert!(!is_block_hash(&"z".repeat(64)));
+  }
 }
@@ -0,0 +1,68 @@
+use {
+  super::*,
+  axum::extract::{path::ErrorKind, rejection::PathRejection, FromRequestParts},
+  serde::de::DeserializeOwned,
+};
+
+/// A type taken as a path parameter, named in the error returned when the
+/// parameter is malformed.
+pub(super) trait PathParam: FromStr {
+  const NAME: &'static str;
+}
+
+impl PathParam for InscriptionId {
+  const NAME: &'static str = "inscription id";
+}
+
+impl PathParam for OutPoint {
+  const NAME: &'static str = "outpoint";
+}
+
+impl PathParam for Sat {
+  const NAME: &'static str = "sat";
+}
+
+/// A path parameter that fails to deserialize with `invalid <name>` instead
+/// of the error of the underlying parser.
+pub(super) struct Param<T>(pub(super) T);
+
+impl<'de, T: PathParam> Deserialize<'de> for Param<T> {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    String::deserialize(deserializer)?
+      .parse()
+      .map(Self)
+      .map_err(|_| serde::de::Error::custom(format!("invalid {}", T::NAME)))
+  }
+}
+
+/// Like `Path`, but rejects a malformed `Param` with a `ServerError::BadRequest`
+/// carrying just its message.
+pub(super) struct PathParams<T>(pub(super) T);
+
+#[async_trait::async_trait]
+impl<S, T> FromRequestParts<S> for PathParams<T>
+where
+  S: Send + Sync,
+  T: DeserializeOwned + Send,
+{
+  type Rejection = ServerError;
+
+  async fn from_request_parts(
+    parts: &mut http::request::Parts,
+    state: &S,
+  ) -> Result<Self, Self::Rejection> {
+    match Path::<T>::from_request_parts(parts, state).await {
+      Ok(Path(params)) => Ok(Self(params)),
+      Err(PathRejection::FailedToDeserializePathParams(error)) => {
+        Err(ServerError::BadRequest(match error.kind() {
+          ErrorKind::Message(message) => message.clone(),
+          _ => error.body_text(),
+        }))
+      }
+      Err(rejection) => Err(ServerError::Internal(anyhow!(rejection.body_text()))),
+    }
+  }
+}
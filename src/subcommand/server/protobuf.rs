@@ -0,0 +1,327 @@
+use {
+  super::{
+    error::ApiError,
+    ord::{
+      ApiContentEncoding, ApiContentHash, ApiInscription, ApiInscriptionDigest, ApiOutPointResult,
+      ApiOutpointInscriptions,
+    },
+    types::ScriptPubkey,
+    *,
+  },
+  axum::{extract::FromRef, Json},
+};
+
+mod messages;
+
+pub(crate) const CONTENT_TYPE: &str = "application/x-protobuf";
+
+/// Whether the client asked for a protobuf response, with an `Accept` header
+/// preferring `application/x-protobuf`, rather than JSON.
+pub(crate) struct AcceptProtobuf(pub(crate) bool);
+
+#[async_trait::async_trait]
+impl<S> axum::extract::FromRequestParts<S> for AcceptProtobuf
+where
+  Arc<ServerConfig>: FromRef<S>,
+  S: Send + Sync,
+{
+  type Rejection = (StatusCode, &'static str);
+
+  async fn from_request_parts(
+    parts: &mut http::request::Parts,
+    _state: &S,
+  ) -> Result<Self, Self::Rejection> {
+    accept_json::negotiated();
+
+    Ok(Self(error::prefers_media_type(
+      &parts.headers,
+      CONTENT_TYPE,
+    )))
+  }
+}
+
+impl AcceptProtobuf {
+  /// `response` as protobuf message `M` if the client asked for protobuf,
+  /// and as JSON otherwise.
+  pub(crate) fn respond<T, M>(&self, response: ApiResponse<T>) -> ApiEncoded<T, M>
+  where
+    T: Serialize,
+    M: From<ApiResponse<T>>,
+  {
+    if self.0 {
+      ApiEncoded::Protobuf(response.into())
+    } else {
+      ApiEncoded::Json(response)
+    }
+  }
+}
+
+/// An API response encoded as JSON, or as protobuf message `M`.
+pub(crate) enum ApiEncoded<T: Serialize, M> {
+  Json(ApiResponse<T>),
+  Protobuf(M),
+}
+
+impl<T, M> IntoResponse for ApiEncoded<T, M>
+where
+  T: Serialize,
+  M: prost::Message,
+{
+  fn into_response(self) -> Response {
+    match self {
+      Self::Json(response) => Json(response).into_response(),
+      Self::Protobuf(message) => (
+        [(header::CONTENT_TYPE, HeaderValue::from_static(CONTENT_TYPE))],
+        message.encode_to_vec(),
+      )
+        .into_response(),
+    }
+  }
+}
+
+pub(crate) type ApiEncodedResult<T, M> = Result<ApiEncoded<T, M>, ApiError>;
+
+pub(crate) use messages::{InscriptionResponse, OutPointResultResponse};
+
+impl From<ScriptPubkey> for messages::ScriptPubkey {
+  fn from(script_pubkey: ScriptPubkey) -> Self {
+    use messages::script_pubkey::Kind;
+
+    Self {
+      kind: Some(match script_pubkey {
+        ScriptPubkey::Address(address) => Kind::Address(address),
+        ScriptPubkey::NonStandard(script_hash) => Kind::NonStandard(script_hash),
+      }),
+    }
+  }
+}
+
+impl From<ApiContentEncoding> for messages::ContentEncoding {
+  fn from(content_encoding: ApiContentEncoding) -> Self {
+    match content_encoding {
+      ApiContentEncoding::Br { decode } => Self {
+        r#type: "br".into(),
+        decode: Some(decode),
+      },
+      ApiContentEncoding::Unknown => Self {
+        r#type: "unknown".into(),
+        decode: None,
+      },
+    }
+  }
+}
+
+impl From<ApiInscription> for messages::Inscription {
+  fn from(inscription: ApiInscription) -> Self {
+    Self {
+      id: inscription.id,
+      number: inscription.number,
+      content_type: inscription.content_type,
+      content: inscription.content,
+      content_length: inscription
+        .content_length
+        .map(|length| length.try_into().unwrap()),
+      content_encoding: inscription.content_encoding.map(Into::into),
+      metadata: inscription.metadata,
+      metaprotocol: inscription.metaprotocol,
+      parent: inscription.parent.map(|parent| parent.to_string()),
+      delegate: inscription.delegate.map(|delegate| delegate.to_string()),
+      pointer: inscription.pointer,
+      owner: inscription.owner.map(Into::into),
+      genesis_height: inscription.genesis_height,
+      genesis_timestamp: inscription.genesis_timestamp,
+      confirmations: inscription.confirmations,
+      location: inscription.location,
+      collections: inscription.collections,
+      charms: inscription.charms,
+      sat: inscription.sat,
+      content_hash: inscription.content_hash.map(Into::into),
+      media_type: inscription.media_type,
+      media_category: inscription.media_category.to_string(),
+      parents: inscription
+        .parents
+        .iter()
+        .map(|parent| parent.to_string())
+        .collect(),
+    }
+  }
+}
+
+impl From<ApiContentHash> for messages::ContentHash {
+  fn from(content_hash: ApiContentHash) -> Self {
+    Self {
+      sha256: content_hash.sha256,
+      delegated: content_hash.delegated,
+    }
+  }
+}
+
+impl From<ApiResponse<ApiInscription>> for InscriptionResponse {
+  fn from(response: ApiResponse<ApiInscription>) -> Self {
+    Self {
+      code: response.code,
+      msg: response.msg,
+      data: Some(response.data.into()),
+    }
+  }
+}
+
+impl From<ApiInscriptionDigest> for messages::InscriptionDigest {
+  fn from(digest: ApiInscriptionDigest) -> Self {
+    Self {
+      id: digest.id,
+      number: digest.number,
+      location: digest.location,
+    }
+  }
+}
+
+impl From<ApiOutpointInscriptions> for messages::OutpointInscriptions {
+  fn from(inscriptions: ApiOutpointInscriptions) -> Self {
+    Self {
+      txid: inscriptions.txid,
+      script_pub_key: inscriptions.script_pub_key,
+      owner: Some(inscriptions.owner.into()),
+      value: inscriptions.value,
+      inscription_digest: inscriptions
+        .inscription_digest
+        .into_iter()
+        .map(Into::into)
+        .collect(),
+      address_type: inscriptions.address_type.to_string(),
+    }
+  }
+}
+
+impl From<ApiResponse<ApiOutPointResult>> for OutPointResultResponse {
+  fn from(response: ApiResponse<ApiOutPointResult>) -> Self {
+    Self {
+      code: response.code,
+      msg: response.msg,
+      data: Some(messages::OutPointResult {
+        result: response.data.result.map(Into::into),
+        latest_blockhash: response.data.latest_blockhash,
+        latest_height: response.data.latest_height,
+      }),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*, media::MediaCategory, prost::Message, serde::de::DeserializeOwned, types::AddressType,
+  };
+
+  /// `value` encoded as JSON and decoded again, and encoded as protobuf and
+  /// decoded again, must make the same message.
+  fn assert_round_trips<T, M>(value: T)
+  where
+    T: Serialize + DeserializeOwned + Clone,
+    M: Message + Default + PartialEq + From<ApiResponse<T>>,
+  {
+    let message = M::from(ApiResponse::ok(value.clone()));
+
+    let json = serde_json::from_str::<T>(&serde_json::to_string(&value).unwrap()).unwrap();
+
+    let protobuf = M::decode(message.encode_to_vec().as_slice()).unwrap();
+
+    assert_eq!(M::from(ApiResponse::ok(json)), protobuf);
+    assert_eq!(protobuf, message);
+  }
+
+  #[test]
+  fn inscription_round_trips() {
+    assert_round_trips::<_, InscriptionResponse>(ApiInscription {
+      id: inscription_id(1).to_string(),
+      number: -3,
+      content_type: Some("text/plain;charset=utf-8".into()),
+      media_type: Some("text/plain".into()),
+      media_category: MediaCategory::Text,
+      content: Some("aGVsbG8=".into()),
+      content_length: Some(5),
+      content_encoding: Some(ApiContentEncoding::Br {
+        decode: "hello".into(),
+      }),
+      metadata: None,
+      metaprotocol: Some("brc-20".into()),
+      parent: Some(inscription_id(2)),
+      parents: vec![inscription_id(2), inscription_id(4)],
+      delegate: None,
+      pointer: Some(0),
+      owner: Some(ScriptPubkey::Address(
+        "bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4".into(),
+      )),
+      genesis_height: 800_000,
+      genesis_timestamp: 1_690_000_000,
+      confirmations: 6,
+      location: format!("{}:0:0", txid(1)),
+      collections: Vec::new(),
+      charms: vec!["cursed".into()],
+      sat: Some(u64::MAX),
+      content_hash: Some(ApiContentHash {
+        sha256: "00".repeat(32),
+        delegated: true,
+      }),
+    });
+
+    assert_round_trips::<_, InscriptionResponse>(ApiInscription {
+      id: inscription_id(3).to_string(),
+      number: 0,
+      content_type: None,
+      media_type: None,
+      media_category: MediaCategory::Other,
+      content: None,
+      content_length: None,
+      content_encoding: Some(ApiContentEncoding::Unknown),
+      metadata: None,
+      metaprotocol: None,
+      parent: None,
+      parents: Vec::new(),
+      delegate: None,
+      pointer: None,
+      owner: Some(ScriptPubkey::NonStandard("00".repeat(32))),
+      genesis_height: 0,
+      genesis_timestamp: 0,
+      confirmations: 0,
+      location: String::new(),
+      collections: Vec::new(),
+      charms: Vec::new(),
+      sat: None,
+      content_hash: None,
+    });
+  }
+
+  #[test]
+  fn outpoint_round_trips() {
+    assert_round_trips::<_, OutPointResultResponse>(ApiOutPointResult {
+      result: Some(ApiOutpointInscriptions {
+        txid: txid(1).to_string(),
+        script_pub_key: "OP_0 OP_PUSHBYTES_20 bb1ba8718014b1c284d2b8dd758ff23d26c8cd5b".into(),
+        owner: ScriptPubkey::Address("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4".into()),
+        address_type: AddressType::P2wpkh,
+        value: 10_000,
+        inscription_digest: vec![
+          ApiInscriptionDigest {
+            id: inscription_id(1).to_string(),
+            number: 1,
+            location: format!("{}:0:0", txid(1)),
+          },
+          ApiInscriptionDigest {
+            id: inscription_id(2).to_string(),
+            number: -1,
+            location: format!("{}:0:546", txid(1)),
+          },
+        ],
+      }),
+      latest_blockhash: "00".repeat(32),
+      latest_height: 840_000,
+    });
+
+    assert_round_trips::<_, OutPointResultResponse>(ApiOutPointResult {
+      result: None,
+      latest_blockhash: "00".repeat(32),
+      latest_height: 0,
+    });
+  }
+}
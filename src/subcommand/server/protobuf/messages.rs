@@ -0,0 +1,140 @@
+//! The messages of `ord.proto`, written out as `prost` would generate them so
+//! that building does not need `protoc`. Keep the two in sync.
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct ScriptPubkey {
+  #[prost(oneof = "script_pubkey::Kind", tags = "1, 2")]
+  pub(crate) kind: Option<script_pubkey::Kind>,
+}
+
+pub(crate) mod script_pubkey {
+  #[derive(Clone, PartialEq, prost::Oneof)]
+  pub(crate) enum Kind {
+    #[prost(string, tag = "1")]
+    Address(String),
+    #[prost(string, tag = "2")]
+    NonStandard(String),
+  }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct ContentEncoding {
+  #[prost(string, tag = "1")]
+  pub(crate) r#type: String,
+  #[prost(string, optional, tag = "2")]
+  pub(crate) decode: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct Inscription {
+  #[prost(string, tag = "1")]
+  pub(crate) id: String,
+  #[prost(int32, tag = "2")]
+  pub(crate) number: i32,
+  #[prost(string, optional, tag = "3")]
+  pub(crate) content_type: Option<String>,
+  #[prost(string, optional, tag = "4")]
+  pub(crate) content: Option<String>,
+  #[prost(uint64, optional, tag = "5")]
+  pub(crate) content_length: Option<u64>,
+  #[prost(message, optional, tag = "6")]
+  pub(crate) content_encoding: Option<ContentEncoding>,
+  #[prost(string, optional, tag = "7")]
+  pub(crate) metadata: Option<String>,
+  #[prost(string, optional, tag = "8")]
+  pub(crate) metaprotocol: Option<String>,
+  #[prost(string, optional, tag = "9")]
+  pub(crate) parent: Option<String>,
+  #[prost(string, optional, tag = "10")]
+  pub(crate) delegate: Option<String>,
+  #[prost(uint64, optional, tag = "11")]
+  pub(crate) pointer: Option<u64>,
+  #[prost(message, optional, tag = "12")]
+  pub(crate) owner: Option<ScriptPubkey>,
+  #[prost(uint32, tag = "13")]
+  pub(crate) genesis_height: u32,
+  #[prost(uint32, tag = "14")]
+  pub(crate) genesis_timestamp: u32,
+  #[prost(string, tag = "15")]
+  pub(crate) location: String,
+  #[prost(string, repeated, tag = "16")]
+  pub(crate) collections: Vec<String>,
+  #[prost(string, repeated, tag = "17")]
+  pub(crate) charms: Vec<String>,
+  #[prost(uint64, optional, tag = "18")]
+  pub(crate) sat: Option<u64>,
+  #[prost(message, optional, tag = "19")]
+  pub(crate) content_hash: Option<ContentHash>,
+  #[prost(string, optional, tag = "20")]
+  pub(crate) media_type: Option<String>,
+  #[prost(string, tag = "21")]
+  pub(crate) media_category: String,
+  #[prost(string, repeated, tag = "22")]
+  pub(crate) parents: Vec<String>,
+  #[prost(uint32, tag = "23")]
+  pub(crate) confirmations: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct ContentHash {
+  #[prost(string, tag = "1")]
+  pub(crate) sha256: String,
+  #[prost(bool, tag = "2")]
+  pub(crate) delegated: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct InscriptionResponse {
+  #[prost(int32, tag = "1")]
+  pub(crate) code: i32,
+  #[prost(string, tag = "2")]
+  pub(crate) msg: String,
+  #[prost(message, optional, tag = "3")]
+  pub(crate) data: Option<Inscription>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct InscriptionDigest {
+  #[prost(string, tag = "1")]
+  pub(crate) id: String,
+  #[prost(int32, tag = "2")]
+  pub(crate) number: i32,
+  #[prost(string, tag = "3")]
+  pub(crate) location: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct OutpointInscriptions {
+  #[prost(string, tag = "1")]
+  pub(crate) txid: String,
+  #[prost(string, tag = "2")]
+  pub(crate) script_pub_key: String,
+  #[prost(message, optional, tag = "3")]
+  pub(crate) owner: Option<ScriptPubkey>,
+  #[prost(uint64, tag = "4")]
+  pub(crate) value: u64,
+  #[prost(message, repeated, tag = "5")]
+  pub(crate) inscription_digest: Vec<InscriptionDigest>,
+  #[prost(string, tag = "6")]
+  pub(crate) address_type: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct OutPointResult {
+  #[prost(message, optional, tag = "1")]
+  pub(crate) result: Option<OutpointInscriptions>,
+  #[prost(string, tag = "2")]
+  pub(crate) latest_blockhash: String,
+  #[prost(uint32, tag = "3")]
+  pub(crate) latest_height: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct OutPointResultResponse {
+  #[prost(int32, tag = "1")]
+  pub(crate) code: i32,
+  #[prost(string, tag = "2")]
+  pub(crate) msg: String,
+  #[prost(message, optional, tag = "3")]
+  pub(crate) data: Option<OutPointResult>,
+}
@@ -0,0 +1,56 @@
+use {
+  super::*,
+  axum::{
+    http::{HeaderName, Request},
+    middleware::Next,
+  },
+  std::future::Future,
+  uuid::Uuid,
+};
+
+pub(crate) const X_REQUEST_ID: &str = "x-request-id";
+
+tokio::task_local! {
+  static REQUEST_ID: String;
+}
+
+pub(crate) struct RequestId;
+
+impl RequestId {
+  /// The id of the request currently being served, if any.
+  pub(crate) fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+  }
+
+  /// Run `future` as part of the request with id `request_id`, if any.
+  pub(super) async fn scope<F: Future>(request_id: Option<String>, future: F) -> F::Output {
+    match request_id {
+      Some(request_id) => REQUEST_ID.scope(request_id, future).await,
+      None => future.await,
+    }
+  }
+}
+
+/// Tag every request with an id, taken from the incoming `X-Request-Id` header
+/// if present, otherwise a fresh UUID, and echo it back in the response.
+pub(super) async fn request_id<B>(request: Request<B>, next: Next<B>) -> Response {
+  let request_id = request
+    .headers()
+    .get(X_REQUEST_ID)
+    .and_then(|value| value.to_str().ok())
+    .filter(|value| !value.is_empty())
+    .map(str::to_string)
+    .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+  let mut response = REQUEST_ID
+    .scope(request_id.clone(), next.run(request))
+    .await;
+
+  if let Ok(value) = HeaderValue::from_str(&request_id) {
+    response
+      .headers_mut()
+      .insert(HeaderName::from_static(X_REQUEST_ID), value);
+  }
+
+  response
+}
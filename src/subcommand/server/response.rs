@@ -10,13 +10,34 @@ use {
   ApiBRC20AllBalance = ApiResponse<brc20::ApiBalances>,
   ApiBRC20TxEvents = ApiResponse<brc20::ApiTxEvents>,
   ApiBRC20BlockEvents = ApiResponse<brc20::ApiBlockEvents>,
+  ApiBRC20TickEvents = ApiResponse<brc20::ApiTickEvents>,
   ApiBRC20Transferable = ApiResponse<brc20::ApiTransferableAssets>,
+  ApiBRC20OutPointResult = ApiResponse<brc20::ApiOutPointResult>,
 
   ApiOrdInscription = ApiResponse<ord::ApiInscription>,
+  ApiOrdInscriptionsBatch = ApiResponse<ord::ApiInscriptionsBatch>,
+  ApiOrdInscriptionMetadata = ApiResponse<ord::ApiInscriptionMetadata>,
   ApiOrdOutPointData = ApiResponse<ord::ApiOutpointInscriptions>,
   ApiOrdOutPointResult = ApiResponse<ord::ApiOutPointResult>,
+  ApiOrdOutpoint = ApiResponse<ord::ApiOutpoint>,
+  ApiOrdSatPointInscriptions = ApiResponse<ord::ApiSatPointInscriptions>,
+  ApiOrdOutputsBatch = ApiResponse<ord::ApiOutputsBatch>,
+  ApiOrdAddressInscriptions = ApiResponse<ord::ApiAddressInscriptions>,
   ApiOrdTxInscriptions = ApiResponse<ord::ApiTxInscriptions>,
-  ApiOrdBlockInscriptions = ApiResponse<ord::ApiBlockInscriptions>,
+  ApiOrdBlock = ApiResponse<ord::ApiBlockMints>,
+  ApiOrdMempoolInscriptions = ApiResponse<ord::ApiMempoolInscriptions>,
+  ApiOrdSearchMatches = ApiResponse<ord::ApiSearchMatches>,
+  ApiOrdSat = ApiResponse<ord::ApiSat>,
+  ApiOrdSatPoint = ApiResponse<ord::ApiSatPoint>,
+  ApiOrdCoalescedSatRanges = ApiResponse<ord::ApiCoalescedSatRanges>,
+  ApiOrdSpecialSats = ApiResponse<ord::ApiSpecialSats>,
+
+  ApiRunesBalances = ApiResponse<runes::ApiRuneBalances>,
+  ApiRunesHolders = ApiResponse<runes::ApiRuneHolders>,
+  ApiRunesList = ApiResponse<runes::ApiRunes>,
+  ApiRunesTransfers = ApiResponse<runes::ApiRuneTransfers>,
+
+  ApiSatOutPointResult = ApiResponse<sat::ApiOutPointResult>,
 
   Node = ApiResponse<NodeInfo>
 )]
@@ -0,0 +1,314 @@
+use {
+  super::{error::ApiError, *},
+  axum::body::Bytes,
+  serde::de::DeserializeOwned,
+  serde_json::Value,
+};
+
+/// Batches may contain at most this many calls.
+const MAX_BATCH_SIZE: usize = 100;
+
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+  jsonrpc: String,
+  method: String,
+  #[serde(default)]
+  params: Value,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct RpcError {
+  code: i32,
+  message: String,
+}
+
+impl RpcError {
+  fn new(code: i32, message: impl ToString) -> Self {
+    Self {
+      code,
+      message: message.to_string(),
+    }
+  }
+}
+
+/// Errors of the REST handlers keep their `ApiError` code and message.
+impl From<ApiError> for RpcError {
+  fn from(error: ApiError) -> Self {
+    Self::new(error.code(), error.message())
+  }
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+  jsonrpc: &'static str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  result: Option<Value>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  error: Option<RpcError>,
+  id: Value,
+}
+
+impl RpcResponse {
+  fn new(id: Value, result: Result<Value, RpcError>) -> Self {
+    let (result, error) = match result {
+      Ok(result) => (Some(result), None),
+      Err(error) => (None, Some(error)),
+    };
+
+    Self {
+      jsonrpc: "2.0",
+      result,
+      error,
+      id,
+    }
+  }
+}
+
+/// Deserialize `params`, given either by position or by the names in `names`,
+/// into a tuple.
+fn params<T: DeserializeOwned>(params: Value, names: &[&str]) -> Result<T, RpcError> {
+  let params = match params {
+    Value::Object(mut params) => names
+      .iter()
+      .map(|name| params.remove(*name).unwrap_or_default())
+      .collect(),
+    params => params,
+  };
+
+  serde_json::from_value(params).map_err(|err| RpcError::new(INVALID_PARAMS, err))
+}
+
+/// The `data` of a REST handler response, or its error.
+fn result<T: Serialize>(result: ApiResult<T>) -> Result<Value, RpcError> {
+  let Json(response) = result?;
+  serde_json::to_value(response.data).map_err(|err| ApiError::internal(err).into())
+}
+
+/// The `data` of a REST handler response that may be encoded as protobuf,
+/// called without asking for protobuf.
+fn encoded_result<T: Serialize, M>(encoded: ApiEncodedResult<T, M>) -> Result<Value, RpcError> {
+  let ApiEncoded::Json(response) = encoded? else {
+    unreachable!("protobuf was not requested");
+  };
+  result(Ok(Json(response)))
+}
+
+/// The route group a method belongs to, if any, so that methods of groups
+/// disabled with `--disable-routes` are not served either.
+fn route_group(method: &str) -> Option<RouteGroup> {
+  method.starts_with("brc20_").then_some(RouteGroup::Brc20)
+}
+
+fn method_not_found(method: &str) -> RpcError {
+  RpcError::new(METHOD_NOT_FOUND, format!("method `{method}` not found"))
+}
+
+async fn dispatch(
+  server_config: &ServerConfig,
+  index: Arc<Index>,
+  content_hashes: Arc<ord::ContentHashCache>,
+  transforms: Arc<TransformPool>,
+  method: &str,
+  params: Value,
+) -> Result<Value, RpcError> {
+  if let Some(group) = route_group(method) {
+    if !server_config
+      .route_groups
+      .iter()
+      .any(|enabled| enabled == group.name())
+    {
+      return Err(method_not_found(method));
+    }
+  }
+
+  match method {
+    "ord_getInscription" => {
+      let (id,) = self::params::<(String,)>(params, &["id"])?;
+      encoded_result(
+        ord::ord_inscription(
+          Extension(index),
+          Extension(content_hashes),
+          Extension(transforms),
+          Path(id),
+          Query(FieldsQuery::default()),
+          Query(IncludeQuery::default()),
+          AcceptProtobuf(false),
+        )
+        .await,
+      )
+    }
+    "ord_getOutpoint" => {
+      let (outpoint,) = self::params::<(Param<OutPoint>,)>(params, &["outpoint"])?;
+      encoded_result(
+        ord::ord_outpoint(
+          Extension(index),
+          PathParams(outpoint),
+          AcceptProtobuf(false),
+        )
+        .await,
+      )
+    }
+    "ord_getRuneBalances" => {
+      let (address,) = self::params::<(String,)>(params, &["address"])?;
+      result(runes::runes_balances(Extension(index), Path(address)).await)
+    }
+    "brc20_getBalance" => {
+      let (tick, address) = self::params::<(String, String)>(params, &["tick", "address"])?;
+      result(brc20::brc20_balance(Extension(index), Path((tick, address))).await)
+    }
+    method => Err(method_not_found(method)),
+  }
+}
+
+/// Serve a single call, returning nothing for notifications, which are
+/// well-formed calls without an `id`. Invalid calls are always answered, with
+/// a `null` `id` if they have none.
+async fn call(
+  server_config: &ServerConfig,
+  index: Arc<Index>,
+  content_hashes: Arc<ord::ContentHashCache>,
+  transforms: Arc<TransformPool>,
+  request: Value,
+) -> Option<RpcResponse> {
+  let id = match &request {
+    Value::Object(request) => request.get("id").cloned(),
+    _ => None,
+  };
+
+  let request = match serde_json::from_value::<RpcRequest>(request) {
+    Ok(request) if request.jsonrpc == "2.0" => request,
+    Ok(request) => {
+      return Some(RpcResponse::new(
+        id.unwrap_or_default(),
+        Err(RpcError::new(
+          INVALID_REQUEST,
+          format!("unsupported jsonrpc version `{}`", request.jsonrpc),
+        )),
+      ))
+    }
+    Err(err) => {
+      return Some(RpcResponse::new(
+        id.unwrap_or_default(),
+        Err(RpcError::new(INVALID_REQUEST, err)),
+      ))
+    }
+  };
+
+  let result = dispatch(
+    server_config,
+    index,
+    content_hashes,
+    transforms,
+    &request.method,
+    request.params,
+  )
+  .await;
+
+  id.map(|id| RpcResponse::new(id, result))
+}
+
+/// Serve JSON-RPC 2.0 calls, singly or in batches, by the REST handlers of
+/// the JSON API.
+pub(super) async fn rpc(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  Extension(content_hashes): Extension<Arc<ord::ContentHashCache>>,
+  Extension(transforms): Extension<Arc<TransformPool>>,
+  body: Bytes,
+) -> Response {
+  let request = match serde_json::from_slice::<Value>(&body) {
+    Ok(request) => request,
+    Err(err) => {
+      return Json(RpcResponse::new(
+        Value::Null,
+        Err(RpcError::new(PARSE_ERROR, err)),
+      ))
+      .into_response()
+    }
+  };
+
+  let Value::Array(requests) = request else {
+    return match call(&server_config, index, content_hashes, transforms, request).await {
+      Some(response) => Json(response).into_response(),
+      None => StatusCode::NO_CONTENT.into_response(),
+    };
+  };
+
+  if requests.is_empty() || requests.len() > MAX_BATCH_SIZE {
+    return Json(RpcResponse::new(
+      Value::Null,
+      Err(RpcError::new(
+        INVALID_REQUEST,
+        format!("batch must contain between 1 and {MAX_BATCH_SIZE} calls"),
+      )),
+    ))
+    .into_response();
+  }
+
+  let mut responses = Vec::new();
+
+  for request in requests {
+    responses.extend(
+      call(
+        &server_config,
+        index.clone(),
+        content_hashes.clone(),
+        transforms.clone(),
+        request,
+      )
+      .await,
+    );
+  }
+
+  if responses.is_empty() {
+    StatusCode::NO_CONTENT.into_response()
+  } else {
+    Json(responses).into_response()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn params_are_taken_by_position_or_name() {
+    assert_eq!(
+      params::<(String, String)>(serde_json::json!(["ordi", "foo"]), &["tick", "address"]).unwrap(),
+      ("ordi".into(), "foo".into())
+    );
+    assert_eq!(
+      params::<(String, String)>(
+        serde_json::json!({"address": "foo", "tick": "ordi"}),
+        &["tick", "address"]
+      )
+      .unwrap(),
+      ("ordi".into(), "foo".into())
+    );
+  }
+
+  #[test]
+  fn missing_and_malformed_params_are_invalid() {
+    assert_eq!(
+      params::<(String,)>(Value::Null, &["id"]).unwrap_err().code,
+      INVALID_PARAMS
+    );
+    assert_eq!(
+      params::<(String,)>(serde_json::json!({}), &["id"])
+        .unwrap_err()
+        .code,
+      INVALID_PARAMS
+    );
+    assert_eq!(
+      params::<(Param<OutPoint>,)>(serde_json::json!(["foo"]), &["outpoint"])
+        .map(|_| ())
+        .unwrap_err(),
+      RpcError::new(INVALID_PARAMS, "invalid outpoint")
+    );
+  }
+}
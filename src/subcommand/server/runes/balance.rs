@@ -0,0 +1,98 @@
+use {
+  super::{types::AddressType, *},
+  axum::Json,
+  utoipa::ToSchema,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = runes::Balance)]
+pub struct ApiRuneBalance {
+  /// Name of the rune, including spacers.
+  pub rune: String,
+  /// Rune id, as `block:tx`.
+  pub id: String,
+  /// Balance with the rune's divisibility applied.
+  pub amount: String,
+  /// Balance in the rune's smallest unit.
+  #[schema(format = "uint128")]
+  pub raw_amount: String,
+  /// Number of decimal places of the rune.
+  pub divisibility: u8,
+  /// Currency symbol of the rune.
+  #[schema(value_type = Option<String>)]
+  pub symbol: Option<char>,
+}
+
+impl ApiRuneBalance {
+  pub(crate) fn new(id: RuneId, spaced_rune: SpacedRune, pile: Pile) -> Self {
+    Self {
+      rune: spaced_rune.to_string(),
+      id: format!("{}:{}", id.height, id.index),
+      amount: Pile {
+        symbol: None,
+        ..pile
+      }
+      .to_string(),
+      raw_amount: pile.amount.to_string(),
+      divisibility: pile.divisibility,
+      symbol: pile.symbol,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = runes::Balances)]
+pub struct ApiRuneBalances {
+  /// The queried address.
+  pub address: String,
+  /// The type of the queried address.
+  pub address_type: AddressType,
+  #[schema(value_type = Vec<runes::Balance>)]
+  pub balances: Vec<ApiRuneBalance>,
+}
+
+/// Get all rune balances of the address.
+///
+/// Retrieve the rune balances of all unspent outputs controlled by the address.
+#[utoipa::path(
+    get,
+    path = "/api/v1/runes/balances/{address}",
+    params(
+        ("address" = String, Path, description = "Address")
+  ),
+    responses(
+      (status = 200, description = "Obtain rune balances by query address.", body = RunesBalances),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn runes_balances(
+  Extension(index): Extension<Arc<Index>>,
+  Path(address): Path<String>,
+) -> ApiResult<ApiRuneBalances> {
+  log::debug!("rpc: get runes_balances: {}", address);
+
+  if !index.has_rune_index() {
+    return Err(RunesApiError::RuneIndexNotEnabled.into());
+  }
+
+  let script_pubkey = Address::from_str(&address)
+    .ok()
+    .and_then(|address| address.require_network(index.get_chain().network()).ok())
+    .ok_or_else(|| RunesApiError::InvalidAddress(address.clone()))?
+    .script_pubkey();
+
+  let balances = index
+    .get_rune_balances_for_script_pubkey(&script_pubkey)?
+    .into_iter()
+    .map(|(id, spaced_rune, pile)| ApiRuneBalance::new(id, spaced_rune, pile))
+    .collect();
+
+  Ok(Json(ApiResponse::ok(ApiRuneBalances {
+    address,
+    address_type: AddressType::from_script(&script_pubkey),
+    balances,
+  })))
+}
@@ -0,0 +1,168 @@
+use {
+  super::{types::AddressType, *},
+  axum::Json,
+  utoipa::{IntoParams, ToSchema},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct RuneHoldersQuery {
+  /// Page index, starting at 0.
+  page: Option<usize>,
+  /// Page size. Defaults to `--default-page-size`, and larger sizes are
+  /// reduced to `--max-page-size`.
+  limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = runes::Holder)]
+pub struct ApiRuneHolder {
+  /// Address of the holder, if its script pubkey has one.
+  pub address: Option<String>,
+  /// Hex-encoded script pubkey of the holder.
+  pub script_pubkey: String,
+  /// Type of the script pubkey of the holder.
+  pub address_type: AddressType,
+  /// Balance in the rune's smallest unit.
+  #[schema(format = "uint128")]
+  pub amount: String,
+  /// Share of the rune's unspent supply held, in percent.
+  pub percent: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = runes::Holders)]
+pub struct ApiRuneHolders {
+  /// Name of the rune, including spacers.
+  pub rune: String,
+  /// Rune id, as `block:tx`.
+  pub id: String,
+  /// Number of decimal places of the rune.
+  pub divisibility: u8,
+  /// Total number of holders.
+  pub holder_count: usize,
+  #[schema(value_type = Vec<runes::Holder>)]
+  pub holders: Vec<ApiRuneHolder>,
+  pub page_index: usize,
+  pub limit: usize,
+  pub more: bool,
+}
+
+struct RuneHolders {
+  holders: Vec<(ScriptBuf, u128)>,
+  supply: u128,
+}
+
+/// Holders of each rune, aggregated at the chain tip `blockhash`. Every
+/// aggregation is dropped once a new block is indexed.
+#[derive(Default)]
+pub(crate) struct RuneHoldersCache {
+  state: Mutex<(Option<BlockHash>, HashMap<RuneId, Arc<RuneHolders>>)>,
+}
+
+impl RuneHoldersCache {
+  fn get(&self, index: &Index, id: RuneId) -> Result<Arc<RuneHolders>> {
+    let blockhash = index.block_hash(None)?;
+
+    {
+      let mut state = self.state.lock().unwrap();
+
+      if state.0 != blockhash {
+        *state = (blockhash, HashMap::new());
+      }
+
+      if let Some(holders) = state.1.get(&id) {
+        return Ok(holders.clone());
+      }
+    }
+
+    let holders = index.get_rune_holders(id)?;
+
+    let holders = Arc::new(RuneHolders {
+      supply: holders.iter().map(|(_, amount)| amount).sum(),
+      holders,
+    });
+
+    let mut state = self.state.lock().unwrap();
+
+    if state.0 == blockhash {
+      state.1.insert(id, holders.clone());
+    }
+
+    Ok(holders)
+  }
+}
+
+/// Get the holders of a rune.
+///
+/// Retrieve the unspent balance of a rune held by each address, largest first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/runes/{rune}/holders",
+    params(
+        ("rune" = String, Path, description = "Rune name, with or without spacers"),
+        RuneHoldersQuery,
+  ),
+    responses(
+      (status = 200, description = "Obtain rune holders by balance.", body = RunesHolders),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Rune not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn runes_holders(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(cache): Extension<Arc<RuneHoldersCache>>,
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Path(rune): Path<String>,
+  Query(query): Query<RuneHoldersQuery>,
+) -> ApiResult<ApiRuneHolders> {
+  log::debug!("rpc: get runes_holders: {} {:?}", rune, query);
+
+  if !index.has_rune_index() {
+    return Err(RunesApiError::RuneIndexNotEnabled.into());
+  }
+
+  let limit = page_size(&server_config, query.limit)?;
+
+  let spaced_rune =
+    SpacedRune::from_str(&rune).map_err(|_| RunesApiError::InvalidRune(rune.clone()))?;
+
+  let (id, entry, _parent) = index
+    .rune(spaced_rune.rune)?
+    .ok_or(RunesApiError::UnknownRune(rune))?;
+
+  let RuneHolders { holders, supply } = &*cache.get(&index, id)?;
+
+  let chain = index.get_chain();
+
+  let page_index = query.page.unwrap_or_default();
+
+  let start = page_index.saturating_mul(limit).min(holders.len());
+
+  let end = start.saturating_add(limit).min(holders.len());
+
+  Ok(Json(ApiResponse::ok(ApiRuneHolders {
+    rune: entry.spaced_rune().to_string(),
+    id: format!("{}:{}", id.height, id.index),
+    divisibility: entry.divisibility,
+    holder_count: holders.len(),
+    holders: holders[start..end]
+      .iter()
+      .map(|(script_pubkey, amount)| ApiRuneHolder {
+        address: chain
+          .address_from_script(script_pubkey)
+          .ok()
+          .map(|address| address.to_string()),
+        script_pubkey: script_pubkey.to_hex_string(),
+        address_type: AddressType::from_script(script_pubkey),
+        amount: amount.to_string(),
+        percent: *amount as f64 / *supply as f64 * 100.0,
+      })
+      .collect(),
+    page_index,
+    limit,
+    more: end < holders.len(),
+  })))
+}
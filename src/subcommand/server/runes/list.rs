@@ -0,0 +1,156 @@
+use {
+  super::*,
+  axum::Json,
+  utoipa::{IntoParams, ToSchema},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum RuneSort {
+  /// Most recently etched first.
+  Timestamp,
+  /// Largest supply first.
+  Supply,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct RunesQuery {
+  /// Page index, starting at 0.
+  page: Option<usize>,
+  /// Page size. Defaults to `--default-page-size`, and larger sizes are
+  /// reduced to `--max-page-size`.
+  limit: Option<usize>,
+  /// Only list runes whose name contains this, with or without spacers.
+  q: Option<String>,
+  /// Sort order, `timestamp` or `supply`. Runes are listed in etching order
+  /// by default.
+  sort: Option<RuneSort>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = runes::Rune)]
+pub struct ApiRune {
+  /// Name of the rune, including spacers.
+  pub rune: String,
+  /// Rune id, as `block:tx`.
+  pub id: String,
+  /// Etching number of the rune.
+  #[schema(format = "uint64")]
+  pub number: u64,
+  /// Number of decimal places of the rune.
+  pub divisibility: u8,
+  /// Currency symbol of the rune.
+  #[schema(value_type = Option<String>)]
+  pub symbol: Option<char>,
+  /// Supply in the rune's smallest unit.
+  #[schema(format = "uint128")]
+  pub supply: String,
+  /// Amount burned, in the rune's smallest unit.
+  #[schema(format = "uint128")]
+  pub burned: String,
+  /// Number of mints of the rune.
+  #[schema(format = "uint64")]
+  pub mints: u64,
+  /// The transaction that etched the rune.
+  pub etching: String,
+  /// Timestamp of the block the rune was etched in.
+  #[schema(format = "uint32")]
+  pub timestamp: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = runes::Runes)]
+pub struct ApiRunes {
+  #[schema(value_type = Vec<runes::Rune>)]
+  pub runes: Vec<ApiRune>,
+  pub page_index: usize,
+  pub limit: usize,
+  pub more: bool,
+}
+
+/// The name of a rune without spacers, so `q` matches either form.
+fn unspaced(name: &str) -> String {
+  name
+    .chars()
+    .filter(|c| *c != '•' && *c != '.')
+    .flat_map(char::to_uppercase)
+    .collect()
+}
+
+/// List runes.
+///
+/// Retrieve etched runes, a page at a time, optionally filtered by name.
+#[utoipa::path(
+    get,
+    path = "/api/v1/runes",
+    params(
+        RunesQuery,
+  ),
+    responses(
+      (status = 200, description = "Obtain a page of runes.", body = RunesList),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn runes_list(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Query(query): Query<RunesQuery>,
+) -> ApiResult<ApiRunes> {
+  log::debug!("rpc: get runes_list: {:?}", query);
+
+  if !index.has_rune_index() {
+    return Err(RunesApiError::RuneIndexNotEnabled.into());
+  }
+
+  let limit = page_size(&server_config, query.limit)?;
+
+  let mut entries = index.runes()?;
+
+  if let Some(q) = query.q.as_deref().map(unspaced) {
+    entries.retain(|(_id, entry)| entry.rune.to_string().contains(&q));
+  }
+
+  match query.sort {
+    Some(RuneSort::Timestamp) => {
+      entries.sort_by_key(|(id, entry)| cmp::Reverse((entry.timestamp, *id)))
+    }
+    Some(RuneSort::Supply) => entries.sort_by_key(|(_id, entry)| cmp::Reverse(entry.supply)),
+    None => {}
+  }
+
+  let page_index = query.page.unwrap_or_default();
+
+  let mut runes = entries
+    .into_iter()
+    .skip(page_index.saturating_mul(limit))
+    .take(limit.saturating_add(1))
+    .map(|(id, entry)| ApiRune {
+      rune: entry.spaced_rune().to_string(),
+      id: format!("{}:{}", id.height, id.index),
+      number: entry.number,
+      divisibility: entry.divisibility,
+      symbol: entry.symbol,
+      supply: entry.supply.to_string(),
+      burned: entry.burned.to_string(),
+      mints: entry.mints,
+      etching: entry.etching.to_string(),
+      timestamp: entry.timestamp,
+    })
+    .collect::<Vec<ApiRune>>();
+
+  let more = runes.len() > limit;
+
+  if more {
+    runes.pop();
+  }
+
+  Ok(Json(ApiResponse::ok(ApiRunes {
+    runes,
+    page_index,
+    limit,
+    more,
+  })))
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+mod balance;
+mod holders;
+mod list;
+mod transfer;
+
+pub(super) use {balance::*, holders::*, list::*, transfer::*};
+
+#[derive(Debug, thiserror::Error)]
+pub(super) enum RunesApiError {
+  /// Thrown when the address is malformed or belongs to another network
+  #[error("invalid address {0}")]
+  InvalidAddress(String),
+  /// Thrown when the rune name is malformed
+  #[error("invalid rune {0}")]
+  InvalidRune(String),
+  /// Thrown when the rune has not been etched
+  #[error("rune {0} not found")]
+  UnknownRune(String),
+  /// Thrown when rune balances were requested but the server does not index runes
+  #[error("rune index is not enabled")]
+  RuneIndexNotEnabled,
+}
+
+impl From<RunesApiError> for ApiError {
+  fn from(error: RunesApiError) -> Self {
+    match error {
+      RunesApiError::InvalidAddress(_) => Self::bad_request(error.to_string()),
+      RunesApiError::InvalidRune(_) => Self::bad_request(error.to_string()),
+      RunesApiError::UnknownRune(_) => Self::not_found(error.to_string()),
+      RunesApiError::RuneIndexNotEnabled => Self::bad_request(error.to_string()),
+    }
+  }
+}
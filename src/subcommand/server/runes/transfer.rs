@@ -0,0 +1,178 @@
+use {
+  super::*,
+  axum::Json,
+  utoipa::{IntoParams, ToSchema},
+};
+
+const RUNE_TRANSFERS_MAX_SPAN: u32 = 1_000;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct RuneTransfersQuery {
+  /// First block height to include.
+  from_height: Option<u32>,
+  /// Block height to stop before, at most 1000 blocks after `from_height`.
+  to_height: Option<u32>,
+  /// Page index, starting at 0.
+  page: Option<usize>,
+  /// Page size. Defaults to `--default-page-size`, and larger sizes are
+  /// reduced to `--max-page-size`.
+  limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = runes::Allocation)]
+pub struct ApiRuneAllocation {
+  /// The output holding the balance.
+  pub outpoint: String,
+  /// Balance in the rune's smallest unit.
+  #[schema(format = "uint128")]
+  pub amount: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = runes::Transfer)]
+pub struct ApiRuneTransfer {
+  /// The height of the block the transfer occurred in.
+  #[schema(format = "uint32")]
+  pub height: u32,
+  /// The transaction the transfer occurred in.
+  pub txid: String,
+  /// Whether the transaction etched the rune.
+  pub etching: bool,
+  /// Balances of the spent outputs that held the rune.
+  #[schema(value_type = Vec<runes::Allocation>)]
+  pub inputs: Vec<ApiRuneAllocation>,
+  /// Balances assigned to the transaction's outputs.
+  #[schema(value_type = Vec<runes::Allocation>)]
+  pub outputs: Vec<ApiRuneAllocation>,
+  /// Amount etched or minted, in the rune's smallest unit.
+  #[schema(format = "uint128")]
+  pub minted: String,
+  /// Amount burned, in the rune's smallest unit.
+  #[schema(format = "uint128")]
+  pub burned: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = runes::Transfers)]
+pub struct ApiRuneTransfers {
+  /// Name of the rune, including spacers.
+  pub rune: String,
+  /// Rune id, as `block:tx`.
+  pub id: String,
+  /// Number of decimal places of the rune.
+  pub divisibility: u8,
+  #[schema(value_type = Vec<runes::Transfer>)]
+  pub transfers: Vec<ApiRuneTransfer>,
+  pub page_index: usize,
+  pub limit: usize,
+  pub more: bool,
+}
+
+/// Get rune transfers by height range.
+///
+/// Retrieve every etching, mint, transfer and burn of a rune, in chain order.
+#[utoipa::path(
+    get,
+    path = "/api/v1/runes/{rune}/transfers",
+    params(
+        ("rune" = String, Path, description = "Rune name, with or without spacers"),
+        RuneTransfersQuery,
+  ),
+    responses(
+      (status = 200, description = "Obtain rune transfers by height range.", body = RunesTransfers),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Rune not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn runes_transfers(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Path(rune): Path<String>,
+  Query(query): Query<RuneTransfersQuery>,
+) -> ApiResult<ApiRuneTransfers> {
+  log::debug!("rpc: get runes_transfers: {} {:?}", rune, query);
+
+  if !index.has_rune_index() {
+    return Err(RunesApiError::RuneIndexNotEnabled.into());
+  }
+
+  let (Some(from_height), Some(to_height)) = (query.from_height, query.to_height) else {
+    return Err(ApiError::bad_request(
+      "from_height and to_height are required",
+    ));
+  };
+
+  if from_height >= to_height {
+    return Err(ApiError::bad_request(
+      "from_height must be less than to_height",
+    ));
+  }
+
+  if to_height - from_height > RUNE_TRANSFERS_MAX_SPAN {
+    return Err(ApiError::bad_request(format!(
+      "height range may span at most {RUNE_TRANSFERS_MAX_SPAN} blocks"
+    )));
+  }
+
+  let spaced_rune =
+    SpacedRune::from_str(&rune).map_err(|_| RunesApiError::InvalidRune(rune.clone()))?;
+
+  let (id, entry, _parent) = index
+    .rune(spaced_rune.rune)?
+    .ok_or(RunesApiError::UnknownRune(rune))?;
+
+  let page_index = query.page.unwrap_or_default();
+
+  let limit = page_size(&server_config, query.limit)?;
+
+  let mut transfers = index
+    .get_rune_transfers(id, from_height, to_height)?
+    .into_iter()
+    .skip(page_index.saturating_mul(limit))
+    .take(limit.saturating_add(1))
+    .map(|(height, transfer)| ApiRuneTransfer {
+      height,
+      txid: transfer.txid.to_string(),
+      etching: transfer.etching,
+      inputs: transfer
+        .inputs
+        .into_iter()
+        .map(|(outpoint, amount)| ApiRuneAllocation {
+          outpoint: outpoint.to_string(),
+          amount: amount.to_string(),
+        })
+        .collect(),
+      outputs: transfer
+        .outputs
+        .into_iter()
+        .map(|(vout, amount)| ApiRuneAllocation {
+          outpoint: OutPoint::new(transfer.txid, vout).to_string(),
+          amount: amount.to_string(),
+        })
+        .collect(),
+      minted: transfer.minted.to_string(),
+      burned: transfer.burned.to_string(),
+    })
+    .collect::<Vec<ApiRuneTransfer>>();
+
+  let more = transfers.len() > limit;
+
+  if more {
+    transfers.pop();
+  }
+
+  Ok(Json(ApiResponse::ok(ApiRuneTransfers {
+    rune: entry.spaced_rune().to_string(),
+    id: format!("{}:{}", id.height, id.index),
+    divisibility: entry.divisibility,
+    transfers,
+    page_index,
+    limit,
+    more,
+  })))
+}
@@ -5,8 +5,10 @@ use {
 };
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = sat::ApiOutPointResult)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiOutPointResult {
+  #[schema(value_type = Option<sat::ApiSatRanges>)]
   pub result: Option<ApiSatRanges>,
   pub latest_blockhash: String,
   #[schema(format = "uint64")]
@@ -14,15 +16,19 @@ pub struct ApiOutPointResult {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = sat::ApiSatRanges)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiSatRanges {
-  /// The transaction id.
+  /// The outpoint.
+  #[schema(value_type = String)]
   pub outpoint: OutPoint,
-  /// The script pubkey.
+  /// The sat ranges of the outpoint.
+  #[schema(value_type = Vec<sat::ApiSatRange>)]
   pub sat_ranges: Vec<ApiSatRange>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = sat::ApiSatRange)]
 #[serde(rename_all = "camelCase")]
 #[serde(untagged)]
 pub enum ApiSatRange {
@@ -31,15 +37,19 @@ pub enum ApiSatRange {
   ExactWithRarity {
     first: u64,
     last: u64,
+    #[schema(value_type = Vec<sat::RaritySat>)]
     rarity_sats: Vec<RaritySat>,
   },
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = sat::RaritySat)]
 #[serde(rename_all = "camelCase")]
 pub struct RaritySat {
+  #[schema(value_type = u64)]
   pub sat: Sat,
   pub offset: u64,
+  #[schema(value_type = String)]
   pub rarity: Rarity,
 }
 
@@ -52,7 +62,7 @@ pub struct RaritySat {
         ("outpoint" = String, Path, description = "Outpoint")
   ),
     responses(
-      (status = 200, description = "Obtain outpoint infomation", body = OrdOutPointData),
+      (status = 200, description = "Obtain outpoint infomation", body = SatOutPointResult),
       (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
       (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
       (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
@@ -61,7 +71,7 @@ pub struct RaritySat {
 
 pub(crate) async fn sat_range_by_outpoint(
   Extension(index): Extension<Arc<Index>>,
-  Path(outpoint): Path<OutPoint>,
+  PathParams(Param(outpoint)): PathParams<Param<OutPoint>>,
 ) -> ApiResult<ApiOutPointResult> {
   log::debug!("rpc: get sat_outpoint_sat_range: {outpoint}");
 
@@ -83,12 +93,58 @@ pub(crate) async fn sat_range_by_outpoint(
   })))
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct RarityQuery {
+  rarity: Option<String>,
+}
+
+impl RarityQuery {
+  /// The comma separated rarities to filter by, if any were requested.
+  fn rarities(&self) -> Result<Option<Vec<Rarity>>, ApiError> {
+    self
+      .rarity
+      .as_deref()
+      .map(|rarities| {
+        rarities
+          .split(',')
+          .map(|rarity| Rarity::from_str(rarity.trim()).map_err(ApiError::bad_request))
+          .collect()
+      })
+      .transpose()
+  }
+}
+
+// /sat/outpoint/:outpoint/rarity
+/// Retrieve the sat ranges of the outpoint with the rare sats they contain.
+#[utoipa::path(
+    get,
+    path = "/api/v1/sat/outpoint/{outpoint}/rarity",
+    params(
+        ("outpoint" = String, Path, description = "Outpoint"),
+        ("rarity" = Option<String>, Query, description = "Comma separated rarities to list, requires `--index-sats`"),
+  ),
+    responses(
+      (status = 200, description = "Obtain the sat ranges of the outpoint with their rare sats", body = SatOutPointResult),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
 pub(crate) async fn sat_range_with_rarity_by_outpoint(
   Extension(index): Extension<Arc<Index>>,
-  Path(outpoint): Path<OutPoint>,
+  PathParams(Param(outpoint)): PathParams<Param<OutPoint>>,
+  Query(query): Query<RarityQuery>,
 ) -> ApiResult<ApiOutPointResult> {
   log::debug!("rpc: get sat_outpoint_sat_range: {outpoint}");
 
+  let rarities = query.rarities()?;
+
+  if rarities.is_some() && !index.has_sat_index() {
+    return Err(ApiError::bad_request(
+      "filtering by rarity requires an index built with --index-sats",
+    ));
+  }
+
   let rtx = index.begin_read()?;
 
   let (latest_height, latest_blockhash) = rtx.latest_block()?.ok_or_api_err(|| {
@@ -103,33 +159,53 @@ pub(crate) async fn sat_range_with_rarity_by_outpoint(
     })));
   };
 
+  Ok(Json(ApiResponse::ok(ApiOutPointResult {
+    result: Some(ApiSatRanges {
+      outpoint,
+      sat_ranges: exact_sat_ranges(sat_ranges, rarities.as_deref()),
+    }),
+    latest_height: latest_height.n(),
+    latest_blockhash: latest_blockhash.to_string(),
+  })))
+}
+
+/// Annotate sat ranges with the rare sats they contain. When `rarities` is
+/// given, only rare sats of those rarities are listed and ranges without any
+/// matching sat are dropped. Common sats are never listed one by one, so a
+/// `common` filter keeps every range that has at least one common sat.
+fn exact_sat_ranges(sat_ranges: Vec<(u64, u64)>, rarities: Option<&[Rarity]>) -> Vec<ApiSatRange> {
   let mut exact_sat_ranges = Vec::new();
   let mut value = 0;
   for sat_range in sat_ranges {
-    let rarity_sats = Index::calculate_rarity_for_sat_range(sat_range)
+    let all_rarity_sats = Index::calculate_rarity_for_sat_range(sat_range);
+
+    let has_common_sats = sat_range.1 - sat_range.0 > all_rarity_sats.len() as u64;
+
+    let rarity_sats = all_rarity_sats
       .into_iter()
+      .filter(|(_sat, rarity)| rarities.map_or(true, |rarities| rarities.contains(rarity)))
       .map(|(sat, rarity)| RaritySat {
         sat,
         offset: sat.0 - sat_range.0 + value,
         rarity,
       })
-      .collect();
+      .collect::<Vec<RaritySat>>();
+
+    value += sat_range.1 - sat_range.0;
+
+    if let Some(rarities) = rarities {
+      if rarity_sats.is_empty() && !(has_common_sats && rarities.contains(&Rarity::Common)) {
+        continue;
+      }
+    }
+
     exact_sat_ranges.push(ApiSatRange::ExactWithRarity {
       first: sat_range.0,
       last: sat_range.1,
       rarity_sats,
     });
-    value += sat_range.1 - sat_range.0;
   }
-
-  Ok(Json(ApiResponse::ok(ApiOutPointResult {
-    result: Some(ApiSatRanges {
-      outpoint,
-      sat_ranges: exact_sat_ranges,
-    }),
-    latest_height: latest_height.n(),
-    latest_blockhash: latest_blockhash.to_string(),
-  })))
+  exact_sat_ranges
 }
 
 #[cfg(test)]
@@ -200,4 +276,135 @@ mod tests {
 }"##
     );
   }
+
+  #[test]
+  fn rarity_query_rejects_unknown_rarities() {
+    let query = |rarity: &str| {
+      RarityQuery {
+        rarity: Some(rarity.into()),
+      }
+      .rarities()
+    };
+
+    assert_eq!(RarityQuery::default().rarities().unwrap(), None);
+    assert_eq!(
+      query("uncommon,rare").unwrap(),
+      Some(vec![Rarity::Uncommon, Rarity::Rare])
+    );
+    assert!(query("uncommon,shiny").is_err());
+    assert!(query("").is_err());
+  }
+
+  #[test]
+  fn exact_sat_ranges_filter_by_rarity() {
+    let first_sat_of_block = Height(1).starting_sat().0;
+    let halving = Height(SUBSIDY_HALVING_INTERVAL).starting_sat().0;
+
+    let sat_ranges = vec![
+      (first_sat_of_block, first_sat_of_block + 10),
+      (halving - 10, halving + 10),
+      (first_sat_of_block + 10, first_sat_of_block + 20),
+    ];
+
+    assert_eq!(
+      exact_sat_ranges(sat_ranges.clone(), Some(&[Rarity::Uncommon])),
+      vec![ApiSatRange::ExactWithRarity {
+        first: first_sat_of_block,
+        last: first_sat_of_block + 10,
+        rarity_sats: vec![RaritySat {
+          sat: Sat(first_sat_of_block),
+          offset: 0,
+          rarity: Rarity::Uncommon,
+        }],
+      }]
+    );
+
+    assert_eq!(
+      exact_sat_ranges(sat_ranges.clone(), Some(&[Rarity::Epic, Rarity::Rare])),
+      vec![ApiSatRange::ExactWithRarity {
+        first: halving - 10,
+        last: halving + 10,
+        rarity_sats: vec![RaritySat {
+          sat: Sat(halving),
+          offset: 20,
+          rarity: Rarity::Epic,
+        }],
+      }]
+    );
+
+    assert_eq!(
+      exact_sat_ranges(sat_ranges.clone(), Some(&[Rarity::Common])).len(),
+      3
+    );
+
+    assert!(exact_sat_ranges(sat_ranges.clone(), Some(&[Rarity::Mythic])).is_empty());
+
+    assert_eq!(exact_sat_ranges(sat_ranges, None).len(), 3);
+  }
+}
+
+// /r/sat/:sat_number
+/// Retrieve the ids of the inscriptions on a sat, 100 per page, in the order
+/// they were inscribed.
+#[utoipa::path(
+    get,
+    path = "/r/sat/{sat_number}",
+    params(
+        ("sat_number" = u64, Path, description = "Sat number"),
+        ("page" = Option<u64>, Query, description = "Page index, 0 if absent"),
+  ),
+    responses(
+      (status = 200, description = "Obtain the inscription ids on the sat.", body = SatInscriptionsJson),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "The sat was never inscribed, or there is no sat index.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn sat_inscriptions(
+  Extension(index): Extension<Arc<Index>>,
+  PathParams(Param(sat)): PathParams<Param<Sat>>,
+  Query(query): Query<PageQuery>,
+) -> ServerResult<Json<SatInscriptionsJson>> {
+  sat_inscriptions_paginated(
+    Extension(index),
+    PathParams((Param(sat), query.page.unwrap_or(0))),
+  )
+  .await
+}
+
+// /r/sat/:sat_number/:page
+/// Retrieve a page of the ids of the inscriptions on a sat.
+#[utoipa::path(
+    get,
+    path = "/r/sat/{sat_number}/{page}",
+    params(
+        ("sat_number" = u64, Path, description = "Sat number"),
+        ("page" = u64, Path, description = "Page index"),
+  ),
+    responses(
+      (status = 200, description = "Obtain the inscription ids on the sat.", body = SatInscriptionsJson),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "The sat was never inscribed, or there is no sat index.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn sat_inscriptions_paginated(
+  Extension(index): Extension<Arc<Index>>,
+  PathParams((Param(sat), page)): PathParams<(Param<Sat>, u64)>,
+) -> ServerResult<Json<SatInscriptionsJson>> {
+  if !index.has_sat_index() {
+    return Err(ServerError::NotFound(
+      "this server has no sat index".to_string(),
+    ));
+  }
+
+  let (ids, more) = index.get_inscription_ids_by_sat_paginated(sat, 100, page)?;
+
+  if page == 0 && ids.is_empty() {
+    return Err(ServerError::NotFound(format!(
+      "inscriptions on sat {sat} not found"
+    )));
+  }
+
+  Ok(Json(SatInscriptionsJson { ids, more, page }))
 }
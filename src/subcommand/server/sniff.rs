@@ -0,0 +1,111 @@
+/// Sniffed content is served with its stored content type, if any, in this
+/// header.
+pub(super) const X_ORIGINAL_CONTENT_TYPE: &str = "x-original-content-type";
+
+/// Content types are sniffed from at most this many leading bytes of content.
+const SNIFF_LIMIT: usize = 512;
+
+/// Whether a stored content type is missing or obviously wrong, in which case
+/// the content type is sniffed from the content instead.
+pub(super) fn needs_sniffing(content_type: Option<&str>) -> bool {
+  match content_type {
+    None => true,
+    Some(content_type) => {
+      content_type
+        .trim()
+        .eq_ignore_ascii_case("application/octet-stream")
+        || content_type.parse::<mime::Mime>().is_err()
+    }
+  }
+}
+
+/// The content type of `body`, judged by its leading bytes, if it is one of
+/// the few formats browsers render that can be recognized reliably.
+pub(super) fn sniff(body: &[u8]) -> Option<&'static str> {
+  let head = &body[..body.len().min(SNIFF_LIMIT)];
+
+  if head.starts_with(b"\x89PNG\r\n\x1a\n") {
+    return Some("image/png");
+  }
+
+  if head.starts_with(b"\xff\xd8\xff") {
+    return Some("image/jpeg");
+  }
+
+  if head.starts_with(b"GIF87a") || head.starts_with(b"GIF89a") {
+    return Some("image/gif");
+  }
+
+  if head.len() >= 12 && head.starts_with(b"RIFF") && &head[8..12] == b"WEBP" {
+    return Some("image/webp");
+  }
+
+  let text = head.strip_prefix(b"\xef\xbb\xbf").unwrap_or(head);
+
+  let text = text[text
+    .iter()
+    .position(|byte| !byte.is_ascii_whitespace())
+    .unwrap_or(text.len())..]
+    .to_ascii_lowercase();
+
+  if text.starts_with(b"<!doctype html") || text.starts_with(b"<html") {
+    return Some("text/html;charset=utf-8");
+  }
+
+  if text.starts_with(b"<svg")
+    || (text.starts_with(b"<?xml") && text.windows(4).any(|window| window == b"<svg"))
+  {
+    return Some("image/svg+xml");
+  }
+
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn missing_invalid_and_generic_content_types_are_sniffed() {
+    assert!(needs_sniffing(None));
+    assert!(needs_sniffing(Some("")));
+    assert!(needs_sniffing(Some("foo")));
+    assert!(needs_sniffing(Some("application/octet-stream")));
+    assert!(!needs_sniffing(Some("text/plain;charset=utf-8")));
+    assert!(!needs_sniffing(Some("image/png")));
+  }
+
+  #[test]
+  fn magic_bytes_are_recognized() {
+    assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\0\0"), Some("image/png"));
+    assert_eq!(sniff(b"\xff\xd8\xff\xe0"), Some("image/jpeg"));
+    assert_eq!(sniff(b"GIF89a"), Some("image/gif"));
+    assert_eq!(sniff(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+    assert_eq!(
+      sniff(b"\xef\xbb\xbf \n<!DOCTYPE html><html>"),
+      Some("text/html;charset=utf-8")
+    );
+    assert_eq!(sniff(b"<HTML>"), Some("text/html;charset=utf-8"));
+    assert_eq!(sniff(b"<svg xmlns=\"x\"/>"), Some("image/svg+xml"));
+    assert_eq!(
+      sniff(b"<?xml version=\"1.0\"?>\n<svg/>"),
+      Some("image/svg+xml")
+    );
+  }
+
+  #[test]
+  fn unrecognized_content_is_not_sniffed() {
+    assert_eq!(sniff(b""), None);
+    assert_eq!(sniff(b"hello"), None);
+    assert_eq!(sniff(b"RIFF\0\0\0\0WAVE"), None);
+    assert_eq!(sniff(b"<?xml version=\"1.0\"?><rss/>"), None);
+  }
+
+  #[test]
+  fn sniffing_is_bounded() {
+    let mut body = b"<?xml version=\"1.0\"?>".to_vec();
+    body.resize(SNIFF_LIMIT, b' ');
+    body.extend(b"<svg/>");
+    assert_eq!(sniff(&body), None);
+  }
+}
@@ -0,0 +1,287 @@
+use {
+  super::{
+    error::{ApiError, ErrorFormat},
+    request_id::RequestId,
+    *,
+  },
+  axum::{extract::MatchedPath, http::Request, middleware::Next},
+  tracing::Instrument,
+};
+
+/// A timeout for requests to one route, given as `<ROUTE>=<SECS>`, where
+/// `<ROUTE>` is a route template like `/api/v1/inscriptions/batch`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RouteTimeout {
+  route: String,
+  timeout: Duration,
+}
+
+impl FromStr for RouteTimeout {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let (route, secs) = s
+      .rsplit_once('=')
+      .ok_or_else(|| anyhow!("route timeout `{s}` must be of the form <ROUTE>=<SECS>"))?;
+
+    if !route.starts_with('/') {
+      bail!("route `{route}` must start with `/`");
+    }
+
+    Ok(Self {
+      route: route.into(),
+      timeout: Duration::from_secs(secs.parse()?),
+    })
+  }
+}
+
+/// When the request being served times out, for handlers that wait for
+/// something to happen, so that they can give up and answer before then.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Deadline(pub(super) tokio::time::Instant);
+
+/// The timeout of requests to each route.
+pub(super) struct Timeouts {
+  default: Duration,
+  routes: HashMap<String, Duration>,
+}
+
+impl Timeouts {
+  pub(super) fn new(default: Duration, overrides: &[RouteTimeout]) -> Self {
+    Self {
+      default,
+      routes: overrides
+        .iter()
+        .map(|route_timeout| (route_timeout.route.clone(), route_timeout.timeout))
+        .collect(),
+    }
+  }
+
+  fn get(&self, route: Option<&str>) -> Duration {
+    route
+      .and_then(|route| self.routes.get(route))
+      .copied()
+      .unwrap_or(self.default)
+  }
+}
+
+/// Sets the cancel flag of a request's index queries when dropped, whether
+/// the request timed out or its client went away.
+struct Cancel(Arc<AtomicBool>);
+
+impl Drop for Cancel {
+  fn drop(&mut self) {
+    self.0.store(true, atomic::Ordering::Relaxed);
+  }
+}
+
+/// Fail requests that are not served within the timeout of their route with a
+/// 504.
+///
+/// Handlers read the index synchronously, so each request is served on a
+/// thread of the blocking pool, leaving the timer free to fire while a query
+/// runs. Once a request is given up on, its cancel flag is set, so that long
+/// queries stop between rows and release their read transaction.
+pub(super) async fn timeout_requests<B: Send + 'static>(
+  timeouts: Arc<Timeouts>,
+  mut request: Request<B>,
+  next: Next<B>,
+) -> Response {
+  let timeout = timeouts.get(
+    request
+      .extensions()
+      .get::<MatchedPath>()
+      .map(MatchedPath::as_str),
+  );
+
+  request
+    .extensions_mut()
+    .insert(Deadline(tokio::time::Instant::now() + timeout));
+
+  let cancel = Cancel(Arc::new(AtomicBool::new(false)));
+
+  // task locals of outer layers do not cross to the blocking thread, so they
+  // are set again there
+  let handler = ErrorFormat::current()
+    .scope(RequestId::scope(RequestId::current(), next.run(request)))
+    .instrument(tracing::Span::current());
+
+  let served = tokio::task::spawn_blocking({
+    let cancelled = cancel.0.clone();
+    let runtime = tokio::runtime::Handle::current();
+    move || cancel::scope(Some(cancelled), || runtime.block_on(handler))
+  });
+
+  match tokio::time::timeout(timeout, served).await {
+    Ok(Ok(response)) => response,
+    Ok(Err(error)) => std::panic::resume_unwind(error.into_panic()),
+    Err(_) => ApiError::gateway_timeout(format!(
+      "request timed out after {} seconds",
+      timeout.as_secs_f64()
+    ))
+    .into_response(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    axum::{body::Body, routing::get},
+    hyper::service::Service,
+    tokio::sync::mpsc,
+  };
+
+  async fn call(router: &mut Router, path: &str) -> Response {
+    router
+      .call(Request::get(path).body(Body::empty()).unwrap())
+      .await
+      .unwrap()
+  }
+
+  fn router() -> Router {
+    Router::new()
+      .route("/fast", get(|| async { "fast" }))
+      .route(
+        "/slow",
+        get(|| async {
+          tokio::time::sleep(Duration::from_secs(5)).await;
+          "slow"
+        }),
+      )
+      .route(
+        "/blocking",
+        get(|| async {
+          std::thread::sleep(Duration::from_secs(2));
+          "blocking"
+        }),
+      )
+      .route(
+        "/request-id",
+        get(|| async { RequestId::current().unwrap_or_default() }),
+      )
+      .route(
+        "/patient/:id",
+        get(|| async {
+          tokio::time::sleep(Duration::from_millis(300)).await;
+          "patient"
+        }),
+      )
+      .route(
+        "/impatient/:id",
+        get(|| async {
+          tokio::time::sleep(Duration::from_millis(300)).await;
+          "impatient"
+        }),
+      )
+      .layer(axum::middleware::from_fn(|request, next| {
+        timeout_requests(
+          Arc::new(Timeouts::new(
+            Duration::from_millis(100),
+            &["/patient/:id=2".parse().unwrap()],
+          )),
+          request,
+          next,
+        )
+      }))
+      .layer(axum::middleware::from_fn(request_id::request_id))
+  }
+
+  #[tokio::test]
+  async fn requests_within_timeout_are_served() {
+    let mut router = router();
+
+    let response = call(&mut router, "/fast").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert_eq!(body, "fast");
+
+    let response = call(&mut router, "/request-id").await;
+    let request_id = response.headers()[request_id::X_REQUEST_ID].clone();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert_eq!(body, request_id.as_bytes());
+  }
+
+  #[tokio::test]
+  async fn slow_requests_time_out() {
+    let mut router = router();
+
+    for path in ["/slow", "/blocking"] {
+      let start = Instant::now();
+
+      let response = call(&mut router, path).await;
+
+      assert!(start.elapsed() < Duration::from_secs(1), "{path}");
+      assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT, "{path}");
+
+      let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+      let json = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+
+      assert_eq!(json["code"], 6, "{path}");
+      assert_eq!(json["msg"], "request timed out after 0.1 seconds", "{path}");
+    }
+  }
+
+  #[tokio::test]
+  async fn queries_of_timed_out_requests_are_cancelled() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    let mut router = Router::new()
+      .route(
+        "/scan",
+        get(move || async move {
+          while cancel::check().is_ok() {
+            std::thread::sleep(Duration::from_millis(10));
+          }
+          tx.send(()).unwrap();
+        }),
+      )
+      .layer(axum::middleware::from_fn(|request, next| {
+        timeout_requests(
+          Arc::new(Timeouts::new(Duration::from_millis(100), &[])),
+          request,
+          next,
+        )
+      }));
+
+    let response = call(&mut router, "/scan").await;
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+
+    tokio::time::timeout(Duration::from_secs(1), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+  }
+
+  #[tokio::test]
+  async fn route_timeouts_override_the_default() {
+    let mut router = router();
+
+    let response = call(&mut router, "/patient/0").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert_eq!(body, "patient");
+
+    let response = call(&mut router, "/impatient/0").await;
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+  }
+
+  #[test]
+  fn route_timeouts_are_parsed() {
+    assert_eq!(
+      "/api/v1/inscriptions/batch=60"
+        .parse::<RouteTimeout>()
+        .unwrap(),
+      RouteTimeout {
+        route: "/api/v1/inscriptions/batch".into(),
+        timeout: Duration::from_secs(60),
+      }
+    );
+
+    assert!("/foo".parse::<RouteTimeout>().is_err());
+    assert!("/foo=bar".parse::<RouteTimeout>().is_err());
+    assert!("foo=1".parse::<RouteTimeout>().is_err());
+  }
+}
@@ -0,0 +1,43 @@
+use {
+  super::{request_id::RequestId, *},
+  axum::{extract::MatchedPath, http::Request, middleware::Next},
+  tracing::{field, Instrument},
+};
+
+/// Run every request inside a `request` span carrying its method, matched
+/// route and request id, and record the status and latency once it completes.
+pub(super) async fn trace_requests<B>(request: Request<B>, next: Next<B>) -> Response {
+  let route = request
+    .extensions()
+    .get::<MatchedPath>()
+    .map(|path| path.as_str().to_string())
+    .unwrap_or_else(|| "unmatched".into());
+
+  let span = tracing::info_span!(
+    "request",
+    method = %request.method(),
+    route = %route,
+    request_id = RequestId::current().as_deref().unwrap_or_default(),
+    status = field::Empty,
+    latency_ms = field::Empty,
+  );
+
+  let start = Instant::now();
+
+  let response = next.run(request).instrument(span.clone()).await;
+
+  let status = response.status().as_u16();
+  let latency_ms = start.elapsed().as_millis();
+
+  span.record("status", status);
+  span.record("latency_ms", latency_ms);
+
+  tracing::debug!(
+    parent: &span,
+    status,
+    latency_ms,
+    "served request"
+  );
+
+  response
+}
@@ -0,0 +1,197 @@
+use {
+  super::{error::ServerError, *},
+  std::sync::atomic::AtomicUsize,
+  tokio::sync::oneshot,
+};
+
+/// Clients turned away by a saturated pool are asked to retry after this many
+/// seconds.
+const RETRY_AFTER_SECS: u64 = 1;
+
+/// Transforms queued or running per thread before further ones are rejected.
+const QUEUE_PER_THREAD: usize = 16;
+
+/// A bounded pool of threads for CPU-bound transforms of inscription content,
+/// like decompression and hashing, so that they do not block the async
+/// runtime.
+pub(super) struct TransformPool {
+  max_pending: usize,
+  pending: Arc<AtomicUsize>,
+  pool: rayon::ThreadPool,
+}
+
+/// A place in the queue of the pool, released when dropped, whether the
+/// transform finished or panicked.
+struct Pending(Arc<AtomicUsize>);
+
+impl Drop for Pending {
+  fn drop(&mut self) {
+    self.0.fetch_sub(1, atomic::Ordering::SeqCst);
+  }
+}
+
+impl TransformPool {
+  pub(super) fn new(threads: usize) -> Result<Self> {
+    ensure!(threads > 0, "--transform-threads must be greater than 0");
+
+    Ok(Self {
+      max_pending: threads * QUEUE_PER_THREAD,
+      pending: Arc::new(AtomicUsize::new(0)),
+      pool: rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("transform-{i}"))
+        .panic_handler(|_| log::error!("transform panicked"))
+        .build()?,
+    })
+  }
+
+  fn acquire(&self) -> Option<Pending> {
+    self
+      .pending
+      .fetch_update(
+        atomic::Ordering::SeqCst,
+        atomic::Ordering::SeqCst,
+        |pending| (pending < self.max_pending).then_some(pending + 1),
+      )
+      .ok()?;
+
+    Some(Pending(self.pending.clone()))
+  }
+
+  /// Run `transform` on the pool and wait for its result, or fail with 429 Too
+  /// Many Requests if the queue of the pool is full.
+  pub(super) async fn run<T, F>(&self, transform: F) -> ServerResult<T>
+  where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+  {
+    let pending = self.acquire().ok_or(ServerError::RateLimited {
+      retry_after_secs: RETRY_AFTER_SECS,
+    })?;
+
+    let (tx, rx) = oneshot::channel();
+
+    let cancelled = cancel::current();
+
+    self.pool.spawn(move || {
+      let output = {
+        let _pending = pending;
+        cancel::scope(cancelled, transform)
+      };
+      tx.send(output).ok();
+    });
+
+    rx.await
+      .map_err(|_| ServerError::Internal(anyhow!("transform panicked")))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    axum::{body::Body, http::Request, routing::get},
+    hyper::service::Service,
+    tokio::sync::RwLock,
+  };
+
+  fn request(path: &str) -> Request<Body> {
+    Request::get(path).body(Body::empty()).unwrap()
+  }
+
+  /// A router whose `/transform` requests run transforms that are blocked
+  /// while the returned lock is held for writing.
+  fn router(threads: usize) -> (Router, Arc<TransformPool>, Arc<RwLock<()>>) {
+    let pool = Arc::new(TransformPool::new(threads).unwrap());
+    let lock = Arc::new(RwLock::new(()));
+
+    let router = Router::new()
+      .route("/status", get(|| async { "ok" }))
+      .route(
+        "/transform",
+        get({
+          let pool = pool.clone();
+          let lock = lock.clone();
+          move || async move {
+            pool
+              .run(move || {
+                let _lock = lock.blocking_read();
+                "transformed"
+              })
+              .await
+          }
+        }),
+      );
+
+    (router, pool, lock)
+  }
+
+  #[tokio::test]
+  async fn runtime_is_responsive_during_transforms() {
+    let (mut router, pool, lock) = router(2);
+
+    let blocked = lock.write().await;
+
+    let transforms = (0..8)
+      .map(|_| tokio::spawn(router.call(request("/transform"))))
+      .collect::<Vec<_>>();
+
+    tokio::task::yield_now().await;
+
+    assert_eq!(pool.pending.load(atomic::Ordering::SeqCst), 8);
+
+    let response = tokio::time::timeout(Duration::from_secs(1), router.call(request("/status")))
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    drop(blocked);
+
+    for transform in transforms {
+      assert_eq!(transform.await.unwrap().unwrap().status(), StatusCode::OK);
+    }
+
+    assert_eq!(pool.pending.load(atomic::Ordering::SeqCst), 0);
+  }
+
+  #[tokio::test]
+  async fn saturated_pool_rejects_transforms() {
+    let (mut router, _pool, lock) = router(1);
+
+    let blocked = lock.write().await;
+
+    let transforms = (0..QUEUE_PER_THREAD)
+      .map(|_| tokio::spawn(router.call(request("/transform"))))
+      .collect::<Vec<_>>();
+
+    tokio::task::yield_now().await;
+
+    let response = router.call(request("/transform")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+
+    drop(blocked);
+
+    for transform in transforms {
+      assert_eq!(transform.await.unwrap().unwrap().status(), StatusCode::OK);
+    }
+
+    let response = router.call(request("/transform")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+  }
+
+  #[tokio::test]
+  async fn panicking_transforms_release_their_place() {
+    let pool = TransformPool::new(1).unwrap();
+
+    assert!(matches!(
+      pool.run(|| panic!("transform")).await,
+      Err(ServerError::Internal(_))
+    ));
+
+    assert_eq!(pool.pending.load(atomic::Ordering::SeqCst), 0);
+
+    assert_eq!(pool.run(|| 1).await.unwrap(), 1);
+  }
+}
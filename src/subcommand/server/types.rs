@@ -26,6 +26,60 @@ impl From<ScriptKey> for ScriptPubkey {
     }
   }
 }
+
+/// The type of a script pubkey, for classifying the owners of outputs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressType {
+  P2pk,
+  P2pkh,
+  P2sh,
+  P2wpkh,
+  P2wsh,
+  P2tr,
+  /// Any other script, including unknown witness versions and `OP_RETURN`.
+  #[default]
+  Nonstandard,
+}
+
+impl AddressType {
+  pub(crate) fn from_script(script: &Script) -> Self {
+    if script.is_p2pk() {
+      Self::P2pk
+    } else if script.is_p2pkh() {
+      Self::P2pkh
+    } else if script.is_p2sh() {
+      Self::P2sh
+    } else if script.is_v0_p2wpkh() {
+      Self::P2wpkh
+    } else if script.is_v0_p2wsh() {
+      Self::P2wsh
+    } else if script.is_v1_p2tr() {
+      Self::P2tr
+    } else {
+      Self::Nonstandard
+    }
+  }
+}
+
+impl Display for AddressType {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::P2pk => "p2pk",
+        Self::P2pkh => "p2pkh",
+        Self::P2sh => "p2sh",
+        Self::P2wpkh => "p2wpkh",
+        Self::P2wsh => "p2wsh",
+        Self::P2tr => "p2tr",
+        Self::Nonstandard => "nonstandard",
+      }
+    )
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -60,4 +114,66 @@ mod tests {
       r#"{"nonStandard":"df65c8a338dce7900824e7bd18c336656ca19e57"}"#
     );
   }
+
+  #[test]
+  fn address_types() {
+    for (address, address_type) in [
+      ("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2", AddressType::P2pkh),
+      ("3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy", AddressType::P2sh),
+      (
+        "bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4",
+        AddressType::P2wpkh,
+      ),
+      (
+        "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3",
+        AddressType::P2wsh,
+      ),
+      (
+        "bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297",
+        AddressType::P2tr,
+      ),
+    ] {
+      let script_pubkey = Address::from_str(address)
+        .unwrap()
+        .assume_checked()
+        .script_pubkey();
+
+      assert_eq!(
+        AddressType::from_script(&script_pubkey),
+        address_type,
+        "{address}"
+      );
+      assert_eq!(
+        serde_json::to_string(&address_type).unwrap(),
+        format!("\"{address_type}\"")
+      );
+    }
+
+    assert_eq!(
+      AddressType::from_script(&ScriptBuf::new_p2pk(
+        &"0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+          .parse()
+          .unwrap()
+      )),
+      AddressType::P2pk
+    );
+
+    for script in [
+      ScriptBuf::new(),
+      ScriptBuf::new_op_return(&[0; 4]),
+      ScriptBuf::from_bytes(
+        hex::decode(
+          "0014017fed86bba5f31f955f8b316c7fb9bd45cb6cbc00000000000000000000000000000000000000",
+        )
+        .unwrap(),
+      ),
+    ] {
+      assert_eq!(AddressType::from_script(&script), AddressType::Nonstandard);
+    }
+
+    assert_eq!(
+      serde_json::to_string(&AddressType::Nonstandard).unwrap(),
+      r#""nonstandard""#
+    );
+  }
 }
@@ -0,0 +1,120 @@
+use {
+  super::*,
+  crate::index::Event,
+  axum::extract::ws::{Message, WebSocket, WebSocketUpgrade},
+  tokio::{sync::broadcast::error::RecvError, time::timeout},
+};
+
+/// How long a single message may take to reach a client before the client is
+/// considered too slow and disconnected.
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize)]
+pub(super) struct InscriptionsQuery {
+  content_type: Option<String>,
+}
+
+impl InscriptionsQuery {
+  fn matches(&self, event: &Event) -> bool {
+    let Event::InscriptionCreated { content_type, .. } = event else {
+      return true;
+    };
+
+    let Some(pattern) = &self.content_type else {
+      return true;
+    };
+
+    let Some(content_type) = content_type else {
+      return false;
+    };
+
+    match pattern.strip_suffix('*') {
+      Some(prefix) => content_type.starts_with(prefix),
+      None => content_type == pattern,
+    }
+  }
+}
+
+/// Stream newly indexed inscriptions, optionally filtered by a content type
+/// like `image/png` or `image/*`, followed by a `block_committed` message for
+/// every block.
+pub(super) async fn inscriptions(
+  Extension(index): Extension<Arc<Index>>,
+  Query(query): Query<InscriptionsQuery>,
+  upgrade: WebSocketUpgrade,
+) -> Response {
+  let receiver = index.subscribe();
+  upgrade.on_upgrade(move |socket| stream_inscriptions(socket, receiver, query))
+}
+
+async fn stream_inscriptions(
+  mut socket: WebSocket,
+  mut receiver: tokio::sync::broadcast::Receiver<Arc<Vec<Event>>>,
+  query: InscriptionsQuery,
+) {
+  loop {
+    let events = match receiver.recv().await {
+      Ok(events) => events,
+      Err(RecvError::Lagged(skipped)) => {
+        log::info!("dropping websocket client that lagged by {skipped} commits");
+        socket.send(Message::Close(None)).await.ok();
+        return;
+      }
+      Err(RecvError::Closed) => return,
+    };
+
+    for event in events.iter().filter(|event| query.matches(event)) {
+      let message = match serde_json::to_string(event) {
+        Ok(message) => message,
+        Err(err) => {
+          log::error!("failed to serialize event: {err}");
+          return;
+        }
+      };
+
+      match timeout(SEND_TIMEOUT, socket.send(Message::Text(message))).await {
+        Ok(Ok(())) => {}
+        Ok(Err(_)) => return,
+        Err(_) => {
+          log::info!("dropping websocket client that timed out");
+          return;
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn created(content_type: Option<&str>) -> Event {
+    Event::InscriptionCreated {
+      id: inscription_id(1),
+      number: 0,
+      height: 1,
+      content_type: content_type.map(str::to_string),
+    }
+  }
+
+  fn query(content_type: Option<&str>) -> InscriptionsQuery {
+    InscriptionsQuery {
+      content_type: content_type.map(str::to_string),
+    }
+  }
+
+  #[test]
+  fn content_type_filter() {
+    assert!(query(None).matches(&created(None)));
+    assert!(query(None).matches(&created(Some("text/plain"))));
+    assert!(query(Some("text/plain")).matches(&created(Some("text/plain"))));
+    assert!(!query(Some("text/plain")).matches(&created(Some("text/html"))));
+    assert!(query(Some("image/*")).matches(&created(Some("image/png"))));
+    assert!(!query(Some("image/*")).matches(&created(Some("text/plain"))));
+    assert!(!query(Some("image/*")).matches(&created(None)));
+    assert!(query(Some("image/*")).matches(&Event::BlockCommitted {
+      height: 1,
+      hash: BlockHash::all_zeros(),
+    }));
+  }
+}
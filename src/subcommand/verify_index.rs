@@ -0,0 +1,32 @@
+pub use crate::index::verify::Invariant;
+
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub invariants: Vec<Invariant>,
+}
+
+pub(crate) fn run(options: Options) -> SubcommandResult {
+  let index = Index::open_readonly(&options)?;
+
+  let output = Output {
+    invariants: index.verify()?,
+  };
+
+  let violations = output
+    .invariants
+    .iter()
+    .filter(|invariant| !invariant.holds())
+    .count();
+
+  if violations > 0 {
+    subcommand::Output::print_json(&output);
+    bail!(
+      "{violations} of {} index invariants violated",
+      output.invariants.len()
+    );
+  }
+
+  Ok(Box::new(output))
+}
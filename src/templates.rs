@@ -3,24 +3,25 @@ use {super::*, boilerplate::Boilerplate};
 pub(crate) use {
   block::{BlockHtml, BlockJson},
   blocks::BlocksHtml,
-  children::{ChildrenHtml, ChildrenJson},
+  children::{ChildInscriptionJson, ChildInscriptionsJson, ChildrenHtml, ChildrenJson},
   clock::ClockSvg,
   collections::CollectionsHtml,
   home::HomeHtml,
   iframe::Iframe,
   input::InputHtml,
-  inscription::{InscriptionHtml, InscriptionJson},
-  inscriptions::{InscriptionsHtml, InscriptionsJson},
+  inscription::{InscriptionHtml, InscriptionJson, InscriptionParentJson},
+  inscriptions::{InscriptionItemJson, InscriptionsHtml, InscriptionsJson},
   inscriptions_block::InscriptionsBlockHtml,
   metadata::MetadataHtml,
   output::{OutputHtml, OutputJson},
+  parents::ParentsJson,
   preview::{
     PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
     PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
   },
   range::RangeHtml,
   rare::RareTxt,
-  rune::{RuneHtml, RuneJson},
+  rune::{RuneHtml, RuneJson, RuneMintJson},
   runes::{RunesHtml, RunesJson},
   sat::{SatHtml, SatInscriptionJson, SatInscriptionsJson, SatJson},
   server_config::ServerConfig,
@@ -41,6 +42,7 @@ pub mod inscriptions;
 mod inscriptions_block;
 mod metadata;
 pub mod output;
+mod parents;
 mod preview;
 mod range;
 mod rare;
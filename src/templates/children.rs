@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, utoipa::ToSchema};
 
 #[derive(Boilerplate)]
 pub(crate) struct ChildrenHtml {
@@ -14,6 +14,38 @@ pub struct ChildrenJson {
   pub ids: Vec<InscriptionId>,
   pub more: bool,
   pub page: usize,
+  /// The children listed, in the same order, with their content.
+  #[serde(default)]
+  pub items: Vec<InscriptionItemJson>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ChildInscriptionJson {
+  /// The child inscription id.
+  #[schema(value_type = String)]
+  pub id: InscriptionId,
+  /// The child inscription number.
+  pub number: i32,
+  /// The content type of the child inscription, if any.
+  pub content_type: Option<String>,
+  /// The length in bytes of the stored content of the child inscription, if
+  /// any.
+  #[schema(format = "uint64")]
+  pub content_length: Option<usize>,
+  /// The current location of the child inscription.
+  #[schema(value_type = String)]
+  pub satpoint: SatPoint,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ChildInscriptionsJson {
+  /// Children of the inscription, in the order they were inscribed.
+  pub children: Vec<ChildInscriptionJson>,
+  /// Whether there are more children on a later page.
+  pub more: bool,
+  /// The page index of the result.
+  #[schema(format = "uint64")]
+  pub page: usize,
 }
 
 impl PageContent for ChildrenHtml {
@@ -11,7 +11,8 @@ pub(crate) struct InscriptionHtml {
   pub(crate) inscription_number: i32,
   pub(crate) next: Option<InscriptionId>,
   pub(crate) output: Option<TxOut>,
-  pub(crate) parent: Option<InscriptionId>,
+  pub(crate) parent_details: Option<Vec<InscriptionParentJson>>,
+  pub(crate) parents: Vec<InscriptionId>,
   pub(crate) previous: Option<InscriptionId>,
   pub(crate) rune: Option<SpacedRune>,
   pub(crate) sat: Option<Sat>,
@@ -33,7 +34,13 @@ pub struct InscriptionJson {
   pub inscription_number: i32,
   pub next: Option<InscriptionId>,
   pub output_value: Option<u64>,
+  /// Deprecated, the first of `parents`.
   pub parent: Option<InscriptionId>,
+  /// Parent details, only present when requested with `?include=parent`.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub parent_details: Option<Vec<InscriptionParentJson>>,
+  #[serde(default)]
+  pub parents: Vec<InscriptionId>,
   pub previous: Option<InscriptionId>,
   pub rune: Option<SpacedRune>,
   pub sat: Option<Sat>,
@@ -41,6 +48,13 @@ pub struct InscriptionJson {
   pub timestamp: i64,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionParentJson {
+  pub id: InscriptionId,
+  pub number: i32,
+  pub content_type: Option<String>,
+}
+
 impl PageContent for InscriptionHtml {
   fn title(&self) -> String {
     format!("Inscription {}", self.inscription_number)
@@ -230,7 +244,7 @@ mod tests {
   fn with_parent() {
     assert_regex_match!(
       InscriptionHtml {
-        parent: Some(inscription_id(2)),
+        parents: vec![inscription_id(2)],
         genesis_fee: 1,
         inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
         inscription_id: inscription_id(1),
@@ -280,6 +294,32 @@ mod tests {
     );
   }
 
+  #[test]
+  fn with_parents() {
+    assert_regex_match!(
+      InscriptionHtml {
+        parents: vec![inscription_id(2), inscription_id(3)],
+        genesis_fee: 1,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        inscription_id: inscription_id(1),
+        inscription_number: 1,
+        satpoint: satpoint(1, 0),
+        ..Default::default()
+      },
+      "
+        .*
+          <dt>id</dt>
+          <dd class=monospace>1{64}i1</dd>
+          <dt>parents</dt>
+          <dd><a class=monospace href=/inscription/2{64}i2>2{64}i2</a></dd>
+          <dd><a class=monospace href=/inscription/3{64}i3>3{64}i3</a></dd>
+          <dt>preview</dt>
+        .*
+      "
+      .unindent()
+    );
+  }
+
   #[test]
   fn with_children() {
     assert_regex_match!(
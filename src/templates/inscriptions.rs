@@ -1,10 +1,11 @@
-use super::*;
+use {super::*, crate::index::Order};
 
 #[derive(Boilerplate)]
 pub(crate) struct InscriptionsHtml {
   pub(crate) inscriptions: Vec<InscriptionId>,
   pub(crate) prev: Option<usize>,
   pub(crate) next: Option<usize>,
+  pub(crate) order: Option<Order>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -12,6 +13,37 @@ pub struct InscriptionsJson {
   pub inscriptions: Vec<InscriptionId>,
   pub more: bool,
   pub page_index: usize,
+  /// The page size used, after reducing it to `--max-page-size`.
+  #[serde(default)]
+  pub limit: usize,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+  /// Set when a filtered listing stopped scanning before filling the page.
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub truncated: bool,
+  /// The inscriptions listed, in the same order, with their content.
+  #[serde(default)]
+  pub items: Vec<InscriptionItemJson>,
+  /// The sequence number of the last inscription listed when polling with
+  /// `since_sequence`, to poll with next.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub last_sequence: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionItemJson {
+  pub id: InscriptionId,
+  pub content_type: Option<String>,
+  pub content_length: Option<usize>,
+}
+
+impl InscriptionsHtml {
+  fn order_query(&self) -> String {
+    self
+      .order
+      .map(|order| format!("?order={order}"))
+      .unwrap_or_default()
+  }
 }
 
 impl PageContent for InscriptionsHtml {
@@ -31,6 +63,7 @@ mod tests {
         inscriptions: vec![inscription_id(1), inscription_id(2)],
         prev: None,
         next: None,
+        order: None,
       },
       "
         <h1>All Inscriptions</h1>
@@ -54,6 +87,7 @@ mod tests {
         inscriptions: vec![inscription_id(1), inscription_id(2)],
         prev: Some(1),
         next: Some(2),
+        order: None,
       },
       "
         <h1>All Inscriptions</h1>
@@ -69,4 +103,23 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn prev_and_next_keep_order() {
+    assert_regex_match!(
+      InscriptionsHtml {
+        inscriptions: vec![inscription_id(1)],
+        prev: Some(1),
+        next: Some(3),
+        order: Some(Order::Asc),
+      },
+      "
+        .*
+        <a class=prev href=/inscriptions/1\\?order=asc>prev</a>
+        <a class=next href=/inscriptions/3\\?order=asc>next</a>
+        .*
+      "
+      .unindent()
+    );
+  }
 }
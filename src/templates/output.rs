@@ -18,6 +18,7 @@ pub struct OutputJson {
   pub transaction: String,
   pub sat_ranges: Option<Vec<(u64, u64)>>,
   pub inscriptions: Vec<InscriptionId>,
+  #[serde(with = "crate::json_amount::map")]
   pub runes: BTreeMap<Rune, u128>,
 }
 
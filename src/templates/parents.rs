@@ -0,0 +1,11 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ParentsJson {
+  pub ids: Vec<InscriptionId>,
+  pub more: bool,
+  pub page: usize,
+  /// The parents listed, in the same order, with their content.
+  #[serde(default)]
+  pub items: Vec<InscriptionItemJson>,
+}
@@ -1,7 +1,5 @@
 use super::*;
 
-pub type RuneJson = RuneHtml;
-
 #[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RuneHtml {
   pub entry: RuneEntry,
@@ -9,6 +7,41 @@ pub struct RuneHtml {
   pub parent: Option<InscriptionId>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneJson {
+  pub entry: RuneEntry,
+  pub id: RuneId,
+  pub mint: RuneMintJson,
+  pub parent: Option<InscriptionId>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneMintJson {
+  /// Whether a claim in the next block would mint the rune.
+  pub mintable: bool,
+  pub mints: u64,
+  /// Open etchings do not limit the number of mints, so this is always null.
+  pub cap: Option<u64>,
+  #[serde(with = "crate::json_amount::option")]
+  pub amount_per_mint: Option<u128>,
+  pub start_height: u32,
+  pub end_height: Option<u32>,
+}
+
+impl RuneMintJson {
+  /// `height` and `timestamp` are those of the chain tip.
+  pub(crate) fn new(entry: &RuneEntry, id: RuneId, height: u32, timestamp: u32) -> Self {
+    Self {
+      mintable: entry.mintable(height + 1, timestamp),
+      mints: entry.mints,
+      cap: None,
+      amount_per_mint: entry.limit,
+      start_height: id.height,
+      end_height: entry.end,
+    }
+  }
+}
+
 impl PageContent for RuneHtml {
   fn title(&self) -> String {
     format!("Rune {}", self.entry.spaced_rune())
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, utoipa::ToSchema};
 
 #[derive(Boilerplate)]
 pub(crate) struct SatHtml {
@@ -26,10 +26,15 @@ pub struct SatJson {
   pub inscriptions: Vec<InscriptionId>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct SatInscriptionsJson {
+  /// Inscription ids on the sat, in the order they were inscribed.
+  #[schema(value_type = Vec<String>)]
   pub ids: Vec<InscriptionId>,
+  /// Whether there are more inscriptions on a later page.
   pub more: bool,
+  /// The page index of the result.
+  #[schema(format = "uint64")]
   pub page: u64,
 }
 
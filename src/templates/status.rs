@@ -5,17 +5,25 @@ pub struct StatusHtml {
   pub blessed_inscriptions: u64,
   pub cursed_inscriptions: u64,
   pub chain: Chain,
+  pub chain_tip: Option<u32>,
   pub height: Option<u32>,
   pub inscriptions: u64,
+  pub lag: Option<u32>,
   pub lost_sats: u64,
   pub minimum_rune_for_next_block: Rune,
+  pub readonly: bool,
   pub rune_index: bool,
+  #[serde(default)]
+  pub route_groups: Vec<String>,
   pub runes: u64,
   pub sat_index: bool,
+  pub shutting_down: bool,
   pub started: DateTime<Utc>,
+  pub synced: bool,
   pub transaction_index: bool,
   pub unrecoverably_reorged: bool,
   pub uptime: Duration,
+  pub uptime_secs: u64,
 }
 
 impl PageContent for StatusHtml {
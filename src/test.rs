@@ -120,7 +120,7 @@ pub(crate) struct InscriptionTemplate {
 impl From<InscriptionTemplate> for Inscription {
   fn from(template: InscriptionTemplate) -> Self {
     Self {
-      parent: template.parent.map(|id| id.value()),
+      parents: template.parent.into_iter().map(|id| id.value()).collect(),
       pointer: template.pointer.map(Inscription::pointer_value),
       ..Default::default()
     }
@@ -19,11 +19,11 @@ use {
   bitcoincore_rpc::json::{
     Bip125Replaceable, CreateRawTransactionInput, Descriptor, EstimateMode, GetBalancesResult,
     GetBalancesResultEntry, GetBlockHeaderResult, GetBlockchainInfoResult, GetDescriptorInfoResult,
-    GetNetworkInfoResult, GetRawTransactionResult, GetTransactionResult,
-    GetTransactionResultDetail, GetTransactionResultDetailCategory, GetWalletInfoResult,
-    ImportDescriptors, ImportMultiResult, ListDescriptorsResult, ListTransactionResult,
-    ListUnspentResultEntry, LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult,
-    Timestamp, WalletTxInfo,
+    GetNetworkInfoResult, GetRawTransactionResult, GetRawTransactionResultVoutScriptPubKey,
+    GetTransactionResult, GetTransactionResultDetail, GetTransactionResultDetailCategory,
+    GetTxOutResult, GetWalletInfoResult, ImportDescriptors, ImportMultiResult,
+    ListDescriptorsResult, ListTransactionResult, ListUnspentResultEntry, LoadWalletResult,
+    SignRawTransactionInput, SignRawTransactionResult, Timestamp, WalletTxInfo,
   },
   jsonrpc_core::{IoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
@@ -49,6 +49,7 @@ pub fn builder() -> Builder {
   Builder {
     fail_lock_unspent: false,
     network: Network::Bitcoin,
+    txindex: true,
     version: 240000,
   }
 }
@@ -56,6 +57,7 @@ pub fn builder() -> Builder {
 pub struct Builder {
   fail_lock_unspent: bool,
   network: Network,
+  txindex: bool,
   version: usize,
 }
 
@@ -71,6 +73,10 @@ impl Builder {
     Self { network, ..self }
   }
 
+  pub fn txindex(self, txindex: bool) -> Self {
+    Self { txindex, ..self }
+  }
+
   pub fn version(self, version: usize) -> Self {
     Self { version, ..self }
   }
@@ -80,6 +86,7 @@ impl Builder {
       self.network,
       self.version,
       self.fail_lock_unspent,
+      self.txindex,
     )));
     let server = Server::new(state.clone());
     let mut io = IoHandler::default();
@@ -131,6 +138,7 @@ pub struct TransactionTemplate<'a> {
   pub inputs: &'a [(usize, usize, usize, Witness)],
   pub op_return: Option<ScriptBuf>,
   pub op_return_index: Option<usize>,
+  pub output_scripts: &'a [ScriptBuf],
   pub output_values: &'a [u64],
   pub outputs: usize,
 }
@@ -184,12 +192,16 @@ impl<'a> Default for TransactionTemplate<'a> {
       inputs: &[],
       op_return: None,
       op_return_index: None,
+      output_scripts: &[],
       output_values: &[],
       outputs: 1,
     }
   }
 }
 
+/// Holds the state of the server, so that calls block until dropped.
+pub struct Paused<'a>(#[allow(unused)] MutexGuard<'a, State>);
+
 pub struct Handle {
   close_handle: Option<CloseHandle>,
   port: u16,
@@ -206,6 +218,12 @@ impl Handle {
     self.state.lock().unwrap()
   }
 
+  /// Block calls to the server until the returned guard is dropped, to
+  /// simulate a slow node.
+  pub fn pause(&self) -> Paused {
+    Paused(self.state())
+  }
+
   pub fn clear_state(&self) {
     self.state.lock().unwrap().clear();
   }
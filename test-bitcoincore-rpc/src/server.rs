@@ -483,8 +483,9 @@ impl Api for Server {
   ) -> Result<Value, jsonrpc_core::Error> {
     assert_eq!(blockhash, None, "Blockhash param is unsupported");
     if verbose.unwrap_or(false) {
-      match self.state().transactions.get(&txid) {
-        Some(_) => Ok(
+      let state = self.state();
+      match state.transactions.get(&txid).filter(|_| state.txindex) {
+        Some(tx) => Ok(
           serde_json::to_value(GetRawTransactionResult {
             in_active_chain: Some(true),
             hex: Vec::new(),
@@ -496,8 +497,8 @@ impl Api for Server {
             locktime: 0,
             vin: Vec::new(),
             vout: Vec::new(),
-            blockhash: None,
-            confirmations: Some(1),
+            blockhash: state.get_blockhash(tx),
+            confirmations: Some(state.get_confirmations(tx).try_into().unwrap()),
             time: None,
             blocktime: None,
           })
@@ -506,13 +507,65 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match self.state().transactions.get(&txid) {
+      let state = self.state();
+      match state
+        .transactions
+        .get(&txid)
+        .or_else(|| state.mempool.iter().find(|tx| tx.txid() == txid))
+      {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
     }
   }
 
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    Ok(self.state().mempool.iter().map(Transaction::txid).collect())
+  }
+
+  fn get_tx_out(
+    &self,
+    txid: Txid,
+    vout: u32,
+    include_mempool: Option<bool>,
+  ) -> Result<Option<GetTxOutResult>, jsonrpc_core::Error> {
+    let state = self.state();
+
+    let outpoint = OutPoint { txid, vout };
+
+    if !state.utxos.contains_key(&outpoint) {
+      return Ok(None);
+    }
+
+    if include_mempool.unwrap_or(true)
+      && state
+        .mempool()
+        .iter()
+        .flat_map(|tx| &tx.input)
+        .any(|input| input.previous_output == outpoint)
+    {
+      return Ok(None);
+    }
+
+    let tx = &state.transactions[&txid];
+    let txout = &tx.output[usize::try_from(vout).unwrap()];
+
+    Ok(Some(GetTxOutResult {
+      bestblock: *state.hashes.last().unwrap(),
+      confirmations: state.get_confirmations(tx).try_into().unwrap(),
+      value: Amount::from_sat(txout.value),
+      script_pub_key: GetRawTransactionResultVoutScriptPubKey {
+        asm: txout.script_pubkey.to_asm_string(),
+        hex: txout.script_pubkey.to_bytes(),
+        req_sigs: None,
+        type_: None,
+        addresses: Vec::new(),
+        address: None,
+      },
+      coinbase: tx.is_coin_base(),
+    }))
+  }
+
   fn list_unspent(
     &self,
     minconf: Option<usize>,
@@ -13,13 +13,19 @@ pub(crate) struct State {
   pub(crate) nonce: u32,
   pub(crate) sent: Vec<Sent>,
   pub(crate) transactions: BTreeMap<Txid, Transaction>,
+  pub(crate) txindex: bool,
   pub(crate) utxos: BTreeMap<OutPoint, Amount>,
   pub(crate) version: usize,
   pub(crate) wallets: BTreeSet<String>,
 }
 
 impl State {
-  pub(crate) fn new(network: Network, version: usize, fail_lock_unspent: bool) -> Self {
+  pub(crate) fn new(
+    network: Network,
+    version: usize,
+    fail_lock_unspent: bool,
+    txindex: bool,
+  ) -> Self {
     let mut hashes = Vec::new();
     let mut blocks = BTreeMap::new();
 
@@ -40,6 +46,7 @@ impl State {
       nonce: 0,
       sent: Vec::new(),
       transactions: BTreeMap::new(),
+      txindex,
       utxos: BTreeMap::new(),
       version,
       wallets: BTreeSet::new(),
@@ -48,7 +55,12 @@ impl State {
   }
 
   pub(crate) fn clear(&mut self) {
-    *self = Self::new(self.network, self.version, self.fail_lock_unspent);
+    *self = Self::new(
+      self.network,
+      self.version,
+      self.fail_lock_unspent,
+      self.txindex,
+    );
   }
 
   pub(crate) fn push_block(&mut self, subsidy: u64) -> Block {
@@ -174,7 +186,11 @@ impl State {
             .get(i)
             .cloned()
             .unwrap_or(value_per_output),
-          script_pubkey: script::Builder::new().into_script(),
+          script_pubkey: template
+            .output_scripts
+            .get(i)
+            .cloned()
+            .unwrap_or_else(|| script::Builder::new().into_script()),
         })
         .collect(),
     };
@@ -198,6 +214,14 @@ impl State {
     &self.mempool
   }
 
+  pub(crate) fn get_blockhash(&self, tx: &Transaction) -> Option<BlockHash> {
+    self
+      .hashes
+      .iter()
+      .find(|hash| self.blocks.get(*hash).unwrap().txdata.contains(tx))
+      .copied()
+  }
+
   pub(crate) fn get_confirmations(&self, tx: &Transaction) -> i32 {
     for (confirmations, hash) in self.hashes.iter().rev().enumerate() {
       if self.blocks.get(hash).unwrap().txdata.contains(tx) {
@@ -1,8 +1,8 @@
 use {
   super::*,
   bitcoin::{
-    absolute::LockTime, consensus::Encodable, opcodes, script, ScriptBuf, Sequence, Transaction,
-    TxIn, Witness,
+    absolute::LockTime, consensus::Encodable, hashes::Hash, opcodes, script, ScriptBuf, Sequence,
+    Transaction, TxIn, Witness,
   },
   ord::{
     subcommand::decode::{CompactInscription, CompactOutput, RawOutput},
@@ -11,12 +11,22 @@ use {
 };
 
 fn transaction() -> Vec<u8> {
-  let script = script::Builder::new()
+  transaction_with_parents(&[])
+}
+
+fn transaction_with_parents(parents: &[Txid]) -> Vec<u8> {
+  let mut builder = script::Builder::new()
     .push_opcode(opcodes::OP_FALSE)
     .push_opcode(opcodes::all::OP_IF)
     .push_slice(b"ord")
     .push_slice([1])
-    .push_slice(b"text/plain;charset=utf-8")
+    .push_slice(b"text/plain;charset=utf-8");
+
+  for parent in parents {
+    builder = builder.push_slice([3]).push_slice(parent.to_byte_array());
+  }
+
+  let script = builder
     .push_slice([])
     .push_slice([0, 1, 2, 3])
     .push_opcode(opcodes::all::OP_ENDIF)
@@ -134,9 +144,34 @@ fn compact() {
         metadata: None,
         metaprotocol: None,
         parent: None,
+        parents: Vec::new(),
         pointer: None,
         unrecognized_even_field: false,
       }],
     },
   );
 }
+
+#[test]
+fn compact_with_multiple_parents() {
+  let parents = [
+    "1111111111111111111111111111111111111111111111111111111111111111"
+      .parse::<Txid>()
+      .unwrap(),
+    "2222222222222222222222222222222222222222222222222222222222222222"
+      .parse::<Txid>()
+      .unwrap(),
+  ];
+
+  let output = CommandBuilder::new("decode --compact --file transaction.bin")
+    .write("transaction.bin", transaction_with_parents(&parents))
+    .run_and_deserialize_output::<CompactOutput>();
+
+  let parents = parents
+    .into_iter()
+    .map(|txid| InscriptionId { txid, index: 0 })
+    .collect::<Vec<InscriptionId>>();
+
+  assert_eq!(output.inscriptions[0].parent, Some(parents[0]));
+  assert_eq!(output.inscriptions[0].parents, parents);
+}
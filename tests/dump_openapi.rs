@@ -0,0 +1,45 @@
+use {super::*, serde_json::Value};
+
+#[test]
+fn json() {
+  let openapi = CommandBuilder::new("dump-openapi").run_and_deserialize_output::<Value>();
+
+  assert!(openapi["components"]["schemas"]["ApiError"].is_object());
+  assert!(openapi["paths"]["/api/v1/ord/outpoint/{outpoint}"].is_object());
+  assert!(openapi["paths"]["/api/v1/brc20/outpoint/{outpoint}/transferable"].is_object());
+  assert!(openapi["paths"]["/api/v1/sat/outpoint/{outpoint}/info"].is_object());
+  assert!(openapi["components"]["schemas"]["brc20.ApiOutPointResult"].is_object());
+  assert!(openapi["components"]["schemas"]["sat.ApiOutPointResult"].is_object());
+}
+
+#[test]
+fn yaml() {
+  let openapi = serde_yaml::from_str::<Value>(
+    &CommandBuilder::new("dump-openapi --format yaml")
+      .stdout_regex(".*")
+      .run_and_extract_stdout(),
+  )
+  .unwrap();
+
+  assert!(openapi["components"]["schemas"]["ApiError"].is_object());
+}
+
+#[test]
+fn templated_paths_are_distinct() {
+  let openapi = CommandBuilder::new("dump-openapi").run_and_deserialize_output::<Value>();
+
+  let mut paths = BTreeMap::new();
+
+  for path in openapi["paths"].as_object().unwrap().keys() {
+    let template = Regex::new(r"\{[^}]*\}")
+      .unwrap()
+      .replace_all(path, "{}")
+      .into_owned();
+
+    if let Some(other) = paths.insert(template, path) {
+      panic!("{path} and {other} match the same requests");
+    }
+  }
+
+  assert!(openapi["paths"]["/api/v1/ord/block/{block}/inscriptions"].is_object());
+}
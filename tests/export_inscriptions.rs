@@ -0,0 +1,82 @@
+use {super::*, ord::subcommand::export_inscriptions::ExportedInscription};
+
+fn exported(ndjson: &str) -> Vec<ExportedInscription> {
+  ndjson
+    .lines()
+    .map(|line| serde_json::from_str(line).unwrap())
+    .collect()
+}
+
+#[test]
+fn inscriptions_are_exported_in_sequence_order() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (first, _) = inscribe(&rpc_server);
+  let (second, reveal) = inscribe(&rpc_server);
+  let (third, _) = inscribe(&rpc_server);
+
+  let inscriptions = exported(
+    &CommandBuilder::new("export-inscriptions --output inscriptions.ndjson")
+      .rpc_server(&rpc_server)
+      .stdout_regex(r#"\{\s*"inscriptions": 3\s*\}\n"#)
+      .run_and_extract_file("inscriptions.ndjson"),
+  );
+
+  assert_eq!(inscriptions.len(), 3);
+
+  assert_eq!(
+    inscriptions
+      .iter()
+      .map(|inscription| inscription.id)
+      .collect::<Vec<InscriptionId>>(),
+    [first, second, third],
+  );
+
+  pretty_assert_eq!(
+    inscriptions[1],
+    ExportedInscription {
+      id: second,
+      number: 1,
+      height: 4,
+      content_type: Some("text/plain;charset=utf-8".into()),
+      content_length: Some(3),
+      sat: None,
+      satpoint: SatPoint {
+        outpoint: OutPoint {
+          txid: reveal,
+          vout: 0
+        },
+        offset: 0,
+      },
+    }
+  );
+}
+
+#[test]
+fn inscriptions_in_height_range_are_exported_to_stdout() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  inscribe(&rpc_server);
+  let (second, _) = inscribe(&rpc_server);
+  inscribe(&rpc_server);
+
+  let inscriptions = exported(
+    &CommandBuilder::new("export-inscriptions --from-height 3 --to-height 5")
+      .rpc_server(&rpc_server)
+      .stdout_regex(".*")
+      .run_and_extract_stdout(),
+  );
+
+  assert_eq!(inscriptions.len(), 1);
+  assert_eq!(inscriptions[0].id, second);
+}
+
+#[test]
+fn to_height_must_be_greater_than_from_height() {
+  CommandBuilder::new("export-inscriptions --from-height 5 --to-height 5")
+    .expected_stderr("error: --to-height must be greater than --from-height\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
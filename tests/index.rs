@@ -116,3 +116,165 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn verify_reports_consistent_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  let output = CommandBuilder::new(format!("--index {} verify-index", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::verify_index::Output>();
+
+  assert_eq!(output.invariants.len(), 3);
+  assert!(output.invariants.iter().all(|invariant| invariant.holds()));
+}
+
+#[test]
+fn verify_fails_on_corrupted_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  {
+    let database = redb::Database::open(&index_path).unwrap();
+    let wtx = database.begin_write().unwrap();
+    wtx
+      .open_table(redb::TableDefinition::<u64, u64>::new("STATISTIC_TO_COUNT"))
+      .unwrap()
+      .insert(3, 1)
+      .unwrap();
+    wtx.commit().unwrap();
+  }
+
+  CommandBuilder::new(format!("--index {} verify-index", index_path.display()))
+    .rpc_server(&rpc_server)
+    .stdout_regex(r".*0 blessed and 1 cursed inscriptions, but 0 in total.*")
+    .stderr_regex("error: 1 of 3 index invariants violated\n.*")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn verify_does_not_create_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} verify-index", index_path.display()))
+    .rpc_server(&rpc_server)
+    .stderr_regex("error: read-only index `.*foo.redb` does not exist\n.*")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  assert!(!index_path.exists());
+}
+
+#[test]
+fn migrating_current_index_is_a_no_op() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  let output = CommandBuilder::new(format!("--index {} migrate-index", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::migrate_index::Output>();
+
+  assert_eq!(output.from_schema, output.to_schema);
+}
+
+#[test]
+fn migrating_missing_index_fails() {
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} migrate-index", index_path.display()))
+    .expected_stderr(format!(
+      "error: index `{}` does not exist\n",
+      index_path.display()
+    ))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn reindexing_runes_replays_every_block() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  rpc_server.mine_blocks(3);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes --index {} index update",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Empty>();
+
+  let output = CommandBuilder::new(format!(
+    "--regtest --index-runes --index {} reindex-runes",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::reindex_runes::Output>();
+
+  assert_eq!(
+    output,
+    ord::subcommand::reindex_runes::Output {
+      blocks: 4,
+      runes: 0
+    }
+  );
+}
+
+#[test]
+fn reindexing_runes_requires_rune_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  CommandBuilder::new(format!("--index {} reindex-runes", index_path.display()))
+    .rpc_server(&rpc_server)
+    .expected_stderr(format!(
+      "error: index at `{}` does not index runes\n",
+      index_path.display()
+    ))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
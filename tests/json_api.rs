@@ -159,6 +159,8 @@ fn get_inscription() {
       next: None,
       output_value: Some(10000),
       parent: None,
+      parent_details: None,
+      parents: Vec::new(),
       previous: None,
       rune: None,
       sat: Some(ord::Sat(50 * COIN_VALUE)),
@@ -397,6 +399,7 @@ fn get_status() {
 
   status_json.started = dummy_started;
   status_json.uptime = dummy_uptime;
+  status_json.uptime_secs = dummy_uptime.as_secs();
 
   pretty_assert_eq!(
     status_json,
@@ -404,17 +407,30 @@ fn get_status() {
       blessed_inscriptions: 1,
       cursed_inscriptions: 0,
       chain: Chain::Regtest,
+      chain_tip: Some(3),
       height: Some(3),
       inscriptions: 1,
+      lag: Some(0),
       lost_sats: 0,
       minimum_rune_for_next_block: Rune(99218849511960410),
+      readonly: false,
       rune_index: true,
+      route_groups: vec![
+        "html".into(),
+        "search".into(),
+        "listings".into(),
+        "ws".into(),
+        "brc20".into(),
+      ],
       runes: 0,
       sat_index: true,
+      shutting_down: false,
       started: dummy_started,
+      synced: true,
       transaction_index: false,
       unrecoverably_reorged: false,
       uptime: dummy_uptime,
+      uptime_secs: 1,
     }
   );
 }
@@ -467,6 +483,14 @@ fn get_runes() {
         height: 5,
         index: 1
       },
+      mint: RuneMintJson {
+        mintable: false,
+        mints: 0,
+        cap: None,
+        amount_per_mint: None,
+        start_height: 5,
+        end_height: None,
+      },
       parent: None,
     }
   );
@@ -548,3 +572,205 @@ fn get_runes() {
     }
   );
 }
+
+#[test]
+fn get_runes_balances() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  let etch = etch(&rpc_server, Rune(RUNE));
+
+  let address = Address::from_script(
+    &rpc_server.tx(2, 1).output[1].script_pubkey,
+    Network::Regtest,
+  )
+  .unwrap();
+
+  let response = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--regtest", "--index-runes"],
+    &["--enable-json-api"],
+  )
+  .json_request(format!("/api/v1/runes/balances/{address}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  assert_eq!(
+    serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap()["data"],
+    serde_json::json!({
+      "address": address.to_string(),
+      "addressType": "p2tr",
+      "balances": [{
+        "rune": etch.rune.to_string(),
+        "id": "2:1",
+        "amount": "1000",
+        "rawAmount": "1000",
+        "divisibility": 0,
+        "symbol": "¢",
+      }],
+    })
+  );
+}
+
+#[test]
+fn get_address_inscriptions() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let address = CommandBuilder::new("wallet receive")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+    .address
+    .assume_checked();
+
+  let mut inscriptions = Vec::new();
+  for content_type in ["txt", "html"] {
+    let output = CommandBuilder::new(format!(
+      "wallet inscribe --destination {address} --fee-rate 1 --file foo.{content_type}"
+    ))
+    .write(format!("foo.{content_type}"), "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+    rpc_server.mine_blocks(1);
+
+    inscriptions.push((output.inscriptions[0].id, output.inscriptions[0].location));
+  }
+
+  let server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"]);
+
+  let response = server.json_request(format!("/api/v1/ord/address/{address}/inscriptions"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let mut data =
+    serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap()["data"].take();
+
+  data["inscriptions"]
+    .as_array_mut()
+    .unwrap()
+    .sort_by_key(|inscription| inscription["number"].as_i64());
+
+  assert_eq!(
+    data,
+    serde_json::json!({
+      "addressType": "p2tr",
+      "inscriptions": [
+        {
+          "id": inscriptions[0].0.to_string(),
+          "number": 0,
+          "contentType": "text/plain;charset=utf-8",
+          "satpoint": inscriptions[0].1.to_string(),
+          "value": 10000,
+        },
+        {
+          "id": inscriptions[1].0.to_string(),
+          "number": 1,
+          "contentType": "text/html;charset=utf-8",
+          "satpoint": inscriptions[1].1.to_string(),
+          "value": 10000,
+        },
+      ],
+      "pageIndex": 0,
+      "limit": 100,
+      "more": false,
+    })
+  );
+
+  let response = server
+    .json_request("/api/v1/ord/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4/inscriptions");
+
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap()["data"]
+      ["inscriptions"],
+    serde_json::json!([])
+  );
+
+  let response = server
+    .json_request("/api/v1/ord/address/tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx/inscriptions");
+
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+  assert_eq!(
+    serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap()["msg"],
+    "invalid address tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+  );
+}
+
+#[test]
+fn get_address_inscriptions_with_min_value() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let address = CommandBuilder::new("wallet receive")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+    .address
+    .assume_checked();
+
+  let mut inscriptions = Vec::new();
+  for postage in ["546sat", "10000sat"] {
+    let output = CommandBuilder::new(format!(
+      "wallet inscribe --destination {address} --fee-rate 1 --file foo.txt --postage {postage}"
+    ))
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+    rpc_server.mine_blocks(1);
+
+    inscriptions.push(output.inscriptions[0].id.to_string());
+  }
+
+  let server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"]);
+
+  let listed = |min_value: u64| {
+    let response = server.json_request(format!(
+      "/api/v1/ord/address/{address}/inscriptions?min_value={min_value}"
+    ));
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut listed = serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap()
+      ["data"]["inscriptions"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .map(|inscription| {
+        (
+          inscription["id"].as_str().unwrap().to_string(),
+          inscription["value"].as_u64().unwrap(),
+        )
+      })
+      .collect::<Vec<(String, u64)>>();
+
+    listed.sort_by_key(|(_id, value)| *value);
+
+    listed
+  };
+
+  let all = vec![
+    (inscriptions[0].clone(), 546),
+    (inscriptions[1].clone(), 10000),
+  ];
+
+  assert_eq!(listed(0), all);
+  assert_eq!(listed(546), all);
+  assert_eq!(listed(547), [all[1].clone()]);
+  assert_eq!(listed(10001), []);
+
+  let response = server.json_request(format!(
+    "/api/v1/ord/address/{address}/inscriptions?min_value=-1"
+  ));
+
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+  assert_eq!(
+    serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap()["msg"],
+    "min_value must not be negative, but was -1"
+  );
+}
@@ -14,8 +14,14 @@ use {
     rarity::Rarity,
     subcommand::runes::RuneInfo,
     templates::{
-      block::BlockJson, inscription::InscriptionJson, inscriptions::InscriptionsJson,
-      output::OutputJson, rune::RuneJson, runes::RunesJson, sat::SatJson, status::StatusHtml,
+      block::BlockJson,
+      inscription::InscriptionJson,
+      inscriptions::InscriptionsJson,
+      output::OutputJson,
+      rune::{RuneJson, RuneMintJson},
+      runes::RunesJson,
+      sat::SatJson,
+      status::StatusHtml,
     },
     Edict, InscriptionId, Rune, RuneEntry, RuneId, Runestone, SatPoint,
   },
@@ -130,8 +136,10 @@ mod test_server;
 mod balances;
 mod core;
 mod decode;
+mod dump_openapi;
 mod epochs;
 mod etch;
+mod export_inscriptions;
 mod find;
 mod index;
 mod info;
@@ -1,6 +1,8 @@
 use {
-  super::*, crate::command_builder::ToArgs, ciborium::value::Integer,
-  ord::subcommand::wallet::send::Output,
+  super::*,
+  crate::command_builder::ToArgs,
+  ciborium::value::Integer,
+  ord::subcommand::{wallet::send::Output, Empty},
 };
 
 #[test]
@@ -214,7 +216,7 @@ fn inscription_content() {
       .into_iter()
       .collect::<Vec<&http::HeaderValue>>(),
     &[
-      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
+      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:; sandbox allow-scripts",
       "default-src *:*/content/ *:*/blockheight *:*/blockhash *:*/blockhash/ *:*/blocktime *:*/r/ 'unsafe-eval' 'unsafe-inline' data: blob:",
     ]
   );
@@ -492,3 +494,285 @@ fn inscription_transactions_are_stored_with_transaction_index() {
     StatusCode::NOT_FOUND,
   );
 }
+
+#[test]
+fn large_json_responses_are_compressed() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &[],
+    &["--enable-json-api", "--compression", "gzip"],
+  );
+
+  server.sync_server();
+
+  let client = reqwest::blocking::Client::new();
+
+  let request = |path: &str, encoding: &str| {
+    client
+      .get(server.url().join(path).unwrap())
+      .header(reqwest::header::ACCEPT_ENCODING, encoding)
+      .send()
+      .unwrap()
+  };
+
+  let response = request("/api/v1/api-docs/openapi.json", "gzip");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response
+      .headers()
+      .get(reqwest::header::CONTENT_ENCODING)
+      .unwrap(),
+    "gzip"
+  );
+  assert!(!response.bytes().unwrap().starts_with(b"{"));
+
+  let response = request("/api/v1/api-docs/openapi.json", "br");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response.headers().get(reqwest::header::CONTENT_ENCODING),
+    None
+  );
+
+  let response = request("/blockcount", "gzip");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response.headers().get(reqwest::header::CONTENT_ENCODING),
+    None
+  );
+  assert_eq!(response.text().unwrap(), "1");
+}
+
+#[test]
+fn cors_origin_allowlist() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &[],
+    &[
+      "--enable-json-api",
+      "--cors-origin",
+      "https://foo.com",
+      "--cors-origin",
+      "https://bar.com",
+      "--cors-allow-credentials",
+    ],
+  );
+
+  server.sync_server();
+
+  let client = reqwest::blocking::Client::new();
+
+  let allowed_origin = |origin: &str| {
+    let response = client
+      .get(server.url().join("/blockcount").unwrap())
+      .header(reqwest::header::ORIGIN, origin)
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    response
+      .headers()
+      .get(reqwest::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+      .map(|value| value.to_str().unwrap().to_string())
+  };
+
+  assert_eq!(
+    allowed_origin("https://foo.com").as_deref(),
+    Some("https://foo.com")
+  );
+  assert_eq!(
+    allowed_origin("https://bar.com").as_deref(),
+    Some("https://bar.com")
+  );
+  assert_eq!(allowed_origin("https://baz.com"), None);
+
+  let response = client
+    .request(
+      reqwest::Method::OPTIONS,
+      server.url().join("/api/v1/inscriptions/batch").unwrap(),
+    )
+    .header(reqwest::header::ORIGIN, "https://foo.com")
+    .header(reqwest::header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+    .header(
+      reqwest::header::ACCESS_CONTROL_REQUEST_HEADERS,
+      "content-type",
+    )
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let header = |name| {
+    response
+      .headers()
+      .get(name)
+      .unwrap()
+      .to_str()
+      .unwrap()
+      .to_string()
+  };
+
+  assert_eq!(
+    header(reqwest::header::ACCESS_CONTROL_ALLOW_ORIGIN),
+    "https://foo.com"
+  );
+  assert!(header(reqwest::header::ACCESS_CONTROL_ALLOW_METHODS).contains("POST"));
+  assert!(header(reqwest::header::ACCESS_CONTROL_ALLOW_HEADERS).contains("content-type"));
+  assert_eq!(
+    header(reqwest::header::ACCESS_CONTROL_ALLOW_CREDENTIALS),
+    "true"
+  );
+}
+
+#[test]
+fn cors_allows_any_origin_by_default() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let server = TestServer::spawn_with_server_args(&rpc_server, &[], &[]);
+
+  server.sync_server();
+
+  let response = reqwest::blocking::Client::new()
+    .get(server.url().join("/blockcount").unwrap())
+    .header(reqwest::header::ORIGIN, "https://baz.com")
+    .send()
+    .unwrap();
+
+  assert_eq!(
+    response
+      .headers()
+      .get(reqwest::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+      .unwrap(),
+    "*"
+  );
+}
+
+#[test]
+fn cors_exposes_response_headers() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let exposed_headers = |server_args: &[&str]| {
+    let server = TestServer::spawn_with_server_args(&rpc_server, &[], server_args);
+
+    server.sync_server();
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.url().join("/blockcount").unwrap())
+      .header(reqwest::header::ORIGIN, "https://foo.com")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    response
+      .headers()
+      .get(reqwest::header::ACCESS_CONTROL_EXPOSE_HEADERS)
+      .unwrap()
+      .to_str()
+      .unwrap()
+      .split(',')
+      .map(|header| header.trim().to_string())
+      .collect::<Vec<String>>()
+  };
+
+  for server_args in [&[][..], &["--cors-origin", "https://foo.com"]] {
+    let exposed_headers = exposed_headers(server_args);
+    assert!(exposed_headers.contains(&"etag".into()));
+    assert!(exposed_headers.contains(&"x-request-id".into()));
+  }
+}
+
+#[test]
+fn cors_preflight_allows_authorization() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  for server_args in [
+    &["--enable-json-api", "--api-key", "foo"][..],
+    &[
+      "--enable-json-api",
+      "--api-key",
+      "foo",
+      "--cors-origin",
+      "https://foo.com",
+    ],
+  ] {
+    let server = TestServer::spawn_with_server_args(&rpc_server, &[], server_args);
+
+    server.sync_server();
+
+    let response = reqwest::blocking::Client::new()
+      .request(
+        reqwest::Method::OPTIONS,
+        server.url().join("/api/v1/node/info").unwrap(),
+      )
+      .header(reqwest::header::ORIGIN, "https://foo.com")
+      .header(reqwest::header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+      .header(
+        reqwest::header::ACCESS_CONTROL_REQUEST_HEADERS,
+        "authorization",
+      )
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK, "{server_args:?}");
+    assert!(response
+      .headers()
+      .get(reqwest::header::ACCESS_CONTROL_ALLOW_HEADERS)
+      .unwrap()
+      .to_str()
+      .unwrap()
+      .contains("authorization"));
+  }
+}
+
+#[test]
+fn readonly_server_never_writes_to_the_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  let mut permissions = fs::metadata(&index_path).unwrap().permissions();
+  permissions.set_readonly(true);
+  fs::set_permissions(&index_path, permissions).unwrap();
+
+  let contents = fs::read(&index_path).unwrap();
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--index", index_path.to_str().unwrap()],
+    &["--readonly", "--enable-json-api"],
+  );
+
+  let response = reqwest::blocking::Client::new()
+    .get(server.url().join("/status").unwrap())
+    .header(reqwest::header::ACCEPT, "application/json")
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let status = response.json::<StatusHtml>().unwrap();
+
+  assert!(status.readonly);
+  assert_eq!(status.height, Some(1));
+
+  let response = reqwest::blocking::get(server.url().join("/blockcount").unwrap()).unwrap();
+  assert_eq!(response.text().unwrap(), "2");
+
+  drop(server);
+
+  assert_eq!(fs::read(&index_path).unwrap(), contents);
+}